@@ -0,0 +1,99 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::store::address_book::{AddressBook, Contact};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+
+/// Résultat de la commande "contacts list".
+#[derive(Debug, Serialize)]
+pub struct ContactListOutput {
+    pub contacts: Vec<Contact>,
+}
+
+impl fmt::Display for ContactListOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.contacts.is_empty() {
+            return write!(f, "No contacts in address book.");
+        }
+
+        let lines: Vec<String> = self
+            .contacts
+            .iter()
+            .map(|contact| format!("{} {}", contact.name, contact.pubkey))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Opération effectuée sur un contact.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactAction {
+    Added,
+    Removed,
+}
+
+/// Résultat des commandes "contacts add|remove".
+#[derive(Debug, Serialize)]
+pub struct ContactActionOutput {
+    pub action: ContactAction,
+    pub contact: Contact,
+}
+
+impl fmt::Display for ContactActionOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.action {
+            ContactAction::Added => write!(
+                f,
+                "Contact '{}' added ({}).",
+                self.contact.name, self.contact.pubkey
+            ),
+            ContactAction::Removed => write!(f, "Contact '{}' removed.", self.contact.name),
+        }
+    }
+}
+
+pub struct AddressBookManager {
+    config: WalletConfig,
+}
+
+impl AddressBookManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Ajoute un contact au carnet d'adresses.
+    pub fn add(
+        &self,
+        name: &str,
+        pubkey: &str,
+    ) -> Result<ContactActionOutput, Box<dyn std::error::Error>> {
+        let address_book = AddressBook::new(self.config.clone());
+
+        let pubkey = Pubkey::from_str(pubkey).map_err(|_| "Invalid public key format")?;
+        Ok(ContactActionOutput {
+            action: ContactAction::Added,
+            contact: address_book.add(name, &pubkey)?,
+        })
+    }
+
+    /// Retourne les contacts du carnet d'adresses.
+    pub fn list(&self) -> Result<ContactListOutput, Box<dyn std::error::Error>> {
+        let address_book = AddressBook::new(self.config.clone());
+
+        Ok(ContactListOutput {
+            contacts: address_book.load()?.contacts,
+        })
+    }
+
+    /// Retire un contact du carnet d'adresses.
+    pub fn remove(&self, name: &str) -> Result<ContactActionOutput, Box<dyn std::error::Error>> {
+        let address_book = AddressBook::new(self.config.clone());
+
+        Ok(ContactActionOutput {
+            action: ContactAction::Removed,
+            contact: address_book.remove(name)?,
+        })
+    }
+}
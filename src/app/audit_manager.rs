@@ -0,0 +1,177 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::store::address_book::AddressBook;
+use crate::store::audit_log::{AuditLine, AuditLog};
+use chrono::{DateTime, NaiveDate};
+use serde::Serialize;
+use std::fmt;
+
+/// Les filtres de la commande "audit show".
+#[derive(Debug, Default)]
+pub struct AuditFilter {
+    /// La commande (sans ou avec sa sous-commande, par exemple "multisig" ou "multisig approve").
+    pub command: Option<String>,
+    /// Une clé publique, un nom de contact ou un label du key store.
+    pub pubkey: Option<String>,
+    /// Une date (AAAA-MM-JJ) ou une date et heure RFC 3339.
+    pub since: Option<String>,
+    /// Le nombre maximal d'entrées (les plus récentes).
+    pub limit: Option<usize>,
+}
+
+/// Résultat de la commande "audit show".
+#[derive(Debug, Serialize)]
+pub struct AuditShowOutput {
+    pub entries: Vec<AuditLine>,
+}
+
+impl fmt::Display for AuditShowOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.entries.is_empty() {
+            return write!(f, "No audit entries.");
+        }
+
+        let mut lines = Vec::new();
+        for line in &self.entries {
+            let entry = &line.entry;
+            let context = match &entry.profile {
+                Some(profile) => format!("profile {}, {}", profile, entry.cluster),
+                None => entry.cluster.clone(),
+            };
+            lines.push(format!(
+                "{} {} ({})",
+                entry.timestamp, entry.command, context
+            ));
+            if !entry.pubkeys.is_empty() {
+                lines.push(format!("  Keys: {}", entry.pubkeys.join(", ")));
+            }
+            if !entry.amounts.is_empty() {
+                lines.push(format!("  Amounts: {}", entry.amounts.join(", ")));
+            }
+            for signature in &entry.signatures {
+                match &entry.status {
+                    Some(status) => lines.push(format!("  Signature: {} ({})", signature, status)),
+                    None => lines.push(format!("  Signature: {}", signature)),
+                }
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "audit verify".
+#[derive(Debug, Serialize)]
+pub struct AuditVerifyOutput {
+    pub file: String,
+    pub entries: usize,
+    /// Le hachage de la dernière entrée : à conserver pour détecter plus tard la suppression d'entrées finales.
+    pub last_hash: String,
+    /// L'ancre vérifiée ("--anchor"), présente dans le journal.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<String>,
+}
+
+impl fmt::Display for AuditVerifyOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Audit log: {}", self.file),
+            format!("Entries: {}", self.entries),
+            "The hash chain is intact.".to_string(),
+        ];
+        match &self.anchor {
+            Some(anchor) => lines.push(format!("Anchored entry {} found.", anchor)),
+            None => lines.push(
+                "Without --anchor, entries removed at the end of the log are not detected."
+                    .to_string(),
+            ),
+        }
+        lines.push(format!("Last hash: {}", self.last_hash));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct AuditManager {
+    config: WalletConfig,
+}
+
+impl AuditManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Affiche les entrées du journal d'audit qui correspondent aux filtres.
+    pub fn show(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<AuditShowOutput, Box<dyn std::error::Error>> {
+        let pubkey = match &filter.pubkey {
+            Some(pubkey) => Some(
+                AddressBook::new(self.config.clone())
+                    .resolve(pubkey)?
+                    .to_string(),
+            ),
+            None => None,
+        };
+        let since = match &filter.since {
+            Some(since) => Some(Self::parse_since(since)?),
+            None => None,
+        };
+
+        let mut entries: Vec<AuditLine> = AuditLog::new(self.config.clone())
+            .load()?
+            .into_iter()
+            .filter(|line| {
+                let entry = &line.entry;
+                if let Some(command) = &filter.command {
+                    if entry.command != *command
+                        && !entry.command.starts_with(&format!("{} ", command))
+                    {
+                        return false;
+                    }
+                }
+                if let Some(pubkey) = &pubkey {
+                    if !entry.pubkeys.contains(pubkey) {
+                        return false;
+                    }
+                }
+                if let Some(since) = since {
+                    return DateTime::parse_from_rfc3339(&entry.timestamp)
+                        .is_ok_and(|timestamp| timestamp.timestamp() >= since);
+                }
+                true
+            })
+            .collect();
+        if let Some(limit) = filter.limit {
+            entries.drain(..entries.len().saturating_sub(limit));
+        }
+        Ok(AuditShowOutput { entries })
+    }
+
+    /// Vérifie la chaîne des hachages du journal d'audit, et la présence de l'entrée ancrée ("--anchor", le dernier
+    /// hachage relevé lors d'une vérification précédente) : seule une troncature antérieure à l'ancre est détectée.
+    pub fn verify(
+        &self,
+        anchor: Option<&str>,
+    ) -> Result<AuditVerifyOutput, Box<dyn std::error::Error>> {
+        let (entries, last_hash) = AuditLog::new(self.config.clone()).verify(anchor)?;
+        Ok(AuditVerifyOutput {
+            file: self.config.audit_log_path.clone(),
+            entries,
+            last_hash,
+            anchor: anchor.map(ToString::to_string),
+        })
+    }
+
+    /// Le timestamp Unix d'une date (AAAA-MM-JJ, à minuit UTC) ou d'une date et heure RFC 3339.
+    fn parse_since(since: &str) -> Result<i64, String> {
+        if let Ok(date) = NaiveDate::parse_from_str(since, "%Y-%m-%d") {
+            return Ok(date
+                .and_hms_opt(0, 0, 0)
+                .expect("midnight is valid")
+                .and_utc()
+                .timestamp());
+        }
+        DateTime::parse_from_rfc3339(since)
+            .map(|date| date.timestamp())
+            .map_err(|_| format!("Invalid date '{}': expected YYYY-MM-DD or RFC 3339", since))
+    }
+}
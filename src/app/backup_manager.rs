@@ -0,0 +1,159 @@
+use crate::bip::mnemonic::BipMnemonic;
+use crate::bip::passphrase::BipPassphrase;
+use crate::bip::slip39::{GroupSpec, Slip39};
+use crate::config::wallet_config::WalletConfig;
+use serde::Serialize;
+use std::fmt;
+use std::io;
+
+/// Un groupe de shares SLIP-39.
+#[derive(Debug, Serialize)]
+pub struct ShareGroupOutput {
+    pub member_threshold: u8,
+    pub member_count: u8,
+    pub shares: Vec<String>,
+}
+
+/// Résultat de la commande "backup split".
+#[derive(Debug, Serialize)]
+pub struct BackupSplitOutput {
+    pub group_threshold: u8,
+    pub groups: Vec<ShareGroupOutput>,
+}
+
+impl fmt::Display for BackupSplitOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!(
+            "SLIP-39 shares ({} of {} group(s) required):",
+            self.group_threshold,
+            self.groups.len()
+        )];
+        for (group_index, group) in self.groups.iter().enumerate() {
+            lines.push(format!(
+                "Group {} ({} of {} shares required):",
+                group_index + 1,
+                group.member_threshold,
+                group.member_count
+            ));
+            for (member_index, share) in group.shares.iter().enumerate() {
+                lines.push(format!(
+                    "Share {}-{}: {}",
+                    group_index + 1,
+                    member_index + 1,
+                    share
+                ));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "backup combine".
+#[derive(Debug, Serialize)]
+pub struct BackupCombineOutput {
+    pub master_secret: String,
+    /// La mnémonique BIP39 correspondante (absente si le secret n'est pas une entropie BIP39 valide).
+    pub mnemonic: Option<String>,
+}
+
+impl fmt::Display for BackupCombineOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Master Secret: {}", self.master_secret)?;
+        match &self.mnemonic {
+            Some(mnemonic) => write!(f, "BIP39 Mnemonic: {}", mnemonic),
+            None => write!(f, "The master secret is not a BIP39 entropy."),
+        }
+    }
+}
+
+pub struct BackupManager {
+    config: WalletConfig,
+}
+
+impl BackupManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Partage le secret maître d'une mnémonique BIP39 (son entropie) en shares SLIP-39.
+    ///
+    /// # Arguments:
+    /// - phrase - La phrase mnémonique BIP39 à sauvegarder.
+    /// - group_threshold - Le nombre de groupes nécessaires pour reconstituer le secret.
+    /// - groups - La définition de chaque groupe (seuil et nombre de shares).
+    /// - iteration_exponent - L'exposant du nombre d'itérations PBKDF2.
+    /// - extendable - Permet de créer ultérieurement d'autres jeux de shares pour le même secret.
+    pub fn split(
+        &self,
+        phrase: &str,
+        group_threshold: u8,
+        groups: &[GroupSpec],
+        iteration_exponent: u8,
+        extendable: bool,
+    ) -> Result<BackupSplitOutput, Box<dyn std::error::Error>> {
+        let mnemonic = BipMnemonic::get_mnemonic_from_phrase(phrase);
+        let passphrase = BipPassphrase::prompt_for_share_passphrase(&self.config.output());
+
+        let shares = Slip39::split(
+            mnemonic.entropy(),
+            &passphrase,
+            group_threshold,
+            groups,
+            iteration_exponent,
+            extendable,
+        )?;
+
+        Ok(BackupSplitOutput {
+            group_threshold,
+            groups: groups
+                .iter()
+                .zip(shares)
+                .map(|(group, shares)| ShareGroupOutput {
+                    member_threshold: group.member_threshold,
+                    member_count: group.member_count,
+                    shares,
+                })
+                .collect(),
+        })
+    }
+
+    /// Reconstitue la mnémonique BIP39 à partir de shares SLIP-39.
+    /// Sans shares en argument, elles sont saisies une par ligne (ligne vide pour terminer).
+    pub fn combine(
+        &self,
+        shares: &[String],
+    ) -> Result<BackupCombineOutput, Box<dyn std::error::Error>> {
+        let shares = if shares.is_empty() {
+            self.prompt_for_shares()
+        } else {
+            shares.to_vec()
+        };
+        let passphrase = BipPassphrase::prompt_for_share_passphrase(&self.config.output());
+
+        let master_secret = Slip39::combine(&shares, &passphrase)?;
+
+        Ok(BackupCombineOutput {
+            master_secret: master_secret.iter().map(|b| format!("{:02x}", b)).collect(),
+            mnemonic: BipMnemonic::get_mnemonic_from_entropy(&master_secret)
+                .ok()
+                .map(|mnemonic| BipMnemonic::get_mnemonic_to_str(&mnemonic).to_string()),
+        })
+    }
+
+    fn prompt_for_shares(&self) -> Vec<String> {
+        self.config
+            .output()
+            .message("Enter SLIP-39 shares (one per line, empty line to finish):");
+
+        let mut shares = Vec::new();
+        loop {
+            let mut line = String::new();
+            match io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if line.trim().is_empty() => break,
+                Ok(_) => shares.push(line.trim().to_string()),
+            }
+        }
+        shares
+    }
+}
@@ -0,0 +1,215 @@
+use crate::bip::bip85::Bip85;
+use crate::bip::mnemonic::BipMnemonic;
+use crate::bip::passphrase::BipPassphrase;
+use crate::bip::seed::BipSeed;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::address::SolanaAddress;
+use crate::store::keystore::{KeySource, KeyStore};
+use bip32::XPrv;
+use serde::Serialize;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use std::fmt;
+
+/// Une clé ajoutée au key store (option "--import").
+#[derive(Debug, Serialize)]
+pub struct ImportedKeyOutput {
+    pub label: String,
+    pub pubkey: String,
+}
+
+/// Résultat des commandes "bip85 mnemonic|hex|ed25519".
+#[derive(Debug, Serialize)]
+pub struct Bip85Output {
+    pub path: String,
+    pub index: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported: Option<ImportedKeyOutput>,
+}
+
+impl fmt::Display for Bip85Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!("BIP85 Path: {}", self.path)];
+        if let Some(mnemonic) = &self.mnemonic {
+            lines.push(format!(
+                "BIP39 Mnemonic (child {}): {}",
+                self.index, mnemonic
+            ));
+        }
+        if let Some(hex) = &self.hex {
+            lines.push(format!("Hex (child {}): {}", self.index, hex));
+        }
+        if let Some(pubkey) = &self.pubkey {
+            lines.push(format!(
+                "Solana Public Key (child {}): {}",
+                self.index, pubkey
+            ));
+        }
+        if let Some(imported) = &self.imported {
+            lines.push(format!(
+                "Solana Public Key ({}): {}",
+                imported.label, imported.pubkey
+            ));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct Bip85Manager {
+    config: WalletConfig,
+}
+
+impl Bip85Manager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Dérive une mnémonique enfant (BIP85) de la mnémonique maîtresse.
+    /// Avec "import_label", la clé principale de la mnémonique enfant est ajoutée au key store.
+    ///
+    /// # Arguments:
+    /// - phrase - La mnémonique maîtresse (sa passphrase est demandée).
+    /// - language - L'index de langue BIP85 (0 = anglais).
+    /// - word_count - Le nombre de mots de la mnémonique enfant (12, 18 ou 24).
+    /// - index - L'index de l'enfant.
+    /// - import_label - Le label de la clé dans le key store (pas d'import si absent).
+    pub fn derive_mnemonic(
+        &self,
+        phrase: &str,
+        language: u32,
+        word_count: u32,
+        index: u32,
+        import_label: Option<&str>,
+    ) -> Result<Bip85Output, Box<dyn std::error::Error>> {
+        let (root, _) = self.master_key(phrase)?;
+
+        let mnemonic = Bip85::derive_mnemonic(&root, language, word_count, index)
+            .map_err(|e| format!("Failed to derive child mnemonic: {}", e))?;
+
+        let mut output = Bip85Output {
+            path: Bip85::mnemonic_path(language, word_count, index),
+            index,
+            mnemonic: Some(BipMnemonic::get_mnemonic_to_str(&mnemonic).to_string()),
+            hex: None,
+            pubkey: None,
+            imported: None,
+        };
+
+        if let Some(label) = import_label {
+            // La clé importée est la clé principale de la mnémonique enfant (seed sans passphrase),
+            // celle que produit "recover_seed" avec cette mnémonique.
+            let seed = BipSeed::generate_seed(&mnemonic, "");
+            let seed_bytes = BipSeed::get_seed_bytes(&seed);
+            let fingerprint = BipSeed::fingerprint(seed_bytes)
+                .map_err(|e| format!("Error computing seed fingerprint: {}", e))?;
+            let source = KeySource::Mnemonic {
+                fingerprint,
+                path: BipSeed::derivation_path(&self.config.derivation_template, 0),
+            };
+            output.imported =
+                Some(self.import(&SolanaAddress::generate_keypair(seed_bytes), source, label)?);
+        }
+
+        Ok(output)
+    }
+
+    /// Dérive de l'entropie brute (application HEX de BIP85).
+    pub fn derive_hex(
+        &self,
+        phrase: &str,
+        num_bytes: u32,
+        index: u32,
+    ) -> Result<Bip85Output, Box<dyn std::error::Error>> {
+        let (root, _) = self.master_key(phrase)?;
+
+        let entropy = Bip85::derive_hex(&root, num_bytes, index)
+            .map_err(|e| format!("Failed to derive entropy: {}", e))?;
+
+        Ok(Bip85Output {
+            path: Bip85::hex_path(num_bytes, index),
+            index,
+            mnemonic: None,
+            hex: Some(entropy.iter().map(|b| format!("{:02x}", b)).collect()),
+            pubkey: None,
+            imported: None,
+        })
+    }
+
+    /// Dérive une paire de clés ed25519 (Solana) et l'ajoute optionnellement au key store.
+    pub fn derive_ed25519(
+        &self,
+        phrase: &str,
+        index: u32,
+        import_label: Option<&str>,
+    ) -> Result<Bip85Output, Box<dyn std::error::Error>> {
+        let (root, fingerprint) = self.master_key(phrase)?;
+
+        let seed = Bip85::derive_ed25519_seed(&root, index)
+            .map_err(|e| format!("Failed to derive ed25519 key: {}", e))?;
+
+        let path = Bip85::hex_path(32, index);
+        let keypair = SolanaAddress::generate_keypair(&seed);
+
+        let imported = match import_label {
+            Some(label) => Some(self.import(
+                &keypair,
+                KeySource::Bip85 {
+                    fingerprint,
+                    path: path.clone(),
+                },
+                label,
+            )?),
+            None => None,
+        };
+
+        Ok(Bip85Output {
+            path,
+            index,
+            mnemonic: None,
+            hex: None,
+            pubkey: Some(keypair.pubkey().to_string()),
+            imported,
+        })
+    }
+
+    /// Demande la passphrase de la mnémonique maîtresse et retourne sa clé racine BIP32 et son empreinte.
+    fn master_key(&self, phrase: &str) -> Result<(XPrv, String), String> {
+        let mnemonic = BipMnemonic::get_mnemonic_from_phrase(phrase);
+        let passphrase = BipPassphrase::prompt_for_passphrase(&self.config.output());
+        let seed = BipSeed::generate_seed(&mnemonic, &passphrase);
+        let seed_bytes = BipSeed::get_seed_bytes(&seed);
+
+        let root = Bip85::root_from_seed(seed_bytes)
+            .map_err(|e| format!("Failed to create master key: {}", e))?;
+        let fingerprint = BipSeed::fingerprint(seed_bytes)
+            .map_err(|e| format!("Error computing seed fingerprint: {}", e))?;
+        Ok((root, fingerprint))
+    }
+
+    /// Écrit la paire de clés dans un fichier et l'enregistre dans le key store.
+    fn import(
+        &self,
+        keypair: &Keypair,
+        source: KeySource,
+        label: &str,
+    ) -> Result<ImportedKeyOutput, String> {
+        let keystore = KeyStore::new(self.config.clone());
+        let pubkey = keypair.pubkey();
+
+        let keypair_path = keystore.new_keypair_path(&pubkey);
+        SolanaAddress::write_keypair(keypair, &keypair_path);
+
+        let entry = keystore
+            .register(label, source, &keypair_path, &pubkey)
+            .map_err(|e| format!("Error registering keypair in key store: {}", e))?;
+        Ok(ImportedKeyOutput {
+            label: entry.label,
+            pubkey: pubkey.to_string(),
+        })
+    }
+}
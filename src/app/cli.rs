@@ -1,31 +1,161 @@
+use crate::app::address_book_manager::AddressBookManager;
+use crate::app::audit_manager::{AuditFilter, AuditManager};
+use crate::app::backup_manager::BackupManager;
+use crate::app::bip85_manager::Bip85Manager;
+use crate::app::config_manager::ConfigManager;
+use crate::app::dashboard_manager::DashboardManager;
+use crate::app::decode_manager::DecodeManager;
+use crate::app::grind_manager::GrindManager;
 use crate::app::keypair_manager::KeypairManager;
+use crate::app::keystore_manager::KeystoreManager;
+use crate::app::lookup_table_manager::LookupTableManager;
+use crate::app::message_manager::MessageManager;
+use crate::app::multisig_manager::MultisigManager;
+use crate::app::output::{Output, OUTPUT_FORMATS};
+use crate::app::rpc_manager::RpcManager;
+use crate::app::shell::Shell;
+use crate::app::sweep_manager::SweepManager;
+use crate::app::token_manager::TokenManager;
 use crate::app::transaction_manager::TransactionManager;
+use crate::app::tui::Tui;
 use crate::app::wallet_manager::WalletManager;
-use crate::config::wallet_config::WalletConfig;
-use clap::{Arg, ArgMatches, Command};
+use crate::bip::entropy::EntropySource;
+use crate::bip::slip39::GroupSpec;
+use crate::config::wallet_config::{ConfigOverrides, WalletConfig};
+use crate::solana::grind::{GrindMode, GrindPattern};
+use crate::store::audit_log::AuditLog;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde::Serialize;
+use std::time::Duration;
 
 pub struct AppCli {
     config: WalletConfig,
+    output: Output,
 }
 
 impl AppCli {
     pub fn new(config: WalletConfig) -> Self {
-        Self { config }
+        Self {
+            output: config.output(),
+            config,
+        }
     }
 
     pub fn setup_cli(&self) -> Command {
         Command::new("Solana Wallet")
             .version("1.0.0")
             .about("Example of a Solana Wallet in Rust")
+            .arg(
+                Arg::new("profile")
+                    .long("profile")
+                    .value_name("NAME")
+                    .help("The profile of the config file to use")
+                    .global(true),
+            )
+            .arg(
+                Arg::new("url")
+                    .short('u')
+                    .long("url")
+                    .value_name("URL_OR_MONIKER")
+                    .help("The RPC URL or cluster moniker: [mainnet-beta, testnet, devnet, localhost] or their first letter (overrides RPC_URL and the profile)")
+                    .global(true),
+            )
+            .arg(
+                Arg::new("commitment")
+                    .long("commitment")
+                    .value_name("LEVEL")
+                    .help("The commitment level of RPC requests")
+                    .value_parser(["processed", "confirmed", "finalized"])
+                    .global(true),
+            )
+            .arg(
+                Arg::new("output")
+                    .long("output")
+                    .value_name("FORMAT")
+                    .help("The output format of the results (overrides OUTPUT_FORMAT)")
+                    .value_parser(OUTPUT_FORMATS)
+                    .global(true),
+            )
             .subcommand(self.configure_generate_seed())
             .subcommand(self.configure_recover_seed())
             .subcommand(self.configure_send())
+            .subcommand(self.configure_batch_send())
+            .subcommand(self.configure_sweep())
+            .subcommand(self.configure_confirm())
+            .subcommand(self.configure_pay())
+            .subcommand(self.configure_request_payment())
+            .subcommand(self.configure_decode())
+            .subcommand(self.configure_sign_tx())
+            .subcommand(self.configure_alt())
+            .subcommand(self.configure_multisig())
+            .subcommand(self.configure_token_accounts())
+            .subcommand(self.configure_create_ata())
             .subcommand(self.configure_pubkey())
             .subcommand(self.configure_balance_by_pubkey())
+            .subcommand(self.configure_history())
+            .subcommand(self.configure_wallet())
+            .subcommand(self.configure_contacts())
+            .subcommand(self.configure_grind())
+            .subcommand(self.configure_backup())
+            .subcommand(self.configure_bip85())
+            .subcommand(self.configure_config())
+            .subcommand(self.configure_audit())
+            .subcommand(self.configure_health())
+            .subcommand(self.configure_shell())
+            .subcommand(self.configure_tui())
+            .subcommand(self.configure_sign_message())
+            .subcommand(self.configure_verify_message())
+    }
+
+    /// Retourne les valeurs de la configuration données par les options globales ("--profile", "--url", "--commitment").
+    pub fn config_overrides(matches: &ArgMatches) -> ConfigOverrides {
+        ConfigOverrides {
+            profile: matches.get_one::<String>("profile").cloned(),
+            rpc_url: matches.get_one::<String>("url").cloned(),
+            commitment: matches.get_one::<String>("commitment").cloned(),
+            output: matches.get_one::<String>("output").cloned(),
+        }
+    }
+
+    /// Option "--from" : sélectionne la paire de clés du key store à utiliser (par label ou par clé publique).
+    fn keypair_selector_arg(&self) -> Arg {
+        Arg::new("from")
+            .long("from")
+            .value_name("LABEL|PUBKEY")
+            .help("The key store entry to use (defaults to the active key, then KEYPAIR_PATH)")
     }
 
     fn configure_generate_seed(&self) -> Command {
-        Command::new("generate_seed").about("Generates a new random mnemonic")
+        Command::new("generate_seed")
+            .about("Generates a new random mnemonic")
+            .arg(
+                Arg::new("words")
+                    .long("words")
+                    .help("The number of words of the mnemonic")
+                    .value_parser(["12", "15", "18", "21", "24"])
+                    .default_value("12"),
+            )
+            .arg(
+                Arg::new("entropy-dice")
+                    .long("entropy-dice")
+                    .value_name("ROLLS")
+                    .help("Uses d6 rolls as entropy (prompted when no value is given)")
+                    .num_args(0..=1)
+                    .default_missing_value("")
+                    .conflicts_with("entropy-hex"),
+            )
+            .arg(
+                Arg::new("entropy-hex")
+                    .long("entropy-hex")
+                    .value_name("HEX")
+                    .help("Uses a hexadecimal string as entropy"),
+            )
+            .arg(
+                Arg::new("mix-os-entropy")
+                    .long("mix-os-entropy")
+                    .action(ArgAction::SetTrue)
+                    .help("Mixes the user entropy with the OS random generator (not reproducible)"),
+            )
     }
 
     fn configure_recover_seed(&self) -> Command {
@@ -44,7 +174,7 @@ impl AppCli {
             .about("Send SOL to a specific address")
             .arg(
                 Arg::new("RECIPIENT")
-                    .help("The recipient's public key")
+                    .help("The recipient's public key, contact name or key store label")
                     .required(true),
             )
             .arg(
@@ -52,10 +182,379 @@ impl AppCli {
                     .help("The amount of SOL to send")
                     .required(true),
             )
+            .arg(self.keypair_selector_arg())
+            .arg(
+                Arg::new("no-wait")
+                    .long("no-wait")
+                    .action(ArgAction::SetTrue)
+                    .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation"),
+            )
+            .args(self.recipient_override_args())
+    }
+
+    fn configure_batch_send(&self) -> Command {
+        Command::new("batch_send")
+            .about("Sends SOL to several recipients in a single transaction")
+            .arg(
+                Arg::new("FILE")
+                    .help("The payments file: one \"<RECIPIENT>,<LAMPORTS>\" line per transfer")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("lookup-table")
+                    .long("lookup-table")
+                    .value_name("ADDRESS")
+                    .help("An address lookup table holding the recipients, to send a v0 transaction (can be repeated)")
+                    .action(ArgAction::Append),
+            )
+            .arg(self.keypair_selector_arg())
+            .arg(
+                Arg::new("no-wait")
+                    .long("no-wait")
+                    .action(ArgAction::SetTrue)
+                    .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation"),
+            )
+            .args(self.recipient_override_args())
+    }
+
+    /// Les options qui ignorent un contrôle du destinataire d'un envoi de SOL ("send", "batch_send", "pay").
+    fn recipient_override_args(&self) -> [Arg; 5] {
+        [
+            Arg::new("allow-self")
+                .long("allow-self")
+                .action(ArgAction::SetTrue)
+                .help("Sends even if the recipient is the sender itself"),
+            Arg::new("allow-off-curve")
+                .long("allow-off-curve")
+                .action(ArgAction::SetTrue)
+                .help("Sends even if the recipient is off-curve (a program derived address)"),
+            Arg::new("allow-token-account")
+                .long("allow-token-account")
+                .action(ArgAction::SetTrue)
+                .help("Sends even if the recipient is a token account or a mint"),
+            Arg::new("allow-program")
+                .long("allow-program")
+                .action(ArgAction::SetTrue)
+                .help("Sends even if the recipient is an executable program"),
+            Arg::new("allow-below-rent")
+                .long("allow-below-rent")
+                .action(ArgAction::SetTrue)
+                .help("Sends even if the sender would keep less than the rent-exempt minimum"),
+        ]
+    }
+
+    fn configure_sweep(&self) -> Command {
+        Command::new("sweep")
+            .about("Moves every asset of a key (SOL, tokens, stake accounts) to another address")
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .value_name("LABEL|PUBKEY")
+                    .help("The destination: a public key, contact name or key store label")
+                    .required(true),
+            )
+            .arg(self.keypair_selector_arg().conflicts_with("all"))
+            .arg(
+                Arg::new("all")
+                    .long("all")
+                    .action(ArgAction::SetTrue)
+                    .help("Sweeps every keypair of the key store"),
+            )
+            .arg(
+                Arg::new("yes")
+                    .long("yes")
+                    .action(ArgAction::SetTrue)
+                    .help("Sweeps without asking for confirmation"),
+            )
+            .arg(
+                Arg::new("no-wait")
+                    .long("no-wait")
+                    .action(ArgAction::SetTrue)
+                    .help("Returns as soon as the last SOL transfer is sent, without waiting for its confirmation"),
+            )
+    }
+
+    fn configure_confirm(&self) -> Command {
+        Command::new("confirm")
+            .about("Waits for a transaction to reach the commitment level")
+            .arg(
+                Arg::new("SIGNATURE")
+                    .help("The signature of the transaction")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("timeout")
+                    .long("timeout")
+                    .value_name("SECONDS")
+                    .help("The maximum waiting time (defaults to CONFIRM_TIMEOUT or the profile retry policy)")
+                    .value_parser(clap::value_parser!(u64)),
+            )
+    }
+
+    fn configure_pay(&self) -> Command {
+        Command::new("pay")
+            .about("Pays a Solana Pay transfer request URL (solana:<RECIPIENT>?amount=...)")
+            .arg(
+                Arg::new("URL")
+                    .help("The Solana Pay URL (quote it in the shell)")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("amount")
+                    .long("amount")
+                    .value_name("AMOUNT")
+                    .help("The amount in SOL or token units, when the request does not specify one"),
+            )
+            .arg(self.keypair_selector_arg())
+            .arg(
+                Arg::new("no-wait")
+                    .long("no-wait")
+                    .action(ArgAction::SetTrue)
+                    .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation"),
+            )
+            .args(self.recipient_override_args())
+    }
+
+    fn configure_request_payment(&self) -> Command {
+        Command::new("request_payment")
+            .about("Generates a Solana Pay transfer request URL (and its QR code) to receive a payment")
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .value_name("LABEL|PUBKEY")
+                    .help("The recipient: public key, contact name or key store label (defaults to the active key)"),
+            )
+            .arg(
+                Arg::new("amount")
+                    .long("amount")
+                    .value_name("AMOUNT")
+                    .help("The amount in SOL, or in token units with --spl-token (the payer enters it if absent)"),
+            )
+            .arg(
+                Arg::new("spl-token")
+                    .long("spl-token")
+                    .value_name("MINT")
+                    .help("The mint of the SPL token to request (SOL if absent)"),
+            )
+            .arg(
+                Arg::new("reference")
+                    .long("reference")
+                    .value_name("PUBKEY")
+                    .help("A reference key, to find the payment transaction (can be repeated)")
+                    .action(ArgAction::Append),
+            )
+            .arg(
+                Arg::new("new-reference")
+                    .long("new-reference")
+                    .help("Generates a new random reference key")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(
+                Arg::new("label")
+                    .long("label")
+                    .help("The source of the request (merchant, application...)"),
+            )
+            .arg(
+                Arg::new("message")
+                    .long("message")
+                    .help("A message describing the request (item, order...)"),
+            )
+            .arg(
+                Arg::new("memo")
+                    .long("memo")
+                    .help("A memo to include in the payment transaction"),
+            )
+            .arg(
+                Arg::new("qr")
+                    .long("qr")
+                    .help("Displays the URL as a QR code in the terminal")
+                    .action(ArgAction::SetTrue),
+            )
+    }
+
+    fn configure_decode(&self) -> Command {
+        Command::new("decode")
+            .about("Decodes a serialized transaction (accounts, signatures and instructions) before signing it")
+            .arg(
+                Arg::new("TRANSACTION")
+                    .help("The legacy or v0 transaction, in base64 or base58 (\"-\" to read it from stdin)")
+                    .required(true)
+                    .index(1),
+            )
+    }
+
+    fn configure_sign_tx(&self) -> Command {
+        Command::new("sign_tx")
+            .about("Adds our signature to a transaction built by someone else, after displaying its content")
+            .arg(
+                Arg::new("TRANSACTION")
+                    .help("The legacy or v0 transaction, in base64 or base58")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(self.keypair_selector_arg())
+            .arg(
+                Arg::new("yes")
+                    .long("yes")
+                    .action(ArgAction::SetTrue)
+                    .help("Signs without asking for confirmation"),
+            )
+    }
+
+    fn configure_alt(&self) -> Command {
+        let no_wait = Arg::new("no-wait")
+            .long("no-wait")
+            .action(ArgAction::SetTrue)
+            .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation");
+        let table = Arg::new("TABLE")
+            .help("The address of the lookup table")
+            .required(true);
+
+        Command::new("alt")
+            .about("Manages our address lookup tables (used by v0 transactions)")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("create")
+                    .about("Creates an address lookup table (the key is its authority and pays its rent)")
+                    .arg(self.keypair_selector_arg())
+                    .arg(no_wait.clone()),
+            )
+            .subcommand(
+                Command::new("extend")
+                    .about("Adds addresses to a lookup table")
+                    .arg(table.clone())
+                    .arg(
+                        Arg::new("ADDRESSES")
+                            .help("Public keys, contact names or key store labels")
+                            .required(true)
+                            .num_args(1..),
+                    )
+                    .arg(self.keypair_selector_arg())
+                    .arg(no_wait.clone()),
+            )
+            .subcommand(
+                Command::new("freeze")
+                    .about("Freezes a lookup table: it can no longer be extended or closed")
+                    .arg(table.clone())
+                    .arg(self.keypair_selector_arg())
+                    .arg(no_wait.clone()),
+            )
+            .subcommand(
+                Command::new("deactivate")
+                    .about("Deactivates a lookup table, before closing it")
+                    .arg(table.clone())
+                    .arg(self.keypair_selector_arg())
+                    .arg(no_wait.clone()),
+            )
+            .subcommand(
+                Command::new("close")
+                    .about("Closes a deactivated lookup table and recovers its rent")
+                    .arg(table.clone())
+                    .arg(self.keypair_selector_arg())
+                    .arg(no_wait),
+            )
+            .subcommand(
+                Command::new("show")
+                    .about("Displays the authority, status and addresses of a lookup table")
+                    .arg(table),
+            )
+    }
+
+    fn configure_multisig(&self) -> Command {
+        let file = Arg::new("FILE")
+            .help("The signing request file (JSON)")
+            .required(true);
+        let address = |name: &'static str, help: &'static str| {
+            Arg::new(name)
+                .long(name)
+                .value_name("LABEL|PUBKEY")
+                .help(help)
+        };
+
+        Command::new("multisig")
+            .about("Signs a transfer with the treasury key and M of N co-signers, through a portable signing request file")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("propose")
+                    .about("Creates a signing request: an unsigned transfer using a durable nonce")
+                    .arg(file.clone())
+                    .arg(address("from", "The treasury sending the lamports (public key, contact name or key store label)").required(true))
+                    .arg(address("to", "The recipient").required(true))
+                    .arg(
+                        Arg::new("amount")
+                            .long("amount")
+                            .value_name("LAMPORTS")
+                            .help("The amount in lamports")
+                            .required(true),
+                    )
+                    .arg(
+                        address("signer", "A co-signer whose signature is also required (can be repeated)")
+                            .action(ArgAction::Append),
+                    )
+                    .arg(
+                        Arg::new("threshold")
+                            .long("threshold")
+                            .value_name("M")
+                            .help("The number of co-signers who must approve (all of them by default)"),
+                    )
+                    .arg(
+                        Arg::new("nonce")
+                            .long("nonce")
+                            .value_name("PUBKEY")
+                            .help("The durable nonce account")
+                            .required(true),
+                    )
+                    .arg(address("nonce-authority", "The authority of the nonce account (defaults to the treasury)"))
+                    .arg(
+                        Arg::new("nonce-blockhash")
+                            .long("nonce-blockhash")
+                            .value_name("HASH")
+                            .help("The current value of the nonce, to work offline (read from the RPC node if absent)"),
+                    )
+                    .arg(address("fee-payer", "The account paying the fees (defaults to the treasury)"))
+                    .arg(
+                        Arg::new("description")
+                            .long("description")
+                            .help("The description of the request (stored in the memo of the transaction)"),
+                    ),
+            )
+            .subcommand(
+                Command::new("approve")
+                    .about("Adds the signature of a local keypair to a signing request")
+                    .arg(file.clone())
+                    .arg(self.keypair_selector_arg())
+                    .arg(
+                        Arg::new("yes")
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Signs without asking for confirmation"),
+                    ),
+            )
+            .subcommand(
+                Command::new("status")
+                    .about("Displays a signing request and its signatures")
+                    .arg(file.clone()),
+            )
+            .subcommand(
+                Command::new("submit")
+                    .about("Sends the transaction of a signing request once enough co-signers have approved it")
+                    .arg(file)
+                    .arg(
+                        Arg::new("no-wait")
+                            .long("no-wait")
+                            .action(ArgAction::SetTrue)
+                            .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation"),
+                    ),
+            )
     }
 
     fn configure_pubkey(&self) -> Command {
-        Command::new("pubkey").about("Displays the public key from the keypair stored in file")
+        Command::new("pubkey")
+            .about("Displays the public key from the keypair stored in file")
+            .arg(self.keypair_selector_arg())
     }
 
     fn configure_balance_by_pubkey(&self) -> Command {
@@ -63,58 +562,1105 @@ impl AppCli {
             .about("Displays the balance for the public key")
             .arg(
                 Arg::new("PUBKEY")
-                    .help("A public key")
+                    .help("A public key, contact name or key store label")
+                    .required(true)
+                    .index(1),
+            )
+    }
+
+    fn configure_history(&self) -> Command {
+        Command::new("history")
+            .about("Displays the recent transactions of an address")
+            .arg(
+                Arg::new("ADDRESS")
+                    .help("A public key, contact name or key store label")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(
+                Arg::new("limit")
+                    .long("limit")
+                    .help("The maximum number of transactions to display")
+                    .value_parser(clap::value_parser!(usize))
+                    .default_value("10"),
+            )
+    }
+
+    fn configure_wallet(&self) -> Command {
+        Command::new("wallet")
+            .about("Manages the keypairs of the key store")
+            .subcommand_required(true)
+            .subcommand(Command::new("list").about("Lists the keypairs of the key store"))
+            .subcommand(
+                Command::new("rename")
+                    .about("Renames a keypair")
+                    .arg(Arg::new("LABEL").help("The current label").required(true))
+                    .arg(Arg::new("NEW_LABEL").help("The new label").required(true)),
+            )
+            .subcommand(
+                Command::new("use")
+                    .about("Selects the keypair used by default")
+                    .arg(
+                        Arg::new("LABEL")
+                            .help("A label or a public key")
+                            .required(true),
+                    ),
+            )
+            .subcommand(
+                Command::new("watch")
+                    .about("Adds a watch-only address (no private key) to the key store")
+                    .arg(Arg::new("LABEL").help("The label").required(true))
+                    .arg(Arg::new("PUBKEY").help("The public key").required(true)),
+            )
+            .subcommand(
+                Command::new("remove")
+                    .about("Removes a keypair from the key store")
+                    .arg(
+                        Arg::new("LABEL")
+                            .help("A label or a public key")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("delete-file")
+                            .long("delete-file")
+                            .action(ArgAction::SetTrue)
+                            .help("Also deletes the keypair file"),
+                    ),
+            )
+    }
+
+    fn configure_contacts(&self) -> Command {
+        Command::new("contacts")
+            .about("Manages the address book")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("add")
+                    .about("Adds a contact")
+                    .arg(Arg::new("NAME").help("The contact name").required(true))
+                    .arg(Arg::new("PUBKEY").help("The public key").required(true)),
+            )
+            .subcommand(Command::new("list").about("Lists the contacts"))
+            .subcommand(
+                Command::new("remove")
+                    .about("Removes a contact")
+                    .arg(Arg::new("NAME").help("The contact name").required(true)),
+            )
+    }
+
+    fn configure_grind(&self) -> Command {
+        Command::new("grind")
+            .about("Searches for a vanity address (public key with a given prefix and/or suffix)")
+            .arg(
+                Arg::new("starts-with")
+                    .long("starts-with")
+                    .value_name("PREFIX")
+                    .help("The prefix of the public key")
+                    .required_unless_present("ends-with"),
+            )
+            .arg(
+                Arg::new("ends-with")
+                    .long("ends-with")
+                    .value_name("SUFFIX")
+                    .help("The suffix of the public key"),
+            )
+            .arg(
+                Arg::new("ignore-case")
+                    .long("ignore-case")
+                    .action(ArgAction::SetTrue)
+                    .help("Matches the prefix and the suffix case-insensitively"),
+            )
+            .arg(
+                Arg::new("count")
+                    .long("count")
+                    .help("The number of addresses to find")
+                    .value_parser(clap::value_parser!(usize))
+                    .default_value("1"),
+            )
+            .arg(
+                Arg::new("threads")
+                    .long("threads")
+                    .help("The number of threads (defaults to the number of CPU cores)")
+                    .value_parser(clap::value_parser!(usize)),
+            )
+            .arg(
+                Arg::new("use-mnemonic")
+                    .long("use-mnemonic")
+                    .action(ArgAction::SetTrue)
+                    .help("Generates BIP39 mnemonics (without passphrase) so the key is recoverable from words"),
+            )
+            .arg(
+                Arg::new("derivation-index")
+                    .long("derivation-index")
+                    .help("The derivation index used with --use-mnemonic (0 = main key)")
+                    .value_parser(clap::value_parser!(usize))
+                    .default_value("0")
+                    .requires("use-mnemonic"),
+            )
+            .arg(
+                Arg::new("label")
+                    .long("label")
+                    .help("The key store label of the found keypairs")
+                    .default_value("vanity"),
+            )
+    }
+
+    fn configure_backup(&self) -> Command {
+        Command::new("backup")
+            .about("Backs up a mnemonic as SLIP-39 shares (Shamir's Secret Sharing)")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("split")
+                    .about("Splits the master secret of a mnemonic into SLIP-39 shares")
+                    .arg(
+                        Arg::new("PHRASE")
+                            .help("The BIP39 mnemonic phrase to back up")
+                            .required(true),
+                    )
+                    .arg(
+                        Arg::new("threshold")
+                            .long("threshold")
+                            .help("The number of shares required to recover the mnemonic")
+                            .value_parser(clap::value_parser!(u8).range(1..=16))
+                            .required_unless_present("group"),
+                    )
+                    .arg(
+                        Arg::new("shares")
+                            .long("shares")
+                            .help("The number of shares to create")
+                            .value_parser(clap::value_parser!(u8).range(1..=16))
+                            .required_unless_present("group"),
+                    )
+                    .arg(
+                        Arg::new("group")
+                            .long("group")
+                            .value_name("THRESHOLD/COUNT")
+                            .help("Defines a group of shares, e.g. 2/3 (repeatable)")
+                            .action(ArgAction::Append)
+                            .conflicts_with_all(["threshold", "shares"]),
+                    )
+                    .arg(
+                        Arg::new("group-threshold")
+                            .long("group-threshold")
+                            .help("The number of groups required to recover the mnemonic")
+                            .value_parser(clap::value_parser!(u8).range(1..=16))
+                            .default_value("1"),
+                    )
+                    .arg(
+                        Arg::new("iteration-exponent")
+                            .long("iteration-exponent")
+                            .help("The PBKDF2 iteration exponent (10000 × 2^e iterations)")
+                            .value_parser(clap::value_parser!(u8).range(0..=15))
+                            .default_value("1"),
+                    )
+                    .arg(
+                        Arg::new("extendable")
+                            .long("extendable")
+                            .action(ArgAction::SetTrue)
+                            .help("Allows creating other share sets for the same mnemonic later"),
+                    ),
+            )
+            .subcommand(
+                Command::new("combine")
+                    .about("Recovers a mnemonic from SLIP-39 shares")
+                    .arg(
+                        Arg::new("share")
+                            .long("share")
+                            .value_name("WORDS")
+                            .help("A SLIP-39 share (repeatable, prompted when omitted)")
+                            .action(ArgAction::Append),
+                    ),
+            )
+    }
+
+    fn configure_bip85(&self) -> Command {
+        let phrase_arg = Arg::new("PHRASE")
+            .help("The master mnemonic phrase")
+            .required(true);
+        let index_arg = Arg::new("index")
+            .long("index")
+            .help("The child index")
+            .value_parser(clap::value_parser!(u32))
+            .default_value("0");
+        let import_arg = Arg::new("import")
+            .long("import")
+            .value_name("LABEL")
+            .help("Imports the child key into the key store with the given label");
+
+        Command::new("bip85")
+            .about("Derives child mnemonics, entropy and keys from a master mnemonic (BIP85)")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("mnemonic")
+                    .about("Derives a child BIP39 mnemonic")
+                    .arg(phrase_arg.clone())
+                    .arg(
+                        Arg::new("words")
+                            .long("words")
+                            .help("The number of words of the child mnemonic")
+                            .value_parser(clap::value_parser!(u32))
+                            .default_value("12"),
+                    )
+                    .arg(
+                        Arg::new("language")
+                            .long("language")
+                            .help("The BIP85 language index (0 = English, 1 = Japanese, 2 = Korean, 3 = Spanish, 4/5 = Chinese simplified/traditional, 6 = French, 7 = Italian)")
+                            .value_parser(clap::value_parser!(u32))
+                            .default_value("0"),
+                    )
+                    .arg(index_arg.clone())
+                    .arg(import_arg.clone()),
+            )
+            .subcommand(
+                Command::new("hex")
+                    .about("Derives raw entropy (hexadecimal)")
+                    .arg(phrase_arg.clone())
+                    .arg(
+                        Arg::new("bytes")
+                            .long("bytes")
+                            .help("The number of bytes (16 to 64)")
+                            .value_parser(clap::value_parser!(u32))
+                            .default_value("32"),
+                    )
+                    .arg(index_arg.clone()),
+            )
+            .subcommand(
+                Command::new("ed25519")
+                    .about("Derives a Solana (ed25519) keypair")
+                    .arg(phrase_arg)
+                    .arg(index_arg)
+                    .arg(import_arg),
+            )
+    }
+
+    fn configure_config(&self) -> Command {
+        Command::new("config")
+            .about("Displays the configuration")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("show")
+                    .about("Displays each configuration value and where it comes from"),
+            )
+    }
+
+    fn configure_audit(&self) -> Command {
+        Command::new("audit")
+            .about("Displays and verifies the audit log of key generations, exports, signatures and sends")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("show")
+                    .about("Displays the entries of the audit log")
+                    .arg(
+                        Arg::new("command")
+                            .long("command")
+                            .help("Only the entries of this command (e.g. send, multisig)"),
+                    )
+                    .arg(
+                        Arg::new("pubkey")
+                            .long("pubkey")
+                            .help("Only the entries involving this public key, contact name or key store label"),
+                    )
+                    .arg(
+                        Arg::new("since")
+                            .long("since")
+                            .help("Only the entries since this date (YYYY-MM-DD or RFC 3339)"),
+                    )
+                    .arg(
+                        Arg::new("limit")
+                            .long("limit")
+                            .value_parser(clap::value_parser!(usize))
+                            .help("Only the last N entries"),
+                    ),
+            )
+            .subcommand(
+                Command::new("verify")
+                    .about("Checks the hash chain of the audit log (modified, removed or inserted entries)")
+                    .arg(
+                        Arg::new("anchor")
+                            .long("anchor")
+                            .value_name("HASH")
+                            .help("The last hash of an earlier verification: detects entries removed at the end of the log"),
+                    ),
+            )
+    }
+
+    fn configure_health(&self) -> Command {
+        Command::new("health").about("Checks the RPC endpoints (getHealth and slot lag)")
+    }
+
+    fn configure_shell(&self) -> Command {
+        Command::new("shell").about(
+            "Starts an interactive shell (the passphrase is kept until the session is locked)",
+        )
+    }
+
+    fn configure_tui(&self) -> Command {
+        Command::new("tui")
+            .about("Displays a full-screen dashboard (keypairs, balances, tokens, recent transactions, send form)")
+            .arg(
+                Arg::new("refresh")
+                    .long("refresh")
+                    .value_name("SECONDS")
+                    .help("The refresh interval of the balances and transactions")
+                    .default_value("30")
+                    .value_parser(clap::value_parser!(u64).range(1..)),
+            )
+            .arg(
+                Arg::new("snapshot")
+                    .long("snapshot")
+                    .help("Prints the dashboard data once and exits (without a terminal)")
+                    .action(ArgAction::SetTrue),
+            )
+    }
+
+    fn configure_sign_message(&self) -> Command {
+        Command::new("sign_message")
+            .about("Signs an off-chain message (proof of address ownership)")
+            .arg(
+                Arg::new("MESSAGE")
+                    .help("The message (UTF-8 text, or hex with --hex)")
+                    .required(true)
+                    .index(1),
+            )
+            .arg(self.message_hex_arg())
+            .arg(
+                Arg::new("raw")
+                    .long("raw")
+                    .help("Signs the message bytes as is (raw ed25519), without the Solana off-chain message format")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(self.keypair_selector_arg())
+    }
+
+    fn configure_verify_message(&self) -> Command {
+        Command::new("verify_message")
+            .about("Verifies the signature of an off-chain message and reports which public key signed it")
+            .arg(
+                Arg::new("MESSAGE")
+                    .help("The message (UTF-8 text, or hex with --hex)")
                     .required(true)
                     .index(1),
             )
+            .arg(
+                Arg::new("SIGNATURE")
+                    .help("The signature (base58)")
+                    .required(true)
+                    .index(2),
+            )
+            .arg(self.message_hex_arg())
+            .arg(
+                Arg::new("signer")
+                    .long("signer")
+                    .value_name("PUBKEY|NAME")
+                    .help("The expected signer (defaults to all the keys of the key store and the address book)"),
+            )
+    }
+
+    fn configure_token_accounts(&self) -> Command {
+        Command::new("token_accounts")
+            .about("Manages the token accounts of a key")
+            .subcommand_required(true)
+            .subcommand(
+                Command::new("cleanup")
+                    .about("Closes the empty token accounts of a key and reclaims their rent")
+                    .arg(self.keypair_selector_arg())
+                    .arg(
+                        Arg::new("burn-dust")
+                            .long("burn-dust")
+                            .value_name("[MINT=]MAX_AMOUNT")
+                            .action(ArgAction::Append)
+                            .help("Also burns and closes the accounts holding at most MAX_AMOUNT tokens, of any mint or of MINT only (can be repeated)"),
+                    )
+                    .arg(
+                        Arg::new("yes")
+                            .long("yes")
+                            .action(ArgAction::SetTrue)
+                            .help("Burns without asking for confirmation"),
+                    )
+                    .arg(
+                        Arg::new("dry-run")
+                            .long("dry-run")
+                            .action(ArgAction::SetTrue)
+                            .help("Lists the accounts to close without sending any transaction"),
+                    )
+                    .arg(
+                        Arg::new("no-wait")
+                            .long("no-wait")
+                            .action(ArgAction::SetTrue)
+                            .help("Returns the signatures as soon as the transactions are sent, without waiting for their confirmation"),
+                    ),
+            )
+    }
+
+    fn configure_create_ata(&self) -> Command {
+        Command::new("create_ata")
+            .about("Creates the associated token account of a key for a mint")
+            .arg(
+                Arg::new("MINT")
+                    .help("The mint of the token")
+                    .required(true),
+            )
+            .arg(
+                Arg::new("owner")
+                    .long("owner")
+                    .value_name("LABEL|PUBKEY")
+                    .help("The owner of the account (defaults to the paying key)"),
+            )
+            .arg(self.keypair_selector_arg())
+            .arg(
+                Arg::new("no-wait")
+                    .long("no-wait")
+                    .action(ArgAction::SetTrue)
+                    .help("Returns the signature as soon as the transaction is sent, without waiting for its confirmation"),
+            )
+    }
+
+    fn message_hex_arg(&self) -> Arg {
+        Arg::new("hex")
+            .long("hex")
+            .help("The message is given in hexadecimal")
+            .action(ArgAction::SetTrue)
     }
 
     pub fn handle_matches(&self, matches: ArgMatches) {
         match matches.subcommand() {
-            Some(("generate_seed", _)) => self.handle_generate_seed(),
+            Some(("generate_seed", sub_matches)) => self.handle_generate_seed(sub_matches),
             Some(("recover_seed", sub_matches)) => self.handle_recover_seed(sub_matches),
             Some(("send", sub_matches)) => self.handle_send(sub_matches),
-            Some(("pubkey", _)) => self.pubkey(),
+            Some(("batch_send", sub_matches)) => self.handle_batch_send(sub_matches),
+            Some(("sweep", sub_matches)) => self.handle_sweep(sub_matches),
+            Some(("confirm", sub_matches)) => self.handle_confirm(sub_matches),
+            Some(("pay", sub_matches)) => self.handle_pay(sub_matches),
+            Some(("request_payment", sub_matches)) => self.handle_request_payment(sub_matches),
+            Some(("decode", sub_matches)) => self.handle_decode(sub_matches),
+            Some(("sign_tx", sub_matches)) => self.handle_sign_tx(sub_matches),
+            Some(("alt", sub_matches)) => self.handle_alt(sub_matches),
+            Some(("multisig", sub_matches)) => self.handle_multisig(sub_matches),
+            Some(("token_accounts", sub_matches)) => self.handle_token_accounts(sub_matches),
+            Some(("create_ata", sub_matches)) => self.handle_create_ata(sub_matches),
+            Some(("pubkey", sub_matches)) => self.pubkey(sub_matches),
             Some(("balance_by_pubkey", sub_matches)) => self.handle_balance_by_pubkey(sub_matches),
-            _ => println!("Unknown command."),
+            Some(("history", sub_matches)) => self.handle_history(sub_matches),
+            Some(("wallet", sub_matches)) => self.handle_wallet(sub_matches),
+            Some(("contacts", sub_matches)) => self.handle_contacts(sub_matches),
+            Some(("grind", sub_matches)) => self.handle_grind(sub_matches),
+            Some(("backup", sub_matches)) => self.handle_backup(sub_matches),
+            Some(("bip85", sub_matches)) => self.handle_bip85(sub_matches),
+            Some(("config", sub_matches)) => self.handle_config(sub_matches),
+            Some(("audit", sub_matches)) => self.handle_audit(sub_matches),
+            Some(("health", _)) => self.handle_health(),
+            Some(("shell", _)) => Shell::new(self.config.clone()).run(),
+            Some(("tui", sub_matches)) => self.handle_tui(sub_matches),
+            Some(("sign_message", sub_matches)) => self.handle_sign_message(sub_matches),
+            Some(("verify_message", sub_matches)) => self.handle_verify_message(sub_matches),
+            _ => self.output.error("Unknown command."),
         }
     }
 
-    fn handle_generate_seed(&self) {
+    fn handle_generate_seed(&self, sub_matches: &ArgMatches) {
         let wallet_manager = WalletManager::new(self.config.clone());
-        wallet_manager.generate_and_print_random_mnemonic();
+        let word_count: usize = sub_matches
+            .get_one::<String>("words")
+            .expect("default value")
+            .parse()
+            .expect("validated by clap");
+
+        let source = if let Some(rolls) = sub_matches.get_one::<String>("entropy-dice") {
+            Some(EntropySource::Dice(
+                Some(rolls.clone()).filter(|rolls| !rolls.is_empty()),
+            ))
+        } else {
+            sub_matches
+                .get_one::<String>("entropy-hex")
+                .map(|hex| EntropySource::Hex(hex.clone()))
+        };
+
+        match source {
+            Some(source) => self.output.print_outcome(self.audited(
+                "generate_seed",
+                wallet_manager.generate_mnemonic_from_entropy(
+                    source,
+                    word_count,
+                    sub_matches.get_flag("mix-os-entropy"),
+                ),
+            )),
+            None if sub_matches.get_flag("mix-os-entropy") => self
+                .output
+                .error("--mix-os-entropy requires --entropy-dice or --entropy-hex."),
+            None => self.output.print_outcome(self.audited(
+                "generate_seed",
+                wallet_manager.generate_random_mnemonic(word_count),
+            )),
+        }
     }
 
     fn handle_recover_seed(&self, sub_matches: &ArgMatches) {
         if let Some(phrase) = sub_matches.get_one::<String>("PHRASE") {
             let wallet_manager = WalletManager::new(self.config.clone());
-            wallet_manager.generate_and_print_mnemonic_from_phrase(phrase);
+            self.output.print_outcome(self.audited(
+                "recover_seed",
+                wallet_manager.generate_mnemonic_from_phrase(phrase),
+            ));
         }
     }
 
-    fn pubkey(&self) {
+    fn pubkey(&self, sub_matches: &ArgMatches) {
         let keypair_manager = KeypairManager::new(self.config.clone());
-        keypair_manager.pubkey();
+        self.output.print_result(
+            keypair_manager.pubkey(sub_matches.get_one::<String>("from").map(String::as_str)),
+            "Failed to read key pair from file",
+        );
     }
 
     fn handle_balance_by_pubkey(&self, sub_matches: &ArgMatches) {
         if let Some(pubkey) = sub_matches.get_one::<String>("PUBKEY") {
             let wallet_manager = WalletManager::new(self.config.clone());
-            match wallet_manager.get_balance_by_pubkey(pubkey) {
-                Ok(balance) => {
-                    let sol_value = balance as f64 / 1_000_000_000_f64; // Convertir les lamports en SOL.
-                    println!("Balance: {:.9} SOL ({} lamports)", sol_value, balance);
-                }
-                Err(e) => println!("Failed to retrieve balance: {}", e),
-            }
+            self.output.print_result(
+                wallet_manager.get_balance_by_pubkey(pubkey),
+                "Failed to retrieve balance",
+            );
+        }
+    }
+
+    fn handle_history(&self, sub_matches: &ArgMatches) {
+        if let Some(address) = sub_matches.get_one::<String>("ADDRESS") {
+            let limit = *sub_matches
+                .get_one::<usize>("limit")
+                .expect("default value");
+            let wallet_manager = WalletManager::new(self.config.clone());
+            self.output.print_result(
+                wallet_manager.get_history(address, limit),
+                "Failed to retrieve history",
+            );
         }
     }
 
     fn handle_send(&self, sub_matches: &ArgMatches) {
         let transaction_manager = TransactionManager::new(self.config.clone());
-        match transaction_manager.send_transaction(sub_matches) {
-            Ok(_) => println!("Transaction sent successfully!"),
-            Err(e) => println!("Failed to send transaction: {}", e),
+        self.output.print_result(
+            self.audited("send", transaction_manager.send_transaction(sub_matches)),
+            "Failed to send transaction",
+        );
+    }
+
+    fn handle_batch_send(&self, sub_matches: &ArgMatches) {
+        let file = sub_matches.get_one::<String>("FILE").expect("required");
+        let lookup_tables: Vec<String> = sub_matches
+            .get_many::<String>("lookup-table")
+            .unwrap_or_default()
+            .cloned()
+            .collect();
+        let transaction_manager = TransactionManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "batch_send",
+                transaction_manager.batch_send(
+                    file,
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                    &lookup_tables,
+                    !sub_matches.get_flag("no-wait"),
+                    &TransactionManager::recipient_overrides(sub_matches),
+                ),
+            ),
+            "Failed to send batch payout",
+        );
+    }
+
+    fn handle_sweep(&self, sub_matches: &ArgMatches) {
+        let to = sub_matches.get_one::<String>("to").expect("required");
+        let sweep_manager = SweepManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "sweep",
+                sweep_manager.sweep(
+                    to,
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                    sub_matches.get_flag("all"),
+                    sub_matches.get_flag("yes"),
+                    !sub_matches.get_flag("no-wait"),
+                ),
+            ),
+            "Failed to sweep",
+        );
+    }
+
+    fn handle_confirm(&self, sub_matches: &ArgMatches) {
+        if let Some(signature) = sub_matches.get_one::<String>("SIGNATURE") {
+            let transaction_manager = TransactionManager::new(self.config.clone());
+            self.output.print_result(
+                transaction_manager
+                    .confirm(signature, sub_matches.get_one::<u64>("timeout").copied()),
+                "Failed to confirm transaction",
+            );
         }
     }
+
+    fn handle_pay(&self, sub_matches: &ArgMatches) {
+        let url = sub_matches.get_one::<String>("URL").expect("required");
+        let transaction_manager = TransactionManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "pay",
+                transaction_manager.pay(
+                    url,
+                    sub_matches.get_one::<String>("amount").map(String::as_str),
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                    !sub_matches.get_flag("no-wait"),
+                    &TransactionManager::recipient_overrides(sub_matches),
+                ),
+            ),
+            "Failed to pay",
+        );
+    }
+
+    fn handle_request_payment(&self, sub_matches: &ArgMatches) {
+        let transaction_manager = TransactionManager::new(self.config.clone());
+        self.output.print_result(
+            transaction_manager.request_payment(sub_matches),
+            "Failed to create payment request",
+        );
+    }
+
+    fn handle_decode(&self, sub_matches: &ArgMatches) {
+        let transaction = sub_matches
+            .get_one::<String>("TRANSACTION")
+            .expect("required");
+        let decode_manager = DecodeManager::new(self.config.clone());
+        self.output.print_result(
+            decode_manager.decode(transaction),
+            "Failed to decode transaction",
+        );
+    }
+
+    fn handle_sign_tx(&self, sub_matches: &ArgMatches) {
+        let transaction = sub_matches
+            .get_one::<String>("TRANSACTION")
+            .expect("required");
+        let decode_manager = DecodeManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "sign_tx",
+                decode_manager.sign(
+                    transaction,
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                    sub_matches.get_flag("yes"),
+                ),
+            ),
+            "Failed to sign transaction",
+        );
+    }
+
+    fn handle_alt(&self, sub_matches: &ArgMatches) {
+        let lookup_table_manager = LookupTableManager::new(self.config.clone());
+        let Some((action, args)) = sub_matches.subcommand() else {
+            return;
+        };
+        if action == "show" {
+            let table = args.get_one::<String>("TABLE").expect("required");
+            self.output.print_result(
+                lookup_table_manager.show(table),
+                "Failed to read address lookup table",
+            );
+            return;
+        }
+
+        let from = args.get_one::<String>("from").map(String::as_str);
+        let wait = !args.get_flag("no-wait");
+        let table = || args.get_one::<String>("TABLE").expect("required").as_str();
+        let result = match action {
+            "create" => lookup_table_manager.create(from, wait),
+            "extend" => {
+                let addresses: Vec<String> = args
+                    .get_many::<String>("ADDRESSES")
+                    .expect("required")
+                    .cloned()
+                    .collect();
+                lookup_table_manager.extend(table(), &addresses, from, wait)
+            }
+            "freeze" => lookup_table_manager.freeze(table(), from, wait),
+            "deactivate" => lookup_table_manager.deactivate(table(), from, wait),
+            "close" => lookup_table_manager.close(table(), from, wait),
+            _ => return,
+        };
+        self.output.print_result(
+            self.audited(&format!("alt {}", action), result),
+            &format!("Failed to {} address lookup table", action),
+        );
+    }
+
+    fn handle_multisig(&self, sub_matches: &ArgMatches) {
+        let multisig_manager = MultisigManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("propose", args)) => self.output.print_result(
+                multisig_manager.propose(args),
+                "Failed to create signing request",
+            ),
+            Some(("approve", args)) => {
+                let file = args.get_one::<String>("FILE").expect("required");
+                self.output.print_result(
+                    self.audited(
+                        "multisig approve",
+                        multisig_manager.approve(
+                            file,
+                            args.get_one::<String>("from").map(String::as_str),
+                            args.get_flag("yes"),
+                        ),
+                    ),
+                    "Failed to approve signing request",
+                );
+            }
+            Some(("status", args)) => {
+                let file = args.get_one::<String>("FILE").expect("required");
+                self.output.print_result(
+                    multisig_manager.status(file),
+                    "Failed to read signing request",
+                );
+            }
+            Some(("submit", args)) => {
+                let file = args.get_one::<String>("FILE").expect("required");
+                self.output.print_result(
+                    self.audited(
+                        "multisig submit",
+                        multisig_manager.submit(file, !args.get_flag("no-wait")),
+                    ),
+                    "Failed to submit signing request",
+                );
+            }
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    fn handle_token_accounts(&self, sub_matches: &ArgMatches) {
+        if let Some(("cleanup", args)) = sub_matches.subcommand() {
+            let token_manager = TokenManager::new(self.config.clone());
+            self.output.print_result(
+                self.audited(
+                    "token_accounts cleanup",
+                    token_manager.cleanup(
+                        args.get_one::<String>("from").map(String::as_str),
+                        &args
+                            .get_many::<String>("burn-dust")
+                            .unwrap_or_default()
+                            .map(String::as_str)
+                            .collect::<Vec<_>>(),
+                        args.get_flag("dry-run"),
+                        args.get_flag("yes"),
+                        !args.get_flag("no-wait"),
+                    ),
+                ),
+                "Failed to clean up token accounts",
+            );
+        }
+    }
+
+    fn handle_create_ata(&self, sub_matches: &ArgMatches) {
+        let mint = sub_matches.get_one::<String>("MINT").expect("required");
+        let token_manager = TokenManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "create_ata",
+                token_manager.create_ata(
+                    mint,
+                    sub_matches.get_one::<String>("owner").map(String::as_str),
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                    !sub_matches.get_flag("no-wait"),
+                ),
+            ),
+            "Failed to create token account",
+        );
+    }
+
+    fn handle_wallet(&self, sub_matches: &ArgMatches) {
+        let keystore_manager = KeystoreManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("list", _)) => self
+                .output
+                .print_result(keystore_manager.list(), "Failed to read key store"),
+            Some(("rename", args)) => {
+                let label = args.get_one::<String>("LABEL").expect("required");
+                let new_label = args.get_one::<String>("NEW_LABEL").expect("required");
+                self.output.print_result(
+                    keystore_manager.rename(label, new_label),
+                    "Failed to rename key",
+                );
+            }
+            Some(("use", args)) => {
+                let label = args.get_one::<String>("LABEL").expect("required");
+                self.output
+                    .print_result(keystore_manager.use_key(label), "Failed to select key");
+            }
+            Some(("watch", args)) => {
+                let label = args.get_one::<String>("LABEL").expect("required");
+                let pubkey = args.get_one::<String>("PUBKEY").expect("required");
+                self.output.print_result(
+                    keystore_manager.watch(label, pubkey),
+                    "Failed to add watch-only key",
+                );
+            }
+            Some(("remove", args)) => {
+                let label = args.get_one::<String>("LABEL").expect("required");
+                self.output.print_result(
+                    keystore_manager.remove(label, args.get_flag("delete-file")),
+                    "Failed to remove key",
+                );
+            }
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    fn handle_contacts(&self, sub_matches: &ArgMatches) {
+        let address_book_manager = AddressBookManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("add", args)) => {
+                let name = args.get_one::<String>("NAME").expect("required");
+                let pubkey = args.get_one::<String>("PUBKEY").expect("required");
+                self.output.print_result(
+                    address_book_manager.add(name, pubkey),
+                    "Failed to add contact",
+                );
+            }
+            Some(("list", _)) => self
+                .output
+                .print_result(address_book_manager.list(), "Failed to read address book"),
+            Some(("remove", args)) => {
+                let name = args.get_one::<String>("NAME").expect("required");
+                self.output.print_result(
+                    address_book_manager.remove(name),
+                    "Failed to remove contact",
+                );
+            }
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    fn handle_grind(&self, sub_matches: &ArgMatches) {
+        let pattern = GrindPattern {
+            prefix: sub_matches.get_one::<String>("starts-with").cloned(),
+            suffix: sub_matches.get_one::<String>("ends-with").cloned(),
+            ignore_case: sub_matches.get_flag("ignore-case"),
+        };
+        let mode = if sub_matches.get_flag("use-mnemonic") {
+            GrindMode::Mnemonic {
+                derivation_index: *sub_matches
+                    .get_one::<usize>("derivation-index")
+                    .expect("default value"),
+                derivation_template: self.config.derivation_template.clone(),
+            }
+        } else {
+            GrindMode::Random
+        };
+        let count = *sub_matches
+            .get_one::<usize>("count")
+            .expect("default value");
+        let threads = sub_matches.get_one::<usize>("threads").copied();
+        let label = sub_matches
+            .get_one::<String>("label")
+            .expect("default value");
+
+        let grind_manager = GrindManager::new(self.config.clone());
+        self.output.print_outcome(self.audited(
+            "grind",
+            grind_manager.grind(&pattern, mode, threads, count, label),
+        ));
+    }
+
+    fn handle_backup(&self, sub_matches: &ArgMatches) {
+        let backup_manager = BackupManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("split", args)) => {
+                let phrase = args.get_one::<String>("PHRASE").expect("required");
+                let groups = match args.get_many::<String>("group") {
+                    Some(groups) => match groups.map(|g| Self::parse_group(g)).collect() {
+                        Ok(groups) => groups,
+                        Err(e) => {
+                            self.output.error(e);
+                            return;
+                        }
+                    },
+                    None => vec![GroupSpec {
+                        member_threshold: *args.get_one::<u8>("threshold").expect("required"),
+                        member_count: *args.get_one::<u8>("shares").expect("required"),
+                    }],
+                };
+                let group_threshold = *args
+                    .get_one::<u8>("group-threshold")
+                    .expect("default value");
+                let iteration_exponent = *args
+                    .get_one::<u8>("iteration-exponent")
+                    .expect("default value");
+                self.output.print_result(
+                    self.audited(
+                        "backup split",
+                        backup_manager.split(
+                            phrase,
+                            group_threshold,
+                            &groups,
+                            iteration_exponent,
+                            args.get_flag("extendable"),
+                        ),
+                    ),
+                    "Failed to split mnemonic",
+                );
+            }
+            Some(("combine", args)) => {
+                let shares: Vec<String> = args
+                    .get_many::<String>("share")
+                    .map(|shares| shares.cloned().collect())
+                    .unwrap_or_default();
+                self.output.print_result(
+                    self.audited("backup combine", backup_manager.combine(&shares)),
+                    "Failed to combine shares",
+                );
+            }
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    fn handle_bip85(&self, sub_matches: &ArgMatches) {
+        let bip85_manager = Bip85Manager::new(self.config.clone());
+        let Some((command, args)) = sub_matches.subcommand() else {
+            self.output.error("Unknown command.");
+            return;
+        };
+
+        let phrase = args.get_one::<String>("PHRASE").expect("required");
+        let index = *args.get_one::<u32>("index").expect("default value");
+        let import_label = || args.get_one::<String>("import").map(String::as_str);
+
+        let result = match command {
+            "mnemonic" => bip85_manager.derive_mnemonic(
+                phrase,
+                *args.get_one::<u32>("language").expect("default value"),
+                *args.get_one::<u32>("words").expect("default value"),
+                index,
+                import_label(),
+            ),
+            "hex" => bip85_manager.derive_hex(
+                phrase,
+                *args.get_one::<u32>("bytes").expect("default value"),
+                index,
+            ),
+            "ed25519" => bip85_manager.derive_ed25519(phrase, index, import_label()),
+            _ => {
+                self.output.error("Unknown command.");
+                return;
+            }
+        };
+        self.output
+            .print_outcome(self.audited(&format!("bip85 {}", command), result));
+    }
+
+    fn handle_health(&self) {
+        let rpc_manager = RpcManager::new(self.config.clone());
+        self.output
+            .print_result(rpc_manager.health(), "Failed to check RPC endpoints");
+    }
+
+    fn handle_tui(&self, sub_matches: &ArgMatches) {
+        if sub_matches.get_flag("snapshot") {
+            let dashboard_manager = DashboardManager::new(self.config.clone());
+            self.output
+                .print_result(dashboard_manager.load(None), "Failed to load dashboard");
+            return;
+        }
+
+        let refresh = *sub_matches
+            .get_one::<u64>("refresh")
+            .expect("default value");
+        if let Err(e) = Tui::new(self.config.clone()).run(Duration::from_secs(refresh)) {
+            self.output.error(format!("Failed to run dashboard: {}", e));
+        }
+    }
+
+    fn handle_sign_message(&self, sub_matches: &ArgMatches) {
+        let message = sub_matches.get_one::<String>("MESSAGE").expect("required");
+        let message_manager = MessageManager::new(self.config.clone());
+        self.output.print_result(
+            self.audited(
+                "sign_message",
+                message_manager.sign(
+                    message,
+                    sub_matches.get_flag("hex"),
+                    sub_matches.get_flag("raw"),
+                    sub_matches.get_one::<String>("from").map(String::as_str),
+                ),
+            ),
+            "Failed to sign message",
+        );
+    }
+
+    fn handle_verify_message(&self, sub_matches: &ArgMatches) {
+        let message = sub_matches.get_one::<String>("MESSAGE").expect("required");
+        let signature = sub_matches
+            .get_one::<String>("SIGNATURE")
+            .expect("required");
+        let message_manager = MessageManager::new(self.config.clone());
+        self.output.print_result(
+            message_manager.verify(
+                message,
+                signature,
+                sub_matches.get_flag("hex"),
+                sub_matches.get_one::<String>("signer").map(String::as_str),
+            ),
+            "Failed to verify message",
+        );
+    }
+
+    fn handle_audit(&self, sub_matches: &ArgMatches) {
+        let audit_manager = AuditManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("show", args)) => {
+                let filter = AuditFilter {
+                    command: args.get_one::<String>("command").cloned(),
+                    pubkey: args.get_one::<String>("pubkey").cloned(),
+                    since: args.get_one::<String>("since").cloned(),
+                    limit: args.get_one::<usize>("limit").copied(),
+                };
+                self.output
+                    .print_result(audit_manager.show(&filter), "Failed to read audit log");
+            }
+            Some(("verify", args)) => self.output.print_result(
+                audit_manager.verify(args.get_one::<String>("anchor").map(String::as_str)),
+                "Failed to verify audit log",
+            ),
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    /// Enregistre le résultat réussi d'une commande dans le journal d'audit (voir `AuditLog`), puis le retourne.
+    /// Une transaction envoyée mais non confirmée est aussi enregistrée, bien que la commande échoue.
+    /// Une erreur d'écriture du journal est signalée sans masquer le résultat de la commande.
+    fn audited<T: Serialize, E: AsRef<dyn std::error::Error>>(
+        &self,
+        command: &str,
+        result: Result<T, E>,
+    ) -> Result<T, E> {
+        if let Err(e) = AuditLog::new(self.config.clone()).record_outcome(command, &result) {
+            self.output
+                .message(format!("Warning: failed to write the audit log: {}", e));
+        }
+        result
+    }
+
+    fn handle_config(&self, sub_matches: &ArgMatches) {
+        let config_manager = ConfigManager::new(self.config.clone());
+        match sub_matches.subcommand() {
+            Some(("show", _)) => self.output.print(&config_manager.show()),
+            _ => self.output.error("Unknown command."),
+        }
+    }
+
+    /// Analyse une définition de groupe de la forme "THRESHOLD/COUNT" (par exemple "2/3").
+    fn parse_group(group: &str) -> Result<GroupSpec, String> {
+        let invalid = || {
+            format!(
+                "Invalid group '{}': expected THRESHOLD/COUNT, e.g. 2/3",
+                group
+            )
+        };
+        let (threshold, count) = group.split_once('/').ok_or_else(invalid)?;
+        Ok(GroupSpec {
+            member_threshold: threshold.trim().parse().map_err(|_| invalid())?,
+            member_count: count.trim().parse().map_err(|_| invalid())?,
+        })
+    }
 }
@@ -0,0 +1,153 @@
+use crate::config::config_file::SpendingPolicy;
+use crate::config::solana_cli_config::SolanaCliConfig;
+use crate::config::wallet_config::WalletConfig;
+use serde::Serialize;
+use std::fmt;
+
+/// Une valeur de la configuration et son origine.
+#[derive(Debug, Serialize)]
+pub struct ConfigValueOutput {
+    pub key: &'static str,
+    pub value: String,
+    pub source: String,
+}
+
+/// Résultat de la commande "config show".
+#[derive(Debug, Serialize)]
+pub struct ConfigOutput {
+    pub config_file: Option<String>,
+    pub solana_cli_config: Option<String>,
+    pub values: Vec<ConfigValueOutput>,
+}
+
+impl fmt::Display for ConfigOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!(
+                "Config file: {}",
+                self.config_file.as_deref().unwrap_or("-")
+            ),
+            format!(
+                "Solana CLI config: {}",
+                self.solana_cli_config.as_deref().unwrap_or("-")
+            ),
+        ];
+        for value in &self.values {
+            lines.push(format!("{}: {} ({})", value.key, value.value, value.source));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct ConfigManager {
+    config: WalletConfig,
+}
+
+impl ConfigManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Retourne chaque valeur de la configuration et son origine (option, variable d'environnement, profil, CLI Solana ou valeur par défaut).
+    pub fn show(&self) -> ConfigOutput {
+        let config = &self.config;
+
+        let compute_unit_limit = config
+            .fee_policy
+            .compute_unit_limit
+            .map_or_else(|| "-".to_string(), |limit| limit.to_string());
+        let spending_policy = Self::spending_policy_summary(&config.spending_policy);
+        let values = [
+            (
+                "profile",
+                config.profile.clone().unwrap_or_else(|| "-".to_string()),
+            ),
+            ("rpc_url", config.rpc_url.clone()),
+            ("rpc_urls", config.rpc_urls.join(", ")),
+            ("commitment", config.commitment.clone()),
+            ("keypair_path", config.keypair_path.clone()),
+            (
+                "keypair_derivations_path",
+                config.keypair_derivations_path.clone(),
+            ),
+            ("keystore_path", config.keystore_path.clone()),
+            ("address_book_path", config.address_book_path.clone()),
+            ("shell_history_path", config.shell_history_path.clone()),
+            ("spending_ledger_path", config.spending_ledger_path.clone()),
+            ("audit_log_path", config.audit_log_path.clone()),
+            ("shell_idle_timeout", config.shell_idle_timeout.to_string()),
+            ("nb_derivations", config.nb_derivations.to_string()),
+            ("derivation_template", config.derivation_template.clone()),
+            ("explorer_template", config.explorer_template.clone()),
+            (
+                "compute_unit_price",
+                config.fee_policy.compute_unit_price.to_string(),
+            ),
+            ("compute_unit_limit", compute_unit_limit),
+            ("max_resends", config.retry_policy.max_resends.to_string()),
+            (
+                "confirm_timeout",
+                config.retry_policy.confirm_timeout.to_string(),
+            ),
+            ("rpc_retries", config.retry_policy.rpc_retries.to_string()),
+            (
+                "rpc_backoff_ms",
+                config.retry_policy.rpc_backoff_ms.to_string(),
+            ),
+            ("max_slot_lag", config.retry_policy.max_slot_lag.to_string()),
+            ("spending_policy", spending_policy),
+            ("output", config.output.clone()),
+        ];
+
+        ConfigOutput {
+            config_file: config.config_path.clone(),
+            solana_cli_config: SolanaCliConfig::path().map(|path| path.display().to_string()),
+            values: values
+                .into_iter()
+                .map(|(key, value)| ConfigValueOutput {
+                    key,
+                    value,
+                    source: config
+                        .sources
+                        .get(key)
+                        .map_or_else(|| "default".to_string(), ToString::to_string),
+                })
+                .collect(),
+        }
+    }
+
+    /// Résumé de la politique de dépense du profil ("-" si elle est vide).
+    fn spending_policy_summary(policy: &SpendingPolicy) -> String {
+        if policy.is_empty() {
+            return "-".to_string();
+        }
+
+        let mut parts = Vec::new();
+        let limits = [
+            ("max_per_transaction", policy.max_per_transaction),
+            ("max_daily", policy.max_daily),
+            ("confirm_above", policy.confirm_above),
+        ];
+        for (name, value) in limits {
+            if let Some(value) = value {
+                parts.push(format!("{}={}", name, value));
+            }
+        }
+        if !policy.allowlist.is_empty() {
+            parts.push(format!("allowlist={}", policy.allowlist.len()));
+        }
+        if !policy.denylist.is_empty() {
+            parts.push(format!("denylist={}", policy.denylist.len()));
+        }
+        if policy.mainnet_strict {
+            parts.push("mainnet_strict".to_string());
+        }
+        if !policy.keys.is_empty() {
+            parts.push(format!(
+                "keys={}",
+                policy.keys.keys().cloned().collect::<Vec<_>>().join("|")
+            ));
+        }
+        parts.join(", ")
+    }
+}
@@ -0,0 +1,223 @@
+use crate::app::keystore_manager::KeystoreManager;
+use crate::app::wallet_manager::{HistoryOutput, WalletManager};
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::token::TokenBalance;
+use crate::store::keystore::KeySource;
+use serde::Serialize;
+use std::fmt;
+
+/// Le nombre de transactions récentes affichées pour le compte sélectionné.
+const HISTORY_LIMIT: usize = 10;
+
+/// Une paire de clés du key store, avec ses soldes.
+#[derive(Debug, Serialize)]
+pub struct AccountOutput {
+    pub label: String,
+    pub pubkey: String,
+    /// Vrai pour la paire de clés utilisée par défaut (voir "wallet use").
+    pub active: bool,
+    pub watch_only: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lamports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sol: Option<f64>,
+    pub tokens: Vec<TokenBalance>,
+    /// L'erreur survenue lors de la lecture des soldes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AccountOutput {
+    /// Le solde affiché dans la liste des comptes.
+    pub fn balance(&self) -> String {
+        match (&self.error, self.sol) {
+            (Some(error), _) => format!("error: {}", error),
+            (None, Some(sol)) => format!("{:.9} SOL", sol),
+            (None, None) => "-".to_string(),
+        }
+    }
+}
+
+/// Résultat de la commande "tui --snapshot" : les données affichées par le tableau de bord.
+#[derive(Debug, Serialize)]
+pub struct DashboardOutput {
+    pub rpc_url: String,
+    pub accounts: Vec<AccountOutput>,
+    /// Le label du compte sélectionné (dont les tokens et les transactions récentes sont affichés).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history: Option<HistoryOutput>,
+    /// L'erreur survenue lors de la lecture de l'historique du compte sélectionné.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_error: Option<String>,
+}
+
+impl DashboardOutput {
+    pub fn selected_account(&self) -> Option<&AccountOutput> {
+        let selected = self.selected.as_deref()?;
+        self.accounts
+            .iter()
+            .find(|account| account.label == selected)
+    }
+
+    /// Les lignes des panneaux "Keypairs", "Tokens" et "Recent transactions".
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec!["Keypairs".to_string()];
+        if self.accounts.is_empty() {
+            lines.push("  No keys in key store.".to_string());
+        }
+        for account in &self.accounts {
+            let marker = if self.selected.as_deref() == Some(account.label.as_str()) {
+                ">"
+            } else {
+                " "
+            };
+            let active = if account.active { "*" } else { " " };
+            let mut line = format!(
+                "{}{} {:<16} {:<44} {}",
+                marker,
+                active,
+                account.label,
+                account.pubkey,
+                account.balance()
+            );
+            if !account.tokens.is_empty() {
+                line.push_str(&format!("  {} token(s)", account.tokens.len()));
+            }
+            if account.watch_only {
+                line.push_str("  (watch-only)");
+            }
+            lines.push(line);
+        }
+
+        let Some(account) = self.selected_account() else {
+            return lines;
+        };
+
+        lines.push(String::new());
+        lines.push(format!("Tokens - {}", account.label));
+        if account.tokens.is_empty() {
+            lines.push("  No token accounts found.".to_string());
+        }
+        for token in &account.tokens {
+            lines.push(format!("  {:<44} {}", token.mint, token.amount));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("Recent transactions - {}", account.label));
+        match (&self.history, &self.history_error) {
+            (_, Some(error)) => lines.push(format!("  Failed to retrieve history: {}", error)),
+            (Some(history), None) => lines.extend(
+                history
+                    .to_string()
+                    .lines()
+                    .map(|line| format!("  {}", line)),
+            ),
+            (None, None) => {}
+        }
+        lines
+    }
+}
+
+impl fmt::Display for DashboardOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lines().join("\n"))
+    }
+}
+
+/// Le service du tableau de bord ("tui") : lit les paires de clés, leurs soldes (SOL et tokens) et l'historique
+/// du compte sélectionné, avec `KeystoreManager` et `WalletManager`.
+pub struct DashboardManager {
+    config: WalletConfig,
+}
+
+impl DashboardManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Lit les données du tableau de bord.
+    /// Les erreurs de lecture des soldes et de l'historique sont retournées compte par compte, sans interrompre la lecture.
+    ///
+    /// # Arguments:
+    /// - selected - Le label du compte sélectionné (la paire de clés active, ou la première, si absent ou inconnu).
+    pub fn load(
+        &self,
+        selected: Option<&str>,
+    ) -> Result<DashboardOutput, Box<dyn std::error::Error>> {
+        let keys = KeystoreManager::new(self.config.clone()).list()?;
+        let wallet_manager = WalletManager::new(self.config.clone());
+
+        // Sans clé active ("wallet use"), la paire de clés utilisée par défaut est celle de KEYPAIR_PATH.
+        let active = keys.active.clone().or_else(|| {
+            keys.keys
+                .iter()
+                .find(|key| key.path.as_deref() == Some(self.config.keypair_path.as_str()))
+                .map(|key| key.label.clone())
+        });
+
+        let accounts: Vec<AccountOutput> = keys
+            .keys
+            .into_iter()
+            .map(|key| {
+                let balance = wallet_manager.get_balance_by_pubkey(&key.pubkey);
+                let tokens = wallet_manager.get_token_balances(&key.pubkey);
+                let error = match (&balance, &tokens) {
+                    (Err(e), _) | (_, Err(e)) => Some(e.to_string()),
+                    _ => None,
+                };
+                let balance = balance.ok();
+                AccountOutput {
+                    active: active.as_deref() == Some(key.label.as_str()),
+                    watch_only: key.source == KeySource::WatchOnly,
+                    lamports: balance.as_ref().map(|balance| balance.lamports),
+                    sol: balance.map(|balance| balance.sol),
+                    tokens: tokens.map(|tokens| tokens.tokens).unwrap_or_default(),
+                    error,
+                    label: key.label,
+                    pubkey: key.pubkey,
+                }
+            })
+            .collect();
+
+        let selected = selected
+            .filter(|selected| accounts.iter().any(|account| account.label == *selected))
+            .or(active.as_deref())
+            .or(accounts.first().map(|account| account.label.as_str()))
+            .map(ToString::to_string);
+
+        let mut output = DashboardOutput {
+            rpc_url: self.config.rpc_url.clone(),
+            accounts,
+            selected,
+            history: None,
+            history_error: None,
+        };
+        self.load_history(&mut output);
+        Ok(output)
+    }
+
+    /// Lit l'historique du compte sélectionné (après un changement de sélection).
+    pub fn load_history(&self, output: &mut DashboardOutput) {
+        let Some(pubkey) = output
+            .selected_account()
+            .map(|account| account.pubkey.clone())
+        else {
+            output.history = None;
+            output.history_error = None;
+            return;
+        };
+
+        match WalletManager::new(self.config.clone()).get_history(&pubkey, HISTORY_LIMIT) {
+            Ok(history) => {
+                output.history = Some(history);
+                output.history_error = None;
+            }
+            Err(e) => {
+                output.history = None;
+                output.history_error = Some(e.to_string());
+            }
+        }
+    }
+}
@@ -0,0 +1,475 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::decoder::{DecodedInstruction, SolanaDecoder};
+use crate::solana::policy::SolanaPolicy;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
+use serde_json::Value;
+use solana_sdk::message::{AccountKeys, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Signature, Signer};
+use solana_sdk::transaction::VersionedTransaction;
+use std::fmt;
+use std::io::{self, Read};
+
+/// Une signature attendue par la transaction.
+#[derive(Debug, Serialize)]
+pub struct DecodedSignature {
+    pub signer: String,
+    pub signature: String,
+    /// "valid", "invalid" (le message a été modifié après la signature) ou "missing" (pas encore signée).
+    pub status: String,
+}
+
+/// Un compte du message.
+#[derive(Debug, Serialize)]
+pub struct DecodedAccount {
+    pub index: usize,
+    /// L'adresse du compte (absente si elle est dans une table d'adresses non chargée).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pubkey: Option<String>,
+    pub signer: bool,
+    pub writable: bool,
+    /// La table d'adresses d'où provient le compte (transactions v0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lookup_table: Option<String>,
+}
+
+/// Résultat de la commande "decode".
+#[derive(Debug, Serialize)]
+pub struct DecodeOutput {
+    /// "legacy" ou "0".
+    pub version: String,
+    pub fee_payer: String,
+    pub recent_blockhash: String,
+    pub signatures: Vec<DecodedSignature>,
+    pub accounts: Vec<DecodedAccount>,
+    pub instructions: Vec<DecodedInstruction>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for DecodeOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Version: {}", self.version),
+            format!("Fee payer: {}", self.fee_payer),
+            format!("Recent blockhash: {}", self.recent_blockhash),
+            "Signatures:".to_string(),
+        ];
+        for signature in &self.signatures {
+            lines.push(format!("  {}: {}", signature.signer, signature.status));
+        }
+
+        lines.push("Accounts:".to_string());
+        for account in &self.accounts {
+            let mut flags = Vec::new();
+            if account.signer {
+                flags.push("signer".to_string());
+            }
+            flags.push(
+                if account.writable {
+                    "writable"
+                } else {
+                    "readonly"
+                }
+                .to_string(),
+            );
+            if let Some(table) = &account.lookup_table {
+                flags.push(format!("lookup table {}", table));
+            }
+            lines.push(format!(
+                "  #{} {} ({})",
+                account.index,
+                account.pubkey.as_deref().unwrap_or("<unresolved>"),
+                flags.join(", ")
+            ));
+        }
+
+        lines.push("Instructions:".to_string());
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            if !instruction.recognised {
+                lines.push(format!(
+                    "  {}. UNRECOGNISED {} ({})",
+                    i + 1,
+                    instruction.program,
+                    instruction.program_id
+                ));
+                lines.push(format!(
+                    "       accounts: {}",
+                    instruction.accounts.join(", ")
+                ));
+                lines.push(format!("       data: {}", instruction.data));
+                continue;
+            }
+            lines.push(format!(
+                "  {}. {}: {}",
+                i + 1,
+                instruction.program,
+                instruction.instruction_type.as_deref().unwrap_or("-")
+            ));
+            if let Value::Object(info) = &instruction.info {
+                for (key, value) in info {
+                    lines.push(format!(
+                        "       {}: {}",
+                        key,
+                        Self::describe_value(key, value)
+                    ));
+                }
+            }
+        }
+
+        for warning in &self.warnings {
+            lines.push(format!("Warning: {}", warning));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl DecodeOutput {
+    /// Une valeur lisible : montant de token (en unités du token), lamports (avec leur équivalent en SOL)...
+    fn describe_value(key: &str, value: &Value) -> String {
+        match value {
+            Value::String(value) => value.clone(),
+            // Les micro-lamports (prix d'une unité de calcul) ne sont pas convertis.
+            Value::Number(number)
+                if key.to_lowercase().ends_with("lamports") && !key.starts_with("micro") =>
+            {
+                match number.as_u64() {
+                    Some(lamports) => {
+                        format!("{} ({} SOL)", lamports, lamports as f64 / 1_000_000_000_f64)
+                    }
+                    None => number.to_string(),
+                }
+            }
+            Value::Object(object) if object.contains_key("uiAmountString") => object
+                ["uiAmountString"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+            value => value.to_string(),
+        }
+    }
+}
+
+/// Le résultat de `DecodeManager::sign_transactions`.
+#[derive(Debug)]
+pub struct SignedTransactions {
+    pub signer: Pubkey,
+    /// Vrai si la clé paie les frais d'une des transactions.
+    pub fee_payer: bool,
+    /// Les avertissements : frais payés par la clé, signatures invalides d'autres signataires.
+    pub warnings: Vec<String>,
+}
+
+/// Résultat de la commande "sign_tx".
+#[derive(Debug, Serialize)]
+pub struct SignTxOutput {
+    /// La transaction avec notre signature, en base64.
+    pub transaction: String,
+    pub signer: String,
+    /// Notre signature, ajoutée à la transaction.
+    pub signature: String,
+    /// Vrai si nous payons les frais de la transaction.
+    pub fee_payer: bool,
+    /// Vrai si toutes les signatures requises sont présentes (la transaction peut être envoyée).
+    pub complete: bool,
+    /// Les signataires dont la signature manque encore.
+    pub missing_signers: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for SignTxOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Signed by: {}", self.signer),
+            format!("Signature: {}", self.signature),
+        ];
+        if self.complete {
+            lines.push("The transaction is fully signed.".to_string());
+        } else {
+            lines.push(format!(
+                "The transaction is partially signed. Missing signatures: {}",
+                self.missing_signers.join(", ")
+            ));
+        }
+        for warning in &self.warnings {
+            lines.push(format!("Warning: {}", warning));
+        }
+        lines.push(format!("Transaction: {}", self.transaction));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct DecodeManager {
+    config: WalletConfig,
+}
+
+impl DecodeManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Décode une transaction sérialisée (legacy ou v0, en base64 ou en base58) sans la signer ni l'envoyer :
+    /// signatures, comptes (signataire, en écriture) et instructions décodées.
+    /// Les tables d'adresses des transactions v0 sont chargées via RPC ; si elles ne peuvent pas l'être,
+    /// les comptes qu'elles contiennent restent non résolus.
+    ///
+    /// # Arguments:
+    /// - encoded - La transaction encodée, ou "-" pour la lire sur l'entrée standard.
+    pub fn decode(&self, encoded: &str) -> Result<DecodeOutput, Box<dyn std::error::Error>> {
+        let transaction = if encoded == "-" {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            SolanaDecoder::deserialize(&input)?
+        } else {
+            SolanaDecoder::deserialize(encoded)?
+        };
+        Ok(self.inspect(&transaction))
+    }
+
+    /// Ajoute notre signature à une transaction construite par un tiers (dApp, co-signataire...),
+    /// après avoir affiché son contenu et demandé confirmation.
+    ///
+    /// # Arguments:
+    /// - encoded - La transaction encodée (base64 ou base58).
+    /// - from - La clé du key store qui signe (clé active par défaut).
+    /// - yes - Signe sans demander de confirmation.
+    ///
+    /// # Returns:
+    /// La transaction signée (partiellement s'il manque d'autres signatures), ou une erreur si notre clé
+    /// n'est pas un signataire requis ou si la signature est refusée.
+    pub fn sign(
+        &self,
+        encoded: &str,
+        from: Option<&str>,
+        yes: bool,
+    ) -> Result<SignTxOutput, Box<dyn std::error::Error>> {
+        let mut transactions = [SolanaDecoder::deserialize(encoded)?];
+        let signed = self.sign_transactions(&mut transactions, from, yes)?;
+        let [transaction] = transactions;
+
+        let position = transaction
+            .message
+            .static_account_keys()
+            .iter()
+            .position(|key| *key == signed.signer)
+            .expect("signed by a required signer");
+        let missing_signers: Vec<String> = transaction
+            .signatures
+            .iter()
+            .zip(transaction.message.static_account_keys())
+            .filter(|(signature, _)| **signature == Signature::default())
+            .map(|(_, key)| key.to_string())
+            .collect();
+
+        Ok(SignTxOutput {
+            transaction: SolanaDecoder::serialize(&transaction)?,
+            signer: signed.signer.to_string(),
+            signature: transaction.signatures[position].to_string(),
+            fee_payer: signed.fee_payer,
+            complete: missing_signers.is_empty(),
+            missing_signers,
+            warnings: signed.warnings,
+        })
+    }
+
+    /// Signe des transactions avec une clé locale après une seule confirmation : chaque transaction dont la clé
+    /// est un signataire requis est signée (par exemple les variantes d'une demande de signature, qui ont le même
+    /// transfert). La première d'entre elles est affichée, et la politique de dépense est évaluée sur elle seule.
+    ///
+    /// # Returns:
+    /// La clé, si elle paie des frais, et les avertissements ; ou une erreur si la clé n'est un signataire requis
+    /// d'aucune transaction ou si la signature est refusée.
+    pub fn sign_transactions(
+        &self,
+        transactions: &mut [VersionedTransaction],
+        from: Option<&str>,
+        yes: bool,
+    ) -> Result<SignedTransactions, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let signer = keypair.pubkey();
+
+        let positions: Vec<Option<usize>> = transactions
+            .iter()
+            .map(|transaction| {
+                let required = transaction.message.header().num_required_signatures as usize;
+                transaction.message.static_account_keys()[..required]
+                    .iter()
+                    .position(|key| *key == signer)
+            })
+            .collect();
+        let first = positions
+            .iter()
+            .position(Option::is_some)
+            .ok_or_else(|| format!("{} is not a required signer of this transaction", signer))?;
+
+        let mut inspection = self.inspect(&transactions[first]);
+        let fee_payer = positions.contains(&Some(0));
+        if fee_payer {
+            inspection
+                .warnings
+                .push(format!("{} is the fee payer of this transaction", signer));
+        }
+
+        let output = self.config.output();
+        if !yes {
+            output.message(&inspection);
+            output.message(format!("Sign this transaction with {}? [y/N]", signer));
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Err("Signature cancelled".into());
+            }
+        }
+
+        // La politique de dépense du profil est évaluée avant la signature (la transaction n'est pas envoyée,
+        // la dépense est enregistrée dès la signature).
+        let spent = if self.config.spending_policy.is_empty() {
+            0
+        } else {
+            let instructions =
+                SolanaDecoder::instructions(&self.config, &transactions[first].message)?;
+            SolanaPolicy::check(&self.config, &signer, &instructions)?
+        };
+
+        for (transaction, position) in transactions.iter_mut().zip(&positions) {
+            if let Some(position) = position {
+                transaction.signatures[*position] =
+                    keypair.sign_message(&transaction.message.serialize());
+            }
+        }
+        let first_position = positions[first].expect("required signer");
+        SolanaPolicy::record(
+            &self.config,
+            &signer,
+            spent,
+            &transactions[first].signatures[first_position].to_string(),
+        )?;
+
+        let mut warnings = Vec::new();
+        if fee_payer {
+            warnings.push("You pay the fees of this transaction".to_string());
+        }
+        // Une signature invalide d'un autre signataire rendra la transaction invalide.
+        for transaction in transactions.iter() {
+            let verified = transaction.verify_with_results();
+            for ((signature, key), valid) in transaction
+                .signatures
+                .iter()
+                .zip(transaction.message.static_account_keys())
+                .zip(verified)
+            {
+                let warning = format!("The signature of {} is invalid", key);
+                if !valid && *signature != Signature::default() && !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        Ok(SignedTransactions {
+            signer,
+            fee_payer,
+            warnings,
+        })
+    }
+
+    /// La vue détaillée d'une transaction (voir `decode`).
+    fn inspect(&self, transaction: &VersionedTransaction) -> DecodeOutput {
+        let message = &transaction.message;
+        let static_keys = message.static_account_keys();
+        let mut warnings = Vec::new();
+
+        // Les adresses des tables d'adresses (v0), puis les comptes du message.
+        let lookups = message.address_table_lookups().unwrap_or_default();
+        let loaded = if lookups.is_empty() {
+            None
+        } else {
+            match SolanaDecoder::load_addresses(&self.config, lookups) {
+                Ok(loaded) => Some(loaded),
+                Err(e) => {
+                    warnings.push(format!("{} (accounts of lookup tables not resolved)", e));
+                    None
+                }
+            }
+        };
+        let account_keys = AccountKeys::new(static_keys, loaded.as_ref());
+
+        let lookup_tables = lookups
+            .iter()
+            .flat_map(|lookup| lookup.writable_indexes.iter().map(|_| lookup.account_key))
+            .chain(
+                lookups
+                    .iter()
+                    .flat_map(|lookup| lookup.readonly_indexes.iter().map(|_| lookup.account_key)),
+            );
+        let accounts = (0..static_keys.len())
+            .map(|index| (index, None))
+            .chain(
+                lookup_tables
+                    .enumerate()
+                    .map(|(i, table)| (static_keys.len() + i, Some(table))),
+            )
+            .map(|(index, table)| DecodedAccount {
+                index,
+                pubkey: account_keys.get(index).map(ToString::to_string),
+                signer: message.is_signer(index),
+                writable: message.is_maybe_writable(index),
+                lookup_table: table.map(|table| table.to_string()),
+            })
+            .collect();
+
+        let signatures: Vec<DecodedSignature> = transaction
+            .signatures
+            .iter()
+            .zip(static_keys)
+            .zip(transaction.verify_with_results())
+            .map(|((signature, signer), valid)| DecodedSignature {
+                signer: signer.to_string(),
+                signature: signature.to_string(),
+                status: if *signature == Signature::default() {
+                    "missing"
+                } else if valid {
+                    "valid"
+                } else {
+                    "invalid"
+                }
+                .to_string(),
+            })
+            .collect();
+        for signature in signatures.iter().filter(|s| s.status == "invalid") {
+            warnings.push(format!(
+                "The signature of {} is invalid (the message was modified after signing)",
+                signature.signer
+            ));
+        }
+
+        let instructions: Vec<DecodedInstruction> = message
+            .instructions()
+            .iter()
+            .map(|instruction| SolanaDecoder::decode_instruction(instruction, &account_keys))
+            .collect();
+        for (i, instruction) in instructions.iter().enumerate() {
+            if !instruction.recognised {
+                warnings.push(format!(
+                    "Instruction {} is not recognised (program {}): check it before signing",
+                    i + 1,
+                    instruction.program_id
+                ));
+            }
+        }
+
+        DecodeOutput {
+            version: match message {
+                VersionedMessage::Legacy(_) => "legacy".to_string(),
+                VersionedMessage::V0(_) => "0".to_string(),
+            },
+            fee_payer: static_keys[0].to_string(),
+            recent_blockhash: message.recent_blockhash().to_string(),
+            signatures,
+            accounts,
+            instructions,
+            warnings,
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::bip::mnemonic::BipMnemonic;
+use crate::bip::seed::BipSeed;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::address::SolanaAddress;
+use crate::solana::grind::{GrindMatch, GrindMode, GrindPattern, SolanaGrind};
+use crate::store::keystore::{KeySource, KeyStore};
+use serde::Serialize;
+use solana_sdk::signer::Signer;
+use std::fmt;
+use std::io::{self, Write};
+use std::time::Instant;
+
+/// Une adresse "vanity" trouvée et enregistrée dans le key store.
+#[derive(Debug, Serialize)]
+pub struct GrindMatchOutput {
+    pub label: String,
+    pub pubkey: String,
+    /// La mnémonique de la paire de clés (mode "--use-mnemonic").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    pub keypair_path: String,
+}
+
+/// Résultat de la commande "grind".
+#[derive(Debug, Serialize)]
+pub struct GrindOutput {
+    pub matches: Vec<GrindMatchOutput>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for GrindOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        for grind_match in &self.matches {
+            if let Some(mnemonic) = &grind_match.mnemonic {
+                lines.push(format!("BIP39 Mnemonic (vanity phrase): {}", mnemonic));
+            }
+            lines.push(format!(
+                "Solana Public Key ({}): {}",
+                grind_match.label, grind_match.pubkey
+            ));
+        }
+        lines.extend(self.warnings.iter().cloned());
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct GrindManager {
+    config: WalletConfig,
+}
+
+impl GrindManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Recherche des adresses "vanity" correspondant au motif, puis les enregistre dans le key store.
+    /// La progression (tentatives, vitesse et temps restant estimé) est affichée sur la sortie d'erreur.
+    ///
+    /// # Arguments:
+    /// - pattern - Le motif recherché (préfixe et/ou suffixe).
+    /// - mode - Paires de clés aléatoires, ou issues de mnémoniques (récupérables).
+    /// - threads - Le nombre de threads (par défaut : le nombre de cœurs).
+    /// - count - Le nombre d'adresses à trouver.
+    /// - label - Le label des paires de clés dans le key store.
+    pub fn grind(
+        &self,
+        pattern: &GrindPattern,
+        mode: GrindMode,
+        threads: Option<usize>,
+        count: usize,
+        label: &str,
+    ) -> Result<GrindOutput, Box<dyn std::error::Error>> {
+        pattern
+            .validate()
+            .map_err(|e| format!("Invalid pattern: {}", e))?;
+
+        let threads =
+            threads.unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()));
+        let expected_attempts = pattern.expected_attempts() * count as f64;
+        self.config.output().message(format!(
+            "Searching {} address(es) on {} thread(s) (about {:.0} attempts expected)...",
+            count, threads, expected_attempts
+        ));
+
+        let start = Instant::now();
+        let matches = SolanaGrind::grind(pattern, &mode, threads, count, |attempts| {
+            Self::print_progress(attempts, expected_attempts, start);
+        });
+        eprintln!();
+
+        let mut output = GrindOutput {
+            matches: Vec::new(),
+            warnings: Vec::new(),
+        };
+        for grind_match in matches {
+            match self.store_match(&grind_match, &mode, label) {
+                Ok(grind_match) => output.matches.push(grind_match),
+                Err(e) => output.warnings.push(e.to_string()),
+            }
+        }
+        Ok(output)
+    }
+
+    /// Affiche la progression sur une seule ligne (sortie d'erreur).
+    fn print_progress(attempts: u64, expected_attempts: f64, start: Instant) {
+        let elapsed = start.elapsed().as_secs_f64();
+        let rate = if elapsed > 0.0 {
+            attempts as f64 / elapsed
+        } else {
+            0.0
+        };
+        let eta = if rate > 0.0 {
+            format!(
+                "{:.0}s",
+                ((expected_attempts - attempts as f64) / rate).max(0.0)
+            )
+        } else {
+            "-".to_string()
+        };
+
+        eprint!("\rAttempts: {} ({:.0}/s), ETA: {}    ", attempts, rate, eta);
+        let _ = io::stderr().flush();
+    }
+
+    /// Écrit la paire de clés trouvée dans un fichier et l'enregistre dans le key store.
+    fn store_match(
+        &self,
+        grind_match: &GrindMatch,
+        mode: &GrindMode,
+        label: &str,
+    ) -> Result<GrindMatchOutput, String> {
+        let keystore = KeyStore::new(self.config.clone());
+        let pubkey = grind_match.keypair.pubkey();
+
+        let source = match (&grind_match.phrase, mode) {
+            (
+                Some(phrase),
+                GrindMode::Mnemonic {
+                    derivation_index,
+                    derivation_template,
+                },
+            ) => {
+                let mnemonic = BipMnemonic::get_mnemonic_from_phrase(phrase);
+                let seed = BipSeed::generate_seed(&mnemonic, "");
+                match BipSeed::fingerprint(BipSeed::get_seed_bytes(&seed)) {
+                    Ok(fingerprint) => KeySource::Mnemonic {
+                        fingerprint,
+                        path: BipSeed::derivation_path(derivation_template, *derivation_index),
+                    },
+                    Err(e) => return Err(format!("Error computing seed fingerprint: {}", e)),
+                }
+            }
+            _ => KeySource::Generated,
+        };
+
+        let keypair_path = keystore.new_keypair_path(&pubkey);
+        SolanaAddress::write_keypair(&grind_match.keypair, &keypair_path);
+
+        let entry = keystore
+            .register(label, source, &keypair_path, &pubkey)
+            .map_err(|e| format!("Error registering keypair in key store: {}", e))?;
+        Ok(GrindMatchOutput {
+            label: entry.label,
+            pubkey: pubkey.to_string(),
+            mnemonic: grind_match.phrase.clone(),
+            keypair_path,
+        })
+    }
+}
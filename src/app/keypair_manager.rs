@@ -1,6 +1,20 @@
 use crate::config::wallet_config::WalletConfig;
-use crate::solana::address::SolanaAddress;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
 use solana_sdk::signer::Signer;
+use std::fmt;
+
+/// Résultat de la commande "pubkey".
+#[derive(Debug, Serialize)]
+pub struct PubkeyOutput {
+    pub pubkey: String,
+}
+
+impl fmt::Display for PubkeyOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Solana Public Key: {}", self.pubkey)
+    }
+}
 
 pub struct KeypairManager {
     config: WalletConfig,
@@ -11,18 +25,19 @@ impl KeypairManager {
         Self { config }
     }
 
-    /// Récupère et affiche la clé publique à partir d'une paire de clés stockée localement dans un fichier.
-    pub fn pubkey(&self) {
-        // Obtient le chemin d'accès au fichier où la paire de clés est stockée.
-        let keypair_path = &self.config.keypair_path;
+    /// Récupère la clé publique à partir d'une paire de clés stockée localement dans un fichier.
+    ///
+    /// # Arguments:
+    /// - from - Un label ou une clé publique du key store (option "--from").
+    ///   Si absent, la clé active du key store est utilisée, puis à défaut `KEYPAIR_PATH`.
+    pub fn pubkey(&self, from: Option<&str>) -> Result<PubkeyOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
 
-        // Tente de lire la paire de clés à partir du fichier spécifié (en utilisant le chemin obtenu précédemment).
-        // La fonction "read_keypair_from_file" gère le chargement et la désérialisation de la paire de clés à partir du fichier.
-        // Ok: En cas de succès, extrait la clé publique de la paire de clés et l'affiche.
-        // Err: En cas d'échec, affiche une erreur indiquant que la lecture a échoué.
-        match SolanaAddress::read_keypair_from_file(keypair_path) {
-            Ok(keypair) => println!("Solana Public Key: {}", keypair.pubkey()),
-            Err(e) => println!("Failed to read key pair from file: {}", e),
-        }
+        // Tente de lire la paire de clés à partir du fichier sélectionné (label, clé publique, clé active ou `KEYPAIR_PATH`),
+        // puis en extrait la clé publique.
+        let keypair = keystore.resolve_keypair(from)?;
+        Ok(PubkeyOutput {
+            pubkey: keypair.pubkey().to_string(),
+        })
     }
 }
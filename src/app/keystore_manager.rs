@@ -0,0 +1,171 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::store::keystore::{KeyEntry, KeyStore};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+use std::str::FromStr;
+
+/// Résultat de la commande "wallet list".
+#[derive(Debug, Serialize)]
+pub struct KeyListOutput {
+    /// Label de la paire de clés active.
+    pub active: Option<String>,
+    pub keys: Vec<KeyEntry>,
+}
+
+impl fmt::Display for KeyListOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.keys.is_empty() {
+            return write!(f, "No keys in key store.");
+        }
+
+        // La clé active est marquée d'un "*".
+        let lines: Vec<String> = self
+            .keys
+            .iter()
+            .map(|entry| {
+                let marker = if self.active.as_deref() == Some(entry.label.as_str()) {
+                    "*"
+                } else {
+                    " "
+                };
+                format!(
+                    "{} {} {} {} {}",
+                    marker, entry.label, entry.pubkey, entry.source, entry.created_at
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Opération effectuée sur une paire de clés du key store.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyAction {
+    Renamed,
+    Activated,
+    Watched,
+    Removed,
+}
+
+/// Résultat des commandes "wallet rename|use|watch|remove".
+#[derive(Debug, Serialize)]
+pub struct KeyActionOutput {
+    pub action: KeyAction,
+    pub key: KeyEntry,
+    /// L'ancien label ("wallet rename").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_label: Option<String>,
+    /// Vrai si le fichier de la paire de clés a été supprimé ("wallet remove --delete-file").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_deleted: Option<bool>,
+}
+
+impl fmt::Display for KeyActionOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let key = &self.key;
+        match self.action {
+            KeyAction::Renamed => write!(
+                f,
+                "Key '{}' renamed to '{}'.",
+                self.previous_label.as_deref().unwrap_or_default(),
+                key.label
+            ),
+            KeyAction::Activated => write!(f, "Active key: {} ({})", key.label, key.pubkey),
+            KeyAction::Watched => {
+                write!(f, "Watch-only key '{}' added ({}).", key.label, key.pubkey)
+            }
+            KeyAction::Removed => match (&key.path, self.file_deleted) {
+                (Some(path), Some(true)) => {
+                    write!(f, "Key '{}' removed (file {} deleted).", key.label, path)
+                }
+                (Some(path), _) => write!(f, "Key '{}' removed (file {} kept).", key.label, path),
+                (None, _) => write!(f, "Key '{}' removed.", key.label),
+            },
+        }
+    }
+}
+
+pub struct KeystoreManager {
+    config: WalletConfig,
+}
+
+impl KeystoreManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Retourne les paires de clés gérées par le key store.
+    pub fn list(&self) -> Result<KeyListOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+
+        let manifest = keystore.load()?;
+        Ok(KeyListOutput {
+            active: manifest.active,
+            keys: manifest.keys,
+        })
+    }
+
+    /// Renomme une paire de clés du key store.
+    pub fn rename(
+        &self,
+        label: &str,
+        new_label: &str,
+    ) -> Result<KeyActionOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+
+        Ok(KeyActionOutput {
+            action: KeyAction::Renamed,
+            key: keystore.rename(label, new_label)?,
+            previous_label: Some(label.to_string()),
+            file_deleted: None,
+        })
+    }
+
+    /// Définit la paire de clés active (utilisée par défaut par "pubkey", "send", etc.).
+    pub fn use_key(&self, selector: &str) -> Result<KeyActionOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+
+        Ok(KeyActionOutput {
+            action: KeyAction::Activated,
+            key: keystore.set_active(selector)?,
+            previous_label: None,
+            file_deleted: None,
+        })
+    }
+
+    /// Retire une paire de clés du key store (et supprime son fichier si demandé).
+    pub fn remove(
+        &self,
+        selector: &str,
+        delete_file: bool,
+    ) -> Result<KeyActionOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+
+        let key = keystore.remove(selector, delete_file)?;
+        Ok(KeyActionOutput {
+            action: KeyAction::Removed,
+            file_deleted: key.path.as_ref().map(|_| delete_file),
+            key,
+            previous_label: None,
+        })
+    }
+
+    /// Ajoute une adresse "watch-only" au key store (suivie sans clé privée).
+    pub fn watch(
+        &self,
+        label: &str,
+        pubkey: &str,
+    ) -> Result<KeyActionOutput, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+
+        let pubkey = Pubkey::from_str(pubkey).map_err(|_| "Invalid public key format")?;
+        Ok(KeyActionOutput {
+            action: KeyAction::Watched,
+            key: keystore.watch(label, &pubkey)?,
+            previous_label: None,
+            file_deleted: None,
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use crate::app::terminal::{self, RawMode};
+use std::io::{self, BufRead, Read, Write};
+
+const CTRL_C: u8 = 3;
+const CTRL_D: u8 = 4;
+const BACKSPACE: u8 = 8;
+const TAB: u8 = 9;
+const LINE_FEED: u8 = 10;
+const CARRIAGE_RETURN: u8 = 13;
+const CTRL_U: u8 = 21;
+const ESCAPE: u8 = 27;
+const DELETE: u8 = 127;
+
+/// Éditeur de ligne du mode interactif.
+///
+/// Dans un terminal, la saisie est lue caractère par caractère (mode "raw") : complétion avec Tab, historique avec
+/// les flèches haut et bas, effacement avec Retour arrière et Ctrl-U, abandon de la ligne avec Ctrl-C, fin avec Ctrl-D.
+/// Sinon (entrée redirigée depuis un fichier ou un autre programme), les lignes sont lues telles quelles.
+pub struct LineEditor {
+    history: Vec<String>,
+    interactive: bool,
+}
+
+impl LineEditor {
+    pub fn new(history: Vec<String>) -> Self {
+        Self {
+            history,
+            interactive: terminal::is_terminal(),
+        }
+    }
+
+    /// Ajoute une ligne à l'historique de navigation (flèches haut et bas).
+    pub fn add_history(&mut self, line: &str) {
+        if self.history.last().map(String::as_str) != Some(line) {
+            self.history.push(line.to_string());
+        }
+    }
+
+    /// Lit une ligne. Retourne None à la fin de l'entrée (Ctrl-D sur une ligne vide).
+    ///
+    /// # Arguments:
+    /// - prompt - L'invite affichée avant la saisie (dans un terminal uniquement).
+    /// - complete - Retourne les complétions possibles du dernier mot de la ligne.
+    pub fn read_line(
+        &mut self,
+        prompt: &str,
+        complete: &dyn Fn(&str) -> Vec<String>,
+    ) -> io::Result<Option<String>> {
+        if !self.interactive {
+            let mut line = String::new();
+            return Ok(match io::stdin().lock().read_line(&mut line)? {
+                0 => None,
+                _ => Some(line.trim_end_matches(['\r', '\n']).to_string()),
+            });
+        }
+
+        let _raw_mode = RawMode::enable()?;
+        let mut line = String::new();
+        // Position dans l'historique pendant la navigation (None = ligne en cours de saisie).
+        let mut history_index: Option<usize> = None;
+        Self::redraw(prompt, &line)?;
+
+        loop {
+            match Self::read_byte()? {
+                None => return Ok(None),
+                Some(CARRIAGE_RETURN | LINE_FEED) => {
+                    print!("\r\n");
+                    io::stdout().flush()?;
+                    return Ok(Some(line));
+                }
+                Some(CTRL_D) if line.is_empty() => {
+                    print!("\r\n");
+                    return Ok(None);
+                }
+                Some(CTRL_C) => {
+                    print!("^C\r\n");
+                    line.clear();
+                    history_index = None;
+                }
+                Some(CTRL_U) => line.clear(),
+                Some(BACKSPACE | DELETE) => {
+                    line.pop();
+                }
+                Some(TAB) => self.complete(prompt, &mut line, complete)?,
+                Some(ESCAPE) => {
+                    // Séquences des flèches : ESC [ A (haut) et ESC [ B (bas). Les autres sont ignorées.
+                    if Self::read_byte()? != Some(b'[') {
+                        continue;
+                    }
+                    match Self::read_byte()? {
+                        Some(b'A') if !self.history.is_empty() => {
+                            let index = history_index
+                                .map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+                            history_index = Some(index);
+                            line = self.history[index].clone();
+                        }
+                        Some(b'B') => match history_index {
+                            Some(index) if index + 1 < self.history.len() => {
+                                history_index = Some(index + 1);
+                                line = self.history[index + 1].clone();
+                            }
+                            Some(_) => {
+                                history_index = None;
+                                line.clear();
+                            }
+                            None => {}
+                        },
+                        _ => {}
+                    }
+                }
+                Some(byte) if byte >= 0x20 => line.push_str(&Self::read_char(byte)?),
+                Some(_) => {}
+            }
+            Self::redraw(prompt, &line)?;
+        }
+    }
+
+    /// Complète le dernier mot de la ligne : directement s'il n'y a qu'une complétion, jusqu'au préfixe commun sinon
+    /// (les complétions possibles sont alors affichées).
+    fn complete(
+        &self,
+        prompt: &str,
+        line: &mut String,
+        complete: &dyn Fn(&str) -> Vec<String>,
+    ) -> io::Result<()> {
+        let candidates = complete(line);
+        let word_start = line.rfind(' ').map_or(0, |index| index + 1);
+        let word = line[word_start..].to_string();
+
+        match candidates.as_slice() {
+            [] => {}
+            [candidate] => {
+                line.truncate(word_start);
+                line.push_str(candidate);
+                line.push(' ');
+            }
+            _ => {
+                let prefix = Self::common_prefix(&candidates);
+                if prefix.len() > word.len() {
+                    line.truncate(word_start);
+                    line.push_str(&prefix);
+                } else {
+                    print!("\r\n{}\r\n", candidates.join("  "));
+                    Self::redraw(prompt, line)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn common_prefix(candidates: &[String]) -> String {
+        let first = &candidates[0];
+        let mut prefix_len = first.len();
+        for candidate in &candidates[1..] {
+            prefix_len = first
+                .char_indices()
+                .zip(candidate.chars())
+                .take_while(|((_, a), b)| a == b)
+                .last()
+                .map_or(0, |((index, a), _)| index + a.len_utf8())
+                .min(prefix_len);
+        }
+        first[..prefix_len].to_string()
+    }
+
+    fn redraw(prompt: &str, line: &str) -> io::Result<()> {
+        print!("\r\x1b[K{}{}", prompt, line);
+        io::stdout().flush()
+    }
+
+    fn read_byte() -> io::Result<Option<u8>> {
+        let mut byte = [0u8; 1];
+        match io::stdin().lock().read(&mut byte)? {
+            0 => Ok(None),
+            _ => Ok(Some(byte[0])),
+        }
+    }
+
+    /// Lit la fin d'un caractère UTF-8 dont le premier octet est donné.
+    fn read_char(first: u8) -> io::Result<String> {
+        let len = match first {
+            0xF0..=0xF7 => 4,
+            0xE0..=0xEF => 3,
+            0xC0..=0xDF => 2,
+            _ => 1,
+        };
+        let mut bytes = vec![first];
+        for _ in 1..len {
+            match Self::read_byte()? {
+                Some(byte) => bytes.push(byte),
+                None => break,
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
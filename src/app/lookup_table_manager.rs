@@ -0,0 +1,334 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::lookup_table::{LookupTableState, SolanaLookupTable, EXTEND_CHUNK_SIZE};
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::transaction::SolanaTransaction;
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
+use solana_sdk::address_lookup_table::instruction as lookup_table_instruction;
+use solana_sdk::address_lookup_table::state::LOOKUP_TABLE_MAX_ADDRESSES;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Résultat des commandes "alt create", "alt extend", "alt freeze", "alt deactivate" et "alt close".
+#[derive(Debug, Serialize)]
+pub struct LookupTableOutput {
+    /// "create", "extend", "freeze", "deactivate" ou "close".
+    pub action: String,
+    pub table: String,
+    pub authority: String,
+    /// Les adresses ajoutées ("alt extend").
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    /// Les signatures des transactions (plusieurs si les adresses ajoutées ne tiennent pas dans une seule).
+    pub signatures: Vec<String>,
+    /// Vrai en mode test (transactions signées mais pas envoyées).
+    pub simulated: bool,
+    /// Vrai si toutes les transactions ont atteint le niveau de confirmation configuré.
+    pub confirmed: bool,
+    pub explorer_url: String,
+}
+
+impl fmt::Display for LookupTableOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!("Address lookup table: {}", self.table)];
+        lines.push(match self.action.as_str() {
+            "create" => format!("Table created (authority {}).", self.authority),
+            "extend" => format!("Addresses added: {}", self.added.len()),
+            "freeze" => "Table frozen: it can no longer be extended or closed.".to_string(),
+            "deactivate" => {
+                "Table deactivated: it can be closed in about 513 slots (\"alt close\")."
+                    .to_string()
+            }
+            _ => "Table closed, its rent was returned to the authority.".to_string(),
+        });
+        if self.simulated {
+            lines.push("(simulated: transactions signed but not sent)".to_string());
+        } else if !self.confirmed {
+            lines.push(
+                "Transaction submitted (not confirmed yet, check it with \"confirm <SIGNATURE>\")"
+                    .to_string(),
+            );
+        }
+        for signature in &self.signatures {
+            lines.push(format!("Signature: {}", signature));
+        }
+        lines.push(format!("Explorer: {}", self.explorer_url));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "alt show".
+#[derive(Debug, Serialize)]
+pub struct LookupTableShowOutput {
+    pub table: String,
+    /// La clé qui peut modifier la table (absente si la table est gelée).
+    pub authority: Option<String>,
+    /// Le slot de désactivation (absent si la table est active).
+    pub deactivation_slot: Option<u64>,
+    pub last_extended_slot: u64,
+    pub addresses: Vec<String>,
+}
+
+impl fmt::Display for LookupTableShowOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Address lookup table: {}", self.table),
+            format!(
+                "Authority: {}",
+                self.authority.as_deref().unwrap_or("none (frozen)")
+            ),
+            match self.deactivation_slot {
+                Some(slot) => format!("Status: deactivated (slot {})", slot),
+                None => "Status: active".to_string(),
+            },
+            format!("Last extended slot: {}", self.last_extended_slot),
+            format!("Addresses: {}", self.addresses.len()),
+        ];
+        for (index, address) in self.addresses.iter().enumerate() {
+            lines.push(format!("  {:>3} {}", index, address));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct LookupTableManager {
+    config: WalletConfig,
+}
+
+impl LookupTableManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Crée une table d'adresses, dont la clé sélectionnée ("--from") est l'autorité et paie le loyer.
+    pub fn create(
+        &self,
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        // L'adresse de la table est dérivée de l'autorité et d'un slot récent. Le programme exige un slot présent
+        // dans "SlotHashes" : un slot finalisé y est toujours, contrairement à un slot "processed" d'une autre fourche.
+        let recent_slot = SolanaRpc::client(&self.config)
+            .get_slot_with_commitment(CommitmentConfig::finalized())?;
+        let (instruction, table) = lookup_table_instruction::create_lookup_table(
+            keypair.pubkey(),
+            keypair.pubkey(),
+            recent_slot,
+        );
+        self.send(
+            "create",
+            &keypair,
+            table,
+            vec![vec![instruction]],
+            Vec::new(),
+            wait,
+        )
+    }
+
+    /// Ajoute des adresses (clés publiques, noms de contacts ou labels du key store) à une table.
+    /// Les adresses déjà présentes sont ignorées ; au-delà de `EXTEND_CHUNK_SIZE` adresses,
+    /// elles sont ajoutées en plusieurs transactions.
+    pub fn extend(
+        &self,
+        table: &str,
+        addresses: &[String],
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let state = self.fetch_owned(table, &keypair)?;
+
+        let address_book = AddressBook::new(self.config.clone());
+        let mut added: Vec<Pubkey> = Vec::new();
+        for address in addresses {
+            let address = address_book.resolve(address)?;
+            if !state.addresses.contains(&address) && !added.contains(&address) {
+                added.push(address);
+            }
+        }
+        if added.is_empty() {
+            return Err("All the addresses are already in the table".into());
+        }
+        if state.addresses.len() + added.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            return Err(format!(
+                "A table holds at most {} addresses ({} already in the table)",
+                LOOKUP_TABLE_MAX_ADDRESSES,
+                state.addresses.len()
+            )
+            .into());
+        }
+
+        let transactions = added
+            .chunks(EXTEND_CHUNK_SIZE)
+            .map(|chunk| {
+                vec![lookup_table_instruction::extend_lookup_table(
+                    state.address,
+                    keypair.pubkey(),
+                    Some(keypair.pubkey()),
+                    chunk.to_vec(),
+                )]
+            })
+            .collect();
+        self.send("extend", &keypair, state.address, transactions, added, wait)
+    }
+
+    /// Gèle une table : elle ne pourra plus être modifiée, ni fermée.
+    pub fn freeze(
+        &self,
+        table: &str,
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let state = self.fetch_owned(table, &keypair)?;
+        if state.addresses.is_empty() {
+            return Err("An empty table cannot be frozen".into());
+        }
+        let instruction =
+            lookup_table_instruction::freeze_lookup_table(state.address, keypair.pubkey());
+        self.send(
+            "freeze",
+            &keypair,
+            state.address,
+            vec![vec![instruction]],
+            Vec::new(),
+            wait,
+        )
+    }
+
+    /// Désactive une table, première étape avant sa fermeture.
+    pub fn deactivate(
+        &self,
+        table: &str,
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let state = self.fetch_owned(table, &keypair)?;
+        if state.deactivation_slot.is_some() {
+            return Err(format!("Address lookup table {} is already deactivated", table).into());
+        }
+        let instruction =
+            lookup_table_instruction::deactivate_lookup_table(state.address, keypair.pubkey());
+        self.send(
+            "deactivate",
+            &keypair,
+            state.address,
+            vec![vec![instruction]],
+            Vec::new(),
+            wait,
+        )
+    }
+
+    /// Ferme une table désactivée (une fois son slot de désactivation trop ancien) et récupère son loyer.
+    pub fn close(
+        &self,
+        table: &str,
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let state = self.fetch_owned(table, &keypair)?;
+        if state.deactivation_slot.is_none() {
+            return Err(format!(
+                "Address lookup table {} must be deactivated first (\"alt deactivate\")",
+                table
+            )
+            .into());
+        }
+        let instruction = lookup_table_instruction::close_lookup_table(
+            state.address,
+            keypair.pubkey(),
+            keypair.pubkey(),
+        );
+        self.send(
+            "close",
+            &keypair,
+            state.address,
+            vec![vec![instruction]],
+            Vec::new(),
+            wait,
+        )
+    }
+
+    /// Affiche une table d'adresses : autorité, état et adresses.
+    pub fn show(&self, table: &str) -> Result<LookupTableShowOutput, Box<dyn std::error::Error>> {
+        let state = SolanaLookupTable::fetch(&self.config, &Self::parse_table(table)?)?;
+        Ok(LookupTableShowOutput {
+            table: state.address.to_string(),
+            authority: state.authority.map(|authority| authority.to_string()),
+            deactivation_slot: state.deactivation_slot,
+            last_extended_slot: state.last_extended_slot,
+            addresses: state.addresses.iter().map(Pubkey::to_string).collect(),
+        })
+    }
+
+    /// Lit une table et vérifie que la clé sélectionnée en est l'autorité.
+    fn fetch_owned(
+        &self,
+        table: &str,
+        keypair: &Keypair,
+    ) -> Result<LookupTableState, Box<dyn std::error::Error>> {
+        let state = SolanaLookupTable::fetch(&self.config, &Self::parse_table(table)?)?;
+        match state.authority {
+            Some(authority) if authority == keypair.pubkey() => Ok(state),
+            Some(authority) => Err(format!(
+                "{} is not the authority of address lookup table {} ({} is)",
+                keypair.pubkey(),
+                table,
+                authority
+            )
+            .into()),
+            None => Err(format!("Address lookup table {} is frozen", table).into()),
+        }
+    }
+
+    fn parse_table(table: &str) -> Result<Pubkey, Box<dyn std::error::Error>> {
+        Pubkey::from_str(table)
+            .map_err(|_| format!("Invalid address lookup table '{}'", table).into())
+    }
+
+    /// Envoie les transactions d'une commande, l'une après l'autre.
+    fn send(
+        &self,
+        action: &str,
+        keypair: &Keypair,
+        table: Pubkey,
+        transactions: Vec<Vec<Instruction>>,
+        added: Vec<Pubkey>,
+        wait: bool,
+    ) -> Result<LookupTableOutput, Box<dyn std::error::Error>> {
+        let mut sent = Vec::new();
+        for instructions in transactions {
+            sent.push(SolanaTransaction::send_instructions(
+                &self.config,
+                keypair,
+                instructions,
+                wait,
+            )?);
+        }
+        let last_signature = sent.last().map(|sent| sent.signature.to_string());
+
+        Ok(LookupTableOutput {
+            action: action.to_string(),
+            table: table.to_string(),
+            authority: keypair.pubkey().to_string(),
+            added: added.iter().map(Pubkey::to_string).collect(),
+            signatures: sent.iter().map(|sent| sent.signature.to_string()).collect(),
+            simulated: sent.iter().any(|sent| sent.simulated),
+            confirmed: sent.iter().all(|sent| sent.confirmed),
+            explorer_url: SolanaExplorer::transaction_url(
+                &self.config.explorer_template,
+                &self.config.rpc_url,
+                &last_signature.unwrap_or_default(),
+            ),
+        })
+    }
+}
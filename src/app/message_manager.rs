@@ -0,0 +1,189 @@
+use crate::bip::entropy::BipEntropy;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::message::{MessageEncoding, SolanaMessage};
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::signer::Signer;
+use std::fmt;
+use std::str::FromStr;
+
+/// Résultat de la commande "sign_message".
+#[derive(Debug, Serialize)]
+pub struct SignMessageOutput {
+    pub signer: String,
+    /// La signature, en base58.
+    pub signature: String,
+    pub encoding: MessageEncoding,
+}
+
+impl fmt::Display for SignMessageOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines = [
+            format!("Signer: {}", self.signer),
+            format!("Format: {}", self.encoding),
+            format!("Signature: {}", self.signature),
+        ];
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "verify_message".
+#[derive(Debug, Serialize)]
+pub struct VerifyMessageOutput {
+    /// Vrai si l'une des clés vérifiées a signé le message.
+    pub valid: bool,
+    /// La clé publique qui a signé le message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer: Option<String>,
+    /// Le label du key store ou le nom de contact de la clé qui a signé le message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signer_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<MessageEncoding>,
+    /// Le nombre de clés publiques vérifiées.
+    pub checked_keys: usize,
+}
+
+impl fmt::Display for VerifyMessageOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.signer, &self.encoding) {
+            (Some(signer), Some(encoding)) => {
+                write!(f, "Valid signature by {}", signer)?;
+                if let Some(name) = &self.signer_name {
+                    write!(f, " ({})", name)?;
+                }
+                write!(f, "\nFormat: {}", encoding)
+            }
+            _ => write!(
+                f,
+                "Invalid signature: not signed by any of the {} key(s) checked.",
+                self.checked_keys
+            ),
+        }
+    }
+}
+
+/// Une clé publique connue, avec son label du key store ou son nom de contact.
+type KnownKey = (Pubkey, Option<String>);
+
+pub struct MessageManager {
+    config: WalletConfig,
+}
+
+impl MessageManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Signe un message avec une paire de clés du key store (format hors chaîne Solana, ou signature brute).
+    ///
+    /// # Arguments:
+    /// - message - Le message (texte UTF-8, ou octets en hexadécimal avec "hex").
+    /// - hex - Le message est donné en hexadécimal.
+    /// - raw - Signer les octets du message tels quels, sans le format hors chaîne.
+    /// - from - Un label ou une clé publique du key store (la clé active, puis `KEYPAIR_PATH`, si absent).
+    pub fn sign(
+        &self,
+        message: &str,
+        hex: bool,
+        raw: bool,
+        from: Option<&str>,
+    ) -> Result<SignMessageOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let bytes = Self::message_bytes(message, hex)?;
+        let (signature, encoding) = SolanaMessage::sign(&keypair, &bytes, raw)?;
+
+        Ok(SignMessageOutput {
+            signer: keypair.pubkey().to_string(),
+            signature: signature.to_string(),
+            encoding,
+        })
+    }
+
+    /// Vérifie la signature d'un message et retourne la clé publique qui l'a signé.
+    /// Une signature ed25519 ne permet pas de retrouver la clé qui l'a produite : sans "signer", elle est vérifiée
+    /// avec chacune des clés du key store et du carnet d'adresses.
+    ///
+    /// # Arguments:
+    /// - message - Le message (texte UTF-8, ou octets en hexadécimal avec "hex").
+    /// - signature - La signature, en base58.
+    /// - hex - Le message est donné en hexadécimal.
+    /// - signer - La clé attendue (clé publique, nom de contact ou label du key store).
+    pub fn verify(
+        &self,
+        message: &str,
+        signature: &str,
+        hex: bool,
+        signer: Option<&str>,
+    ) -> Result<VerifyMessageOutput, Box<dyn std::error::Error>> {
+        let signature = Signature::from_str(signature)
+            .map_err(|_| format!("Invalid signature '{}'", signature))?;
+        let bytes = Self::message_bytes(message, hex)?;
+
+        let candidates = match signer {
+            Some(signer) => {
+                let pubkey = AddressBook::new(self.config.clone()).resolve(signer)?;
+                let name = (signer != pubkey.to_string()).then(|| signer.to_string());
+                vec![(pubkey, name)]
+            }
+            None => self.known_keys()?,
+        };
+
+        let found = candidates.iter().find_map(|(pubkey, name)| {
+            SolanaMessage::verify(pubkey, &bytes, &signature)
+                .map(|encoding| (pubkey, name, encoding))
+        });
+
+        Ok(VerifyMessageOutput {
+            valid: found.is_some(),
+            signer: found.map(|(pubkey, _, _)| pubkey.to_string()),
+            signer_name: found.and_then(|(_, name, _)| name.clone()),
+            encoding: found.map(|(_, _, encoding)| encoding),
+            checked_keys: candidates.len(),
+        })
+    }
+
+    /// Les clés publiques du key store puis du carnet d'adresses, avec leur label ou nom de contact
+    /// (en commençant par la paire de clés utilisée par défaut, qui n'est pas forcément dans le key store).
+    fn known_keys(&self) -> Result<Vec<KnownKey>, Box<dyn std::error::Error>> {
+        let keystore = KeyStore::new(self.config.clone());
+        let keys = keystore.load()?.keys;
+        let contacts = AddressBook::new(self.config.clone()).load()?.contacts;
+
+        let mut known: Vec<KnownKey> = Vec::new();
+        if let Ok(keypair) = keystore.resolve_keypair(None) {
+            let label = keys
+                .iter()
+                .find(|key| key.pubkey == keypair.pubkey().to_string())
+                .map(|key| key.label.clone());
+            known.push((keypair.pubkey(), label));
+        }
+        let named = keys.into_iter().map(|key| (key.pubkey, key.label)).chain(
+            contacts
+                .into_iter()
+                .map(|contact| (contact.pubkey, contact.name)),
+        );
+        for (pubkey, name) in named {
+            let Ok(pubkey) = Pubkey::from_str(&pubkey) else {
+                continue;
+            };
+            if !known
+                .iter()
+                .any(|(known_pubkey, _)| *known_pubkey == pubkey)
+            {
+                known.push((pubkey, Some(name)));
+            }
+        }
+        Ok(known)
+    }
+
+    fn message_bytes(message: &str, hex: bool) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+        if !hex {
+            return Ok(message.as_bytes().to_vec());
+        }
+        BipEntropy::decode_hex(message).map_err(|e| format!("Invalid hex message: {}", e).into())
+    }
+}
@@ -1,9 +1,88 @@
+use crate::app::wallet_manager::EntropyOutput;
+use crate::bip::mnemonic::BipMnemonic;
 use crate::bip::passphrase::BipPassphrase;
 use crate::bip::seed::BipSeed;
-use crate::config::wallet_config::WalletConfig;
+use crate::config::wallet_config::{ConfigSource, WalletConfig};
 use crate::solana::address::SolanaAddress;
+use crate::store::keystore::{KeySource, KeyStore};
 use bip39::Mnemonic;
-use solana_sdk::signer::Signer;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::{keypair::Keypair, Signer};
+use std::fmt;
+
+/// Origine de la phrase mnémonique.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MnemonicOrigin {
+    /// Générée aléatoirement ("generate_seed").
+    Random,
+    /// Générée à partir d'entropie fournie par l'utilisateur ("generate_seed --entropy-dice|--entropy-hex").
+    UserEntropy,
+    /// Donnée par l'utilisateur ("recover_seed").
+    Given,
+}
+
+impl fmt::Display for MnemonicOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MnemonicOrigin::Random => write!(f, "random phrase"),
+            MnemonicOrigin::UserEntropy => write!(f, "user entropy"),
+            MnemonicOrigin::Given => write!(f, "given phrase"),
+        }
+    }
+}
+
+/// Une paire de clés dérivée de la mnémonique et enregistrée dans le key store.
+#[derive(Debug, Serialize)]
+pub struct DerivedKeyOutput {
+    pub index: usize,
+    pub path: String,
+    pub pubkey: String,
+    pub keypair_path: String,
+}
+
+/// Résultat des commandes "generate_seed" et "recover_seed".
+#[derive(Debug, Serialize)]
+pub struct MnemonicOutput {
+    pub origin: MnemonicOrigin,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entropy: Option<EntropyOutput>,
+    pub mnemonic: String,
+    pub seed: String,
+    pub fingerprint: String,
+    pub keys: Vec<DerivedKeyOutput>,
+    /// Erreurs non bloquantes (dérivation ou enregistrement d'une clé).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for MnemonicOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        if let Some(entropy) = &self.entropy {
+            lines.push(entropy.to_string());
+        }
+        lines.push(format!(
+            "BIP39 Mnemonic ({}): {}",
+            self.origin, self.mnemonic
+        ));
+        lines.push(format!("Seed: {}", self.seed));
+        for key in &self.keys {
+            // La clé publique est également utilisée comme adresse publique du wallet.
+            if key.index == 0 {
+                lines.push(format!("Solana Public Key: {}", key.pubkey));
+            } else {
+                lines.push(format!(
+                    "Solana Public Key (derivation {}): {}",
+                    key.index, key.pubkey
+                ));
+            }
+        }
+        lines.extend(self.warnings.iter().cloned());
+        write!(f, "{}", lines.join("\n"))
+    }
+}
 
 pub struct MnemonicManager {
     config: WalletConfig,
@@ -14,25 +93,44 @@ impl MnemonicManager {
         Self { config }
     }
 
-    /// Traite une mnémonique pour générer et afficher la clé publique correspondante, en prenant en compte les dérivations spécifiées.
+    /// Traite une mnémonique pour générer la clé publique correspondante, en prenant en compte les dérivations spécifiées.
     /// Cette fonction sert de point central pour la création de clés Solana à partir d'une phrase mnémonique.
-    pub fn process_mnemonic(&self, mnemonic: &Mnemonic) {
+    pub fn process_mnemonic(
+        &self,
+        mnemonic: &Mnemonic,
+        origin: MnemonicOrigin,
+        entropy: Option<EntropyOutput>,
+    ) -> Result<MnemonicOutput, Box<dyn std::error::Error>> {
         // Demande à l'utilisateur d'entrer une passphrase optionnelle qui sera utilisée lors de la génération de la seed.
         // (laisser vide pour ne pas utiliser de passphrase)
-        let passphrase = BipPassphrase::prompt_for_passphrase();
+        let passphrase = BipPassphrase::prompt_for_passphrase(&self.config.output());
 
         // Génère une seed en format hexadécimal à partir de la phrase mnémonique et de la passphrase.
         // Cette seed de portefeuille HD (Hiérarchiquement Déterministe) permettra de produire une suite cohérente de clés dérivées.
         let seed = BipSeed::generate_seed(mnemonic, &passphrase);
-        println!("Seed: {:X}", seed);
 
         // Convertit la seed en un tableau de bytes bruts, qui servira de base pour la génération de clés dérivées.
         let seed_bytes = BipSeed::get_seed_bytes(&seed);
-        self.handle_key_derivation(seed_bytes);
+
+        // L'empreinte de la clé maîtresse identifie la mnémonique d'origine dans le key store.
+        let fingerprint = BipSeed::fingerprint(seed_bytes)
+            .map_err(|e| format!("Error computing seed fingerprint: {}", e))?;
+
+        let mut output = MnemonicOutput {
+            origin,
+            entropy,
+            mnemonic: BipMnemonic::get_mnemonic_to_str(mnemonic).to_string(),
+            seed: format!("{:X}", seed),
+            fingerprint,
+            keys: Vec::new(),
+            warnings: Vec::new(),
+        };
+        self.handle_key_derivation(seed_bytes, &mut output);
+        Ok(output)
     }
 
     /// Gère la dérivation de clés et leur enregistrement.
-    fn handle_key_derivation(&self, seed_bytes: &[u8]) {
+    fn handle_key_derivation(&self, seed_bytes: &[u8], output: &mut MnemonicOutput) {
         // Récupère le nombre de dérivations souhaitées (est de 1 par défaut).
         let nb_derivations = self.config.nb_derivations;
 
@@ -41,41 +139,87 @@ impl MnemonicManager {
 
         // Gère les dérivations multiples pour générer plusieurs paires de clés.
         for index in 0..nb_pubkeys {
-            self.derive_and_store_keypair(seed_bytes, index);
+            self.derive_and_store_keypair(seed_bytes, index, output);
         }
     }
 
     /// Dérive et stocke une paire de clés.
-    fn derive_and_store_keypair(&self, seed_bytes: &[u8], index: usize) {
+    fn derive_and_store_keypair(
+        &self,
+        seed_bytes: &[u8],
+        index: usize,
+        output: &mut MnemonicOutput,
+    ) {
         // Dériver la seed pour chaque index spécifié (sauf pour l'index 0 qui utilise la seed originale).
-        match BipSeed::derive_seed_bytes(seed_bytes, index) {
+        match BipSeed::derive_seed_bytes(seed_bytes, index, &self.config.derivation_template) {
             Ok(derived_seed_bytes) => {
                 // Génerer une paire de clés (clé publique et clé privée) à partir de la seed en bytes.
                 // Puis écrire cette paire de clés dans un fichier JSON.
                 let keypair = SolanaAddress::generate_keypair(&derived_seed_bytes);
 
-                let keypair_path = self.derive_keypair_path(index);
+                let keypair_path = self.derive_keypair_path(index, &keypair.pubkey());
+                if index == 0 && keypair_path != self.config.keypair_path {
+                    output.warnings.push(format!(
+                        "The Solana CLI keypair {} is only used for signing: the key was written to {}",
+                        self.config.keypair_path, keypair_path
+                    ));
+                }
                 SolanaAddress::write_keypair(&keypair, &keypair_path);
-
-                // Affiche la clé publique (qui dans le cas de Solana, est également utilisée comme adresse publique du wallet).
-                if index == 0 {
-                    println!("Solana Public Key: {}", keypair.pubkey());
-                } else {
-                    println!(
-                        "Solana Public Key (derivation {}): {}",
-                        index,
-                        keypair.pubkey()
-                    );
+                if let Err(e) =
+                    self.register_keypair(&keypair, &keypair_path, index, &output.fingerprint)
+                {
+                    output
+                        .warnings
+                        .push(format!("Error registering keypair in key store: {}", e));
                 }
+
+                output.keys.push(DerivedKeyOutput {
+                    index,
+                    path: BipSeed::derivation_path(&self.config.derivation_template, index),
+                    pubkey: keypair.pubkey().to_string(),
+                    keypair_path,
+                });
             }
-            Err(e) => println!("Error deriving seed bytes: {}", e),
+            Err(e) => output
+                .warnings
+                .push(format!("Error deriving seed bytes: {}", e)),
         }
     }
 
+    /// Enregistre la paire de clés dans le key store ("main" pour la clé principale, "derivation-{index}" pour les dérivations).
+    fn register_keypair(
+        &self,
+        keypair: &Keypair,
+        keypair_path: &str,
+        index: usize,
+        fingerprint: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let label = if index == 0 {
+            "main".to_string()
+        } else {
+            format!("derivation-{}", index)
+        };
+        let source = KeySource::Mnemonic {
+            fingerprint: fingerprint.to_string(),
+            path: BipSeed::derivation_path(&self.config.derivation_template, index),
+        };
+
+        let keystore = KeyStore::new(self.config.clone());
+        keystore
+            .register(&label, source, keypair_path, &keypair.pubkey())
+            .map(|_| ())
+    }
+
     /// Construit le chemin du fichier de la paire de clés en fonction de l'index de dérivation.
-    fn derive_keypair_path(&self, index: usize) -> String {
+    /// La paire de clés de la CLI Solana ("keypair_path" de son fichier de configuration) n'est jamais réécrite :
+    /// la clé principale est alors écrite dans le dossier du key store.
+    fn derive_keypair_path(&self, index: usize, pubkey: &Pubkey) -> String {
         if index == 0 {
-            self.config.keypair_path.clone()
+            if self.config.sources.get("keypair_path") == Some(&ConfigSource::SolanaCli) {
+                KeyStore::new(self.config.clone()).new_keypair_path(pubkey)
+            } else {
+                self.config.keypair_path.clone()
+            }
         } else {
             format!(
                 "{}/keypair-{}.json",
@@ -1,5 +1,27 @@
+pub mod address_book_manager;
+pub mod audit_manager;
+pub mod backup_manager;
+pub mod bip85_manager;
 pub mod cli;
+pub mod config_manager;
+pub mod dashboard_manager;
+pub mod decode_manager;
+pub mod grind_manager;
 pub mod keypair_manager;
+pub mod keystore_manager;
+pub mod line_editor;
+pub mod lookup_table_manager;
+pub mod message_manager;
 pub mod mnemonic_manager;
+pub mod multisig_manager;
+pub mod output;
+pub mod qr_code;
+pub mod rpc_manager;
+pub mod session;
+pub mod shell;
+pub mod sweep_manager;
+pub mod terminal;
+pub mod token_manager;
 pub mod transaction_manager;
+pub mod tui;
 pub mod wallet_manager;
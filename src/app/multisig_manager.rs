@@ -0,0 +1,399 @@
+use crate::app::decode_manager::DecodeManager;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::multisig::{combinations, SigningRequest, MAX_VARIANTS};
+use crate::solana::pay::MEMO_PROGRAM_ID;
+use crate::solana::transaction::SolanaTransaction;
+use crate::store::address_book::AddressBook;
+use clap::ArgMatches;
+use serde::Serialize;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::VersionedTransaction;
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// La signature d'un signataire requis.
+#[derive(Debug, Serialize)]
+pub struct SignerStatus {
+    pub signer: String,
+    pub signed: bool,
+}
+
+/// Résultat des commandes "multisig propose", "multisig approve" et "multisig status". Le transfert et les
+/// signataires sont déduits des transactions de la demande.
+#[derive(Debug, Serialize)]
+pub struct SigningRequestOutput {
+    pub file: String,
+    pub description: String,
+    pub from: String,
+    pub to: String,
+    pub lamports: u64,
+    pub nonce_account: String,
+    /// Le nombre de co-signataires requis parmi "co_signers".
+    pub threshold: usize,
+    pub co_signers: Vec<String>,
+    /// Les co-signataires qui ont signé.
+    pub approvals: usize,
+    /// Tous les signataires (trésorerie, payeur des frais, autorité du nonce et co-signataires).
+    pub signers: Vec<SignerStatus>,
+    /// Vrai si une variante a toutes ses signatures ("multisig submit" peut l'envoyer).
+    pub ready: bool,
+    /// La clé qui vient de signer ("multisig approve").
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approved_by: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for SigningRequestOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        if let Some(signer) = &self.approved_by {
+            lines.push(format!("Approved by: {}", signer));
+        }
+        lines.push(format!("Signing request: {}", self.file));
+        lines.push(format!("Description: {}", self.description));
+        lines.push(format!(
+            "Transfer: {} lamports from {} to {}",
+            self.lamports, self.from, self.to
+        ));
+        lines.push(format!("Nonce account: {}", self.nonce_account));
+        lines.push(format!(
+            "Threshold: {} of {} co-signers",
+            self.threshold,
+            self.co_signers.len()
+        ));
+        lines.push(format!("Approvals: {}/{}", self.approvals, self.threshold));
+        for signer in &self.signers {
+            lines.push(format!(
+                "  {} {}",
+                signer.signer,
+                if signer.signed { "signed" } else { "pending" }
+            ));
+        }
+        if self.ready {
+            lines.push(
+                "All required signatures are present: send it with \"multisig submit\"."
+                    .to_string(),
+            );
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "multisig submit".
+#[derive(Debug, Serialize)]
+pub struct SubmitOutput {
+    pub file: String,
+    pub signature: String,
+    /// Vrai en mode test (transaction signée mais pas envoyée).
+    pub simulated: bool,
+    /// Vrai si la transaction a atteint le niveau de confirmation configuré (faux avec "--no-wait").
+    pub confirmed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<String>,
+    pub explorer_url: String,
+}
+
+impl fmt::Display for SubmitOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        if self.simulated {
+            lines.push(format!("Simulating submission of {}", self.file));
+        }
+        if self.simulated || self.confirmed {
+            lines.push("Transaction sent successfully!".to_string());
+        } else {
+            lines.push(
+                "Transaction submitted (not confirmed yet, check it with \"confirm <SIGNATURE>\")"
+                    .to_string(),
+            );
+        }
+        lines.push(format!("Signature: {}", self.signature));
+        if let Some(slot) = self.slot {
+            lines.push(format!("Slot: {}", slot));
+        }
+        if let Some(status) = &self.confirmation_status {
+            lines.push(format!("Confirmation status: {}", status));
+        }
+        lines.push(format!("Explorer: {}", self.explorer_url));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct MultisigManager {
+    config: WalletConfig,
+}
+
+impl MultisigManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Crée une demande de signature : un transfert depuis la trésorerie ("--from"), dans des transactions
+    /// non signées utilisant un nonce durable (elles n'expirent pas).
+    ///
+    /// Les co-signataires ("--signer") sont les comptes signataires d'une instruction Memo contenant la
+    /// description : le programme Memo exige leur signature. Avec "--threshold M", une variante de la
+    /// transaction est créée pour chaque groupe de M co-signataires (tous par défaut) ; toutes avancent le même
+    /// nonce, une seule peut donc être exécutée. Sans "--nonce-blockhash", la valeur du nonce est lue via RPC ;
+    /// avec, la commande fonctionne hors ligne.
+    ///
+    /// # Arguments:
+    /// - matches - Arguments de ligne de commande : fichier de la demande, trésorerie, destinataire, montant
+    ///   (en lamports), co-signataires et seuil, compte de nonce (et son autorité, sa valeur), payeur des frais,
+    ///   description.
+    pub fn propose(
+        &self,
+        matches: &ArgMatches,
+    ) -> Result<SigningRequestOutput, Box<dyn std::error::Error>> {
+        let file = matches.get_one::<String>("FILE").expect("required");
+        if Path::new(file).exists() {
+            return Err(format!("'{}' already exists", file).into());
+        }
+
+        let address_book = AddressBook::new(self.config.clone());
+        let resolve = |name: &str| {
+            matches
+                .get_one::<String>(name)
+                .map(|v| address_book.resolve(v))
+        };
+        let from = resolve("from").expect("required")?;
+        let to = resolve("to").expect("required")?;
+        let fee_payer = resolve("fee-payer").transpose()?.unwrap_or(from);
+        let nonce_authority = resolve("nonce-authority").transpose()?.unwrap_or(from);
+        let amount = matches.get_one::<String>("amount").expect("required");
+        let lamports = amount
+            .parse::<u64>()
+            .map_err(|_| "Amount needs to be a number")?;
+        let nonce_account = matches.get_one::<String>("nonce").expect("required");
+        let nonce_account = Pubkey::from_str(nonce_account)
+            .map_err(|_| format!("Invalid nonce account '{}'", nonce_account))?;
+
+        let mut co_signers: Vec<Pubkey> = Vec::new();
+        for signer in matches.get_many::<String>("signer").unwrap_or_default() {
+            let signer = address_book.resolve(signer)?;
+            if !co_signers.contains(&signer) {
+                co_signers.push(signer);
+            }
+        }
+        let threshold = match matches.get_one::<String>("threshold") {
+            Some(threshold) => threshold
+                .parse::<usize>()
+                .ok()
+                .filter(|threshold| (1..=co_signers.len()).contains(threshold))
+                .ok_or_else(|| {
+                    format!(
+                        "The threshold must be a number between 1 and the number of co-signers ({})",
+                        co_signers.len()
+                    )
+                })?,
+            None => co_signers.len(),
+        };
+        let groups = combinations(co_signers.len(), threshold);
+        if groups.len() > MAX_VARIANTS {
+            return Err(format!(
+                "{} of {} co-signers needs {} transactions (at most {})",
+                threshold,
+                co_signers.len(),
+                groups.len(),
+                MAX_VARIANTS
+            )
+            .into());
+        }
+
+        let nonce_blockhash = match matches.get_one::<String>("nonce-blockhash") {
+            Some(hash) => {
+                Hash::from_str(hash).map_err(|_| format!("Invalid nonce blockhash '{}'", hash))?
+            }
+            None => {
+                let (hash, authority) =
+                    SolanaTransaction::fetch_nonce(&self.config, &nonce_account)?;
+                if authority != nonce_authority {
+                    return Err(format!(
+                        "The authority of nonce account {} is {} (pass it with --nonce-authority)",
+                        nonce_account, authority
+                    )
+                    .into());
+                }
+                hash
+            }
+        };
+
+        let description = matches
+            .get_one::<String>("description")
+            .cloned()
+            .unwrap_or_else(|| format!("Transfer of {} lamports to {}", lamports, to));
+
+        let transactions: Vec<VersionedTransaction> = groups
+            .iter()
+            .map(|group| {
+                let memo = Instruction::new_with_bytes(
+                    MEMO_PROGRAM_ID,
+                    description.as_bytes(),
+                    group
+                        .iter()
+                        .map(|index| AccountMeta::new_readonly(co_signers[*index], true))
+                        .collect(),
+                );
+                let transfer = system_instruction::transfer(&from, &to, lamports);
+                let message = SolanaTransaction::nonce_message(
+                    &self.config,
+                    &fee_payer,
+                    vec![memo, transfer],
+                    &nonce_account,
+                    &nonce_authority,
+                    nonce_blockhash,
+                );
+                VersionedTransaction {
+                    signatures: vec![
+                        Signature::default();
+                        message.header.num_required_signatures as usize
+                    ],
+                    message: VersionedMessage::Legacy(message),
+                }
+            })
+            .collect();
+
+        let request = SigningRequest::new(&transactions)?;
+        request.save(file)?;
+        self.output(file, &request, None, Vec::new())
+    }
+
+    /// Ajoute la signature d'une clé locale ("--from") à une demande de signature, après avoir affiché la
+    /// transaction et demandé confirmation (voir "sign_tx") : la clé signe toutes les variantes qui l'exigent.
+    /// Le fichier est mis à jour.
+    pub fn approve(
+        &self,
+        file: &str,
+        from: Option<&str>,
+        yes: bool,
+    ) -> Result<SigningRequestOutput, Box<dyn std::error::Error>> {
+        let request = SigningRequest::load(file)?;
+        let mut transactions = request.transactions()?;
+        let signed = DecodeManager::new(self.config.clone()).sign_transactions(
+            &mut transactions,
+            from,
+            yes,
+        )?;
+        let request = SigningRequest::new(&transactions)?;
+        request.save(file)?;
+        self.output(
+            file,
+            &request,
+            Some(signed.signer.to_string()),
+            signed.warnings,
+        )
+    }
+
+    /// Affiche une demande de signature et l'état de ses signatures.
+    pub fn status(&self, file: &str) -> Result<SigningRequestOutput, Box<dyn std::error::Error>> {
+        let request = SigningRequest::load(file)?;
+        self.output(file, &request, None, Vec::new())
+    }
+
+    /// Envoie une variante de la demande de signature dont toutes les signatures sont présentes.
+    pub fn submit(
+        &self,
+        file: &str,
+        wait: bool,
+    ) -> Result<SubmitOutput, Box<dyn std::error::Error>> {
+        let request = SigningRequest::load(file)?;
+        let Some(transaction) = request.ready_transaction()? else {
+            return Err(format!("Missing signatures: {}", Self::missing(&request)?).into());
+        };
+
+        let sent = SolanaTransaction::send_signed(&self.config, &transaction, wait)?;
+        Ok(SubmitOutput {
+            file: file.to_string(),
+            signature: sent.signature.to_string(),
+            simulated: sent.simulated,
+            confirmed: sent.confirmed,
+            slot: sent.slot,
+            confirmation_status: sent.confirmation_status,
+            explorer_url: SolanaExplorer::transaction_url(
+                &self.config.explorer_template,
+                &self.config.rpc_url,
+                &sent.signature.to_string(),
+            ),
+        })
+    }
+
+    /// Les signatures qui manquent : celles des signataires de toutes les variantes, et le nombre de
+    /// co-signataires qui doivent encore approuver (par exemple "1 of A, B").
+    fn missing(request: &SigningRequest) -> Result<String, Box<dyn std::error::Error>> {
+        let details = request.details()?;
+        let status = request.signature_status()?;
+        let pending = |co_signer: bool| {
+            status
+                .iter()
+                .filter(|(signer, signed)| {
+                    !signed && details.co_signers.contains(signer) == co_signer
+                })
+                .map(|(signer, _)| signer.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let mut missing = pending(false);
+        let co_signers = pending(true);
+        let needed = details
+            .threshold
+            .saturating_sub(details.co_signers.len() - co_signers.len());
+        if needed == co_signers.len() {
+            missing.extend(co_signers);
+        } else if needed > 0 {
+            missing.push(format!("{} of {}", needed, co_signers.join(", ")));
+        }
+        Ok(missing.join(", "))
+    }
+
+    fn output(
+        &self,
+        file: &str,
+        request: &SigningRequest,
+        approved_by: Option<String>,
+        warnings: Vec<String>,
+    ) -> Result<SigningRequestOutput, Box<dyn std::error::Error>> {
+        let details = request.details()?;
+        let signers: Vec<SignerStatus> = request
+            .signature_status()?
+            .into_iter()
+            .map(|(signer, signed)| SignerStatus {
+                signer: signer.to_string(),
+                signed,
+            })
+            .collect();
+        Ok(SigningRequestOutput {
+            file: file.to_string(),
+            description: details.description,
+            from: details.from.to_string(),
+            to: details.to.to_string(),
+            lamports: details.lamports,
+            nonce_account: details.nonce_account.to_string(),
+            threshold: details.threshold,
+            approvals: signers
+                .iter()
+                .filter(|status| {
+                    status.signed
+                        && details
+                            .co_signers
+                            .iter()
+                            .any(|co_signer| co_signer.to_string() == status.signer)
+                })
+                .count(),
+            co_signers: details.co_signers.iter().map(ToString::to_string).collect(),
+            ready: request.ready_transaction()?.is_some(),
+            signers,
+            approved_by,
+            warnings,
+        })
+    }
+}
@@ -0,0 +1,146 @@
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Noms des formats de sortie acceptés par l'option globale "--output".
+pub const OUTPUT_FORMATS: [&str; 3] = ["text", "json", "json-compact"];
+
+/// Format d'affichage des résultats des commandes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// Texte lisible par un humain (affichage historique).
+    #[default]
+    Text,
+    /// JSON indenté.
+    Json,
+    /// JSON sur une seule ligne (pratique pour les scripts et les fichiers de logs).
+    JsonCompact,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            "json-compact" => Ok(OutputFormat::JsonCompact),
+            _ => Err(format!(
+                "Invalid output format '{}' (expected {})",
+                name,
+                OUTPUT_FORMATS.join(", ")
+            )),
+        }
+    }
+}
+
+/// Vrai si une erreur a été affichée : le processus se termine alors avec un code de sortie non nul.
+static ERROR_REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Erreur d'une commande, telle qu'elle est affichée en JSON.
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub error: String,
+}
+
+impl fmt::Display for ErrorOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+/// Affiche les résultats des commandes dans le format choisi.
+///
+/// Chaque commande produit un résultat structuré : son implémentation de `Display` donne l'affichage texte,
+/// son implémentation de `Serialize` l'affichage JSON. En JSON, la sortie standard ne contient que le résultat :
+/// les questions et messages intermédiaires sont écrits sur la sortie d'erreur. Les erreurs sont toujours écrites
+/// sur la sortie d'erreur, et le code de sortie du processus est alors non nul.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Output {
+    format: OutputFormat,
+}
+
+impl Output {
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format }
+    }
+
+    /// Affiche le résultat d'une commande.
+    pub fn print<T: Serialize + fmt::Display>(&self, result: &T) {
+        println!("{}", self.format(result));
+    }
+
+    /// Affiche l'erreur d'une commande sur la sortie d'erreur (le message en texte, un objet `{"error": "..."}`
+    /// en JSON), et retient l'échec pour le code de sortie.
+    pub fn error(&self, message: impl fmt::Display) {
+        ERROR_REPORTED.store(true, Ordering::Relaxed);
+        eprintln!(
+            "{}",
+            self.format(&ErrorOutput {
+                error: message.to_string(),
+            })
+        );
+    }
+
+    /// Vrai si une erreur a été affichée depuis le dernier appel (le processus, ou la dernière commande du shell,
+    /// a échoué).
+    pub fn take_error() -> bool {
+        ERROR_REPORTED.swap(false, Ordering::Relaxed)
+    }
+
+    fn format<T: Serialize + fmt::Display>(&self, result: &T) -> String {
+        match self.format {
+            OutputFormat::Text => result.to_string(),
+            OutputFormat::Json => {
+                serde_json::to_string_pretty(result).expect("command results are serializable")
+            }
+            OutputFormat::JsonCompact => {
+                serde_json::to_string(result).expect("command results are serializable")
+            }
+        }
+    }
+
+    /// Affiche le résultat d'une commande, ou son erreur précédée de "context" (par exemple "Failed to retrieve balance").
+    pub fn print_result<T, E>(&self, result: Result<T, E>, context: &str)
+    where
+        T: Serialize + fmt::Display,
+        E: fmt::Display,
+    {
+        self.print_outcome(result.map_err(|e| format!("{}: {}", context, e)));
+    }
+
+    /// Affiche le résultat d'une commande, ou son erreur telle quelle (message déjà complet).
+    pub fn print_outcome<T, E>(&self, result: Result<T, E>)
+    where
+        T: Serialize + fmt::Display,
+        E: fmt::Display,
+    {
+        match result {
+            Ok(result) => self.print(&result),
+            Err(e) => self.error(e),
+        }
+    }
+
+    /// Affiche un message destiné à l'utilisateur (question, progression) : sur la sortie standard en texte,
+    /// sur la sortie d'erreur en JSON.
+    pub fn message(&self, message: impl fmt::Display) {
+        match self.format {
+            OutputFormat::Text => println!("{}", message),
+            OutputFormat::Json | OutputFormat::JsonCompact => eprintln!("{}", message),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_from_str() {
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("json-compact".parse(), Ok(OutputFormat::JsonCompact));
+        assert!("yaml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,82 @@
+//! QR codes (mode "octets", niveau de correction d'erreurs M) affichables dans le terminal, encodés par "qrcodegen".
+
+use qrcodegen::{QrCodeEcc, QrSegment, Version};
+
+/// Largeur de la marge claire autour du QR code, en modules (4 selon la norme ISO/IEC 18004).
+const QUIET_ZONE: i32 = 4;
+
+pub struct QrCode {
+    code: qrcodegen::QrCode,
+}
+
+impl QrCode {
+    /// Encode des données dans le plus petit QR code possible (versions 1 à 40).
+    pub fn encode(data: &[u8]) -> Result<Self, Box<dyn std::error::Error>> {
+        let code = qrcodegen::QrCode::encode_segments_advanced(
+            &[QrSegment::make_bytes(data)],
+            QrCodeEcc::Medium,
+            Version::MIN,
+            Version::MAX,
+            None,
+            false,
+        )
+        .map_err(|_| format!("Data too long for a QR code ({} bytes)", data.len()))?;
+        Ok(Self { code })
+    }
+
+    /// Le QR code en caractères "demi-bloc" (deux lignes de modules par ligne de texte).
+    /// Les modules clairs sont dessinés et les modules sombres laissés vides, pour un terminal sur fond sombre.
+    pub fn to_terminal(&self) -> String {
+        let size = self.code.size();
+        // "get_module" retourne faux (clair) en dehors du QR code : la marge est claire.
+        let is_light = |x: i32, y: i32| !self.code.get_module(x, y);
+
+        (-QUIET_ZONE..size + QUIET_ZONE)
+            .step_by(2)
+            .map(|y| {
+                (-QUIET_ZONE..size + QUIET_ZONE)
+                    .map(|x| match (is_light(x, y), is_light(x, y + 1)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode() {
+        let qr_code =
+            QrCode::encode(b"solana:DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2").unwrap();
+        // 51 octets : version 4 (33 x 33 modules) au niveau M.
+        assert_eq!(qr_code.code.size(), 33);
+
+        let terminal = qr_code.to_terminal();
+        let width = 33 + 2 * QUIET_ZONE as usize;
+        assert_eq!(terminal.lines().count(), width.div_ceil(2));
+        assert!(terminal.lines().all(|line| line.chars().count() == width));
+        // La marge est claire. Les deux premières lignes du repère en haut à gauche : la marge, le bord sombre du
+        // repère, puis une colonne sombre en haut (bord) et claire en bas.
+        assert!(terminal.lines().next().unwrap().chars().all(|c| c == '█'));
+        let finder: String = terminal
+            .lines()
+            .nth(2)
+            .unwrap()
+            .chars()
+            .skip(3)
+            .take(3)
+            .collect();
+        assert_eq!(finder, "█ ▄");
+
+        assert!(QrCode::encode(&[b'a'; 2331]).is_ok());
+        assert!(QrCode::encode(&[b'a'; 2332]).is_err());
+    }
+}
@@ -0,0 +1,57 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::{EndpointHealth, SolanaRpc};
+use serde::Serialize;
+use std::fmt;
+
+/// Résultat de la commande "health".
+#[derive(Debug, Serialize)]
+pub struct HealthOutput {
+    pub endpoints: Vec<EndpointHealth>,
+}
+
+impl fmt::Display for HealthOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let mut line = format!(
+                    "{}: {}",
+                    endpoint.url,
+                    if endpoint.healthy { "ok" } else { "unhealthy" }
+                );
+                if let Some(slot) = endpoint.slot {
+                    line.push_str(&format!(", slot {}", slot));
+                }
+                if let Some(lag) = endpoint.slot_lag {
+                    line.push_str(&format!(" ({} behind)", lag));
+                }
+                if !endpoint.preferred {
+                    line.push_str(", used as last resort");
+                }
+                if let Some(error) = &endpoint.error {
+                    line.push_str(&format!(" - {}", error));
+                }
+                line
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct RpcManager {
+    config: WalletConfig,
+}
+
+impl RpcManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Vérifie la santé de chaque point d'accès RPC ("getHealth" et retard du slot sur le plus avancé).
+    pub fn health(&self) -> Result<HealthOutput, Box<dyn std::error::Error>> {
+        Ok(HealthOutput {
+            endpoints: SolanaRpc::check_health(&self.config)?,
+        })
+    }
+}
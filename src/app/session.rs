@@ -0,0 +1,120 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use zeroize::Zeroize;
+
+/// État de la session du mode interactif.
+struct SessionState {
+    /// La passphrase BIP39 saisie (None tant que la session est verrouillée).
+    passphrase: Option<String>,
+    last_activity: Instant,
+    /// Délai d'inactivité avant le verrouillage (None = jamais).
+    idle_timeout: Option<Duration>,
+    /// Vrai si la session a été verrouillée par inactivité depuis la dernière vérification.
+    locked_by_timeout: bool,
+}
+
+impl SessionState {
+    fn lock(&mut self) {
+        if let Some(mut passphrase) = self.passphrase.take() {
+            passphrase.zeroize();
+        }
+    }
+
+    fn is_idle(&self) -> bool {
+        self.idle_timeout
+            .is_some_and(|timeout| self.last_activity.elapsed() >= timeout)
+    }
+}
+
+static SESSION: Mutex<Option<SessionState>> = Mutex::new(None);
+
+/// Session du mode interactif ("shell").
+///
+/// La passphrase BIP39 saisie lors d'une commande est conservée en mémoire (la session est "déverrouillée") et réutilisée
+/// par les commandes suivantes, jusqu'au verrouillage : commande "lock", fin du mode interactif ou inactivité prolongée.
+/// Hors du mode interactif, aucune session n'est active et la passphrase est demandée à chaque commande.
+pub struct Session {}
+
+impl Session {
+    /// Démarre la session. Un fil d'exécution efface la passphrase dès que le délai d'inactivité est écoulé.
+    pub fn start(idle_timeout: Option<Duration>) {
+        *SESSION.lock().unwrap() = Some(SessionState {
+            passphrase: None,
+            last_activity: Instant::now(),
+            idle_timeout,
+            locked_by_timeout: false,
+        });
+
+        if idle_timeout.is_some() {
+            thread::spawn(|| loop {
+                thread::sleep(Duration::from_secs(1));
+                match SESSION.lock().unwrap().as_mut() {
+                    Some(state) => {
+                        if state.passphrase.is_some() && state.is_idle() {
+                            state.lock();
+                            state.locked_by_timeout = true;
+                        }
+                    }
+                    None => break,
+                }
+            });
+        }
+    }
+
+    /// Termine la session (la passphrase est effacée).
+    pub fn end() {
+        if let Some(mut state) = SESSION.lock().unwrap().take() {
+            state.lock();
+        }
+    }
+
+    /// Enregistre une activité de l'utilisateur. Retourne vrai si la session a été verrouillée par inactivité depuis
+    /// la dernière activité (pour en informer l'utilisateur).
+    pub fn touch() -> bool {
+        let mut session = SESSION.lock().unwrap();
+        let Some(state) = session.as_mut() else {
+            return false;
+        };
+
+        if state.passphrase.is_some() && state.is_idle() {
+            state.lock();
+            state.locked_by_timeout = true;
+        }
+        state.last_activity = Instant::now();
+        std::mem::take(&mut state.locked_by_timeout)
+    }
+
+    /// Retourne la passphrase de la session si elle est déverrouillée.
+    pub fn passphrase() -> Option<String> {
+        SESSION
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|state| state.passphrase.clone())
+    }
+
+    /// Déverrouille la session avec la passphrase saisie (sans effet hors du mode interactif).
+    pub fn unlock(passphrase: &str) {
+        if let Some(state) = SESSION.lock().unwrap().as_mut() {
+            state.lock();
+            state.passphrase = Some(passphrase.to_string());
+        }
+    }
+
+    /// Verrouille la session : la passphrase sera demandée à nouveau.
+    pub fn lock() {
+        if let Some(state) = SESSION.lock().unwrap().as_mut() {
+            state.lock();
+        }
+    }
+
+    /// Vrai si la session est déverrouillée.
+    pub fn is_unlocked() -> bool {
+        SESSION
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|state| state.passphrase.is_some())
+    }
+}
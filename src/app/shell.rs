@@ -0,0 +1,239 @@
+use crate::app::cli::AppCli;
+use crate::app::line_editor::LineEditor;
+use crate::app::output::Output;
+use crate::app::session::Session;
+use crate::config::wallet_config::WalletConfig;
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use crate::store::shell_history::ShellHistory;
+use clap::Command;
+use std::time::Duration;
+
+/// Commandes propres au mode interactif (en plus des commandes de la CLI).
+const SHELL_COMMANDS: [&str; 4] = ["exit", "help", "lock", "quit"];
+
+/// Le mode interactif ("shell") : une boucle qui lit et exécute les commandes de la CLI, sans relancer le programme.
+/// La passphrase saisie est conservée par la session jusqu'à son verrouillage (voir `Session`).
+pub struct Shell {
+    config: WalletConfig,
+}
+
+impl Shell {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Lit et exécute les commandes jusqu'à "exit", "quit" ou la fin de l'entrée (Ctrl-D).
+    pub fn run(&self) {
+        let output = self.config.output();
+        let history = ShellHistory::new(self.config.clone());
+        let mut editor = LineEditor::new(history.load().unwrap_or_default());
+
+        let idle_timeout = self.config.shell_idle_timeout;
+        Session::start(Some(Duration::from_secs(idle_timeout)).filter(|_| idle_timeout > 0));
+        output.message(
+            "Solana Wallet shell. Type \"help\" for the commands, \"lock\" to forget the passphrase, \"exit\" to quit.",
+        );
+
+        loop {
+            let prompt = if Session::is_unlocked() {
+                "wallet (unlocked)> "
+            } else {
+                "wallet> "
+            };
+            let line = match editor.read_line(prompt, &|line| self.complete(line)) {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(e) => {
+                    output.error(format!("Failed to read input: {}", e));
+                    break;
+                }
+            };
+            if Session::touch() {
+                output.message(format!(
+                    "Session locked after {} seconds of inactivity.",
+                    idle_timeout
+                ));
+            }
+
+            let args = match Self::split_arguments(&line) {
+                Ok(args) if args.is_empty() => continue,
+                Ok(args) => args,
+                Err(e) => {
+                    output.error(e);
+                    continue;
+                }
+            };
+
+            // Les commandes contenant des secrets ne sont enregistrées ni dans le fichier, ni dans l'historique de navigation.
+            match history.append(&line, &args) {
+                Ok(true) => editor.add_history(&line),
+                Ok(false) => {}
+                Err(e) => output.error(format!("Failed to write shell history: {}", e)),
+            }
+
+            match args[0].as_str() {
+                "exit" | "quit" => break,
+                "lock" => {
+                    Session::lock();
+                    output.message("Session locked.");
+                }
+                _ => self.execute(&args),
+            }
+        }
+
+        // Les erreurs des commandes sont affichées dans le shell : elles ne changent pas son code de sortie.
+        Output::take_error();
+        Session::end();
+    }
+
+    /// Exécute une commande de la CLI, avec la configuration rechargée selon ses options globales ("--profile", "--url"...).
+    fn execute(&self, args: &[String]) {
+        let matches = match self.cli().no_binary_name(true).try_get_matches_from(args) {
+            Ok(matches) => matches,
+            Err(e) => {
+                // Erreur de syntaxe, ou affichage de l'aide ("help", "--help").
+                let _ = e.print();
+                return;
+            }
+        };
+        if matches.subcommand_name() == Some("shell") {
+            self.config.output().error("Already in the shell.");
+            return;
+        }
+
+        match WalletConfig::load(&AppCli::config_overrides(&matches)) {
+            Ok(config) => AppCli::new(config).handle_matches(matches),
+            Err(e) => self
+                .config
+                .output()
+                .error(format!("Invalid configuration: {}", e)),
+        }
+    }
+
+    /// Retourne les complétions possibles du dernier mot de la ligne :
+    /// - les commandes pour le premier mot, et les sous-commandes pour le deuxième ("wallet use"...) ;
+    /// - les options de la commande pour un mot commençant par "-" ;
+    /// - les noms des contacts et les labels du key store pour les autres arguments.
+    fn complete(&self, line: &str) -> Vec<String> {
+        let cli = self.cli();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (previous, word) = if line.is_empty() || line.ends_with(' ') {
+            (&words[..], "")
+        } else {
+            (&words[..words.len() - 1], words[words.len() - 1])
+        };
+        let commands: Vec<&str> = previous
+            .iter()
+            .filter(|word| !word.starts_with('-'))
+            .copied()
+            .collect();
+
+        let command = commands.first().and_then(|name| cli.find_subcommand(name));
+        let subcommand = command
+            .zip(commands.get(1))
+            .and_then(|(command, name)| command.find_subcommand(name));
+
+        let mut candidates: Vec<String> = if word.starts_with('-') {
+            subcommand
+                .or(command)
+                .into_iter()
+                .chain(Some(&cli))
+                .flat_map(Command::get_arguments)
+                .filter_map(|arg| arg.get_long().map(|long| format!("--{}", long)))
+                .collect()
+        } else if commands.is_empty() {
+            cli.get_subcommands()
+                .map(|command| command.get_name().to_string())
+                .chain(SHELL_COMMANDS.iter().map(ToString::to_string))
+                .collect()
+        } else if commands.len() == 1 && command.is_some_and(Command::has_subcommands) {
+            command
+                .into_iter()
+                .flat_map(Command::get_subcommands)
+                .map(|command| command.get_name().to_string())
+                .collect()
+        } else {
+            self.names()
+        };
+
+        candidates.retain(|candidate| candidate.starts_with(word));
+        candidates.sort();
+        candidates.dedup();
+        candidates
+    }
+
+    /// Les noms des contacts et les labels du key store.
+    fn names(&self) -> Vec<String> {
+        let contacts = AddressBook::new(self.config.clone())
+            .load()
+            .map(|data| data.contacts)
+            .unwrap_or_default();
+        let keys = KeyStore::new(self.config.clone())
+            .load()
+            .map(|manifest| manifest.keys)
+            .unwrap_or_default();
+
+        contacts
+            .into_iter()
+            .map(|contact| contact.name)
+            .chain(keys.into_iter().map(|key| key.label))
+            .collect()
+    }
+
+    fn cli(&self) -> Command {
+        AppCli::new(self.config.clone()).setup_cli()
+    }
+
+    /// Découpe une ligne en arguments, séparés par des espaces.
+    /// Les guillemets (simples ou doubles) regroupent un argument contenant des espaces (une mnémonique par exemple),
+    /// et "\" échappe le caractère suivant.
+    fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        let mut current: Option<String> = None;
+        let mut quote: Option<char> = None;
+        let mut chars = line.chars();
+
+        while let Some(c) = chars.next() {
+            match (c, quote) {
+                ('\\', _) => {
+                    let escaped = chars.next().ok_or("Unexpected end of line after '\\'")?;
+                    current.get_or_insert_with(String::new).push(escaped);
+                }
+                (c, Some(q)) if c == q => quote = None,
+                (c, Some(_)) => current.get_or_insert_with(String::new).push(c),
+                ('"' | '\'', None) => {
+                    quote = Some(c);
+                    current.get_or_insert_with(String::new);
+                }
+                (c, None) if c.is_whitespace() => args.extend(current.take()),
+                (c, None) => current.get_or_insert_with(String::new).push(c),
+            }
+        }
+
+        if quote.is_some() {
+            return Err("Unterminated quote".to_string());
+        }
+        args.extend(current);
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_arguments() {
+        assert_eq!(
+            Shell::split_arguments(r#"recover_seed "word1 word2" --from 'my key' a\ b"#).unwrap(),
+            ["recover_seed", "word1 word2", "--from", "my key", "a b"]
+        );
+        assert_eq!(Shell::split_arguments("  ").unwrap(), Vec::<String>::new());
+        assert_eq!(
+            Shell::split_arguments(r#"label """#).unwrap(),
+            ["label", ""]
+        );
+        assert!(Shell::split_arguments(r#"recover_seed "word1"#).is_err());
+    }
+}
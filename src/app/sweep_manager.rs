@@ -0,0 +1,478 @@
+use crate::app::token_manager::SkippedTokenAccount;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::stake::{SolanaStake, StakeAccount};
+use crate::solana::token::{self, SolanaToken, TokenAccount};
+use crate::solana::transaction::{SentTransaction, SolanaTransaction};
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::fmt;
+use std::io;
+
+/// Le nombre maximal de comptes de tokens (ou de comptes de stake) traités par transaction : chaque compte
+/// de token ajoute son mint et le compte de destination, et la transaction doit tenir dans un paquet.
+const SWEEP_BATCH_SIZE: usize = 4;
+
+/// Un compte de token vidé vers le compte associé de la destination.
+#[derive(Clone, Debug, Serialize)]
+pub struct SweptToken {
+    pub account: String,
+    pub mint: String,
+    /// Le montant transféré, en unités du token.
+    pub amount: String,
+    /// Le compte de token associé de la destination.
+    pub destination: String,
+    /// Vrai si le compte de destination est créé (il n'existe pas encore).
+    pub create_destination: bool,
+    /// Vrai si le compte vidé est fermé (son loyer revient à la clé balayée).
+    pub close: bool,
+}
+
+/// Un compte de stake dont les autorités sont transférées à la destination.
+#[derive(Debug, Serialize)]
+pub struct SweptStakeAccount {
+    pub account: String,
+    pub lamports: u64,
+    /// Le compte de vote auquel le stake est délégué.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voter: Option<String>,
+}
+
+/// Les actifs d'une clé balayée.
+#[derive(Debug, Serialize)]
+pub struct SweptKey {
+    pub from: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub tokens: Vec<SweptToken>,
+    pub stake_accounts: Vec<SweptStakeAccount>,
+    /// Les comptes de tokens laissés en place, et la raison.
+    pub skipped: Vec<SkippedTokenAccount>,
+    /// Les lamports envoyés en dernier (le solde moins les frais ; une estimation dans l'aperçu).
+    pub lamports: u64,
+    pub signatures: Vec<String>,
+    /// L'erreur qui a interrompu le balayage de cette clé (les transactions suivantes ne sont pas envoyées).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Résultat de la commande "sweep" (et son aperçu, affiché avant la confirmation).
+#[derive(Debug, Serialize)]
+pub struct SweepOutput {
+    pub to: String,
+    /// Faux pour l'aperçu (aucune transaction envoyée).
+    pub executed: bool,
+    pub keys: Vec<SweptKey>,
+    /// Vrai en mode test (transactions signées mais pas envoyées).
+    pub simulated: bool,
+    /// Vrai si toutes les transactions ont atteint le niveau de confirmation configuré.
+    pub confirmed: bool,
+    /// Le nombre de clés dont le balayage a échoué.
+    pub failed: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for SweepOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        lines.push(format!(
+            "{} to {}",
+            if self.executed {
+                "Sweep report"
+            } else {
+                "Sweep plan"
+            },
+            self.to
+        ));
+        for key in &self.keys {
+            match &key.label {
+                Some(label) => lines.push(format!("From: {} ({})", key.from, label)),
+                None => lines.push(format!("From: {}", key.from)),
+            }
+            for token in &key.tokens {
+                let mut line = format!("  Token {}: {}", token.mint, token.amount);
+                if token.create_destination {
+                    line.push_str(" (creates the destination account)");
+                }
+                if token.close {
+                    line.push_str(", account closed");
+                }
+                lines.push(line);
+            }
+            for stake in &key.stake_accounts {
+                lines.push(format!(
+                    "  Stake account {}: {} lamports, authorities transferred",
+                    stake.account, stake.lamports
+                ));
+            }
+            for account in &key.skipped {
+                lines.push(format!("  Kept {}: {}", account.account, account.reason));
+            }
+            lines.push(format!(
+                "  SOL: {} lamports ({} SOL){}",
+                key.lamports,
+                key.lamports as f64 / 1_000_000_000_f64,
+                match (self.executed, &key.error) {
+                    (false, _) => ", estimated",
+                    (true, Some(_)) => ", not sent",
+                    (true, None) => "",
+                }
+            ));
+            for signature in &key.signatures {
+                lines.push(format!("  Signature: {}", signature));
+            }
+            if let Some(error) = &key.error {
+                lines.push(format!("  Failed: {}", error));
+            }
+        }
+        if self.failed > 0 {
+            lines.push(format!(
+                "Sweep incomplete: {} of {} key(s) failed",
+                self.failed,
+                self.keys.len()
+            ));
+        }
+        if self.simulated {
+            lines.push("(simulated: transactions signed but not sent)".to_string());
+        } else if self.executed && !self.confirmed {
+            lines.push(
+                "Transaction submitted (not confirmed yet, check it with \"confirm <SIGNATURE>\")"
+                    .to_string(),
+            );
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Ce qu'il faut balayer pour une clé.
+struct SweepPlan {
+    label: Option<String>,
+    keypair: Keypair,
+    tokens: Vec<(TokenAccount, SweptToken)>,
+    stake_accounts: Vec<StakeAccount>,
+    skipped: Vec<SkippedTokenAccount>,
+    /// Le solde de SOL estimé à la fin du balayage, frais du dernier transfert déduits.
+    lamports: u64,
+}
+
+pub struct SweepManager {
+    config: WalletConfig,
+}
+
+impl SweepManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Balaye tous les actifs d'une clé locale ("--from", ou toutes avec "--all") vers une nouvelle adresse,
+    /// par exemple après la compromission d'une machine : les tokens (en créant les comptes associés de la
+    /// destination si nécessaire, puis en fermant les comptes vidés), les autorités des comptes de stake,
+    /// et en dernier les SOL restants, frais déduits.
+    ///
+    /// Le plan est affiché et confirmé avant l'envoi (sauf avec "--yes").
+    ///
+    /// # Arguments:
+    /// - to - La destination (clé publique, nom de contact ou label du key store).
+    /// - from - La clé à balayer (la clé active par défaut).
+    /// - all - Balaye toutes les clés du key store (sauf la destination et les entrées "watch-only").
+    /// - yes - Envoie sans demander confirmation.
+    /// - wait - Attendre la confirmation du dernier transfert de SOL (les transactions précédentes sont
+    ///   toujours confirmées, pour connaître le solde restant).
+    pub fn sweep(
+        &self,
+        to: &str,
+        from: Option<&str>,
+        all: bool,
+        yes: bool,
+        wait: bool,
+    ) -> Result<SweepOutput, Box<dyn std::error::Error>> {
+        let address_book = AddressBook::new(self.config.clone());
+        let destination = address_book.resolve(to)?;
+
+        let keystore = KeyStore::new(self.config.clone());
+        let manifest = keystore.load()?;
+        let keypairs = if all {
+            manifest
+                .keys
+                .iter()
+                .filter(|entry| entry.path.is_some() && entry.pubkey != destination.to_string())
+                .map(|entry| keystore.resolve_keypair(Some(&entry.label)))
+                .collect::<Result<Vec<_>, _>>()?
+        } else {
+            vec![keystore.resolve_keypair(from)?]
+        };
+        if keypairs.is_empty() {
+            return Err("No keypair to sweep".into());
+        }
+        if keypairs
+            .iter()
+            .any(|keypair| keypair.pubkey() == destination)
+        {
+            return Err(format!("{} is the swept key", destination).into());
+        }
+
+        let mut plans = Vec::new();
+        for keypair in keypairs {
+            let label = KeyStore::find(&manifest, &keypair.pubkey().to_string())
+                .map(|entry| entry.label.clone());
+            plans.push(self.plan(keypair, label, &destination)?);
+        }
+
+        let mut warnings = Vec::new();
+        if !address_book.is_known(&destination)? {
+            warnings.push(format!(
+                "Warning: {} is not in your address book and has never received funds from this wallet.",
+                destination
+            ));
+        }
+
+        if !yes {
+            let output = self.config.output();
+            output.message(Self::output(&destination, &plans, None, warnings.clone()));
+            output.message(format!("Sweep everything to {}? [y/N]", destination));
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Err("Sweep cancelled".into());
+            }
+        }
+
+        // Une clé en échec n'interrompt pas le balayage des suivantes : le rapport liste ce qui a été envoyé.
+        let mut sent = Vec::new();
+        for plan in &mut plans {
+            let mut key_sent = Vec::new();
+            let error = self
+                .execute(plan, &destination, wait, &mut key_sent)
+                .err()
+                .map(|e| e.to_string());
+            sent.push((key_sent, error));
+        }
+        if sent.iter().any(|(key_sent, _)| !key_sent.is_empty()) {
+            address_book.remember_recipient(&destination)?;
+        }
+        Ok(Self::output(&destination, &plans, Some(sent), warnings))
+    }
+
+    /// Inventorie les actifs d'une clé : solde de SOL, comptes de tokens et comptes de stake.
+    fn plan(
+        &self,
+        keypair: Keypair,
+        label: Option<String>,
+        destination: &Pubkey,
+    ) -> Result<SweepPlan, Box<dyn std::error::Error>> {
+        let owner = keypair.pubkey();
+        let client = SolanaRpc::client(&self.config);
+        let balance = client.get_balance(&owner)?;
+
+        let accounts = SolanaToken::new(self.config.clone()).get_token_accounts(&owner)?;
+        let destinations: Vec<Pubkey> = accounts
+            .iter()
+            .map(|account| {
+                token::associated_token_address(destination, &account.mint, &account.program_id)
+            })
+            .collect();
+        let existing = if destinations.is_empty() {
+            Vec::new()
+        } else {
+            client.get_multiple_accounts(&destinations)?
+        };
+
+        let mut tokens: Vec<(TokenAccount, SweptToken)> = Vec::new();
+        let mut skipped = Vec::new();
+        // Le loyer rendu par les comptes fermés, moins celui des comptes créés pour la destination.
+        let mut rent: i128 = 0;
+        for ((account, ata), existing) in accounts.into_iter().zip(destinations).zip(existing) {
+            // Un compte ne peut être fermé que par son autorité de fermeture (par défaut, la clé balayée).
+            let foreign_close_authority = account
+                .close_authority
+                .filter(|authority| *authority != owner);
+            let reason = if account.frozen {
+                Some("frozen by the mint".to_string())
+            } else if account.amount == 0 && foreign_close_authority.is_some() {
+                foreign_close_authority.map(|authority| format!("closable only by {}", authority))
+            } else if account.amount == 0 && account.withheld_fees > 0 {
+                Some(format!(
+                    "{} withheld transfer fees to harvest first",
+                    account.withheld_fees
+                ))
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                skipped.push(SkippedTokenAccount {
+                    account: account.address.to_string(),
+                    mint: account.mint.to_string(),
+                    reason,
+                });
+                continue;
+            }
+
+            let destination = ata.to_string();
+            // Le compte de destination n'est créé qu'une fois, même si plusieurs comptes vidés ont le même mint.
+            let create_destination = account.amount > 0
+                && existing.is_none()
+                && !tokens
+                    .iter()
+                    .any(|(_, token)| token.destination == destination && token.create_destination);
+            let close = foreign_close_authority.is_none() && account.withheld_fees == 0;
+            if create_destination {
+                rent -= account.lamports as i128;
+            }
+            if close {
+                rent += account.lamports as i128;
+            }
+            tokens.push((
+                account.clone(),
+                SweptToken {
+                    account: account.address.to_string(),
+                    mint: account.mint.to_string(),
+                    amount: account.ui_amount,
+                    destination,
+                    create_destination,
+                    close,
+                },
+            ));
+        }
+
+        let stake_accounts = SolanaStake::get_stake_accounts(&self.config, &owner)?;
+        let transactions = tokens.len().div_ceil(SWEEP_BATCH_SIZE)
+            + stake_accounts.len().div_ceil(SWEEP_BATCH_SIZE)
+            + 1;
+        let fees =
+            transactions as i128 * SolanaTransaction::estimate_transfer_fee(&self.config) as i128;
+
+        Ok(SweepPlan {
+            label,
+            keypair,
+            tokens,
+            stake_accounts,
+            skipped,
+            lamports: (balance as i128 + rent - fees).max(0) as u64,
+        })
+    }
+
+    /// Envoie les transactions d'un plan : les tokens, les autorités de stake, puis les SOL restants.
+    /// Les transactions envoyées sont ajoutées à "sent" au fur et à mesure, y compris en cas d'erreur.
+    fn execute(
+        &self,
+        plan: &mut SweepPlan,
+        destination: &Pubkey,
+        wait: bool,
+        sent: &mut Vec<SentTransaction>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let owner = plan.keypair.pubkey();
+        let mut transactions: Vec<Vec<Instruction>> = Vec::new();
+        for batch in plan.tokens.chunks(SWEEP_BATCH_SIZE) {
+            let mut instructions = Vec::new();
+            for (account, swept) in batch {
+                if account.amount > 0 {
+                    if swept.create_destination {
+                        instructions.push(token::create_associated_token_account(
+                            &owner,
+                            destination,
+                            &account.mint,
+                            &account.program_id,
+                        ));
+                    }
+                    let ata = token::associated_token_address(
+                        destination,
+                        &account.mint,
+                        &account.program_id,
+                    );
+                    instructions.push(token::transfer_all(account, &ata, &owner));
+                }
+                if swept.close {
+                    instructions.push(token::close_account(account, &owner, &owner));
+                }
+            }
+            transactions.push(instructions);
+        }
+        for batch in plan.stake_accounts.chunks(SWEEP_BATCH_SIZE) {
+            transactions.push(
+                batch
+                    .iter()
+                    .flat_map(|stake| SolanaStake::transfer_authorities(stake, &owner, destination))
+                    .collect(),
+            );
+        }
+
+        for instructions in transactions {
+            // Chaque transaction est confirmée avant la suivante : le transfert des SOL restants vient en dernier.
+            sent.push(SolanaTransaction::send_instructions(
+                &self.config,
+                &plan.keypair,
+                instructions,
+                true,
+            )?);
+        }
+
+        // Hors simulation, le solde réel est relu (loyers rendus et frais payés).
+        if !sent.iter().any(|sent| sent.simulated) {
+            let balance = SolanaRpc::client(&self.config).get_balance(&owner)?;
+            plan.lamports =
+                balance.saturating_sub(SolanaTransaction::estimate_transfer_fee(&self.config));
+        }
+        if plan.lamports > 0 {
+            sent.push(SolanaTransaction::send_lamports(
+                &self.config,
+                &plan.keypair,
+                destination,
+                plan.lamports,
+                wait,
+            )?);
+        }
+        Ok(())
+    }
+
+    fn output(
+        destination: &Pubkey,
+        plans: &[SweepPlan],
+        sent: Option<Vec<(Vec<SentTransaction>, Option<String>)>>,
+        warnings: Vec<String>,
+    ) -> SweepOutput {
+        let executed = sent.is_some();
+        let sent = sent.unwrap_or_default();
+        let transactions = || sent.iter().flat_map(|(key_sent, _)| key_sent);
+        let failed = sent.iter().filter(|(_, error)| error.is_some()).count();
+        SweepOutput {
+            to: destination.to_string(),
+            executed,
+            keys: plans
+                .iter()
+                .enumerate()
+                .map(|(index, plan)| SweptKey {
+                    from: plan.keypair.pubkey().to_string(),
+                    label: plan.label.clone(),
+                    tokens: plan.tokens.iter().map(|(_, swept)| swept.clone()).collect(),
+                    stake_accounts: plan
+                        .stake_accounts
+                        .iter()
+                        .map(|stake| SweptStakeAccount {
+                            account: stake.address.to_string(),
+                            lamports: stake.lamports,
+                            voter: stake.voter.map(|voter| voter.to_string()),
+                        })
+                        .collect(),
+                    skipped: plan.skipped.clone(),
+                    lamports: plan.lamports,
+                    signatures: sent
+                        .get(index)
+                        .into_iter()
+                        .flat_map(|(key_sent, _)| key_sent)
+                        .map(|sent| sent.signature.to_string())
+                        .collect(),
+                    error: sent.get(index).and_then(|(_, error)| error.clone()),
+                })
+                .collect(),
+            simulated: transactions().any(|sent| sent.simulated),
+            confirmed: executed && failed == 0 && transactions().all(|sent| sent.confirmed),
+            failed,
+            warnings,
+        }
+    }
+}
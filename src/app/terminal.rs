@@ -0,0 +1,210 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// Une touche lue par `Screen::read_key`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Key {
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Up,
+    Down,
+    Escape,
+    /// Ctrl-C.
+    Interrupt,
+}
+
+/// Vrai si l'entrée et la sortie standard sont un terminal.
+#[cfg(unix)]
+pub fn is_terminal() -> bool {
+    // SAFETY: isatty ne fait que consulter les descripteurs de fichiers.
+    unsafe { libc::isatty(libc::STDIN_FILENO) == 1 && libc::isatty(libc::STDOUT_FILENO) == 1 }
+}
+
+#[cfg(not(unix))]
+pub fn is_terminal() -> bool {
+    false
+}
+
+/// Mode "raw" du terminal (saisie non mise en forme, sans écho), rétabli lorsque la valeur est détruite.
+pub struct RawMode {
+    #[cfg(unix)]
+    original: libc::termios,
+}
+
+impl RawMode {
+    #[cfg(unix)]
+    pub fn enable() -> io::Result<Self> {
+        // SAFETY: la structure termios est initialisée par tcgetattr avant d'être utilisée.
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG | libc::IEXTEN);
+            raw.c_iflag &= !(libc::IXON | libc::ICRNL);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(Self { original })
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn enable() -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Raw terminal mode is not supported on this platform",
+        ))
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // SAFETY: rétablit les attributs lus par tcgetattr.
+        #[cfg(unix)]
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+/// L'écran plein du terminal (écran alternatif, curseur masqué, mode "raw"), rétabli lorsque la valeur est détruite.
+pub struct Screen {
+    _raw_mode: RawMode,
+}
+
+impl Screen {
+    pub fn enter() -> io::Result<Self> {
+        let raw_mode = RawMode::enable()?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+        Ok(Self {
+            _raw_mode: raw_mode,
+        })
+    }
+
+    /// Retourne la taille du terminal (colonnes, lignes), ou 80x24 si elle est inconnue.
+    pub fn size(&self) -> (usize, usize) {
+        #[cfg(unix)]
+        {
+            // SAFETY: la structure winsize est remplie par ioctl, et n'est utilisée qu'en cas de succès.
+            unsafe {
+                let mut size: libc::winsize = std::mem::zeroed();
+                if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) == 0
+                    && size.ws_col > 0
+                    && size.ws_row > 0
+                {
+                    return (size.ws_col as usize, size.ws_row as usize);
+                }
+            }
+        }
+        (80, 24)
+    }
+
+    /// Efface l'écran et affiche les lignes données.
+    pub fn draw(&self, lines: &[String]) -> io::Result<()> {
+        print!("\x1b[H\x1b[2J{}", lines.join("\r\n"));
+        io::stdout().flush()
+    }
+
+    /// Attend une touche pendant au plus "timeout". Retourne None si aucune touche n'a été pressée.
+    pub fn read_key(&self, timeout: Duration) -> io::Result<Option<Key>> {
+        let Some(byte) = Self::read_byte(timeout)? else {
+            return Ok(None);
+        };
+
+        Ok(match byte {
+            3 => Some(Key::Interrupt),
+            9 => Some(Key::Tab),
+            10 | 13 => Some(Key::Enter),
+            8 | 127 => Some(Key::Backspace),
+            // Séquences des flèches : ESC [ A (haut) et ESC [ B (bas). Échap seul n'est suivi d'aucun octet.
+            27 => match Self::read_byte(Duration::from_millis(30))? {
+                None => Some(Key::Escape),
+                Some(b'[') => match Self::read_byte(Duration::from_millis(30))? {
+                    Some(b'A') => Some(Key::Up),
+                    Some(b'B') => Some(Key::Down),
+                    _ => None,
+                },
+                Some(_) => None,
+            },
+            byte if byte >= 0x20 => Self::read_char(byte)?.map(Key::Char),
+            _ => None,
+        })
+    }
+
+    /// Lit la fin d'un caractère UTF-8 dont le premier octet est donné.
+    fn read_char(first: u8) -> io::Result<Option<char>> {
+        let len = match first {
+            0xF0..=0xF7 => 4,
+            0xE0..=0xEF => 3,
+            0xC0..=0xDF => 2,
+            _ => 1,
+        };
+        let mut bytes = vec![first];
+        for _ in 1..len {
+            match Self::read_byte(Duration::from_millis(30))? {
+                Some(byte) => bytes.push(byte),
+                None => break,
+            }
+        }
+        Ok(std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|c| c.chars().next()))
+    }
+
+    /// Lit un octet de l'entrée standard (sans passer par le tampon de `io::stdin`), en attendant au plus "timeout".
+    #[cfg(unix)]
+    fn read_byte(timeout: Duration) -> io::Result<Option<u8>> {
+        let mut poll = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = timeout.as_millis().min(i32::MAX as u128) as i32;
+        // SAFETY: poll et read n'accèdent qu'aux structures passées, valides pendant l'appel.
+        unsafe {
+            match libc::poll(&mut poll, 1, timeout) {
+                0 => return Ok(None),
+                n if n < 0 => {
+                    let error = io::Error::last_os_error();
+                    // Un signal (redimensionnement du terminal...) interrompt l'attente sans erreur.
+                    return match error.kind() {
+                        io::ErrorKind::Interrupted => Ok(None),
+                        _ => Err(error),
+                    };
+                }
+                _ => {}
+            }
+
+            let mut byte = 0u8;
+            match libc::read(
+                libc::STDIN_FILENO,
+                &mut byte as *mut u8 as *mut libc::c_void,
+                1,
+            ) {
+                1 => Ok(Some(byte)),
+                0 => Err(io::ErrorKind::UnexpectedEof.into()),
+                _ => Err(io::Error::last_os_error()),
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn read_byte(_timeout: Duration) -> io::Result<Option<u8>> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+    }
+}
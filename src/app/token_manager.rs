@@ -0,0 +1,404 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::pay;
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::token::{self, SolanaToken, TokenAccount, TOKEN_PROGRAM_IDS};
+use crate::solana::transaction::{SentTransaction, SolanaTransaction};
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// Le nombre maximal de comptes fermés par transaction (chaque compte, et son mint en cas de burn,
+/// occupe 32 octets : la transaction doit tenir dans un paquet).
+const CLEANUP_BATCH_SIZE: usize = 10;
+
+/// Le mint du SOL "wrappé" : ses comptes ne peuvent pas être brûlés (leur fermeture rend les SOL).
+const NATIVE_MINT: &str = "So11111111111111111111111111111111111111112";
+
+/// Un compte de token fermé (ou à fermer, avec "--dry-run").
+#[derive(Debug, Serialize)]
+pub struct ClosedTokenAccount {
+    pub account: String,
+    pub mint: String,
+    /// Le montant brûlé avant la fermeture ("--burn-dust"), en unités du token.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub burned: Option<String>,
+    pub lamports: u64,
+}
+
+/// Un compte de token conservé, et la raison.
+#[derive(Clone, Debug, Serialize)]
+pub struct SkippedTokenAccount {
+    pub account: String,
+    pub mint: String,
+    pub reason: String,
+}
+
+/// Résultat de la commande "token_accounts cleanup".
+#[derive(Debug, Serialize)]
+pub struct CleanupOutput {
+    pub owner: String,
+    pub closed: Vec<ClosedTokenAccount>,
+    pub skipped: Vec<SkippedTokenAccount>,
+    /// Le loyer récupéré (la somme des lamports des comptes fermés).
+    pub reclaimed_lamports: u64,
+    /// Vrai avec "--dry-run" (aucune transaction envoyée).
+    pub dry_run: bool,
+    pub signatures: Vec<String>,
+    /// Vrai en mode test (transactions signées mais pas envoyées).
+    pub simulated: bool,
+    /// Vrai si toutes les transactions ont atteint le niveau de confirmation configuré.
+    pub confirmed: bool,
+}
+
+impl fmt::Display for CleanupOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!("Owner: {}", self.owner)];
+        if self.closed.is_empty() {
+            lines.push("No token account to close.".to_string());
+        } else {
+            lines.push(format!(
+                "{}: {}",
+                if self.dry_run {
+                    "Accounts to close"
+                } else {
+                    "Accounts closed"
+                },
+                self.closed.len()
+            ));
+        }
+        for account in &self.closed {
+            match &account.burned {
+                Some(amount) => lines.push(format!(
+                    "  {} (mint {}, burned {})",
+                    account.account, account.mint, amount
+                )),
+                None => lines.push(format!("  {} (mint {})", account.account, account.mint)),
+            }
+        }
+        if !self.skipped.is_empty() {
+            lines.push(format!("Accounts kept: {}", self.skipped.len()));
+        }
+        for account in &self.skipped {
+            lines.push(format!("  {}: {}", account.account, account.reason));
+        }
+        lines.push(format!(
+            "{}: {} lamports ({} SOL)",
+            if self.dry_run {
+                "Rent to reclaim"
+            } else {
+                "Rent reclaimed"
+            },
+            self.reclaimed_lamports,
+            self.reclaimed_lamports as f64 / 1_000_000_000_f64
+        ));
+        if self.simulated {
+            lines.push("(simulated: transactions signed but not sent)".to_string());
+        } else if !self.dry_run && !self.confirmed {
+            lines.push(
+                "Transactions submitted (not confirmed yet, check them with \"confirm <SIGNATURE>\")"
+                    .to_string(),
+            );
+        }
+        for signature in &self.signatures {
+            lines.push(format!("Signature: {}", signature));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "create_ata".
+#[derive(Debug, Serialize)]
+pub struct CreateAtaOutput {
+    pub owner: String,
+    pub mint: String,
+    pub account: String,
+    pub signature: String,
+    /// Vrai en mode test (transaction signée mais pas envoyée).
+    pub simulated: bool,
+    /// Vrai si la transaction a atteint le niveau de confirmation configuré (faux avec "--no-wait").
+    pub confirmed: bool,
+    pub explorer_url: String,
+}
+
+impl fmt::Display for CreateAtaOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Token account: {}", self.account),
+            format!("Owner: {}", self.owner),
+            format!("Mint: {}", self.mint),
+        ];
+        if self.simulated {
+            lines.push("(simulated: transaction signed but not sent)".to_string());
+        } else if !self.confirmed {
+            lines.push(
+                "Transaction submitted (not confirmed yet, check it with \"confirm <SIGNATURE>\")"
+                    .to_string(),
+            );
+        }
+        lines.push(format!("Signature: {}", self.signature));
+        lines.push(format!("Explorer: {}", self.explorer_url));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+pub struct TokenManager {
+    config: WalletConfig,
+}
+
+impl TokenManager {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Ferme les comptes de tokens vides d'une clé locale ("--from") et récupère leur loyer,
+    /// par transactions de `CLEANUP_BATCH_SIZE` comptes.
+    ///
+    /// Les comptes à brûler sont affichés et confirmés avant l'envoi (sauf avec "--yes").
+    ///
+    /// # Arguments:
+    /// - from - La clé du key store (la clé active par défaut).
+    /// - burn_dust - Brûle puis ferme aussi les comptes dont le solde ne dépasse pas ces montants (en unités du
+    ///   token) : "MAX_AMOUNT" pour tous les mints, ou "MINT=MAX_AMOUNT" pour un mint.
+    /// - dry_run - Liste les comptes à fermer, sans envoyer de transaction.
+    /// - yes - Brûle sans demander confirmation.
+    /// - wait - Attendre la confirmation des transactions.
+    pub fn cleanup(
+        &self,
+        from: Option<&str>,
+        burn_dust: &[&str],
+        dry_run: bool,
+        yes: bool,
+        wait: bool,
+    ) -> Result<CleanupOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let owner = keypair.pubkey();
+        let burn_dust = burn_dust
+            .iter()
+            .map(|threshold| Self::parse_dust_threshold(threshold))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut closable: Vec<(TokenAccount, bool)> = Vec::new();
+        let mut skipped = Vec::new();
+        for account in SolanaToken::new(self.config.clone()).get_token_accounts(&owner)? {
+            match Self::skip_reason(&account, &owner, &burn_dust) {
+                Some(reason) => skipped.push(SkippedTokenAccount {
+                    account: account.address.to_string(),
+                    mint: account.mint.to_string(),
+                    reason,
+                }),
+                None => {
+                    let burn = account.amount > 0;
+                    closable.push((account, burn));
+                }
+            }
+        }
+
+        if !dry_run && !yes && closable.iter().any(|(_, burn)| *burn) {
+            let output = self.config.output();
+            output.message(Self::output(&owner, &closable, &skipped, true, &[]));
+            output.message(format!(
+                "Burn {} token balances and close these accounts? [y/N]",
+                closable.iter().filter(|(_, burn)| *burn).count()
+            ));
+            let mut answer = String::new();
+            io::stdin().read_line(&mut answer)?;
+            if !matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+                return Err("Cleanup cancelled".into());
+            }
+        }
+
+        let mut sent = Vec::new();
+        if !dry_run {
+            for batch in closable.chunks(CLEANUP_BATCH_SIZE) {
+                let mut instructions = Vec::new();
+                for (account, burn) in batch {
+                    if *burn {
+                        instructions.push(token::burn_all(account, &owner));
+                    }
+                    instructions.push(token::close_account(account, &owner, &owner));
+                }
+                sent.push(SolanaTransaction::send_instructions(
+                    &self.config,
+                    &keypair,
+                    instructions,
+                    wait,
+                )?);
+            }
+        }
+
+        Ok(Self::output(&owner, &closable, &skipped, dry_run, &sent))
+    }
+
+    /// Le résultat du nettoyage (ou son aperçu, avant la confirmation).
+    fn output(
+        owner: &Pubkey,
+        closable: &[(TokenAccount, bool)],
+        skipped: &[SkippedTokenAccount],
+        dry_run: bool,
+        sent: &[SentTransaction],
+    ) -> CleanupOutput {
+        CleanupOutput {
+            owner: owner.to_string(),
+            reclaimed_lamports: closable.iter().map(|(account, _)| account.lamports).sum(),
+            closed: closable
+                .iter()
+                .map(|(account, burn)| ClosedTokenAccount {
+                    account: account.address.to_string(),
+                    mint: account.mint.to_string(),
+                    burned: Some(account.ui_amount.clone()).filter(|_| *burn),
+                    lamports: account.lamports,
+                })
+                .collect(),
+            skipped: skipped.to_vec(),
+            dry_run,
+            signatures: sent.iter().map(|sent| sent.signature.to_string()).collect(),
+            simulated: sent.iter().any(|sent| sent.simulated),
+            confirmed: !sent.is_empty() && sent.iter().all(|sent| sent.confirmed),
+        }
+    }
+
+    /// Analyse un seuil de "--burn-dust" : "MAX_AMOUNT" (tous les mints) ou "MINT=MAX_AMOUNT".
+    fn parse_dust_threshold(
+        threshold: &str,
+    ) -> Result<(Option<Pubkey>, String), Box<dyn std::error::Error>> {
+        let (mint, amount) = match threshold.split_once('=') {
+            Some((mint, amount)) => (
+                Some(Pubkey::from_str(mint).map_err(|_| format!("Invalid mint '{}'", mint))?),
+                amount,
+            ),
+            None => (None, threshold),
+        };
+        pay::split_amount(amount).map_err(|_| format!("Invalid dust amount '{}'", amount))?;
+        Ok((mint, amount.to_string()))
+    }
+
+    /// Vrai si le solde du compte ne dépasse pas le montant (en unités du token), comparés en unités de base :
+    /// solde / 10^décimales <= montant / 10^f, soit solde * 10^f <= montant * 10^décimales (sans arrondi).
+    fn is_dust(account: &TokenAccount, max_amount: &str) -> bool {
+        let Ok((_, fraction)) = pay::split_amount(max_amount) else {
+            return false;
+        };
+        let Ok(fraction_digits) = u8::try_from(fraction.len()) else {
+            return false;
+        };
+        let Ok(max_units) = pay::parse_amount(max_amount, fraction_digits) else {
+            return false;
+        };
+        let scaled = |units: u64, digits: u8| {
+            10u128
+                .checked_pow(digits as u32)
+                .and_then(|scale| (units as u128).checked_mul(scale))
+        };
+        match (
+            scaled(account.amount, fraction_digits),
+            scaled(max_units, account.decimals),
+        ) {
+            (Some(balance), Some(max)) => balance <= max,
+            _ => false,
+        }
+    }
+
+    /// Crée le compte de token associé d'un propriétaire ("--owner", la clé payeuse par défaut) pour un mint.
+    pub fn create_ata(
+        &self,
+        mint: &str,
+        owner: Option<&str>,
+        from: Option<&str>,
+        wait: bool,
+    ) -> Result<CreateAtaOutput, Box<dyn std::error::Error>> {
+        let keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let owner = match owner {
+            Some(owner) => AddressBook::new(self.config.clone()).resolve(owner)?,
+            None => keypair.pubkey(),
+        };
+        let mint = Pubkey::from_str(mint).map_err(|_| format!("Invalid mint '{}'", mint))?;
+
+        let client = SolanaRpc::client(&self.config);
+        let token_program = client
+            .get_account(&mint)
+            .map_err(|e| format!("Cannot read mint {}: {}", mint, e))?
+            .owner;
+        if !TOKEN_PROGRAM_IDS.contains(&token_program) {
+            return Err(format!("{} is not a token mint", mint).into());
+        }
+        let account = token::associated_token_address(&owner, &mint, &token_program);
+        if client
+            .get_account_with_commitment(&account, self.config.commitment_config())?
+            .value
+            .is_some()
+        {
+            return Err(format!(
+                "The token account {} of {} for mint {} already exists",
+                account, owner, mint
+            )
+            .into());
+        }
+
+        let instruction = token::create_associated_token_account(
+            &keypair.pubkey(),
+            &owner,
+            &mint,
+            &token_program,
+        );
+        let sent =
+            SolanaTransaction::send_instructions(&self.config, &keypair, vec![instruction], wait)?;
+        Ok(CreateAtaOutput {
+            owner: owner.to_string(),
+            mint: mint.to_string(),
+            account: account.to_string(),
+            signature: sent.signature.to_string(),
+            simulated: sent.simulated,
+            confirmed: sent.confirmed,
+            explorer_url: SolanaExplorer::transaction_url(
+                &self.config.explorer_template,
+                &self.config.rpc_url,
+                &sent.signature.to_string(),
+            ),
+        })
+    }
+
+    /// La raison pour laquelle un compte ne peut pas (ou ne doit pas) être fermé par "owner".
+    fn skip_reason(
+        account: &TokenAccount,
+        owner: &Pubkey,
+        burn_dust: &[(Option<Pubkey>, String)],
+    ) -> Option<String> {
+        if account.frozen {
+            return Some("frozen by the mint".to_string());
+        }
+        if let Some(authority) = account
+            .close_authority
+            .filter(|authority| authority != owner)
+        {
+            return Some(format!("closable only by {}", authority));
+        }
+        if account.withheld_fees > 0 {
+            return Some(format!(
+                "{} withheld transfer fees to harvest first",
+                account.withheld_fees
+            ));
+        }
+        if account.amount == 0 {
+            return None;
+        }
+        // Le seuil propre au mint l'emporte sur le seuil de tous les mints.
+        let threshold = burn_dust
+            .iter()
+            .find(|(mint, _)| *mint == Some(account.mint))
+            .or_else(|| burn_dust.iter().find(|(mint, _)| mint.is_none()));
+        let is_dust = threshold.is_some_and(|(_, max)| Self::is_dust(account, max));
+        if !is_dust {
+            Some(format!("balance {}", account.ui_amount))
+        } else if account.mint.to_string() == NATIVE_MINT {
+            Some(format!("wrapped SOL balance {}", account.ui_amount))
+        } else {
+            None
+        }
+    }
+}
@@ -1,8 +1,342 @@
+use crate::app::qr_code::QrCode;
 use crate::config::wallet_config::WalletConfig;
-use crate::solana::transaction::SolanaTransaction;
+use crate::solana::confirmation::SolanaConfirmation;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::lookup_table::SolanaLookupTable;
+use crate::solana::pay::{self, SolanaPay, TransferRequest};
+use crate::solana::recipient::{RecipientOverrides, SolanaRecipient};
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::transaction::{SentTransaction, SolanaTransaction};
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
 use clap::ArgMatches;
-use solana_sdk::{pubkey::Pubkey, signature::read_keypair_file};
+use serde::Serialize;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature};
+use solana_sdk::signer::Signer;
+use solana_sdk::system_instruction;
+use std::fmt;
+use std::fs;
 use std::str::FromStr;
+use std::time::Duration;
+
+/// Le résultat de l'envoi d'une transaction, commun aux commandes "send", "batch_send" et "pay".
+#[derive(Debug, Serialize)]
+pub struct SentSummary {
+    pub signature: String,
+    /// Vrai en mode test (transaction signée mais pas envoyée).
+    pub simulated: bool,
+    /// Vrai si la transaction a atteint le niveau de confirmation configuré (faux avec "--no-wait").
+    pub confirmed: bool,
+    /// Le nombre d'envois (plus d'un si le blockhash a expiré avant que la transaction n'arrive dans un bloc).
+    pub attempts: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Les frais payés, en lamports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<String>,
+    /// L'URL de la transaction dans l'explorateur (voir "explorer_template").
+    pub explorer_url: String,
+}
+
+impl SentSummary {
+    pub fn new(config: &WalletConfig, sent: SentTransaction) -> Self {
+        let signature = sent.signature.to_string();
+        Self {
+            explorer_url: SolanaExplorer::transaction_url(
+                &config.explorer_template,
+                &config.rpc_url,
+                &signature,
+            ),
+            signature,
+            simulated: sent.simulated,
+            confirmed: sent.confirmed,
+            attempts: sent.attempts,
+            slot: sent.slot,
+            fee: sent.fee,
+            confirmation_status: sent.confirmation_status,
+        }
+    }
+
+    /// Les lignes affichées après l'envoi ("subject" : "Transaction" ou "Payment").
+    fn lines(&self, subject: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        if self.simulated || self.confirmed {
+            lines.push(format!("{} sent successfully!", subject));
+        } else {
+            lines.push(format!(
+                "{} submitted (not confirmed yet, check it with \"confirm <SIGNATURE>\")",
+                subject
+            ));
+        }
+        lines.push(format!("Signature: {}", self.signature));
+        if self.attempts > 1 {
+            lines.push(format!(
+                "Attempts: {} (blockhash expired, transaction signed again)",
+                self.attempts
+            ));
+        }
+        if let Some(slot) = self.slot {
+            lines.push(format!("Slot: {}", slot));
+        }
+        if let Some(fee) = self.fee {
+            lines.push(format!("Fee: {} lamports", fee));
+        }
+        if let Some(status) = &self.confirmation_status {
+            lines.push(format!("Confirmation status: {}", status));
+        }
+        lines.push(format!("Explorer: {}", self.explorer_url));
+        lines
+    }
+}
+
+/// Résultat de la commande "send".
+#[derive(Debug, Serialize)]
+pub struct SendOutput {
+    pub from: String,
+    pub to: String,
+    pub lamports: u64,
+    #[serde(flatten)]
+    pub sent: SentSummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for SendOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        if self.sent.simulated {
+            lines.push(format!(
+                "Simulating transaction: {} lamports from {} to {}",
+                self.lamports, self.from, self.to
+            ));
+        }
+        lines.extend(self.sent.lines("Transaction"));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Un transfert d'un paiement groupé.
+#[derive(Debug, Serialize)]
+pub struct BatchTransfer {
+    pub to: String,
+    pub lamports: u64,
+}
+
+/// Résultat de la commande "batch_send".
+#[derive(Debug, Serialize)]
+pub struct BatchSendOutput {
+    pub from: String,
+    pub transfers: Vec<BatchTransfer>,
+    pub total_lamports: u64,
+    /// "legacy", ou "0" avec des tables d'adresses.
+    pub version: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lookup_tables: Vec<String>,
+    #[serde(flatten)]
+    pub sent: SentSummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for BatchSendOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        lines.push(format!(
+            "{} {} transfers ({} lamports) from {} in a {} transaction",
+            if self.sent.simulated {
+                "Simulating batch payout:"
+            } else {
+                "Batch payout:"
+            },
+            self.transfers.len(),
+            self.total_lamports,
+            self.from,
+            if self.version == "legacy" {
+                "legacy".to_string()
+            } else {
+                format!("v{}", self.version)
+            }
+        ));
+        for transfer in &self.transfers {
+            lines.push(format!(
+                "  {} lamports to {}",
+                transfer.lamports, transfer.to
+            ));
+        }
+        for table in &self.lookup_tables {
+            lines.push(format!("Address lookup table: {}", table));
+        }
+        lines.extend(self.sent.lines("Transaction"));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// L'aperçu d'un envoi, avant sa confirmation par l'utilisateur (formulaire d'envoi du tableau de bord).
+#[derive(Clone, Debug, Serialize)]
+pub struct SendPreviewOutput {
+    pub from: String,
+    pub to: String,
+    pub lamports: u64,
+    pub sol: f64,
+    /// Les frais estimés, en lamports (frais de base et frais de priorité).
+    pub estimated_fee: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for SendPreviewOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        lines.push(format!("From: {}", self.from));
+        lines.push(format!("To: {}", self.to));
+        lines.push(format!(
+            "Amount: {:.9} SOL ({} lamports)",
+            self.sol, self.lamports
+        ));
+        lines.push(format!("Estimated fee: {} lamports", self.estimated_fee));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "pay".
+#[derive(Debug, Serialize)]
+pub struct PayOutput {
+    pub from: String,
+    pub to: String,
+    /// Le montant, en unités de l'utilisateur (SOL ou unités du token).
+    pub amount: String,
+    /// Le mint du token SPL payé (des SOL si absent).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spl_token: Option<String>,
+    /// Le montant en unités de base (lamports, ou plus petites unités du token).
+    pub base_units: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(flatten)]
+    pub sent: SentSummary,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for PayOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = self.warnings.clone();
+        if let Some(label) = &self.label {
+            lines.push(format!("Label: {}", label));
+        }
+        if let Some(message) = &self.message {
+            lines.push(format!("Message: {}", message));
+        }
+        if let Some(memo) = &self.memo {
+            lines.push(format!("Memo: {}", memo));
+        }
+        let unit = self.spl_token.as_deref().unwrap_or("SOL");
+        lines.push(format!(
+            "{} {} {} from {} to {}",
+            if self.sent.simulated {
+                "Simulating payment:"
+            } else {
+                "Payment:"
+            },
+            self.amount,
+            unit,
+            self.from,
+            self.to
+        ));
+        lines.extend(self.sent.lines("Payment"));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "request_payment".
+#[derive(Debug, Serialize)]
+pub struct RequestPaymentOutput {
+    /// L'URL Solana Pay ("solana:...").
+    pub url: String,
+    pub recipient: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    /// Le QR code de l'URL, affiché dans le terminal ("--qr").
+    #[serde(skip)]
+    pub qr_code: Option<String>,
+}
+
+impl fmt::Display for RequestPaymentOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![
+            format!("Payment request: {}", self.url),
+            format!("Recipient: {}", self.recipient),
+        ];
+        for reference in &self.references {
+            lines.push(format!("Reference: {}", reference));
+        }
+        if let Some(qr_code) = &self.qr_code {
+            lines.push(String::new());
+            lines.push(qr_code.clone());
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Résultat de la commande "confirm".
+#[derive(Debug, Serialize)]
+pub struct ConfirmOutput {
+    pub signature: String,
+    /// Le niveau de confirmation attendu.
+    pub commitment: String,
+    /// Le délai d'attente, en secondes.
+    pub timeout: u64,
+    /// Vrai si la transaction est arrivée dans un bloc.
+    pub found: bool,
+    /// Vrai si la transaction a atteint le niveau de confirmation attendu.
+    pub confirmed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmation_status: Option<String>,
+    /// L'erreur d'exécution de la transaction (la transaction est dans un bloc, mais a échoué).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_error: Option<String>,
+    pub explorer_url: String,
+}
+
+impl fmt::Display for ConfirmOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = vec![format!("Signature: {}", self.signature)];
+        if let Some(slot) = self.slot {
+            lines.push(format!("Slot: {}", slot));
+        }
+        if let Some(status) = &self.confirmation_status {
+            lines.push(format!("Confirmation status: {}", status));
+        }
+        if let Some(error) = &self.transaction_error {
+            lines.push(format!("Transaction failed: {}", error));
+        } else if self.confirmed {
+            lines.push(format!("Transaction confirmed ({}).", self.commitment));
+        } else if self.found {
+            lines.push(format!(
+                "Transaction not {} after {} seconds.",
+                self.commitment, self.timeout
+            ));
+        } else {
+            lines.push(format!(
+                "Transaction not found after {} seconds.",
+                self.timeout
+            ));
+        }
+        lines.push(format!("Explorer: {}", self.explorer_url));
+        write!(f, "{}", lines.join("\n"))
+    }
+}
 
 pub struct TransactionManager {
     config: WalletConfig,
@@ -17,33 +351,431 @@ impl TransactionManager {
     /// Envoie des lamports (unité de SOL) à une adresse spécifiée en utilisant la clé privée du wallet.
     ///
     /// # Arguments:
-    /// - matches - Arguments de ligne de commande traités, fournissant le destinataire et le montant.
+    /// - matches - Arguments de ligne de commande traités, fournissant le destinataire, le montant et éventuellement la clé de l'expéditeur ("--from").
     ///
     /// # Returns:
-    /// - Ok(()) - Si la transaction est envoyée avec succès.
+    /// - Ok(output) - Si la transaction est envoyée avec succès (avec sa signature).
     /// - Err(e) - Si une erreur se produit lors de la lecture de la clé, la conversion des arguments, ou l'envoi de la transaction.
-    pub fn send_transaction(&self, matches: &ArgMatches) -> Result<(), Box<dyn std::error::Error>> {
-        // Lecture du fichier contenant la clé privée du sender.
-        let sender_keypair = read_keypair_file(&self.config.keypair_path)
-            .map_err(|_| "Failed to read keypair from file")?;
-
+    pub fn send_transaction(
+        &self,
+        matches: &ArgMatches,
+    ) -> Result<SendOutput, Box<dyn std::error::Error>> {
         // Extraction et validation de l'adresse publique du destinataire.
         let recipient_pubkey = self.get_pubkey_from_matches(matches)?;
 
         // Extraction et validation du montant à envoyer.
         let amount = self.get_amount_from_matches(matches)?;
 
-        // Envoi via le réseau Solana.
-        SolanaTransaction::send_lamports(
-            &self.config.rpc_url,
-            &sender_keypair,
+        self.send(
+            matches.get_one::<String>("from").map(String::as_str),
             &recipient_pubkey,
             amount,
+            !matches.get_flag("no-wait"),
+            &Self::recipient_overrides(matches),
         )
-        .map_err(Into::into)
+    }
+
+    /// Les contrôles du destinataire ignorés à la demande de l'utilisateur (options "--allow-...").
+    pub fn recipient_overrides(matches: &ArgMatches) -> RecipientOverrides {
+        RecipientOverrides {
+            allow_self: matches.get_flag("allow-self"),
+            allow_off_curve: matches.get_flag("allow-off-curve"),
+            allow_token_account: matches.get_flag("allow-token-account"),
+            allow_program: matches.get_flag("allow-program"),
+            allow_below_rent: matches.get_flag("allow-below-rent"),
+        }
+    }
+
+    /// Retourne l'aperçu d'un envoi (adresses résolues, frais estimés, avertissements), sans rien signer ni envoyer.
+    ///
+    /// # Arguments:
+    /// - from - La clé de l'expéditeur (label ou clé publique du key store, la clé active si absente).
+    /// - recipient - Le destinataire (clé publique, nom de contact ou label du key store).
+    /// - amount - Le montant à envoyer, en lamports.
+    pub fn preview(
+        &self,
+        from: Option<&str>,
+        recipient: &str,
+        amount: &str,
+    ) -> Result<SendPreviewOutput, Box<dyn std::error::Error>> {
+        let sender_keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let recipient_pubkey = AddressBook::new(self.config.clone()).resolve(recipient)?;
+        let lamports = Self::parse_amount(amount)?;
+
+        Ok(SendPreviewOutput {
+            from: sender_keypair.pubkey().to_string(),
+            to: recipient_pubkey.to_string(),
+            lamports,
+            sol: lamports as f64 / 1_000_000_000_f64,
+            estimated_fee: SolanaTransaction::estimate_transfer_fee(&self.config),
+            warnings: self.recipient_warnings(&recipient_pubkey)?,
+        })
+    }
+
+    /// Envoie des lamports depuis une clé du key store.
+    ///
+    /// # Arguments:
+    /// - from - La clé de l'expéditeur (label ou clé publique du key store, la clé active si absente).
+    /// - recipient_pubkey - L'adresse du destinataire.
+    /// - amount - Le montant à envoyer, en lamports.
+    /// - wait - Attendre la confirmation de la transaction.
+    /// - overrides - Les contrôles du destinataire à ignorer (voir `SolanaRecipient::check`).
+    pub fn send(
+        &self,
+        from: Option<&str>,
+        recipient_pubkey: &Pubkey,
+        amount: u64,
+        wait: bool,
+        overrides: &RecipientOverrides,
+    ) -> Result<SendOutput, Box<dyn std::error::Error>> {
+        // Lecture du fichier contenant la clé privée du sender (sélectionnée via le key store).
+        let keystore = KeyStore::new(self.config.clone());
+        let sender_keypair = keystore.resolve_keypair(from)?;
+
+        // Contrôles du destinataire (adresse hors courbe, compte de token, programme...) avant la signature.
+        let mut warnings = SolanaRecipient::check(
+            &self.config,
+            &sender_keypair.pubkey(),
+            recipient_pubkey,
+            amount,
+            overrides,
+        )?;
+        warnings.extend(self.recipient_warnings(recipient_pubkey)?);
+
+        // Envoi via le réseau Solana.
+        let sent = SolanaTransaction::send_lamports(
+            &self.config,
+            &sender_keypair,
+            recipient_pubkey,
+            amount,
+            wait,
+        )?;
+
+        // Mémorise le destinataire pour ne plus avertir lors des prochains envois.
+        AddressBook::new(self.config.clone()).remember_recipient(recipient_pubkey)?;
+
+        Ok(SendOutput {
+            from: sender_keypair.pubkey().to_string(),
+            to: recipient_pubkey.to_string(),
+            lamports: amount,
+            sent: SentSummary::new(&self.config, sent),
+            warnings,
+        })
+    }
+
+    /// Envoie des lamports à plusieurs destinataires dans une seule transaction.
+    /// Avec des tables d'adresses ("--lookup-table"), la transaction est une transaction v0, ce qui permet de
+    /// dépasser la limite de comptes d'une transaction legacy (une vingtaine de destinataires).
+    ///
+    /// # Arguments:
+    /// - file - Le fichier des paiements : une ligne "<DESTINATAIRE>,<LAMPORTS>" par transfert (le destinataire
+    ///   est une clé publique, un nom de contact ou un label du key store). Les lignes vides et celles commençant
+    ///   par "#" sont ignorées.
+    /// - from - La clé de l'expéditeur (la clé active si absente).
+    /// - lookup_tables - Les adresses des tables d'adresses à utiliser.
+    /// - wait - Attendre la confirmation de la transaction.
+    /// - overrides - Les contrôles des destinataires à ignorer (voir `SolanaRecipient::check`).
+    pub fn batch_send(
+        &self,
+        file: &str,
+        from: Option<&str>,
+        lookup_tables: &[String],
+        wait: bool,
+        overrides: &RecipientOverrides,
+    ) -> Result<BatchSendOutput, Box<dyn std::error::Error>> {
+        let content =
+            fs::read_to_string(file).map_err(|e| format!("Cannot read '{}': {}", file, e))?;
+        let address_book = AddressBook::new(self.config.clone());
+        let mut transfers = Vec::new();
+        for (number, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (recipient, amount) = line.split_once(',').ok_or_else(|| {
+                format!("Line {}: expected \"<RECIPIENT>,<LAMPORTS>\"", number + 1)
+            })?;
+            let recipient = address_book
+                .resolve(recipient.trim())
+                .map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            let lamports = Self::parse_amount(amount.trim())
+                .map_err(|e| format!("Line {}: {}", number + 1, e))?;
+            transfers.push((recipient, lamports));
+        }
+        if transfers.is_empty() {
+            return Err(format!("No payment in '{}'", file).into());
+        }
+
+        let lookup_tables = lookup_tables
+            .iter()
+            .map(|table| {
+                Pubkey::from_str(table)
+                    .map_err(|_| format!("Invalid address lookup table '{}'", table))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let lookup_table_accounts =
+            SolanaLookupTable::fetch_accounts(&self.config, &lookup_tables)?;
+
+        let sender_keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+
+        // Contrôles des destinataires (adresse hors courbe, compte de token, programme...) avant la signature.
+        let recipients: Vec<Pubkey> = transfers.iter().map(|(recipient, _)| *recipient).collect();
+        let mut warnings = SolanaRecipient::check_all(
+            &self.config,
+            &sender_keypair.pubkey(),
+            &recipients,
+            transfers.iter().map(|(_, lamports)| lamports).sum(),
+            overrides,
+        )?;
+        for (recipient, _) in &transfers {
+            for warning in self.recipient_warnings(recipient)? {
+                if !warnings.contains(&warning) {
+                    warnings.push(warning);
+                }
+            }
+        }
+
+        let instructions = transfers
+            .iter()
+            .map(|(recipient, lamports)| {
+                system_instruction::transfer(&sender_keypair.pubkey(), recipient, *lamports)
+            })
+            .collect();
+        let sent = SolanaTransaction::send_versioned_instructions(
+            &self.config,
+            &sender_keypair,
+            instructions,
+            &lookup_table_accounts,
+            wait,
+        )?;
+
+        // Mémorise les destinataires pour ne plus avertir lors des prochains envois.
+        for (recipient, _) in &transfers {
+            address_book.remember_recipient(recipient)?;
+        }
+
+        Ok(BatchSendOutput {
+            from: sender_keypair.pubkey().to_string(),
+            total_lamports: transfers.iter().map(|(_, lamports)| lamports).sum(),
+            transfers: transfers
+                .iter()
+                .map(|(recipient, lamports)| BatchTransfer {
+                    to: recipient.to_string(),
+                    lamports: *lamports,
+                })
+                .collect(),
+            version: if lookup_tables.is_empty() {
+                "legacy".to_string()
+            } else {
+                "0".to_string()
+            },
+            lookup_tables: lookup_tables.iter().map(Pubkey::to_string).collect(),
+            sent: SentSummary::new(&self.config, sent),
+            warnings,
+        })
+    }
+
+    /// Paie une demande de transfert Solana Pay ("solana:<recipient>?amount=...").
+    /// Le mémo précède le transfert, et les clés de référence sont ajoutées au transfert (en lecture seule),
+    /// pour que le destinataire retrouve la transaction.
+    ///
+    /// # Arguments:
+    /// - url - L'URL de la demande de transfert.
+    /// - amount - Le montant, si la demande n'en précise pas (en unités de l'utilisateur : SOL ou unités du token).
+    /// - from - La clé du payeur (label ou clé publique du key store, la clé active si absente).
+    /// - wait - Attendre la confirmation de la transaction.
+    /// - overrides - Les contrôles du destinataire d'un paiement en SOL à ignorer (voir `SolanaRecipient::check`).
+    pub fn pay(
+        &self,
+        url: &str,
+        amount: Option<&str>,
+        from: Option<&str>,
+        wait: bool,
+        overrides: &RecipientOverrides,
+    ) -> Result<PayOutput, Box<dyn std::error::Error>> {
+        let request = TransferRequest::parse(url)?;
+        let amount = match (&request.amount, amount) {
+            (Some(requested), Some(amount)) if requested != amount => {
+                return Err(format!(
+                    "The payment request is for {} (--amount {} does not match)",
+                    requested, amount
+                )
+                .into())
+            }
+            (Some(requested), _) => requested.clone(),
+            (None, Some(amount)) => amount.to_string(),
+            (None, None) => {
+                return Err("The payment request has no amount, pass it with --amount".into())
+            }
+        };
+
+        let payer_keypair = KeyStore::new(self.config.clone()).resolve_keypair(from)?;
+        let (instructions, base_units) = SolanaPay::new(self.config.clone())
+            .transfer_instructions(&payer_keypair.pubkey(), &request, &amount)?;
+
+        // Un paiement en SOL est contrôlé comme "send" ; un paiement en tokens va au compte associé du destinataire.
+        let mut warnings = if request.spl_token.is_none() {
+            SolanaRecipient::check(
+                &self.config,
+                &payer_keypair.pubkey(),
+                &request.recipient,
+                base_units,
+                overrides,
+            )?
+        } else {
+            Vec::new()
+        };
+        warnings.extend(self.recipient_warnings(&request.recipient)?);
+
+        let sent =
+            SolanaTransaction::send_instructions(&self.config, &payer_keypair, instructions, wait)?;
+
+        // Mémorise le destinataire pour ne plus avertir lors des prochains envois.
+        AddressBook::new(self.config.clone()).remember_recipient(&request.recipient)?;
+
+        Ok(PayOutput {
+            from: payer_keypair.pubkey().to_string(),
+            to: request.recipient.to_string(),
+            amount,
+            spl_token: request.spl_token.map(|mint| mint.to_string()),
+            base_units,
+            references: request.references.iter().map(Pubkey::to_string).collect(),
+            label: request.label,
+            message: request.message,
+            memo: request.memo,
+            sent: SentSummary::new(&self.config, sent),
+            warnings,
+        })
+    }
+
+    /// Génère une URL de demande de transfert Solana Pay pour l'une de nos adresses (et son QR code avec "--qr").
+    ///
+    /// # Arguments:
+    /// - matches - Arguments de ligne de commande : destinataire ("--to", la clé active si absent), montant, token,
+    ///   références ("--reference", ou "--new-reference" pour en générer une), libellé, message et mémo.
+    pub fn request_payment(
+        &self,
+        matches: &ArgMatches,
+    ) -> Result<RequestPaymentOutput, Box<dyn std::error::Error>> {
+        let recipient = match matches.get_one::<String>("to") {
+            Some(to) => AddressBook::new(self.config.clone()).resolve(to)?,
+            None => KeyStore::new(self.config.clone())
+                .resolve_keypair(None)?
+                .pubkey(),
+        };
+
+        let amount = matches.get_one::<String>("amount").cloned();
+        if let Some(amount) = &amount {
+            // Les décimales d'un token ne sont connues qu'au moment du paiement : seul le format est alors vérifié.
+            if matches.contains_id("spl-token") {
+                pay::split_amount(amount)?;
+            } else {
+                pay::parse_amount(amount, pay::SOL_DECIMALS)?;
+            }
+        }
+        let spl_token = matches
+            .get_one::<String>("spl-token")
+            .map(|mint| Pubkey::from_str(mint).map_err(|_| format!("Invalid mint '{}'", mint)))
+            .transpose()?;
+
+        let mut references = matches
+            .get_many::<String>("reference")
+            .unwrap_or_default()
+            .map(|reference| {
+                Pubkey::from_str(reference)
+                    .map_err(|_| format!("Invalid reference '{}'", reference))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if matches.get_flag("new-reference") {
+            references.push(Keypair::new().pubkey());
+        }
+
+        let request = TransferRequest {
+            recipient,
+            amount,
+            spl_token,
+            references,
+            label: matches.get_one::<String>("label").cloned(),
+            message: matches.get_one::<String>("message").cloned(),
+            memo: matches.get_one::<String>("memo").cloned(),
+        };
+        let url = request.to_url();
+        let qr_code = if matches.get_flag("qr") {
+            Some(QrCode::encode(url.as_bytes())?.to_terminal())
+        } else {
+            None
+        };
+
+        Ok(RequestPaymentOutput {
+            url,
+            recipient: recipient.to_string(),
+            references: request.references.iter().map(Pubkey::to_string).collect(),
+            qr_code,
+        })
+    }
+
+    /// Attend qu'une transaction atteigne le niveau de confirmation configuré ("--commitment"), échoue,
+    /// ou que le délai soit dépassé.
+    ///
+    /// # Arguments:
+    /// - signature - La signature de la transaction.
+    /// - timeout - Le délai d'attente en secondes ("confirm_timeout" de la politique de renvoi par défaut).
+    pub fn confirm(
+        &self,
+        signature: &str,
+        timeout: Option<u64>,
+    ) -> Result<ConfirmOutput, Box<dyn std::error::Error>> {
+        let signature = Signature::from_str(signature)
+            .map_err(|_| format!("Invalid signature '{}'", signature))?;
+        let timeout = timeout.unwrap_or(self.config.retry_policy.confirm_timeout);
+
+        let client = SolanaRpc::client(&self.config);
+        let landed = SolanaConfirmation::wait_for_confirmation(
+            &client,
+            &signature,
+            self.config.commitment_config(),
+            Duration::from_secs(timeout),
+        )?;
+
+        Ok(ConfirmOutput {
+            signature: signature.to_string(),
+            commitment: self.config.commitment.clone(),
+            timeout,
+            found: landed.is_some(),
+            confirmed: landed.as_ref().is_some_and(|landed| landed.confirmed),
+            slot: landed.as_ref().map(|landed| landed.slot),
+            confirmation_status: landed
+                .as_ref()
+                .and_then(|landed| landed.confirmation_status.clone()),
+            transaction_error: landed
+                .and_then(|landed| landed.error)
+                .map(|error| error.to_string()),
+            explorer_url: SolanaExplorer::transaction_url(
+                &self.config.explorer_template,
+                &self.config.rpc_url,
+                &signature.to_string(),
+            ),
+        })
+    }
+
+    /// Avertit l'utilisateur lors d'un premier envoi vers une adresse inconnue (ni contact, ni clé gérée).
+    fn recipient_warnings(
+        &self,
+        recipient_pubkey: &Pubkey,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let address_book = AddressBook::new(self.config.clone());
+        if address_book.is_known(recipient_pubkey)? {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![format!(
+            "Warning: {} is not in your address book and has never received funds from this wallet.",
+            recipient_pubkey
+        )])
     }
 
     /// Extrait l'adresse publique du destinataire à partir des arguments de ligne de commande.
+    /// Le destinataire peut être une clé publique, un nom de contact ou un label du key store.
     ///
     /// Arguments:
     /// - matches - Arguments de ligne de commande pour l'opération de transaction.
@@ -57,7 +789,7 @@ impl TransactionManager {
         let recipient = matches
             .get_one::<String>("RECIPIENT")
             .ok_or("Recipient required")?;
-        Pubkey::from_str(recipient).map_err(|_| "Invalid public key format".into())
+        AddressBook::new(self.config.clone()).resolve(recipient)
     }
 
     /// Extrait le montant des lamports à envoyer à partir des arguments de ligne de commande.
@@ -74,7 +806,11 @@ impl TransactionManager {
         let amount_str = matches
             .get_one::<String>("AMOUNT")
             .ok_or("Amount required")?;
-        amount_str
+        Self::parse_amount(amount_str)
+    }
+
+    fn parse_amount(amount: &str) -> Result<u64, Box<dyn std::error::Error>> {
+        amount
             .parse::<u64>()
             .map_err(|_| "Amount needs to be a number".into())
     }
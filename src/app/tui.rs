@@ -0,0 +1,522 @@
+use crate::app::dashboard_manager::{DashboardManager, DashboardOutput};
+use crate::app::terminal::{self, Key, Screen};
+use crate::app::transaction_manager::{SendPreviewOutput, TransactionManager};
+use crate::config::wallet_config::{PolicyConfirmation, WalletConfig};
+use crate::solana::policy::PolicyViolation;
+use crate::solana::recipient::RecipientOverrides;
+use crate::store::audit_log::AuditLog;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Le formulaire d'envoi (depuis le compte sélectionné).
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SendForm {
+    recipient: String,
+    amount: String,
+    /// Vrai si la saisie porte sur le montant (sinon sur le destinataire).
+    amount_focused: bool,
+}
+
+impl SendForm {
+    fn focused(&mut self) -> &mut String {
+        if self.amount_focused {
+            &mut self.amount
+        } else {
+            &mut self.recipient
+        }
+    }
+}
+
+/// La confirmation saisie qu'exige la politique de dépense (elle ne peut pas être lue sur l'entrée standard).
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ConfirmForm {
+    message: String,
+    phrase: String,
+    typed: String,
+}
+
+#[derive(Debug)]
+enum Mode {
+    /// Navigation dans la liste des comptes.
+    Browse,
+    Form(SendForm),
+    /// Aperçu de l'envoi, en attente de confirmation.
+    Preview(SendForm, SendPreviewOutput),
+    /// Confirmation saisie de la politique de dépense, avant un nouvel envoi.
+    Confirm(SendPreviewOutput, ConfirmForm),
+    /// Résultat de l'envoi (ou son erreur).
+    Sent(Vec<String>),
+}
+
+/// L'opération à effectuer après une touche (les opérations réseau sont effectuées par `Tui::perform`).
+#[derive(Debug, PartialEq)]
+enum Action {
+    None,
+    Quit,
+    Refresh,
+    LoadHistory,
+    Preview,
+    Send,
+}
+
+/// Le tableau de bord plein écran ("tui") : paires de clés et soldes, tokens et transactions récentes du compte
+/// sélectionné, et formulaire d'envoi avec aperçu et confirmation. Les données sont rafraîchies périodiquement.
+pub struct Tui {
+    config: WalletConfig,
+    dashboard: Option<DashboardOutput>,
+    mode: Mode,
+    /// Message affiché au-dessus de l'aide (erreur, opération en cours...).
+    status: String,
+    /// L'heure du dernier rafraîchissement.
+    refreshed_at: Option<String>,
+}
+
+impl Tui {
+    pub fn new(config: WalletConfig) -> Self {
+        Self {
+            config,
+            dashboard: None,
+            mode: Mode::Browse,
+            status: String::new(),
+            refreshed_at: None,
+        }
+    }
+
+    /// Affiche le tableau de bord jusqu'à ce que l'utilisateur le quitte ("q", Échap ou Ctrl-C).
+    ///
+    /// # Arguments:
+    /// - refresh - L'intervalle de rafraîchissement des données.
+    pub fn run(&mut self, refresh: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        if !terminal::is_terminal() {
+            return Err(
+                "The dashboard needs a terminal (use \"tui --snapshot\" to print it once)".into(),
+            );
+        }
+
+        let screen = Screen::enter()?;
+        self.perform(&screen, Action::Refresh)?;
+        let mut next_refresh = Instant::now() + refresh;
+
+        loop {
+            let (width, height) = screen.size();
+            screen.draw(&self.render(width, height))?;
+
+            let timeout = next_refresh.saturating_duration_since(Instant::now());
+            let action = match screen.read_key(timeout)? {
+                Some(key) => self.handle_key(key),
+                None if Instant::now() >= next_refresh => Action::Refresh,
+                None => Action::None,
+            };
+            if action == Action::Quit {
+                return Ok(());
+            }
+            if action == Action::Refresh {
+                next_refresh = Instant::now() + refresh;
+            }
+            self.perform(&screen, action)?;
+        }
+    }
+
+    /// Effectue l'opération demandée, après avoir affiché qu'elle est en cours.
+    fn perform(
+        &mut self,
+        screen: &Screen,
+        action: Action,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let pending = match action {
+            Action::Refresh => "Refreshing...",
+            Action::Send => "Sending...",
+            _ => "",
+        };
+        if !pending.is_empty() {
+            self.status = pending.to_string();
+            let (width, height) = screen.size();
+            screen.draw(&self.render(width, height))?;
+            self.status.clear();
+        }
+
+        match action {
+            Action::Refresh => self.refresh(),
+            Action::LoadHistory => {
+                if let Some(dashboard) = self.dashboard.as_mut() {
+                    DashboardManager::new(self.config.clone()).load_history(dashboard);
+                }
+            }
+            Action::Preview => self.preview(),
+            Action::Send => self.send(),
+            Action::None | Action::Quit => {}
+        }
+        Ok(())
+    }
+
+    /// Relit les données du tableau de bord (en conservant le compte sélectionné).
+    fn refresh(&mut self) {
+        let manager = DashboardManager::new(self.config.clone());
+        match manager.load(self.selected()) {
+            Ok(dashboard) => {
+                self.dashboard = Some(dashboard);
+                self.refreshed_at = Some(chrono::Local::now().format("%H:%M:%S").to_string());
+            }
+            Err(e) => self.status = format!("Failed to load dashboard: {}", e),
+        }
+    }
+
+    fn preview(&mut self) {
+        let Mode::Form(form) = &self.mode else {
+            return;
+        };
+        let manager = TransactionManager::new(self.config.clone());
+        match manager.preview(self.selected(), &form.recipient, &form.amount) {
+            Ok(preview) => self.mode = Mode::Preview(form.clone(), preview),
+            Err(e) => self.status = format!("Invalid transaction: {}", e),
+        }
+    }
+
+    fn send(&mut self) {
+        let (preview, confirmation) = match &self.mode {
+            Mode::Preview(_, preview) => (preview.clone(), PolicyConfirmation::Deferred),
+            Mode::Confirm(preview, form) => (
+                preview.clone(),
+                PolicyConfirmation::Typed(form.typed.clone()),
+            ),
+            _ => return,
+        };
+        let mut config = self.config.clone();
+        config.policy_confirmation = confirmation;
+        let manager = TransactionManager::new(config);
+        let result = Pubkey::from_str(&preview.to)
+            .map_err(Into::into)
+            .and_then(|to| {
+                manager.send(
+                    self.selected(),
+                    &to,
+                    preview.lamports,
+                    true,
+                    &RecipientOverrides::default(),
+                )
+            });
+        // La confirmation de la politique est saisie dans un formulaire, puis la transaction est signée à nouveau.
+        if let Err(e) = &result {
+            if let Some(PolicyViolation::ConfirmationRequired(_, _, phrase)) = e.downcast_ref() {
+                self.mode = Mode::Confirm(
+                    preview,
+                    ConfirmForm {
+                        message: e.to_string(),
+                        phrase: phrase.clone(),
+                        typed: String::new(),
+                    },
+                );
+                return;
+            }
+        }
+        let recorded = AuditLog::new(self.config.clone()).record_outcome("tui send", &result);
+        let mut lines: Vec<String> = match result {
+            Ok(sent) => sent.to_string().lines().map(ToString::to_string).collect(),
+            Err(e) => vec![format!("Failed to send transaction: {}", e)],
+        };
+        if let Err(e) = recorded {
+            lines.push(format!("Warning: failed to write the audit log: {}", e));
+        }
+        self.mode = Mode::Sent(lines);
+    }
+
+    fn selected(&self) -> Option<&str> {
+        self.dashboard.as_ref()?.selected.as_deref()
+    }
+
+    /// Sélectionne le compte précédent (-1) ou suivant (+1) de la liste.
+    fn select(&mut self, offset: isize) -> Action {
+        let Some(dashboard) = self.dashboard.as_mut() else {
+            return Action::None;
+        };
+        let count = dashboard.accounts.len() as isize;
+        let current = dashboard
+            .accounts
+            .iter()
+            .position(|account| Some(&account.label) == dashboard.selected.as_ref());
+        let Some(current) = current else {
+            return Action::None;
+        };
+
+        let index = (current as isize + offset).clamp(0, count - 1) as usize;
+        if index == current {
+            return Action::None;
+        }
+        dashboard.selected = Some(dashboard.accounts[index].label.clone());
+        Action::LoadHistory
+    }
+
+    /// Met à jour l'état selon la touche pressée, et retourne l'opération à effectuer.
+    fn handle_key(&mut self, key: Key) -> Action {
+        if key == Key::Interrupt {
+            return Action::Quit;
+        }
+        self.status.clear();
+
+        match &mut self.mode {
+            Mode::Browse => match key {
+                Key::Up | Key::Char('k') => self.select(-1),
+                Key::Down | Key::Char('j') => self.select(1),
+                Key::Char('r') => Action::Refresh,
+                Key::Char('s') if self.selected().is_some() => {
+                    self.mode = Mode::Form(SendForm::default());
+                    Action::None
+                }
+                Key::Char('s') => {
+                    self.status = "No keys in key store.".to_string();
+                    Action::None
+                }
+                Key::Char('q') | Key::Escape => Action::Quit,
+                _ => Action::None,
+            },
+            Mode::Form(form) => match key {
+                Key::Escape => {
+                    self.mode = Mode::Browse;
+                    Action::None
+                }
+                Key::Tab | Key::Up | Key::Down => {
+                    form.amount_focused = !form.amount_focused;
+                    Action::None
+                }
+                Key::Backspace => {
+                    form.focused().pop();
+                    Action::None
+                }
+                Key::Enter if !form.amount_focused => {
+                    form.amount_focused = true;
+                    Action::None
+                }
+                Key::Enter => Action::Preview,
+                Key::Char(c) => {
+                    form.focused().push(c);
+                    Action::None
+                }
+                Key::Interrupt => Action::Quit,
+            },
+            Mode::Preview(form, _) => match key {
+                Key::Char('y') | Key::Enter => Action::Send,
+                Key::Char('n') | Key::Backspace => {
+                    self.mode = Mode::Form(std::mem::take(form));
+                    Action::None
+                }
+                Key::Escape => {
+                    self.mode = Mode::Browse;
+                    Action::None
+                }
+                _ => Action::None,
+            },
+            Mode::Confirm(_, form) => match key {
+                Key::Enter => Action::Send,
+                Key::Backspace => {
+                    form.typed.pop();
+                    Action::None
+                }
+                Key::Escape => {
+                    self.mode = Mode::Browse;
+                    Action::None
+                }
+                Key::Char(c) => {
+                    form.typed.push(c);
+                    Action::None
+                }
+                _ => Action::None,
+            },
+            // Les soldes et l'historique sont relus après un envoi.
+            Mode::Sent(_) => {
+                self.mode = Mode::Browse;
+                Action::Refresh
+            }
+        }
+    }
+
+    /// Retourne les lignes de l'écran, tronquées à la taille du terminal.
+    fn render(&self, width: usize, height: usize) -> Vec<String> {
+        let mut title = format!(
+            "Solana Wallet - {} ({})",
+            self.config.rpc_url, self.config.commitment
+        );
+        if let Some(refreshed_at) = &self.refreshed_at {
+            let updated = format!("Updated {}", refreshed_at);
+            let padding = width.saturating_sub(title.chars().count() + updated.chars().count());
+            title = format!("{}{}{}", title, " ".repeat(padding.max(2)), updated);
+        }
+
+        let mut top = vec![title, String::new()];
+        match &self.dashboard {
+            Some(dashboard) => top.extend(dashboard.lines()),
+            None => top.push("Loading...".to_string()),
+        }
+
+        let sender = self.selected().unwrap_or_default();
+        let mut bottom = Vec::new();
+        let help = match &self.mode {
+            Mode::Browse => "Up/Down: select  s: send  r: refresh  q: quit",
+            Mode::Form(form) => {
+                let cursor = |focused: bool| if focused { "_" } else { "" };
+                bottom.push(format!("Send from {}", sender));
+                bottom.push(format!(
+                    "  To:     {}{}",
+                    form.recipient,
+                    cursor(!form.amount_focused)
+                ));
+                bottom.push(format!(
+                    "  Amount: {}{} lamports",
+                    form.amount,
+                    cursor(form.amount_focused)
+                ));
+                "Tab: next field  Enter: preview  Esc: cancel"
+            }
+            Mode::Preview(_, preview) => {
+                bottom.push(format!("Send from {} - preview", sender));
+                bottom.extend(
+                    preview
+                        .to_string()
+                        .lines()
+                        .map(|line| format!("  {}", line)),
+                );
+                "y: confirm and send  n: edit  Esc: cancel"
+            }
+            Mode::Confirm(_, form) => {
+                bottom.push(format!("Send from {} - spending policy", sender));
+                bottom.push(format!("  {}", form.message));
+                bottom.push(format!(
+                    "  Type \"{}\" to sign: {}_",
+                    form.phrase, form.typed
+                ));
+                "Enter: sign and send  Esc: cancel"
+            }
+            Mode::Sent(lines) => {
+                bottom.push(format!("Send from {}", sender));
+                bottom.extend(lines.iter().map(|line| format!("  {}", line)));
+                "Press any key to continue"
+            }
+        };
+        if !self.status.is_empty() {
+            bottom.push(self.status.clone());
+        }
+        bottom.push(help.to_string());
+
+        // Les panneaux du haut sont tronqués pour que le formulaire et l'aide restent visibles.
+        top.truncate(height.saturating_sub(bottom.len() + 1));
+        top.push(String::new());
+        top.extend(bottom);
+        top.into_iter()
+            .map(|line| line.chars().take(width).collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::dashboard_manager::AccountOutput;
+
+    fn account(label: &str) -> AccountOutput {
+        AccountOutput {
+            label: label.to_string(),
+            pubkey: "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2".to_string(),
+            active: label == "main",
+            watch_only: false,
+            lamports: Some(2_000_000),
+            sol: Some(0.002),
+            tokens: Vec::new(),
+            error: None,
+        }
+    }
+
+    fn tui() -> Tui {
+        let mut tui = Tui::new(WalletConfig::default());
+        tui.dashboard = Some(DashboardOutput {
+            rpc_url: tui.config.rpc_url.clone(),
+            accounts: vec![account("main"), account("savings")],
+            selected: Some("main".to_string()),
+            history: None,
+            history_error: None,
+        });
+        tui
+    }
+
+    fn type_text(tui: &mut Tui, text: &str) {
+        for c in text.chars() {
+            assert_eq!(tui.handle_key(Key::Char(c)), Action::None);
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let mut tui = tui();
+        assert_eq!(tui.handle_key(Key::Up), Action::None);
+        assert_eq!(tui.handle_key(Key::Down), Action::LoadHistory);
+        assert_eq!(tui.selected(), Some("savings"));
+        assert_eq!(tui.handle_key(Key::Down), Action::None);
+        assert_eq!(tui.handle_key(Key::Char('r')), Action::Refresh);
+        assert_eq!(tui.handle_key(Key::Char('q')), Action::Quit);
+    }
+
+    #[test]
+    fn test_send_form() {
+        let mut tui = tui();
+        tui.handle_key(Key::Char('s'));
+        type_text(&mut tui, "alicex");
+        tui.handle_key(Key::Backspace);
+        assert_eq!(tui.handle_key(Key::Enter), Action::None);
+        type_text(&mut tui, "1000");
+        assert_eq!(tui.handle_key(Key::Enter), Action::Preview);
+
+        let Mode::Form(form) = &tui.mode else {
+            panic!("Send form expected");
+        };
+        assert_eq!(form.recipient, "alice");
+        assert_eq!(form.amount, "1000");
+
+        let screen = tui.render(80, 24);
+        assert!(screen.contains(&"  To:     alice".to_string()));
+        assert!(screen.contains(&"  Amount: 1000_ lamports".to_string()));
+
+        assert_eq!(tui.handle_key(Key::Escape), Action::None);
+        assert!(matches!(tui.mode, Mode::Browse));
+    }
+
+    #[test]
+    fn test_policy_confirmation() {
+        let mut tui = tui();
+        tui.mode = Mode::Confirm(
+            SendPreviewOutput {
+                from: "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2".to_string(),
+                to: "FTGJPL5hia749v3jhNWJA7uE2VoVGyofB7BBL2cLwoPc".to_string(),
+                lamports: 4000,
+                sol: 0.000004,
+                estimated_fee: 5000,
+                warnings: Vec::new(),
+            },
+            ConfirmForm {
+                message: "Spending policy: sending 4000 lamports requires a confirmation."
+                    .to_string(),
+                phrase: "4000".to_string(),
+                typed: String::new(),
+            },
+        );
+        type_text(&mut tui, "40001");
+        tui.handle_key(Key::Backspace);
+
+        let screen = tui.render(80, 24);
+        assert!(screen.contains(&"  Type \"4000\" to sign: 4000_".to_string()));
+        // La saisie est transmise à la politique au nouvel envoi.
+        assert_eq!(tui.handle_key(Key::Enter), Action::Send);
+        assert_eq!(tui.handle_key(Key::Escape), Action::None);
+        assert!(matches!(tui.mode, Mode::Browse));
+    }
+
+    #[test]
+    fn test_render_fits_terminal() {
+        let mut tui = tui();
+        tui.mode = Mode::Sent(vec!["Transaction sent successfully!".to_string()]);
+
+        let screen = tui.render(40, 8);
+        assert!(screen.len() <= 8);
+        assert!(screen.iter().all(|line| line.chars().count() <= 40));
+        assert_eq!(screen.last().unwrap(), "Press any key to continue");
+        assert_eq!(tui.handle_key(Key::Char('x')), Action::Refresh);
+    }
+}
@@ -1,7 +1,153 @@
-use crate::app::mnemonic_manager::MnemonicManager;
+use crate::app::mnemonic_manager::{MnemonicManager, MnemonicOrigin, MnemonicOutput};
+use crate::bip::entropy::{BipEntropy, EntropyError, EntropySource};
 use crate::bip::mnemonic::BipMnemonic;
 use crate::config::wallet_config::WalletConfig;
 use crate::solana::balance::SolanaBalance;
+use crate::solana::history::SolanaHistory;
+use crate::solana::token::{SolanaToken, TokenBalance};
+use crate::store::address_book::AddressBook;
+use serde::Serialize;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use std::fmt;
+
+/// L'entropie fournie par l'utilisateur lors de la création d'une mnémonique.
+#[derive(Debug, Serialize)]
+pub struct EntropyOutput {
+    /// "dice" ou "hex".
+    pub source: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rolls: Option<usize>,
+    pub bits_collected: f64,
+    pub bits_required: usize,
+    /// Entropie combinée avec le générateur aléatoire du système (résultat non reproductible).
+    pub mixed_with_os: bool,
+}
+
+impl fmt::Display for EntropyOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.rolls {
+            Some(rolls) => write!(
+                f,
+                "Entropy: {} rolls, {:.1} bits collected ({} bits required)",
+                rolls, self.bits_collected, self.bits_required
+            )?,
+            None => write!(
+                f,
+                "Entropy: {} bits used ({} bits required)",
+                self.bits_collected, self.bits_required
+            )?,
+        }
+        if self.mixed_with_os {
+            write!(
+                f,
+                "\nEntropy mixed with the OS random generator (the result is not reproducible)."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Résultat de la commande "balance_by_pubkey".
+#[derive(Debug, Serialize)]
+pub struct BalanceOutput {
+    pub pubkey: String,
+    pub sol: f64,
+    pub lamports: u64,
+}
+
+impl fmt::Display for BalanceOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Balance: {:.9} SOL ({} lamports)",
+            self.sol, self.lamports
+        )
+    }
+}
+
+/// Une transaction de l'historique d'une adresse.
+#[derive(Debug, Serialize)]
+pub struct HistoryEntryOutput {
+    pub signature: String,
+    pub slot: u64,
+    /// Date du bloc (RFC 3339), si elle est connue.
+    pub date: Option<String>,
+    /// "ok" ou "failed".
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<RpcConfirmedTransactionStatusWithSignature> for HistoryEntryOutput {
+    fn from(transaction: RpcConfirmedTransactionStatusWithSignature) -> Self {
+        Self {
+            date: transaction
+                .block_time
+                .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                .map(|d| d.to_rfc3339()),
+            status: if transaction.err.is_some() {
+                "failed"
+            } else {
+                "ok"
+            },
+            error: transaction.err.map(|e| e.to_string()),
+            signature: transaction.signature,
+            slot: transaction.slot,
+        }
+    }
+}
+
+/// Résultat de la commande "history" (de la transaction la plus récente à la plus ancienne).
+#[derive(Debug, Serialize)]
+pub struct HistoryOutput {
+    pub address: String,
+    pub transactions: Vec<HistoryEntryOutput>,
+}
+
+impl fmt::Display for HistoryOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.transactions.is_empty() {
+            return write!(f, "No transactions found.");
+        }
+
+        let lines: Vec<String> = self
+            .transactions
+            .iter()
+            .map(|transaction| {
+                format!(
+                    "{} slot {} {} {}",
+                    transaction.signature,
+                    transaction.slot,
+                    transaction.date.as_deref().unwrap_or("-"),
+                    transaction.status
+                )
+            })
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Les soldes des tokens SPL d'une adresse.
+#[derive(Debug, Serialize)]
+pub struct TokenBalancesOutput {
+    pub address: String,
+    pub tokens: Vec<TokenBalance>,
+}
+
+impl fmt::Display for TokenBalancesOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tokens.is_empty() {
+            return write!(f, "No token accounts found.");
+        }
+
+        let lines: Vec<String> = self
+            .tokens
+            .iter()
+            .map(|token| format!("{} {}", token.mint, token.amount))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
 
 pub struct WalletManager {
     config: WalletConfig,
@@ -12,40 +158,152 @@ impl WalletManager {
         Self { config }
     }
 
-    /// Génère une phrase mnémonique aléatoire de 12 mots et affiche la clé publique Solana correspondante.
+    /// Génère une phrase mnémonique aléatoire de 12 mots et la clé publique Solana correspondante.
     /// Cette fonction est typiquement utilisée pour la création initiale d'un portefeuille.
-    pub fn generate_and_print_random_mnemonic(&self) {
+    pub fn generate_random_mnemonic(
+        &self,
+        word_count: usize,
+    ) -> Result<MnemonicOutput, Box<dyn std::error::Error>> {
         let mnemonic_manager = MnemonicManager::new(self.config.clone());
 
         // Créer une nouvelle phrase mnémonique générée aléatoirement (en suivant le standard BIP39).
-        // Il s'agit d'une mnémonique de 12 mots, ce qui est un standard commun pour de nombreux portefeuilles.
-        let mnemonic = BipMnemonic::generate_mnemonic();
+        // Par défaut, il s'agit d'une mnémonique de 12 mots, ce qui est un standard commun pour de nombreux portefeuilles.
+        let mnemonic = BipMnemonic::generate_mnemonic_with_word_count(word_count)
+            .map_err(|e| format!("Failed to generate mnemonic: {}", e))?;
+
+        mnemonic_manager.process_mnemonic(&mnemonic, MnemonicOrigin::Random, None)
+    }
+
+    /// Génère une phrase mnémonique à partir d'entropie fournie par l'utilisateur (lancers de dé ou hexadécimal)
+    /// et la clé publique Solana correspondante.
+    /// Sans "mix_os_entropy", le résultat est reproductible avec d'autres outils "dice/hex to BIP39".
+    pub fn generate_mnemonic_from_entropy(
+        &self,
+        source: EntropySource,
+        word_count: usize,
+        mix_os_entropy: bool,
+    ) -> Result<MnemonicOutput, Box<dyn std::error::Error>> {
+        let mnemonic_manager = MnemonicManager::new(self.config.clone());
+
+        let bits = BipEntropy::required_bits(word_count)
+            .map_err(|e| format!("Failed to collect entropy: {}", e))?;
+        let (mut entropy, mut entropy_output) = self
+            .collect_entropy(source, bits)
+            .map_err(|e| format!("Failed to collect entropy: {}", e))?;
 
-        // Convertit la mnémonique en une chaîne de caractères (pour pouvoir l'afficher et l'utiliser ultérieurement).
-        // Cette phrase est utilisée pour générer une seed et peut être utilisée pour la récupération d'un portefeuille.
-        let phrase = BipMnemonic::get_mnemonic_to_str(&mnemonic);
+        if mix_os_entropy {
+            BipEntropy::mix_os_entropy(&mut entropy);
+            entropy_output.mixed_with_os = true;
+        }
 
-        println!("BIP39 Mnemonic (random phrase): {}", phrase);
+        let mnemonic = BipMnemonic::get_mnemonic_from_entropy(&entropy)
+            .map_err(|e| format!("Failed to generate mnemonic: {}", e))?;
 
-        mnemonic_manager.process_mnemonic(&mnemonic);
+        mnemonic_manager.process_mnemonic(
+            &mnemonic,
+            MnemonicOrigin::UserEntropy,
+            Some(entropy_output),
+        )
     }
 
-    /// Génère une phrase mnémonique à partir d'une phrase donnée (12/24/Etc. mots) et affiche la clé publique Solana correspondante.
+    /// Génère une phrase mnémonique à partir d'une phrase donnée (12/24/Etc. mots) et la clé publique Solana correspondante.
     /// Cette méthode permet d'utiliser une phrase existante pour récupérer ou accéder à un portefeuille.
-    pub fn generate_and_print_mnemonic_from_phrase(&self, phrase: &str) {
+    pub fn generate_mnemonic_from_phrase(
+        &self,
+        phrase: &str,
+    ) -> Result<MnemonicOutput, Box<dyn std::error::Error>> {
         let mnemonic_manager = MnemonicManager::new(self.config.clone());
 
-        println!("BIP39 Mnemonic (given phrase): {}", phrase);
-
         // Convertit la chaîne de caractères fournie en une structure mnémonique valide.
         // Cette étape vérifie que la phrase correspond aux critères du standard BIP39 et qu'elle peut être utilisée pour générer une seed.
         let mnemonic = BipMnemonic::get_mnemonic_from_phrase(phrase);
 
-        mnemonic_manager.process_mnemonic(&mnemonic);
+        mnemonic_manager.process_mnemonic(&mnemonic, MnemonicOrigin::Given, None)
+    }
+
+    /// Collecte "bits" bits d'entropie fournie par l'utilisateur (les lancers de dé sont demandés s'ils sont absents).
+    fn collect_entropy(
+        &self,
+        source: EntropySource,
+        bits: usize,
+    ) -> Result<(Vec<u8>, EntropyOutput), EntropyError> {
+        match source {
+            EntropySource::Dice(rolls) => {
+                let rolls = match rolls {
+                    Some(rolls) => BipEntropy::parse_dice_rolls(&rolls)?,
+                    None => BipEntropy::prompt_for_dice_rolls(&self.config.output(), bits)?,
+                };
+                let entropy = BipEntropy::from_dice(&rolls, bits)?;
+                let entropy_output = EntropyOutput {
+                    source: "dice",
+                    rolls: Some(rolls.len()),
+                    bits_collected: BipEntropy::dice_bits(rolls.len()),
+                    bits_required: bits,
+                    mixed_with_os: false,
+                };
+                Ok((entropy, entropy_output))
+            }
+            EntropySource::Hex(hex) => {
+                let entropy = BipEntropy::from_hex(&hex, bits)?;
+                let entropy_output = EntropyOutput {
+                    source: "hex",
+                    rolls: None,
+                    bits_collected: (entropy.len() * 8) as f64,
+                    bits_required: bits,
+                    mixed_with_os: false,
+                };
+                Ok((entropy, entropy_output))
+            }
+        }
     }
 
-    pub fn get_balance_by_pubkey(&self, pubkey: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    /// Retourne la balance (en SOL et en lamports) d'une adresse.
+    /// L'adresse peut être une clé publique, un nom de contact ou un label du key store.
+    pub fn get_balance_by_pubkey(
+        &self,
+        pubkey: &str,
+    ) -> Result<BalanceOutput, Box<dyn std::error::Error>> {
+        let pubkey = AddressBook::new(self.config.clone()).resolve(pubkey)?;
         let solana_balance = SolanaBalance::new(self.config.clone());
-        solana_balance.get_balance_by_pubkey(pubkey)
+        let lamports = solana_balance.get_balance_by_pubkey(&pubkey.to_string())?;
+
+        Ok(BalanceOutput {
+            pubkey: pubkey.to_string(),
+            sol: lamports as f64 / 1_000_000_000_f64, // Convertir les lamports en SOL.
+            lamports,
+        })
+    }
+
+    /// Retourne les transactions récentes d'une adresse (clé publique, nom de contact ou label du key store).
+    pub fn get_history(
+        &self,
+        address: &str,
+        limit: usize,
+    ) -> Result<HistoryOutput, Box<dyn std::error::Error>> {
+        let pubkey = AddressBook::new(self.config.clone()).resolve(address)?;
+        let solana_history = SolanaHistory::new(self.config.clone());
+        let transactions = solana_history.get_history(&pubkey, limit)?;
+
+        Ok(HistoryOutput {
+            address: pubkey.to_string(),
+            transactions: transactions
+                .into_iter()
+                .map(HistoryEntryOutput::from)
+                .collect(),
+        })
+    }
+
+    /// Retourne les soldes des tokens SPL d'une adresse (clé publique, nom de contact ou label du key store).
+    pub fn get_token_balances(
+        &self,
+        address: &str,
+    ) -> Result<TokenBalancesOutput, Box<dyn std::error::Error>> {
+        let pubkey = AddressBook::new(self.config.clone()).resolve(address)?;
+        let solana_token = SolanaToken::new(self.config.clone());
+
+        Ok(TokenBalancesOutput {
+            address: pubkey.to_string(),
+            tokens: solana_token.get_token_balances(&pubkey)?,
+        })
     }
 }
@@ -0,0 +1,237 @@
+use bip32::{ChildNumber, Error as Bip32Error, XPrv};
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use std::fmt;
+
+/// "purpose" BIP85 : les chiffres de "DRNG" sur un clavier de téléphone.
+const BIP85_PURPOSE: u32 = 83696968;
+/// Application BIP39 (mnémoniques enfants).
+const APP_BIP39: u32 = 39;
+/// Application HEX (entropie brute).
+const APP_HEX: u32 = 128169;
+/// Clé HMAC-SHA512 appliquée à la clé privée dérivée.
+const HMAC_KEY: &[u8] = b"bip-entropy-from-k";
+
+/// Erreurs de dérivation BIP85.
+#[derive(Debug)]
+pub enum Bip85Error {
+    Bip32(Bip32Error),
+    InvalidWordCount(u32),
+    UnsupportedLanguage(u32),
+    InvalidLength(u32),
+    InvalidIndex(u32),
+}
+
+impl fmt::Display for Bip85Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bip85Error::Bip32(e) => write!(f, "BIP32 derivation failed: {}", e),
+            Bip85Error::InvalidWordCount(count) => {
+                write!(f, "Invalid word count {} (expected 12, 18 or 24)", count)
+            }
+            Bip85Error::UnsupportedLanguage(index) => {
+                write!(f, "Unsupported language index {}", index)
+            }
+            Bip85Error::InvalidLength(length) => {
+                write!(f, "Invalid length {} (expected 16 to 64 bytes)", length)
+            }
+            Bip85Error::InvalidIndex(index) => {
+                write!(f, "Invalid index {} (must be lower than 2^31)", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Bip85Error {}
+
+impl From<Bip32Error> for Bip85Error {
+    fn from(e: Bip32Error) -> Self {
+        Bip85Error::Bip32(e)
+    }
+}
+
+pub struct Bip85 {}
+
+impl Bip85 {
+    /// Crée la clé racine BIP32 (secp256k1) à partir de la seed produite par `BipSeed::generate_seed`.
+    pub fn root_from_seed(seed_bytes: &[u8]) -> Result<XPrv, Bip85Error> {
+        Ok(XPrv::new(seed_bytes)?)
+    }
+
+    /// Retourne le chemin de dérivation BIP85 d'une mnémonique enfant : m/83696968'/39'/{language}'/{words}'/{index}'.
+    pub fn mnemonic_path(language: u32, word_count: u32, index: u32) -> String {
+        Self::format_path(&[APP_BIP39, language, word_count, index])
+    }
+
+    /// Retourne le chemin de dérivation BIP85 d'une entropie brute : m/83696968'/128169'/{num_bytes}'/{index}'.
+    pub fn hex_path(num_bytes: u32, index: u32) -> String {
+        Self::format_path(&[APP_HEX, num_bytes, index])
+    }
+
+    /// Dérive une mnémonique BIP39 enfant.
+    ///
+    /// # Arguments:
+    /// - root - La clé racine (maîtresse) BIP32.
+    /// - language - L'index de langue BIP85 (0 = anglais, 1 = japonais, 2 = coréen, 3 = espagnol,
+    ///   4 = chinois simplifié, 5 = chinois traditionnel, 6 = français, 7 = italien).
+    /// - word_count - Le nombre de mots (12, 18 ou 24).
+    /// - index - L'index de l'enfant.
+    pub fn derive_mnemonic(
+        root: &XPrv,
+        language: u32,
+        word_count: u32,
+        index: u32,
+    ) -> Result<Mnemonic, Bip85Error> {
+        let length = match word_count {
+            12 => 16,
+            18 => 24,
+            24 => 32,
+            _ => return Err(Bip85Error::InvalidWordCount(word_count)),
+        };
+        let bip39_language = Self::language(language)?;
+
+        let entropy = Self::derive_entropy(root, &[APP_BIP39, language, word_count, index])?;
+        Ok(Mnemonic::from_entropy(&entropy[..length], bip39_language)
+            .expect("entropy length is valid for BIP39"))
+    }
+
+    /// Dérive "num_bytes" octets (16 à 64) d'entropie brute (application HEX).
+    pub fn derive_hex(root: &XPrv, num_bytes: u32, index: u32) -> Result<Vec<u8>, Bip85Error> {
+        if !(16..=64).contains(&num_bytes) {
+            return Err(Bip85Error::InvalidLength(num_bytes));
+        }
+
+        let entropy = Self::derive_entropy(root, &[APP_HEX, num_bytes, index])?;
+        Ok(entropy[..num_bytes as usize].to_vec())
+    }
+
+    /// Dérive la seed (32 octets) d'une clé ed25519.
+    /// BIP85 ne définit pas d'application ed25519 : la seed est l'entropie HEX de 32 octets au même index,
+    /// ce qui permet de la reproduire avec n'importe quel outil BIP85.
+    pub fn derive_ed25519_seed(root: &XPrv, index: u32) -> Result<Vec<u8>, Bip85Error> {
+        Self::derive_hex(root, 32, index)
+    }
+
+    /// Dérive la clé privée au chemin m/83696968'/{path}' (tous les niveaux sont "hardened"),
+    /// puis calcule l'entropie : HMAC-SHA512(clé = "bip-entropy-from-k", message = clé privée).
+    fn derive_entropy(root: &XPrv, path: &[u32]) -> Result<[u8; 64], Bip85Error> {
+        let mut xprv = root.derive_child(ChildNumber::new(BIP85_PURPOSE, true)?)?;
+        for index in path {
+            let child_number =
+                ChildNumber::new(*index, true).map_err(|_| Bip85Error::InvalidIndex(*index))?;
+            xprv = xprv.derive_child(child_number)?;
+        }
+
+        let mut mac =
+            Hmac::<Sha512>::new_from_slice(HMAC_KEY).expect("HMAC accepts keys of any length");
+        mac.update(&xprv.private_key().to_bytes());
+
+        let mut entropy = [0u8; 64];
+        entropy.copy_from_slice(&mac.finalize().into_bytes());
+        Ok(entropy)
+    }
+
+    fn language(index: u32) -> Result<Language, Bip85Error> {
+        match index {
+            0 => Ok(Language::English),
+            1 => Ok(Language::Japanese),
+            2 => Ok(Language::Korean),
+            3 => Ok(Language::Spanish),
+            4 => Ok(Language::ChineseSimplified),
+            5 => Ok(Language::ChineseTraditional),
+            6 => Ok(Language::French),
+            7 => Ok(Language::Italian),
+            _ => Err(Bip85Error::UnsupportedLanguage(index)),
+        }
+    }
+
+    fn format_path(path: &[u32]) -> String {
+        std::iter::once(BIP85_PURPOSE)
+            .chain(path.iter().copied())
+            .fold("m".to_string(), |acc, index| format!("{}/{}'", acc, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    // Vecteurs de test de la spécification BIP85 (https://github.com/bitcoin/bips/blob/master/bip-0085.mediawiki).
+    const MASTER_XPRV: &str = "xprv9s21ZrQH143K2LBWUUQRFXhucrQqBpKdRRxNVq2zBqsx8HVqFk2uYo8kmbaLLHRdqtQpUm98uKfu3vca1LqdGhUtyoFnCNkfmXRyPXLjbKb";
+
+    fn root() -> XPrv {
+        XPrv::from_str(MASTER_XPRV).unwrap()
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn test_vector_derived_entropy() {
+        let entropy = Bip85::derive_entropy(&root(), &[0, 0]).unwrap();
+        assert_eq!(
+            to_hex(&entropy),
+            "efecfbccffea313214232d29e71563d941229afb4338c21f9517c41aaa0d16f00b83d2a09ef747e7a64e8e2bd5a14869e693da66ce94ac2da570ab7ee48618f7"
+        );
+    }
+
+    #[test]
+    fn test_vector_bip39_mnemonics() {
+        let mnemonic = Bip85::derive_mnemonic(&root(), 0, 12, 0).unwrap();
+        assert_eq!(
+            mnemonic.phrase(),
+            "girl mad pet galaxy egg matter matrix prison refuse sense ordinary nose"
+        );
+
+        let mnemonic = Bip85::derive_mnemonic(&root(), 0, 18, 0).unwrap();
+        assert_eq!(
+            mnemonic.phrase(),
+            "near account window bike charge season chef number sketch tomorrow excuse sniff circle vital hockey outdoor supply token"
+        );
+
+        let mnemonic = Bip85::derive_mnemonic(&root(), 0, 24, 0).unwrap();
+        assert_eq!(
+            mnemonic.phrase(),
+            "puppy ocean match cereal symbol another shed magic wrap hammer bulb intact gadget divorce twin tonight reason outdoor destroy simple truth cigar social volcano"
+        );
+    }
+
+    #[test]
+    fn test_vector_hex() {
+        let entropy = Bip85::derive_hex(&root(), 64, 0).unwrap();
+        assert_eq!(
+            to_hex(&entropy),
+            "492db4698cf3b73a5a24998aa3e9d7fa96275d85724a91e71aa2d645442f878555d078fd1f1f67e368976f04137b1f7a0d19232136ca50c44614af72b5582a5c"
+        );
+        assert_eq!(
+            Bip85::derive_ed25519_seed(&root(), 0).unwrap(),
+            Bip85::derive_hex(&root(), 32, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_paths_and_invalid_parameters() {
+        assert_eq!(Bip85::mnemonic_path(0, 12, 0), "m/83696968'/39'/0'/12'/0'");
+        assert_eq!(Bip85::hex_path(64, 0), "m/83696968'/128169'/64'/0'");
+
+        assert!(matches!(
+            Bip85::derive_mnemonic(&root(), 0, 15, 0),
+            Err(Bip85Error::InvalidWordCount(15))
+        ));
+        assert!(matches!(
+            Bip85::derive_mnemonic(&root(), 9, 12, 0),
+            Err(Bip85Error::UnsupportedLanguage(9))
+        ));
+        assert!(matches!(
+            Bip85::derive_hex(&root(), 8, 0),
+            Err(Bip85Error::InvalidLength(8))
+        ));
+        assert!(matches!(
+            Bip85::derive_hex(&root(), 32, 1 << 31),
+            Err(Bip85Error::InvalidIndex(_))
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::app::output::Output;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io;
+
+/// Erreurs de collecte d'entropie fournie par l'utilisateur.
+#[derive(Debug, PartialEq)]
+pub enum EntropyError {
+    /// Un caractère n'est pas un lancer de dé (1 à 6) ou un chiffre hexadécimal.
+    InvalidCharacter(char),
+    /// Nombre impair de chiffres hexadécimaux.
+    OddLength,
+    /// Pas assez d'entropie : (bits collectés, bits requis).
+    Insufficient(f64, usize),
+    /// Nombre de mots BIP39 non supporté.
+    InvalidWordCount(usize),
+}
+
+impl fmt::Display for EntropyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntropyError::InvalidCharacter(c) => write!(f, "Invalid character '{}'", c),
+            EntropyError::OddLength => write!(f, "The hex string must have an even length"),
+            EntropyError::Insufficient(collected, required) => write!(
+                f,
+                "Insufficient entropy: {:.1} bits collected, {} bits required",
+                collected, required
+            ),
+            EntropyError::InvalidWordCount(count) => write!(
+                f,
+                "Invalid word count {} (expected 12, 15, 18, 21 or 24)",
+                count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EntropyError {}
+
+/// Source de l'entropie fournie par l'utilisateur.
+pub enum EntropySource {
+    /// Lancers de dé à 6 faces (saisis interactivement si absents).
+    Dice(Option<String>),
+    /// Entropie brute en hexadécimal.
+    Hex(String),
+}
+
+pub struct BipEntropy {}
+
+impl BipEntropy {
+    /// Retourne le nombre de bits d'entropie d'une mnémonique BIP39 de "word_count" mots (128 bits pour 12 mots, 256 pour 24).
+    pub fn required_bits(word_count: usize) -> Result<usize, EntropyError> {
+        match word_count {
+            12 | 15 | 18 | 21 | 24 => Ok(word_count / 3 * 32),
+            _ => Err(EntropyError::InvalidWordCount(word_count)),
+        }
+    }
+
+    /// Nombre de bits d'entropie apportés par des lancers de dé à 6 faces (log2(6) ≈ 2,585 bits par lancer).
+    pub fn dice_bits(roll_count: usize) -> f64 {
+        roll_count as f64 * 6f64.log2()
+    }
+
+    /// Nombre minimal de lancers de dé pour obtenir "bits" bits d'entropie.
+    pub fn required_rolls(bits: usize) -> usize {
+        (bits as f64 / 6f64.log2()).ceil() as usize
+    }
+
+    /// Extrait les lancers de dé (chiffres 1 à 6) d'une chaîne, en ignorant les espaces et les séparateurs usuels.
+    pub fn parse_dice_rolls(input: &str) -> Result<String, EntropyError> {
+        input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',' && *c != '-')
+            .map(|c| match c {
+                '1'..='6' => Ok(c),
+                _ => Err(EntropyError::InvalidCharacter(c)),
+            })
+            .collect()
+    }
+
+    /// Convertit des lancers de dé en entropie BIP39.
+    /// La conversion est celle de Coldcard (et d'autres outils "dice to BIP39") : SHA-256 de la suite des lancers
+    /// (caractères ASCII "1" à "6"), tronqué au nombre de bits requis. Le résultat est donc reproductible.
+    ///
+    /// # Arguments:
+    /// - rolls - Les lancers de dé (par exemple "3624155...").
+    /// - bits - Le nombre de bits d'entropie souhaité (128 à 256).
+    pub fn from_dice(rolls: &str, bits: usize) -> Result<Vec<u8>, EntropyError> {
+        let rolls = Self::parse_dice_rolls(rolls)?;
+
+        let collected = Self::dice_bits(rolls.len());
+        if rolls.len() < Self::required_rolls(bits) {
+            return Err(EntropyError::Insufficient(collected, bits));
+        }
+
+        let hash = Sha256::digest(rolls.as_bytes());
+        Ok(hash[..bits / 8].to_vec())
+    }
+
+    /// Convertit une chaîne hexadécimale en entropie BIP39 (les premiers "bits" bits sont utilisés tels quels).
+    pub fn from_hex(hex: &str, bits: usize) -> Result<Vec<u8>, EntropyError> {
+        let bytes = Self::decode_hex(hex)?;
+
+        if bytes.len() * 8 < bits {
+            return Err(EntropyError::Insufficient((bytes.len() * 8) as f64, bits));
+        }
+
+        Ok(bytes[..bits / 8].to_vec())
+    }
+
+    /// Combine l'entropie de l'utilisateur avec celle du système (XOR avec des octets aléatoires).
+    /// Le résultat n'est plus reproductible, mais reste au moins aussi sûr que la meilleure des deux sources.
+    pub fn mix_os_entropy(entropy: &mut [u8]) {
+        let mut os_entropy = vec![0u8; entropy.len()];
+        rand::rngs::OsRng.fill_bytes(&mut os_entropy);
+
+        for (byte, os_byte) in entropy.iter_mut().zip(os_entropy) {
+            *byte ^= os_byte;
+        }
+    }
+
+    /// Demande à l'utilisateur de saisir des lancers de dé, jusqu'à en avoir suffisamment (ligne vide pour terminer).
+    pub fn prompt_for_dice_rolls(output: &Output, bits: usize) -> Result<String, EntropyError> {
+        let required_rolls = Self::required_rolls(bits);
+        output.message(format!(
+            "Enter at least {} d6 rolls (digits 1 to 6, empty line to finish):",
+            required_rolls
+        ));
+
+        let mut rolls = String::new();
+        while rolls.len() < required_rolls {
+            let mut line = String::new();
+            match io::stdin().read_line(&mut line) {
+                Ok(0) | Err(_) => break,
+                Ok(_) if line.trim().is_empty() => break,
+                Ok(_) => {
+                    rolls.push_str(&Self::parse_dice_rolls(&line)?);
+                    output.message(format!(
+                        "{} rolls ({:.1} bits) collected",
+                        rolls.len(),
+                        Self::dice_bits(rolls.len())
+                    ));
+                }
+            }
+        }
+
+        Ok(rolls)
+    }
+
+    /// Décode une chaîne hexadécimale (avec ou sans préfixe "0x").
+    pub fn decode_hex(hex: &str) -> Result<Vec<u8>, EntropyError> {
+        let hex = hex.trim();
+        let hex: Vec<char> = hex.strip_prefix("0x").unwrap_or(hex).chars().collect();
+        if hex.len() % 2 == 1 {
+            return Err(EntropyError::OddLength);
+        }
+
+        hex.chunks(2)
+            .map(|pair| {
+                let digit = |c: char| c.to_digit(16).ok_or(EntropyError::InvalidCharacter(c));
+                Ok((digit(pair[0])? * 16 + digit(pair[1])?) as u8)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_bits() {
+        assert_eq!(BipEntropy::required_bits(12), Ok(128));
+        assert_eq!(BipEntropy::required_bits(24), Ok(256));
+        assert_eq!(
+            BipEntropy::required_bits(13),
+            Err(EntropyError::InvalidWordCount(13))
+        );
+        assert_eq!(BipEntropy::required_rolls(128), 50);
+        assert_eq!(BipEntropy::required_rolls(256), 100);
+    }
+
+    #[test]
+    fn test_from_dice_is_sha256_of_rolls() {
+        let rolls = "123456".repeat(17);
+        let entropy = BipEntropy::from_dice(&rolls, 128).unwrap();
+        assert_eq!(
+            entropy,
+            BipEntropy::decode_hex("8fd128918b2e29d6dcbfa5b9a118e5c1").unwrap()
+        );
+
+        // Les espaces et séparateurs sont ignorés.
+        let spaced = rolls.chars().map(|c| format!("{} ", c)).collect::<String>();
+        assert_eq!(BipEntropy::from_dice(&spaced, 128).unwrap(), entropy);
+
+        let entropy = BipEntropy::from_dice(&"1".repeat(100), 256).unwrap();
+        assert_eq!(
+            entropy,
+            BipEntropy::decode_hex(
+                "380b4863f69ebaacc794bfa1742a8a6ddc575e8cf0ded4341ab9da158881ea2d"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_dice_rejects_invalid_input() {
+        assert_eq!(
+            BipEntropy::from_dice(&"1".repeat(49), 128),
+            Err(EntropyError::Insufficient(BipEntropy::dice_bits(49), 128))
+        );
+        assert_eq!(
+            BipEntropy::from_dice(&"7".repeat(50), 128),
+            Err(EntropyError::InvalidCharacter('7'))
+        );
+    }
+
+    #[test]
+    fn test_from_hex() {
+        let hex = "00112233445566778899aabbccddeeff";
+        assert_eq!(
+            BipEntropy::from_hex(hex, 128).unwrap(),
+            BipEntropy::decode_hex(hex).unwrap()
+        );
+        assert_eq!(
+            BipEntropy::from_hex(hex, 256),
+            Err(EntropyError::Insufficient(128.0, 256))
+        );
+        assert_eq!(
+            BipEntropy::from_hex("abc", 128),
+            Err(EntropyError::OddLength)
+        );
+        assert_eq!(
+            BipEntropy::from_hex(&"zz".repeat(16), 128),
+            Err(EntropyError::InvalidCharacter('z'))
+        );
+    }
+
+    #[test]
+    fn test_mix_os_entropy() {
+        let mut entropy = vec![0u8; 32];
+        BipEntropy::mix_os_entropy(&mut entropy);
+        assert_ne!(entropy, vec![0u8; 32]);
+    }
+}
@@ -1,4 +1,5 @@
 use bip39::{Language, Mnemonic, MnemonicType};
+use std::error::Error;
 
 pub struct BipMnemonic {}
 
@@ -9,6 +10,14 @@ impl BipMnemonic {
         Mnemonic::new(MnemonicType::Words12, Language::English)
     }
 
+    /// Génère une phrase mnémonique aléatoire de "word_count" mots (12, 15, 18, 21 ou 24).
+    pub fn generate_mnemonic_with_word_count(
+        word_count: usize,
+    ) -> Result<Mnemonic, Box<dyn Error>> {
+        let mnemonic_type = MnemonicType::for_word_count(word_count)?;
+        Ok(Mnemonic::new(mnemonic_type, Language::English))
+    }
+
     /// Récupère et retourne la phrase mnémonique sous forme de chaîne de caractères.
     /// La phrase est la représentation humainement lisible de la seed cryptographique.
     pub fn get_mnemonic_to_str(mnemonic: &Mnemonic) -> &str {
@@ -22,4 +31,9 @@ impl BipMnemonic {
         Mnemonic::from_phrase(phrase, Language::English)
             .expect("Failed to get mnemonic from phrase")
     }
+
+    /// Crée une phrase mnémonique à partir de son entropie (128 à 256 bits, par pas de 32 bits).
+    pub fn get_mnemonic_from_entropy(entropy: &[u8]) -> Result<Mnemonic, Box<dyn Error>> {
+        Ok(Mnemonic::from_entropy(entropy, Language::English)?)
+    }
 }
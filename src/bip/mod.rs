@@ -1,3 +1,6 @@
+pub mod bip85;
+pub mod entropy;
 pub mod mnemonic;
 pub mod passphrase;
 pub mod seed;
+pub mod slip39;
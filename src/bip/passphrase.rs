@@ -1,3 +1,5 @@
+use crate::app::output::Output;
+use crate::app::session::Session;
 use std::io;
 
 pub struct BipPassphrase {}
@@ -5,8 +7,25 @@ pub struct BipPassphrase {}
 impl BipPassphrase {
     /// Demande à l'utilisateur d'entrer une passphrase optionnelle pour renforcer la sécurité de la seed.
     /// Laisser vide si aucune passphrase n'est souhaitée.
-    pub fn prompt_for_passphrase() -> String {
-        println!("Enter passphrase (optional):");
+    /// En mode interactif ("shell"), la passphrase est conservée par la session jusqu'à son verrouillage.
+    pub fn prompt_for_passphrase(output: &Output) -> String {
+        if let Some(passphrase) = Session::passphrase() {
+            return passphrase;
+        }
+
+        let passphrase = Self::prompt(output, "Enter passphrase (optional):");
+        Session::unlock(&passphrase);
+        passphrase
+    }
+
+    /// Demande à l'utilisateur la passphrase (optionnelle) protégeant des shares SLIP-39.
+    pub fn prompt_for_share_passphrase(output: &Output) -> String {
+        Self::prompt(output, "Enter SLIP-39 passphrase (optional):")
+    }
+
+    /// La question est affichée sur la sortie standard (ou sur la sortie d'erreur avec "--output json").
+    fn prompt(output: &Output, message: &str) -> String {
+        output.message(message);
 
         let mut passphrase = String::new();
         io::stdin()
@@ -34,10 +34,15 @@ impl BipSeed {
     /// # Arguments:
     /// - seed_bytes - Les octets de la seed à partir de laquelle les clés seront dérivées.
     /// - index - L'index de dérivation utilisé pour générer différentes clés privées.
+    /// - template - Le modèle du chemin de dérivation, où "{index}" est remplacé par l'index (voir `WalletConfig::derivation_template`).
     ///
     /// # Returns:
     /// Retourne un vecteur contenant les octets de la clé privée dérivée. Ou retourne une erreur en cas de problème lors de la dérivation.
-    pub fn derive_seed_bytes(seed_bytes: &[u8], index: usize) -> Result<Vec<u8>, Bip32Error> {
+    pub fn derive_seed_bytes(
+        seed_bytes: &[u8],
+        index: usize,
+        template: &str,
+    ) -> Result<Vec<u8>, Bip32Error> {
         // Si l'index est 0, retourne directement les octets de la seed originale sans modification.
         // Cela garantit que la clé principale reste inchangée si aucune dérivation n'est requise.
         if index == 0 {
@@ -45,7 +50,7 @@ impl BipSeed {
         }
 
         // Construit le chemin de dérivation complet en utilisant le standard BIP44 pour Solana.
-        // Le format est "m/44'/501'/0'/0/{index}", où {index} représente le numéro de la dérivation.
+        // Le format par défaut est "m/44'/501'/0'/0/{index}", où {index} représente le numéro de la dérivation.
         // SLIP44: 501 = Solana Coin (SOL Symbol).
         //
         // Le chemin de dérivation utilisé dans BIP44 suit cette structure :
@@ -64,7 +69,7 @@ impl BipSeed {
         //   La constante 0 est utilisée pour la chaîne externe. La constante 1 pour la chaîne interne (également appelée adresse de changement).
         // - address_index:
         //   Représente le numéro séquentiel d’une adresse à l’intérieur de cet "account".
-        let path = format!("m/44'/501'/0'/0/{}", index);
+        let path = Self::derivation_path(template, index);
         let derivation_path = DerivationPath::from_str(&path)?;
 
         // Créer une clé privée étendue à partir des octets de la seed.
@@ -79,4 +84,28 @@ impl BipSeed {
         // Retourne les octets de la clé privée dérivée, utilisable pour générer des paires de clés.
         Ok(master_xprv.private_key().to_bytes().to_vec())
     }
+
+    /// Retourne le chemin de dérivation utilisé pour un index donné.
+    /// L'index 0 correspond à la seed originale (sans dérivation), représentée par "m".
+    pub fn derivation_path(template: &str, index: usize) -> String {
+        if index == 0 {
+            "m".to_string()
+        } else {
+            template.replace("{index}", &index.to_string())
+        }
+    }
+
+    /// Calcule l'empreinte (fingerprint BIP32) de la clé maîtresse issue de la seed.
+    /// Cette empreinte permet d'identifier la mnémonique d'origine d'une clé sans exposer de secret.
+    ///
+    /// # Arguments:
+    /// - seed_bytes - Les octets de la seed.
+    ///
+    /// # Returns:
+    /// Retourne l'empreinte sous forme hexadécimale (8 caractères), ou une erreur si la seed est invalide.
+    pub fn fingerprint(seed_bytes: &[u8]) -> Result<String, Bip32Error> {
+        let master_xprv = XPrv::new(seed_bytes)?;
+        let fingerprint = master_xprv.public_key().fingerprint();
+        Ok(fingerprint.iter().map(|b| format!("{:02x}", b)).collect())
+    }
 }
@@ -0,0 +1,85 @@
+use hmac::Hmac;
+use sha2::Sha256;
+
+/// Nombre total d'itérations PBKDF2 (réparties sur les tours), multiplié par 2^e.
+const BASE_ITERATION_COUNT: u32 = 10000;
+/// Nombre de tours du réseau de Feistel.
+const ROUND_COUNT: u8 = 4;
+
+/// Sel utilisé par la fonction de tour : "shamir" + identifiant, sauf pour les shares "extendable" (sel vide).
+fn salt(identifier: u16, extendable: bool) -> Vec<u8> {
+    if extendable {
+        Vec::new()
+    } else {
+        let mut salt = b"shamir".to_vec();
+        salt.extend_from_slice(&identifier.to_be_bytes());
+        salt
+    }
+}
+
+fn round_function(
+    round: u8,
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    salt: &[u8],
+    right: &[u8],
+) -> Vec<u8> {
+    let mut password = vec![round];
+    password.extend_from_slice(passphrase);
+
+    let mut full_salt = salt.to_vec();
+    full_salt.extend_from_slice(right);
+
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / ROUND_COUNT as u32;
+    let mut output = vec![0u8; right.len()];
+    pbkdf2::pbkdf2::<Hmac<Sha256>>(&password, &full_salt, iterations, &mut output);
+    output
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+/// Chiffre le secret maître avec la passphrase (réseau de Feistel à 4 tours).
+pub fn encrypt(
+    master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    let (left, right) = master_secret.split_at(master_secret.len() / 2);
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    let salt = salt(identifier, extendable);
+
+    for round in 0..ROUND_COUNT {
+        let f = round_function(round, passphrase, iteration_exponent, &salt, &right);
+        let new_right = xor(&left, &f);
+        left = right;
+        right = new_right;
+    }
+
+    [right, left].concat()
+}
+
+/// Déchiffre le secret maître (opération inverse de `encrypt`).
+pub fn decrypt(
+    encrypted_master_secret: &[u8],
+    passphrase: &[u8],
+    iteration_exponent: u8,
+    identifier: u16,
+    extendable: bool,
+) -> Vec<u8> {
+    let (left, right) = encrypted_master_secret.split_at(encrypted_master_secret.len() / 2);
+    let (mut left, mut right) = (left.to_vec(), right.to_vec());
+    let salt = salt(identifier, extendable);
+
+    for round in (0..ROUND_COUNT).rev() {
+        let f = round_function(round, passphrase, iteration_exponent, &salt, &right);
+        let new_right = xor(&left, &f);
+        left = right;
+        right = new_right;
+    }
+
+    [right, left].concat()
+}
@@ -0,0 +1,475 @@
+mod cipher;
+mod rs1024;
+mod shamir;
+mod share;
+
+use rand::RngCore;
+use share::Share;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Erreurs de création ou de reconstitution de shares SLIP-39.
+#[derive(Debug, PartialEq)]
+pub enum Slip39Error {
+    Parameters(String),
+    Mnemonic(String),
+    Checksum,
+    Padding,
+    Shares(String),
+    Digest,
+}
+
+impl fmt::Display for Slip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slip39Error::Parameters(message) => write!(f, "Invalid parameters: {}", message),
+            Slip39Error::Mnemonic(message) => write!(f, "Invalid share: {}", message),
+            Slip39Error::Checksum => write!(f, "Invalid share checksum"),
+            Slip39Error::Padding => write!(f, "Invalid share padding"),
+            Slip39Error::Shares(message) => write!(f, "Invalid set of shares: {}", message),
+            Slip39Error::Digest => {
+                write!(
+                    f,
+                    "Invalid digest of the shared secret (corrupted or mismatched shares)"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Slip39Error {}
+
+/// Un groupe de shares : "member_threshold" shares parmi "member_count" sont nécessaires pour reconstituer le groupe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GroupSpec {
+    pub member_threshold: u8,
+    pub member_count: u8,
+}
+
+pub struct Slip39 {}
+
+impl Slip39 {
+    /// Partage un secret maître en shares SLIP-39 (Shamir's Secret Sharing), réparties en groupes.
+    ///
+    /// # Arguments:
+    /// - master_secret - Le secret maître (au moins 128 bits, longueur paire), par exemple l'entropie d'une mnémonique BIP39.
+    /// - passphrase - La passphrase SLIP-39 (caractères ASCII imprimables), nécessaire à la reconstitution.
+    /// - group_threshold - Le nombre de groupes nécessaires pour reconstituer le secret.
+    /// - groups - La définition de chaque groupe (seuil et nombre de shares).
+    /// - iteration_exponent - L'exposant du nombre d'itérations PBKDF2 (10000 × 2^e).
+    /// - extendable - Indique si d'autres jeux de shares pourront être créés pour le même secret (drapeau "extendable").
+    ///
+    /// # Returns:
+    /// Retourne les phrases mnémoniques de chaque groupe, ou une erreur si les paramètres sont invalides.
+    pub fn split(
+        master_secret: &[u8],
+        passphrase: &str,
+        group_threshold: u8,
+        groups: &[GroupSpec],
+        iteration_exponent: u8,
+        extendable: bool,
+    ) -> Result<Vec<Vec<String>>, Slip39Error> {
+        Self::validate_passphrase(passphrase)?;
+
+        if master_secret.len() < 16 || master_secret.len() % 2 == 1 {
+            return Err(Slip39Error::Parameters(
+                "The master secret must be at least 128 bits long and a multiple of 16 bits"
+                    .to_string(),
+            ));
+        }
+        if iteration_exponent > 15 {
+            return Err(Slip39Error::Parameters(
+                "The iteration exponent must be at most 15".to_string(),
+            ));
+        }
+        if group_threshold as usize > groups.len() {
+            return Err(Slip39Error::Parameters(
+                "The group threshold cannot be greater than the number of groups".to_string(),
+            ));
+        }
+        if groups
+            .iter()
+            .any(|g| g.member_threshold == 1 && g.member_count > 1)
+        {
+            return Err(Slip39Error::Parameters(
+                "Creating multiple member shares with member threshold 1 is not allowed"
+                    .to_string(),
+            ));
+        }
+
+        // Identifiant aléatoire de 15 bits, commun à toutes les shares.
+        let mut random = [0u8; 2];
+        rand::rngs::OsRng.fill_bytes(&mut random);
+        let identifier = u16::from_be_bytes(random) & 0x7FFF;
+
+        let encrypted_master_secret = cipher::encrypt(
+            master_secret,
+            passphrase.as_bytes(),
+            iteration_exponent,
+            identifier,
+            extendable,
+        );
+
+        let group_shares = shamir::split_secret(
+            group_threshold,
+            groups.len() as u8,
+            &encrypted_master_secret,
+        )?;
+
+        groups
+            .iter()
+            .zip(group_shares)
+            .map(|(group, (group_index, group_secret))| {
+                let member_shares = shamir::split_secret(
+                    group.member_threshold,
+                    group.member_count,
+                    &group_secret,
+                )?;
+
+                Ok(member_shares
+                    .into_iter()
+                    .map(|(member_index, value)| {
+                        Share {
+                            identifier,
+                            extendable,
+                            iteration_exponent,
+                            group_index,
+                            group_threshold,
+                            group_count: groups.len() as u8,
+                            member_index,
+                            member_threshold: group.member_threshold,
+                            value,
+                        }
+                        .to_mnemonic()
+                    })
+                    .collect())
+            })
+            .collect()
+    }
+
+    /// Reconstitue le secret maître à partir de shares SLIP-39.
+    ///
+    /// # Arguments:
+    /// - mnemonics - Les phrases mnémoniques des shares (au moins le seuil de chaque groupe requis).
+    /// - passphrase - La passphrase SLIP-39 utilisée lors du partage.
+    ///
+    /// # Returns:
+    /// Retourne le secret maître, ou une erreur si les shares sont invalides ou insuffisantes.
+    ///   @note Une passphrase erronée ne provoque pas d'erreur : elle produit un autre secret (déni plausible).
+    pub fn combine(mnemonics: &[String], passphrase: &str) -> Result<Vec<u8>, Slip39Error> {
+        Self::validate_passphrase(passphrase)?;
+
+        let shares = mnemonics
+            .iter()
+            .map(|mnemonic| Share::from_mnemonic(mnemonic))
+            .collect::<Result<Vec<Share>, _>>()?;
+
+        let first = shares
+            .first()
+            .ok_or_else(|| Slip39Error::Shares("No share given".to_string()))?;
+
+        let is_compatible = |share: &Share| {
+            share.identifier == first.identifier
+                && share.extendable == first.extendable
+                && share.iteration_exponent == first.iteration_exponent
+                && share.group_threshold == first.group_threshold
+                && share.group_count == first.group_count
+                && share.value.len() == first.value.len()
+        };
+        if !shares.iter().all(is_compatible) {
+            return Err(Slip39Error::Shares(
+                "All shares must belong to the same set (identifier, iteration exponent, group threshold and count)"
+                    .to_string(),
+            ));
+        }
+
+        // Regroupe les shares par groupe (en ignorant les doublons exacts).
+        let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+        for share in &shares {
+            let members = groups.entry(share.group_index).or_default();
+            if !members.contains(&share) {
+                members.push(share);
+            }
+        }
+
+        // Comme l'implémentation de référence, exactement le nombre de groupes et de shares requis.
+        if groups.len() != first.group_threshold as usize {
+            return Err(Slip39Error::Shares(format!(
+                "Wrong number of groups: {} provided, {} required",
+                groups.len(),
+                first.group_threshold
+            )));
+        }
+
+        let mut group_secrets = Vec::new();
+        for (group_index, members) in &groups {
+            let member_threshold = members[0].member_threshold;
+            if members
+                .iter()
+                .any(|m| m.member_threshold != member_threshold)
+            {
+                return Err(Slip39Error::Shares(format!(
+                    "Shares of group {} have different member thresholds",
+                    group_index + 1
+                )));
+            }
+            if members.len() != member_threshold as usize {
+                return Err(Slip39Error::Shares(format!(
+                    "Wrong number of shares in group {}: {} provided, {} required",
+                    group_index + 1,
+                    members.len(),
+                    member_threshold
+                )));
+            }
+
+            let member_shares: Vec<(u8, Vec<u8>)> = members
+                .iter()
+                .map(|m| (m.member_index, m.value.clone()))
+                .collect();
+            group_secrets.push((
+                *group_index,
+                shamir::recover_secret(member_threshold, &member_shares)?,
+            ));
+        }
+
+        let encrypted_master_secret =
+            shamir::recover_secret(first.group_threshold, &group_secrets)?;
+
+        Ok(cipher::decrypt(
+            &encrypted_master_secret,
+            passphrase.as_bytes(),
+            first.iteration_exponent,
+            first.identifier,
+            first.extendable,
+        ))
+    }
+
+    /// La passphrase SLIP-39 ne peut contenir que des caractères ASCII imprimables.
+    fn validate_passphrase(passphrase: &str) -> Result<(), Slip39Error> {
+        if passphrase.bytes().all(|b| (32..=126).contains(&b)) {
+            Ok(())
+        } else {
+            Err(Slip39Error::Parameters(
+                "The passphrase must only contain printable ASCII characters".to_string(),
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bip32::{Prefix, XPrv};
+
+    // Une sélection des vecteurs de test officiels SLIP-39, copiés tels quels depuis vectors.json
+    // (https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json) : le fichier complet peut
+    // remplacer celui-ci sans modifier le test.
+    // [description, shares, secret maître en hexadécimal (vide si les shares sont invalides), xprv BIP32].
+    // Passphrase : "TREZOR".
+    const VECTORS: &str = include_str!("vectors.json");
+
+    /// Une modification de l'en-tête d'une share.
+    type Modify = fn(&mut Share);
+
+    const SHADOW_1: &str = "shadow pistol academic always adequate wildlife fancy gross oasis cylinder mustang wrist rescue view short owner flip making coding armed";
+    const SHADOW_2: &str = "shadow pistol academic acid actress prayer class unknown daughter sweater depict flip twice unkind craft early superior advocate guest smoking";
+    /// Les shares du vecteur "Threshold number of groups and members in each group (128 bits, case 1)" :
+    /// deux groupes requis, les deux premières shares dans l'un, les trois suivantes dans l'autre.
+    const ERASER: [&str; 5] = [
+        "eraser senior decision roster beard treat identify grumpy salt index fake aviation theater cubic bike cause research dragon emphasis counter",
+        "eraser senior decision smug corner ruin rescue cubic angel tackle skin skunk program roster trash rumor slush angel flea amazing",
+        "eraser senior ceramic snake clay various huge numb argue hesitate auction category timber browser greatest hanger petition script leaf pickup",
+        "eraser senior ceramic shaft dynamic become junior wrist silver peasant force math alto coal amazing segment yelp velvet image paces",
+        "eraser senior ceramic round column hawk trust auction smug shame alive greatest sheriff living perfect corner chest sled fumes adequate",
+    ];
+
+    fn combine_hex(mnemonics: &[&str]) -> Result<String, Slip39Error> {
+        let mnemonics: Vec<String> = mnemonics.iter().map(|m| m.to_string()).collect();
+        Slip39::combine(&mnemonics, "TREZOR")
+            .map(|secret| secret.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Une share modifiée puis encodée à nouveau (avec un checksum valide).
+    fn modified(mnemonic: &str, modify: impl FnOnce(&mut Share)) -> String {
+        let mut share = Share::from_mnemonic(mnemonic).unwrap();
+        modify(&mut share);
+        share.to_mnemonic()
+    }
+
+    #[test]
+    fn test_vectors() {
+        let vectors: Vec<(String, Vec<String>, String, String)> =
+            serde_json::from_str(VECTORS).unwrap();
+        assert!(!vectors.is_empty());
+
+        for (description, mnemonics, master_secret, xprv) in vectors {
+            let mnemonics: Vec<&str> = mnemonics.iter().map(String::as_str).collect();
+            let result = combine_hex(&mnemonics);
+            if master_secret.is_empty() {
+                assert!(result.is_err(), "{}: invalid shares accepted", description);
+            } else {
+                assert_eq!(result, Ok(master_secret.clone()), "{}", description);
+                // Le secret maître est la graine BIP32 du wallet.
+                let seed: Vec<u8> = (0..master_secret.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(&master_secret[i..i + 2], 16).unwrap())
+                    .collect();
+                let master_xprv = XPrv::new(seed).unwrap().to_string(Prefix::XPRV);
+                assert_eq!(*master_xprv, xprv, "{}", description);
+            }
+        }
+    }
+
+    #[test]
+    fn test_vector_invalid_checksum_128_bits() {
+        let result = combine_hex(&["duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision kidney"]);
+        assert_eq!(result, Err(Slip39Error::Checksum));
+    }
+
+    #[test]
+    fn test_vector_invalid_padding_128_bits() {
+        let result = combine_hex(&["duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"]);
+        assert_eq!(result, Err(Slip39Error::Padding));
+    }
+
+    #[test]
+    fn test_vector_basic_sharing_2_of_3_insufficient_shares() {
+        let result = combine_hex(&[SHADOW_1]);
+        assert!(matches!(result, Err(Slip39Error::Shares(_))));
+    }
+
+    #[test]
+    fn test_vector_mnemonics_with_different_identifiers() {
+        let result = combine_hex(&[
+            "adequate smoking academic acid debut wine petition glen cluster slow rhyme slow simple epidemic rumor junk tracks treat olympic tolerate",
+            "adequate stay academic agency agency formal party ting frequent learn upstairs remember smear leaf damage anatomy ladle market hush corner",
+        ]);
+        assert!(matches!(result, Err(Slip39Error::Shares(_))));
+    }
+
+    // Les cas invalides des vecteurs officiels, construits à partir des shares valides : seul le champ testé
+    // diffère, le checksum est valide.
+
+    #[test]
+    fn test_invalid_share_headers() {
+        let cases: [(&str, Modify); 4] = [
+            ("different iteration exponents", |share| {
+                share.iteration_exponent += 1
+            }),
+            ("duplicate member indices", |share| share.member_index = 2),
+            ("mismatching member thresholds", |share| {
+                share.member_threshold = 3
+            }),
+            ("mismatching group counts", |share| share.group_count = 2),
+        ];
+        assert_eq!(Share::from_mnemonic(SHADOW_1).unwrap().member_index, 2);
+
+        for (description, modify) in cases {
+            let result = combine_hex(&[SHADOW_1, &modified(SHADOW_2, modify)]);
+            assert!(
+                matches!(result, Err(Slip39Error::Shares(_))),
+                "{}: {:?}",
+                description,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_group_thresholds() {
+        // Des seuils de groupe différents.
+        let first = Share::from_mnemonic(ERASER[0]).unwrap();
+        assert_eq!((first.group_threshold, first.group_count), (2, 4));
+        let last = modified(ERASER[4], |share| share.group_threshold = 3);
+        let result = combine_hex(&[ERASER[0], ERASER[1], ERASER[2], ERASER[3], &last]);
+        assert!(matches!(result, Err(Slip39Error::Shares(_))));
+
+        // Un seuil de groupe supérieur au nombre de groupes.
+        let share = modified(SHADOW_2, |share| share.group_threshold = 2);
+        assert!(matches!(
+            combine_hex(&[&share]),
+            Err(Slip39Error::Mnemonic(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_digest() {
+        let share = modified(SHADOW_2, |share| share.value[0] ^= 1);
+        assert_eq!(combine_hex(&[SHADOW_1, &share]), Err(Slip39Error::Digest));
+    }
+
+    #[test]
+    fn test_insufficient_groups_and_members() {
+        // Un seul groupe complet.
+        assert!(matches!(
+            combine_hex(&ERASER[2..]),
+            Err(Slip39Error::Shares(_))
+        ));
+        assert!(matches!(
+            combine_hex(&[ERASER[0], ERASER[2], ERASER[3], ERASER[4]]),
+            Err(Slip39Error::Shares(_))
+        ));
+        // Deux groupes, mais un membre manquant dans l'un d'eux.
+        assert!(matches!(
+            combine_hex(&ERASER[..4]),
+            Err(Slip39Error::Shares(_))
+        ));
+        // Les shares de chaque groupe, dans un autre ordre.
+        assert_eq!(
+            combine_hex(&[ERASER[4], ERASER[1], ERASER[3], ERASER[0], ERASER[2]]).unwrap(),
+            "7c3397a292a5941682d7a4ae2d898d11"
+        );
+    }
+
+    #[test]
+    fn test_split_and_combine_with_groups() {
+        let master_secret: Vec<u8> = (0..16).collect();
+        let groups = [
+            GroupSpec {
+                member_threshold: 1,
+                member_count: 1,
+            },
+            GroupSpec {
+                member_threshold: 2,
+                member_count: 3,
+            },
+            GroupSpec {
+                member_threshold: 3,
+                member_count: 5,
+            },
+        ];
+
+        let shares = Slip39::split(&master_secret, "TREZOR", 2, &groups, 0, true).unwrap();
+        assert_eq!(shares.iter().map(Vec::len).collect::<Vec<_>>(), [1, 3, 5]);
+
+        // Groupe 2 (2 shares sur 3) + groupe 3 (3 shares sur 5).
+        let selection = vec![
+            shares[1][0].clone(),
+            shares[1][2].clone(),
+            shares[2][1].clone(),
+            shares[2][3].clone(),
+            shares[2][4].clone(),
+        ];
+        assert_eq!(
+            Slip39::combine(&selection, "TREZOR").unwrap(),
+            master_secret
+        );
+
+        // Un seul groupe complet : insuffisant.
+        assert!(Slip39::combine(&selection[..2], "TREZOR").is_err());
+
+        // Comme l'implémentation de référence, une share ou un groupe en trop est refusé.
+        let mut extra_member = selection.clone();
+        extra_member.push(shares[2][0].clone());
+        assert!(matches!(
+            Slip39::combine(&extra_member, "TREZOR"),
+            Err(Slip39Error::Shares(message)) if message.starts_with("Wrong number of shares in group 3")
+        ));
+        let mut extra_group = selection.clone();
+        extra_group.push(shares[0][0].clone());
+        assert!(matches!(
+            Slip39::combine(&extra_group, "TREZOR"),
+            Err(Slip39Error::Shares(message)) if message.starts_with("Wrong number of groups: 3 provided, 2 required")
+        ));
+    }
+}
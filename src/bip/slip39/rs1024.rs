@@ -0,0 +1,51 @@
+/// Générateurs du code de Reed-Solomon sur GF(1024) utilisé comme checksum par SLIP-39.
+const GENERATORS: [u32; 10] = [
+    0xE0E040, 0x1C1C080, 0x3838100, 0x7070200, 0xE0E0009, 0x1C0C2412, 0x38086C24, 0x3090FC48,
+    0x21B1F890, 0x3F3F120,
+];
+
+/// Nombre de mots (de 10 bits) du checksum.
+pub const CHECKSUM_LENGTH_WORDS: usize = 3;
+
+fn polymod(values: impl Iterator<Item = u32>) -> u32 {
+    let mut checksum: u32 = 1;
+    for value in values {
+        let top = checksum >> 20;
+        checksum = ((checksum & 0xFFFFF) << 10) ^ value;
+        for (i, generator) in GENERATORS.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Calcule les 3 mots de checksum à ajouter aux données.
+///
+/// # Arguments:
+/// - customization - La chaîne de personnalisation ("shamir" ou "shamir_extendable").
+/// - data - Les indices des mots de la share (sans checksum).
+pub fn create_checksum(customization: &[u8], data: &[u16]) -> [u16; CHECKSUM_LENGTH_WORDS] {
+    let values = customization
+        .iter()
+        .map(|b| *b as u32)
+        .chain(data.iter().map(|w| *w as u32))
+        .chain([0; CHECKSUM_LENGTH_WORDS]);
+    let checksum = polymod(values) ^ 1;
+
+    [
+        ((checksum >> 20) & 1023) as u16,
+        ((checksum >> 10) & 1023) as u16,
+        (checksum & 1023) as u16,
+    ]
+}
+
+/// Vérifie le checksum d'une share (les 3 derniers mots de "data").
+pub fn verify_checksum(customization: &[u8], data: &[u16]) -> bool {
+    let values = customization
+        .iter()
+        .map(|b| *b as u32)
+        .chain(data.iter().map(|w| *w as u32));
+    polymod(values) == 1
+}
@@ -0,0 +1,161 @@
+use super::Slip39Error;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+
+/// Abscisse de la share contenant le secret partagé.
+const SECRET_INDEX: u8 = 255;
+/// Abscisse de la share contenant le digest (permettant de vérifier le secret reconstitué).
+const DIGEST_INDEX: u8 = 254;
+/// Longueur du digest (en octets).
+const DIGEST_LENGTH_BYTES: usize = 4;
+
+/// Tables d'exponentielles et de logarithmes de GF(256) (polynôme de Rijndael x^8 + x^4 + x^3 + x + 1, générateur 3).
+struct Gf256 {
+    exp: [u8; 255],
+    log: [u8; 256],
+}
+
+impl Gf256 {
+    fn new() -> Self {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut poly: u16 = 1;
+
+        for (i, value) in exp.iter_mut().enumerate() {
+            *value = poly as u8;
+            log[poly as usize] = i as u8;
+
+            // Multiplie par le générateur 3 : poly * (x + 1).
+            poly = (poly << 1) ^ poly;
+            if poly & 0x100 != 0 {
+                poly ^= 0x11B;
+            }
+        }
+
+        Self { exp, log }
+    }
+}
+
+/// Évalue en "x" le polynôme d'interpolation de Lagrange passant par les shares données.
+pub fn interpolate(shares: &[(u8, Vec<u8>)], x: u8) -> Result<Vec<u8>, Slip39Error> {
+    let mut x_coordinates: Vec<u8> = shares.iter().map(|(index, _)| *index).collect();
+    x_coordinates.sort_unstable();
+    x_coordinates.dedup();
+    if x_coordinates.len() != shares.len() {
+        return Err(Slip39Error::Shares(
+            "Share indices must be unique".to_string(),
+        ));
+    }
+
+    let length = shares.first().map_or(0, |(_, value)| value.len());
+    if shares.iter().any(|(_, value)| value.len() != length) {
+        return Err(Slip39Error::Shares(
+            "All share values must have the same length".to_string(),
+        ));
+    }
+
+    if let Some((_, value)) = shares.iter().find(|(index, _)| *index == x) {
+        return Ok(value.clone());
+    }
+
+    let gf = Gf256::new();
+    let log = |value: u8| gf.log[value as usize] as i32;
+
+    // Les calculs se font dans le domaine logarithmique (modulo 255).
+    let log_prod: i32 = shares.iter().map(|(index, _)| log(index ^ x)).sum();
+    let mut result = vec![0u8; length];
+
+    for (index, value) in shares {
+        let log_basis_eval = (log_prod
+            - log(index ^ x)
+            - shares
+                .iter()
+                .map(|(other, _)| log(index ^ other))
+                .sum::<i32>())
+        .rem_euclid(255);
+
+        for (byte, share_byte) in result.iter_mut().zip(value) {
+            if *share_byte != 0 {
+                *byte ^= gf.exp[((log(*share_byte) + log_basis_eval) % 255) as usize];
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Calcule le digest (4 octets) protégeant le secret partagé.
+fn create_digest(random_data: &[u8], shared_secret: &[u8]) -> Vec<u8> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(random_data).expect("HMAC accepts keys of any length");
+    mac.update(shared_secret);
+    mac.finalize().into_bytes()[..DIGEST_LENGTH_BYTES].to_vec()
+}
+
+/// Partage un secret en "share_count" shares, dont "threshold" suffisent à le reconstituer.
+pub fn split_secret(
+    threshold: u8,
+    share_count: u8,
+    shared_secret: &[u8],
+) -> Result<Vec<(u8, Vec<u8>)>, Slip39Error> {
+    if threshold < 1 || threshold > share_count || share_count > 16 {
+        return Err(Slip39Error::Parameters(format!(
+            "Invalid threshold {} for {} shares",
+            threshold, share_count
+        )));
+    }
+
+    // Avec un seuil de 1, chaque share est une copie du secret.
+    if threshold == 1 {
+        return Ok((0..share_count)
+            .map(|index| (index, shared_secret.to_vec()))
+            .collect());
+    }
+
+    let mut rng = rand::rngs::OsRng;
+    let random_share_count = threshold - 2;
+
+    let mut shares: Vec<(u8, Vec<u8>)> = (0..random_share_count)
+        .map(|index| {
+            let mut value = vec![0u8; shared_secret.len()];
+            rng.fill_bytes(&mut value);
+            (index, value)
+        })
+        .collect();
+
+    let mut random_part = vec![0u8; shared_secret.len() - DIGEST_LENGTH_BYTES];
+    rng.fill_bytes(&mut random_part);
+    let mut digest_share = create_digest(&random_part, shared_secret);
+    digest_share.extend_from_slice(&random_part);
+
+    let mut base_shares = shares.clone();
+    base_shares.push((DIGEST_INDEX, digest_share));
+    base_shares.push((SECRET_INDEX, shared_secret.to_vec()));
+
+    for index in random_share_count..share_count {
+        shares.push((index, interpolate(&base_shares, index)?));
+    }
+
+    Ok(shares)
+}
+
+/// Reconstitue le secret à partir de "threshold" shares, en vérifiant son digest.
+pub fn recover_secret(threshold: u8, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, Slip39Error> {
+    if threshold == 1 {
+        return shares
+            .first()
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| Slip39Error::Shares("No share given".to_string()));
+    }
+
+    let shared_secret = interpolate(shares, SECRET_INDEX)?;
+    let digest_share = interpolate(shares, DIGEST_INDEX)?;
+    let (digest, random_part) = digest_share.split_at(DIGEST_LENGTH_BYTES);
+
+    if digest != create_digest(random_part, &shared_secret).as_slice() {
+        return Err(Slip39Error::Digest);
+    }
+
+    Ok(shared_secret)
+}
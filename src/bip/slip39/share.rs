@@ -0,0 +1,159 @@
+use super::rs1024::{self, CHECKSUM_LENGTH_WORDS};
+use super::Slip39Error;
+
+/// La liste des 1024 mots SLIP-39 (un mot par ligne, triés par ordre alphabétique).
+const WORDLIST: &str = include_str!("wordlist.txt");
+
+/// Nombre de bits encodés par un mot.
+const RADIX_BITS: usize = 10;
+/// Nombre de mots de l'en-tête (identifiant, exposant, indices et seuils).
+const HEADER_LENGTH_WORDS: usize = 4;
+/// Nombre minimal de mots d'une share (secret maître de 128 bits).
+const MIN_MNEMONIC_LENGTH_WORDS: usize = 20;
+
+/// Une share SLIP-39 décodée.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Share {
+    pub identifier: u16,
+    pub extendable: bool,
+    pub iteration_exponent: u8,
+    pub group_index: u8,
+    pub group_threshold: u8,
+    pub group_count: u8,
+    pub member_index: u8,
+    pub member_threshold: u8,
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    /// La chaîne de personnalisation du checksum dépend du drapeau "extendable".
+    pub fn customization(extendable: bool) -> &'static [u8] {
+        if extendable {
+            b"shamir_extendable"
+        } else {
+            b"shamir"
+        }
+    }
+
+    /// Encode la share en phrase mnémonique.
+    pub fn to_mnemonic(&self) -> String {
+        let words: Vec<&str> = WORDLIST.lines().collect();
+
+        let id_exp = ((self.identifier as u32) << 5)
+            | ((self.extendable as u32) << 4)
+            | self.iteration_exponent as u32;
+        let indices_and_thresholds = ((self.group_index as u32) << 16)
+            | (((self.group_threshold - 1) as u32) << 12)
+            | (((self.group_count - 1) as u32) << 8)
+            | ((self.member_index as u32) << 4)
+            | (self.member_threshold - 1) as u32;
+
+        let mut data = vec![
+            (id_exp >> 10) as u16,
+            (id_exp & 1023) as u16,
+            (indices_and_thresholds >> 10) as u16,
+            (indices_and_thresholds & 1023) as u16,
+        ];
+        data.extend(Self::bytes_to_words(&self.value));
+
+        let checksum = rs1024::create_checksum(Self::customization(self.extendable), &data);
+        data.extend_from_slice(&checksum);
+
+        data.iter()
+            .map(|index| words[*index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Décode une phrase mnémonique en share, en vérifiant son checksum et son padding.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, Slip39Error> {
+        let data = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                WORDLIST
+                    .lines()
+                    .position(|w| w == word)
+                    .map(|index| index as u16)
+                    .ok_or_else(|| Slip39Error::Mnemonic(format!("Unknown word '{}'", word)))
+            })
+            .collect::<Result<Vec<u16>, _>>()?;
+
+        if data.len() < MIN_MNEMONIC_LENGTH_WORDS {
+            return Err(Slip39Error::Mnemonic(format!(
+                "A share must contain at least {} words",
+                MIN_MNEMONIC_LENGTH_WORDS
+            )));
+        }
+
+        let value_words = &data[HEADER_LENGTH_WORDS..data.len() - CHECKSUM_LENGTH_WORDS];
+        let padding_bits = (RADIX_BITS * value_words.len()) % 16;
+        if padding_bits > 8 {
+            return Err(Slip39Error::Mnemonic("Invalid share length".to_string()));
+        }
+
+        let id_exp = ((data[0] as u32) << 10) | data[1] as u32;
+        let extendable = (id_exp >> 4) & 1 == 1;
+        if !rs1024::verify_checksum(Self::customization(extendable), &data) {
+            return Err(Slip39Error::Checksum);
+        }
+
+        let indices_and_thresholds = ((data[2] as u32) << 10) | data[3] as u32;
+        let group_threshold = ((indices_and_thresholds >> 12) & 15) as u8 + 1;
+        let group_count = ((indices_and_thresholds >> 8) & 15) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(Slip39Error::Mnemonic(
+                "Group threshold cannot be greater than the group count".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            identifier: (id_exp >> 5) as u16,
+            extendable,
+            iteration_exponent: (id_exp & 15) as u8,
+            group_index: (indices_and_thresholds >> 16) as u8,
+            group_threshold,
+            group_count,
+            member_index: ((indices_and_thresholds >> 4) & 15) as u8,
+            member_threshold: (indices_and_thresholds & 15) as u8 + 1,
+            value: Self::words_to_bytes(value_words, padding_bits)?,
+        })
+    }
+
+    /// Convertit des octets en mots de 10 bits (complétés à gauche par des bits à zéro).
+    fn bytes_to_words(bytes: &[u8]) -> Vec<u16> {
+        let word_count = (bytes.len() * 8).div_ceil(RADIX_BITS);
+        let padding_bits = word_count * RADIX_BITS - bytes.len() * 8;
+
+        let bits = vec![false; padding_bits]
+            .into_iter()
+            .chain(
+                bytes
+                    .iter()
+                    .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1)),
+            )
+            .collect::<Vec<bool>>();
+
+        bits.chunks(RADIX_BITS)
+            .map(|chunk| chunk.iter().fold(0u16, |acc, bit| (acc << 1) | *bit as u16))
+            .collect()
+    }
+
+    /// Convertit des mots de 10 bits en octets, en vérifiant que les bits de padding sont à zéro.
+    fn words_to_bytes(words: &[u16], padding_bits: usize) -> Result<Vec<u8>, Slip39Error> {
+        let bits = words
+            .iter()
+            .flat_map(|word| (0..RADIX_BITS).rev().map(move |i| (word >> i) & 1 == 1))
+            .collect::<Vec<bool>>();
+
+        let (padding, value) = bits.split_at(padding_bits);
+        if padding.iter().any(|bit| *bit) {
+            return Err(Slip39Error::Padding);
+        }
+
+        Ok(value
+            .chunks(8)
+            .map(|chunk| chunk.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8))
+            .collect())
+    }
+}
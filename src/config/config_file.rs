@@ -0,0 +1,273 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Nom du dossier de l'application dans le dossier de configuration XDG.
+const APP_DIRECTORY: &str = "rust-solana-wallet";
+/// Nom du fichier de configuration.
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Politique de frais appliquée aux transactions (frais de priorité).
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeePolicy {
+    /// Prix d'une unité de calcul, en micro-lamports (0 = pas de frais de priorité).
+    #[serde(default)]
+    pub compute_unit_price: u64,
+    /// Nombre maximal d'unités de calcul de la transaction (limite par défaut du réseau si absent).
+    pub compute_unit_limit: Option<u32>,
+}
+
+/// Politique d'envoi des transactions : attente de la confirmation et renvoi après expiration du blockhash.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetryPolicy {
+    /// Nombre maximal de renvois (transaction signée à nouveau avec un nouveau blockhash) lorsque le blockhash expire
+    /// avant que la transaction n'arrive dans un bloc (0 = pas de renvoi).
+    pub max_resends: u32,
+    /// Délai maximal d'attente de la confirmation, en secondes.
+    pub confirm_timeout: u64,
+    /// Nombre de nouvelles tentatives d'une requête RPC sur le même point d'accès (erreurs 429, 5xx ou de connexion),
+    /// avant de passer au point d'accès suivant.
+    pub rpc_retries: u32,
+    /// Délai avant la première nouvelle tentative, en millisecondes (doublé à chaque tentative).
+    pub rpc_backoff_ms: u64,
+    /// Retard maximal (en slots) d'un point d'accès sur le plus avancé, au-delà duquel il n'est utilisé qu'en dernier recours.
+    pub max_slot_lag: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_resends: 3,
+            confirm_timeout: 90,
+            rpc_retries: 2,
+            rpc_backoff_ms: 250,
+            max_slot_lag: 150,
+        }
+    }
+}
+
+/// Plafonds de dépense d'une clé, en lamports.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpendingLimits {
+    pub max_per_transaction: Option<u64>,
+    pub max_daily: Option<u64>,
+}
+
+/// Politique de dépense, évaluée avant toute signature (voir `SolanaPolicy`).
+/// Les montants sont en lamports ; les listes contiennent des clés publiques, noms de contacts ou labels du key store.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpendingPolicy {
+    /// Montant maximal de SOL envoyé par transaction.
+    pub max_per_transaction: Option<u64>,
+    /// Montant maximal de SOL envoyé par une clé sur 24 heures glissantes.
+    pub max_daily: Option<u64>,
+    /// Au-delà de ce montant, l'envoi doit être confirmé en saisissant le montant.
+    pub confirm_above: Option<u64>,
+    /// Les seuls destinataires autorisés (tous si la liste est vide).
+    pub allowlist: Vec<String>,
+    /// Les destinataires interdits.
+    pub denylist: Vec<String>,
+    /// Sur le mainnet : destinataires limités à la liste autorisée, aux contacts et aux clés du key store,
+    /// et confirmation saisie de chaque envoi.
+    pub mainnet_strict: bool,
+    /// Plafonds propres à une clé (indexés par label ou clé publique), prioritaires sur les plafonds généraux.
+    pub keys: BTreeMap<String, SpendingLimits>,
+}
+
+impl SpendingPolicy {
+    /// Vrai si la politique ne contient aucune règle.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Un profil nommé (par exemple "devnet", "mainnet" ou "local").
+/// Les valeurs absentes sont reprises des variables d'environnement ou des valeurs par défaut.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub rpc_url: Option<String>,
+    /// Les points d'accès RPC, essayés dans l'ordre (après "rpc_url" s'il est défini).
+    pub rpc_urls: Option<Vec<String>>,
+    pub commitment: Option<String>,
+    pub keystore_path: Option<String>,
+    pub derivation_template: Option<String>,
+    pub explorer_template: Option<String>,
+    pub fee_policy: Option<FeePolicy>,
+    pub retry_policy: Option<RetryPolicy>,
+    pub spending_policy: Option<SpendingPolicy>,
+}
+
+/// Le fichier de configuration TOML :
+///
+/// ```toml
+/// default_profile = "devnet"
+///
+/// [profiles.devnet]
+/// rpc_urls = ["https://api.devnet.solana.com", "https://devnet.helius-rpc.com/?api-key=KEY"]
+/// commitment = "confirmed"
+///
+/// [profiles.mainnet]
+/// rpc_url = "https://api.mainnet-beta.solana.com"
+/// keystore_path = "~/wallets/mainnet/keystore.json"
+/// fee_policy = { compute_unit_price = 5000, compute_unit_limit = 200000 }
+///
+/// [profiles.mainnet.spending_policy]
+/// max_per_transaction = 1000000000
+/// max_daily = 5000000000
+/// confirm_above = 100000000
+/// denylist = ["DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2"]
+/// mainnet_strict = true
+/// keys.treasury = { max_daily = 20000000000 }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub default_profile: Option<String>,
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+impl ConfigFile {
+    /// Retourne le chemin du fichier de configuration : la variable d'environnement `WALLET_CONFIG` si elle est définie,
+    /// sinon "$XDG_CONFIG_HOME/rust-solana-wallet/config.toml" (ou "~/.config/rust-solana-wallet/config.toml").
+    pub fn path() -> Option<PathBuf> {
+        if let Ok(path) = env::var("WALLET_CONFIG") {
+            return Some(PathBuf::from(expand_home(&path)));
+        }
+
+        let config_home = env::var("XDG_CONFIG_HOME")
+            .ok()
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var("HOME")
+                    .ok()
+                    .map(|home| Path::new(&home).join(".config"))
+            })?;
+
+        Some(config_home.join(APP_DIRECTORY).join(CONFIG_FILE_NAME))
+    }
+
+    /// Charge le fichier de configuration.
+    /// Retourne une configuration vide si le fichier n'existe pas.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&content)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))
+    }
+}
+
+/// Remplace un "~" en tête de chemin par le dossier personnel (variable d'environnement `HOME`), comme le ferait un
+/// shell : les chemins des fichiers .env et de configuration ne passent pas par un shell.
+pub fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => home + rest,
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let config: ConfigFile = toml::from_str(
+            r#"
+            default_profile = "devnet"
+
+            [profiles.devnet]
+            rpc_url = "https://api.devnet.solana.com"
+            commitment = "confirmed"
+
+            [profiles.mainnet]
+            keystore_path = "./mainnet/keystore.json"
+            derivation_template = "m/44'/501'/{index}'/0'"
+            explorer_template = "https://solscan.io/tx/{signature}"
+            fee_policy = { compute_unit_price = 5000 }
+            retry_policy = { max_resends = 1 }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.default_profile.as_deref(), Some("devnet"));
+        assert_eq!(
+            config.profiles["devnet"].commitment.as_deref(),
+            Some("confirmed")
+        );
+        assert_eq!(config.profiles["mainnet"].rpc_url, None);
+        assert_eq!(
+            config.profiles["mainnet"].explorer_template.as_deref(),
+            Some("https://solscan.io/tx/{signature}")
+        );
+        assert_eq!(
+            config.profiles["mainnet"].fee_policy,
+            Some(FeePolicy {
+                compute_unit_price: 5000,
+                compute_unit_limit: None
+            })
+        );
+        assert_eq!(
+            config.profiles["mainnet"].retry_policy,
+            Some(RetryPolicy {
+                max_resends: 1,
+                ..RetryPolicy::default()
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_spending_policy() {
+        let config: ConfigFile = toml::from_str(
+            r#"
+            [profiles.mainnet.spending_policy]
+            max_daily = 5000000000
+            allowlist = ["alice"]
+            keys.treasury = { max_per_transaction = 1000 }
+            "#,
+        )
+        .unwrap();
+
+        let policy = config.profiles["mainnet"].spending_policy.clone().unwrap();
+        assert_eq!(policy.max_daily, Some(5_000_000_000));
+        assert_eq!(policy.max_per_transaction, None);
+        assert_eq!(policy.allowlist, vec!["alice".to_string()]);
+        assert_eq!(policy.keys["treasury"].max_per_transaction, Some(1000));
+        assert!(!policy.is_empty());
+        assert!(SpendingPolicy::default().is_empty());
+    }
+
+    #[test]
+    fn test_reject_unknown_keys() {
+        let result = toml::from_str::<ConfigFile>("[profiles.devnet]\nrpc = \"http://x\"\n");
+        assert!(result.is_err());
+        let result = toml::from_str::<ConfigFile>(
+            "[profiles.devnet]\nfee_policy = { compute_unit_prices = 5000 }\n",
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_expand_home() {
+        let home = env::var("HOME").unwrap();
+        assert_eq!(expand_home("~"), home);
+        assert_eq!(
+            expand_home("~/wallets/id.json"),
+            format!("{}/wallets/id.json", home)
+        );
+        assert_eq!(expand_home("./storage/~/id.json"), "./storage/~/id.json");
+        assert_eq!(expand_home("~other/id.json"), "~other/id.json");
+    }
+}
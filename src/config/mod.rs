@@ -1 +1,3 @@
+pub mod config_file;
+pub mod solana_cli_config;
 pub mod wallet_config;
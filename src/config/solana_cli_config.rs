@@ -0,0 +1,117 @@
+use crate::config::config_file::expand_home;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// URL RPC des clusters Solana, désignés par leur nom ou leur initiale (comme avec `solana -u`).
+const CLUSTER_MONIKERS: [(&str, &str, &str); 4] = [
+    ("m", "mainnet-beta", "https://api.mainnet-beta.solana.com"),
+    ("d", "devnet", "https://api.devnet.solana.com"),
+    ("t", "testnet", "https://api.testnet.solana.com"),
+    ("l", "localhost", "http://localhost:8899"),
+];
+
+/// Le fichier de configuration de la CLI Solana ("~/.config/solana/cli/config.yml").
+/// Seules les valeurs utiles au wallet sont lues, les autres clés ("websocket_url", "address_labels"...) sont ignorées.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+pub struct SolanaCliConfig {
+    pub json_rpc_url: Option<String>,
+    pub keypair_path: Option<String>,
+    pub commitment: Option<String>,
+}
+
+impl SolanaCliConfig {
+    /// Retourne le chemin du fichier de configuration de la CLI Solana : la variable d'environnement `SOLANA_CLI_CONFIG`
+    /// si elle est définie, sinon "~/.config/solana/cli/config.yml" (le chemin par défaut de la CLI Solana).
+    pub fn path() -> Option<PathBuf> {
+        if let Ok(path) = env::var("SOLANA_CLI_CONFIG") {
+            return Some(PathBuf::from(expand_home(&path)));
+        }
+
+        env::var("HOME").ok().map(|home| {
+            Path::new(&home)
+                .join(".config")
+                .join("solana")
+                .join("cli")
+                .join("config.yml")
+        })
+    }
+
+    /// Charge le fichier de configuration de la CLI Solana.
+    /// Retourne une configuration vide si le fichier n'existe pas.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+        serde_yaml::from_str(&content)
+            .map_err(|e| format!("Invalid Solana CLI config file {}: {}", path.display(), e))
+    }
+
+    /// Retourne l'URL RPC d'un cluster désigné par son nom ou son initiale ("m", "d", "t", "l"),
+    /// ou la valeur inchangée si ce n'est pas un cluster connu.
+    pub fn normalize_to_url_if_moniker(url_or_moniker: &str) -> String {
+        CLUSTER_MONIKERS
+            .iter()
+            .find(|(short, name, _)| url_or_moniker == *short || url_or_moniker == *name)
+            .map_or_else(|| url_or_moniker.to_string(), |(_, _, url)| url.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_solana_cli_config() {
+        let config: SolanaCliConfig = serde_yaml::from_str(
+            r#"
+---
+json_rpc_url: "https://api.testnet.solana.com"
+websocket_url: ""
+keypair_path: /home/user/.config/solana/id.json
+address_labels:
+  "11111111111111111111111111111111": System Program
+commitment: confirmed
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.json_rpc_url.as_deref(),
+            Some("https://api.testnet.solana.com")
+        );
+        assert_eq!(
+            config.keypair_path.as_deref(),
+            Some("/home/user/.config/solana/id.json")
+        );
+        assert_eq!(config.commitment.as_deref(), Some("confirmed"));
+    }
+
+    #[test]
+    fn test_normalize_to_url_if_moniker() {
+        assert_eq!(
+            SolanaCliConfig::normalize_to_url_if_moniker("m"),
+            "https://api.mainnet-beta.solana.com"
+        );
+        assert_eq!(
+            SolanaCliConfig::normalize_to_url_if_moniker("devnet"),
+            "https://api.devnet.solana.com"
+        );
+        assert_eq!(
+            SolanaCliConfig::normalize_to_url_if_moniker("t"),
+            "https://api.testnet.solana.com"
+        );
+        assert_eq!(
+            SolanaCliConfig::normalize_to_url_if_moniker("l"),
+            "http://localhost:8899"
+        );
+        assert_eq!(
+            SolanaCliConfig::normalize_to_url_if_moniker("https://rpc.example.com"),
+            "https://rpc.example.com"
+        );
+    }
+}
@@ -1,14 +1,107 @@
+use crate::app::output::{Output, OutputFormat};
+use crate::config::config_file::{
+    expand_home, ConfigFile, FeePolicy, Profile, RetryPolicy, SpendingPolicy,
+};
+use crate::config::solana_cli_config::SolanaCliConfig;
+use crate::solana::explorer::DEFAULT_EXPLORER_TEMPLATE;
+use solana_sdk::commitment_config::CommitmentConfig;
+use std::collections::BTreeMap;
 use std::env;
+use std::fmt;
+use std::str::FromStr;
 
 #[cfg(test)]
 use serial_test::serial;
 
+/// Le nombre maximal d'unités de calcul d'une transaction.
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Chemin de dérivation par défaut ("{index}" est remplacé par l'index de dérivation).
+pub const DEFAULT_DERIVATION_TEMPLATE: &str = "m/44'/501'/0'/0/{index}";
+
+/// Origine d'une valeur de la configuration (voir la commande "config show").
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigSource {
+    /// Option de la ligne de commande (par exemple "--url").
+    Flag(&'static str),
+    /// Variable d'environnement (éventuellement chargée depuis le fichier ".env").
+    Env(&'static str),
+    /// Profil du fichier de configuration.
+    Profile(String),
+    /// Fichier de configuration (hors profil, par exemple "default_profile").
+    File,
+    /// Fichier de configuration de la CLI Solana ("~/.config/solana/cli/config.yml").
+    SolanaCli,
+    Default,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Flag(flag) => write!(f, "flag {}", flag),
+            ConfigSource::Env(name) => write!(f, "env {}", name),
+            ConfigSource::Profile(name) => write!(f, "profile '{}'", name),
+            ConfigSource::File => write!(f, "config file"),
+            ConfigSource::SolanaCli => write!(f, "solana cli config"),
+            ConfigSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// La façon d'obtenir la confirmation saisie qu'exige la politique de dépense.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum PolicyConfirmation {
+    /// Demandée sur l'entrée standard (ligne de commande, mode interactif).
+    #[default]
+    Prompt,
+    /// Pas de saisie possible sur l'entrée standard (tableau de bord) : la transaction n'est pas signée et
+    /// l'erreur `PolicyViolation::ConfirmationRequired` indique la phrase à saisir.
+    Deferred,
+    /// Déjà saisie (dans le formulaire du tableau de bord).
+    Typed(String),
+}
+
+/// Valeurs données par les options globales de la ligne de commande (prioritaires sur tout le reste).
+#[derive(Clone, Debug, Default)]
+pub struct ConfigOverrides {
+    pub profile: Option<String>,
+    pub rpc_url: Option<String>,
+    pub commitment: Option<String>,
+    pub output: Option<String>,
+}
+
 #[derive(Clone)] // Cette ligne est utile pour implémenter automatiquement Clone.
 pub struct WalletConfig {
     pub keypair_path: String,
     pub keypair_derivations_path: String,
+    pub keystore_path: String,
+    pub address_book_path: String,
+    pub spending_ledger_path: String,
+    pub audit_log_path: String,
+    pub shell_history_path: String,
+    /// Délai d'inactivité (en secondes) après lequel la session du mode interactif est verrouillée (0 = jamais).
+    pub shell_idle_timeout: u64,
     pub nb_derivations: usize,
     pub rpc_url: String,
+    /// Tous les points d'accès RPC, dans l'ordre où ils sont essayés ("rpc_url" en premier).
+    pub rpc_urls: Vec<String>,
+    pub commitment: String,
+    pub derivation_template: String,
+    pub explorer_template: String,
+    pub fee_policy: FeePolicy,
+    pub retry_policy: RetryPolicy,
+    pub spending_policy: SpendingPolicy,
+    /// La confirmation de la politique de dépense (choisie par le tableau de bord, pas par la configuration).
+    pub policy_confirmation: PolicyConfirmation,
+    pub output: String,
+    /// Le profil sélectionné (s'il y en a un).
+    pub profile: Option<String>,
+    /// Le chemin du fichier de configuration.
+    pub config_path: Option<String>,
+    /// L'origine de chaque valeur, indexée par son nom.
+    pub sources: BTreeMap<&'static str, ConfigSource>,
+    /// Les valeurs qui n'ont pas pu être lues (signalées par `validate`).
+    invalid_values: Vec<String>,
 }
 
 impl Default for WalletConfig {
@@ -19,39 +112,524 @@ impl Default for WalletConfig {
 
 /// # Assignments:
 ///
+/// Chaque valeur est résolue dans cet ordre de priorité : option de la ligne de commande, variable d'environnement,
+/// profil du fichier de configuration, fichier de configuration de la CLI Solana ("json_rpc_url", "keypair_path"
+/// et "commitment"), puis valeur par défaut.
+///
 /// - nb_derivations:
 ///   Récupère le nombre de dérivations à effectuer à partir de la variable d'environnement `NB_DERIVATIONS`.
 ///   Retourne 1 par défaut si la variable n'est pas définie ou si sa valeur n'est pas un entier valide.
 ///
 /// - keypair_path:
-///   Récupère le chemin d'accès au fichier où la paire de clés principale est stockée à partir de la variable d'environnement `KEYPAIR_PATH`.
-///   Retourne un chemin par défaut si la variable d'environnement n'est pas définie.
+///   Récupère le chemin d'accès au fichier où la paire de clés principale est stockée à partir de la variable d'environnement `KEYPAIR_PATH`
+///   ou du "keypair_path" de la CLI Solana. Retourne un chemin par défaut sinon.
+///   La paire de clés de la CLI Solana ne sert qu'à signer : elle n'est jamais réécrite (voir "generate_seed").
 ///
 /// - keypair_derivations_path:
 ///   Récupère le chemin d'accès au dossier où les paires de clés dérivées sont stockées, spécifié par la variable d'environnement `KEYPAIR_DERIVATIONS_PATH`.
 ///   Retourne un chemin par défaut si la variable d'environnement n'est pas définie.
 ///
+/// - keystore_path:
+///   Récupère le chemin d'accès au manifeste du key store (labels, sources et clés publiques des paires de clés gérées),
+///   spécifié par la variable d'environnement `KEYSTORE_PATH` ou par le profil.
+///   Retourne un chemin par défaut sinon.
+///
+/// - address_book_path:
+///   Récupère le chemin d'accès au fichier du carnet d'adresses (contacts et destinataires connus),
+///   spécifié par la variable d'environnement `ADDRESS_BOOK_PATH`.
+///   Retourne un chemin par défaut si la variable d'environnement n'est pas définie.
+///
+/// - spending_ledger_path:
+///   Le chemin du journal des dépenses des dernières 24 heures (plafond journalier de la politique de dépense),
+///   spécifié par la variable d'environnement `SPENDING_LEDGER_PATH`. Retourne "./storage/spending_ledger.json" par défaut.
+///
+/// - audit_log_path:
+///   Le chemin du journal d'audit des opérations du wallet (JSON Lines chaîné par hachage),
+///   spécifié par la variable d'environnement `AUDIT_LOG_PATH`. Retourne "./storage/audit_log.jsonl" par défaut.
+///
+/// - shell_history_path:
+///   Le chemin du fichier de l'historique du mode interactif ("shell"), spécifié par la variable d'environnement `SHELL_HISTORY_PATH`.
+///   Retourne "./storage/shell_history" par défaut.
+///
+/// - shell_idle_timeout:
+///   Le délai d'inactivité (en secondes) après lequel la session du mode interactif est verrouillée
+///   (variable d'environnement `SHELL_IDLE_TIMEOUT`). Retourne 300 secondes par défaut (0 = jamais).
+///
 /// - rpc_url:
-///   Récupère l'URL du serveur RPC pour l'accès au réseau Solana à partir de la variable d'environnement `RPC_URL`.
-///   Retourne "https://api.devnet.solana.com" par défaut, indiquant que le réseau Testnet est utilisé si la variable d'environnement n'est pas définie.
-
+///   Récupère l'URL du serveur RPC pour l'accès au réseau Solana (option `--url`/`-u`, variable d'environnement `RPC_URL`, profil
+///   ou "json_rpc_url" de la CLI Solana). Les noms de clusters ("m", "d", "t", "l", "mainnet-beta"...) sont convertis en URL.
+///   Retourne "https://api.devnet.solana.com" par défaut, indiquant que le réseau Devnet est utilisé.
+///
+/// - rpc_urls:
+///   Les points d'accès RPC de secours, séparés par des virgules (variable d'environnement `RPC_URLS` ou liste "rpc_urls" du profil).
+///   Ils sont essayés dans l'ordre après "rpc_url" lorsqu'il est indisponible (voir `SolanaRpc`).
+///
+/// - commitment:
+///   Le niveau de confirmation des requêtes RPC (option `--commitment`, variable d'environnement `COMMITMENT`, profil
+///   ou "commitment" de la CLI Solana).
+///   Retourne "finalized" par défaut.
+///
+/// - derivation_template:
+///   Le chemin de dérivation des clés dérivées, où "{index}" est remplacé par l'index de dérivation
+///   (variable d'environnement `DERIVATION_TEMPLATE` ou profil). Retourne "m/44'/501'/0'/0/{index}" par défaut.
+///
+/// - explorer_template:
+///   Le modèle de l'URL des transactions dans l'explorateur, où "{signature}" est remplacé par la signature et "{cluster}"
+///   par le cluster de l'URL RPC (variable d'environnement `EXPLORER_TEMPLATE` ou profil).
+///   Retourne "https://explorer.solana.com/tx/{signature}?cluster={cluster}" par défaut.
+///
+/// - fee_policy:
+///   Les frais de priorité des transactions (variables d'environnement `COMPUTE_UNIT_PRICE` et `COMPUTE_UNIT_LIMIT`, ou profil).
+///   Aucun frais de priorité par défaut. Une valeur invalide (ou une limite hors de 1 à 1 400 000) est une erreur.
+///
+/// - retry_policy:
+///   Le nombre maximal de renvois après expiration du blockhash et le délai d'attente de la confirmation, en secondes
+///   (variables d'environnement `MAX_RESENDS` et `CONFIRM_TIMEOUT`, ou profil). Retourne 3 renvois et 90 secondes par défaut.
+///   Ainsi que les nouvelles tentatives des requêtes RPC et le retard maximal d'un point d'accès (variables d'environnement
+///   `RPC_RETRIES`, `RPC_BACKOFF_MS` et `MAX_SLOT_LAG`, ou profil). Retourne 2 tentatives, 250 ms et 150 slots par défaut.
+///
+/// - spending_policy:
+///   Les plafonds de dépense, listes de destinataires et confirmations (profil uniquement). Aucune règle par défaut.
+///
+/// - output:
+///   Le format d'affichage des résultats : "text", "json" ou "json-compact" (option `--output` ou variable d'environnement `OUTPUT_FORMAT`).
+///   Retourne "text" par défaut.
 impl WalletConfig {
-    /// Charge la configuration depuis les variables d'environnement ou utilise les valeurs par défaut.
+    /// Charge la configuration depuis les variables d'environnement ou utilise les valeurs par défaut (sans fichier de configuration).
     pub fn new() -> Self {
+        Self::resolve(
+            &ConfigOverrides::default(),
+            None,
+            &SolanaCliConfig::default(),
+        )
+    }
+
+    /// Charge la configuration en tenant compte des options de la ligne de commande et du profil sélectionné
+    /// (option `--profile`, variable d'environnement `WALLET_PROFILE` ou "default_profile" du fichier de configuration).
+    ///
+    /// # Returns:
+    /// Retourne la configuration, ou une erreur si le fichier de configuration est invalide,
+    /// si le profil demandé n'existe pas ou si une valeur est invalide.
+    pub fn load(overrides: &ConfigOverrides) -> Result<Self, String> {
+        let config_path = ConfigFile::path();
+        let config_file = match &config_path {
+            Some(path) => ConfigFile::load(path)?,
+            None => ConfigFile::default(),
+        };
+
+        let (profile_name, profile_source) = if let Some(name) = &overrides.profile {
+            (Some(name.clone()), ConfigSource::Flag("--profile"))
+        } else if let Ok(name) = env::var("WALLET_PROFILE") {
+            (Some(name), ConfigSource::Env("WALLET_PROFILE"))
+        } else {
+            (config_file.default_profile.clone(), ConfigSource::File)
+        };
+
+        let profile = match &profile_name {
+            Some(name) => match config_file.profiles.get(name) {
+                Some(profile) => Some((name.as_str(), profile)),
+                None => {
+                    let available: Vec<&str> =
+                        config_file.profiles.keys().map(String::as_str).collect();
+                    return Err(format!(
+                        "Unknown profile '{}' (available profiles: {})",
+                        name,
+                        if available.is_empty() {
+                            "none".to_string()
+                        } else {
+                            available.join(", ")
+                        }
+                    ));
+                }
+            },
+            None => None,
+        };
+
+        let solana_cli_config = match SolanaCliConfig::path() {
+            Some(path) => SolanaCliConfig::load(&path)?,
+            None => SolanaCliConfig::default(),
+        };
+
+        let mut config = Self::resolve(overrides, profile, &solana_cli_config);
+        config.config_path = config_path.map(|path| path.display().to_string());
+        if config.profile.is_some() {
+            config.sources.insert("profile", profile_source);
+        }
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Retourne le niveau de confirmation à utiliser pour les requêtes RPC.
+    pub fn commitment_config(&self) -> CommitmentConfig {
+        CommitmentConfig::from_str(&self.commitment).unwrap_or_default()
+    }
+
+    /// Retourne l'affichage des résultats des commandes, dans le format configuré.
+    pub fn output(&self) -> Output {
+        Output::new(self.output.parse().unwrap_or_default())
+    }
+
+    /// Résout chaque valeur selon l'ordre de priorité : option, variable d'environnement, profil, CLI Solana, valeur par défaut.
+    fn resolve(
+        overrides: &ConfigOverrides,
+        profile: Option<(&str, &Profile)>,
+        solana_cli_config: &SolanaCliConfig,
+    ) -> Self {
+        let mut sources = BTreeMap::new();
+        let empty_profile = Profile::default();
+        let (profile_name, profile_values) = match profile {
+            Some((name, values)) => (Some(name.to_string()), values),
+            None => (None, &empty_profile),
+        };
+
+        let mut pick = |key: &'static str,
+                        flag: Option<(&'static str, &Option<String>)>,
+                        env_name: &'static str,
+                        profile_value: Option<String>,
+                        solana_cli_value: Option<String>,
+                        default: &str| {
+            let (value, source) = if let Some((flag, Some(value))) = flag {
+                (value.clone(), ConfigSource::Flag(flag))
+            } else if let Ok(value) = env::var(env_name) {
+                (value, ConfigSource::Env(env_name))
+            } else if let (Some(value), Some(name)) = (profile_value, &profile_name) {
+                (value, ConfigSource::Profile(name.clone()))
+            } else if let Some(value) = solana_cli_value {
+                (value, ConfigSource::SolanaCli)
+            } else {
+                (default.to_string(), ConfigSource::Default)
+            };
+            sources.insert(key, source);
+            value
+        };
+
+        let fee_policy = profile_values.fee_policy.clone().unwrap_or_default();
+        let retry_policy = profile_values.retry_policy.clone();
+        let default_retry_policy = RetryPolicy::default();
+
+        let nb_derivations = pick("nb_derivations", None, "NB_DERIVATIONS", None, None, "0")
+            .parse::<usize>() // Tente de convertir la chaîne de caractères en un entier de type usize.
+            .unwrap_or(0); // Retourne 0 si la conversion échoue ou si la valeur convertie n'est pas un nombre.
+        let keypair_path = expand_home(&pick(
+            "keypair_path",
+            None,
+            "KEYPAIR_PATH",
+            None,
+            solana_cli_config.keypair_path.clone(),
+            "./storage/keypair/id.json",
+        ));
+        let keypair_derivations_path = expand_home(&pick(
+            "keypair_derivations_path",
+            None,
+            "KEYPAIR_DERIVATIONS_PATH",
+            None,
+            None,
+            "./storage/keypair/derived",
+        ));
+        let keystore_path = expand_home(&pick(
+            "keystore_path",
+            None,
+            "KEYSTORE_PATH",
+            profile_values.keystore_path.clone(),
+            None,
+            "./storage/keypair/keystore.json",
+        ));
+        let address_book_path = expand_home(&pick(
+            "address_book_path",
+            None,
+            "ADDRESS_BOOK_PATH",
+            None,
+            None,
+            "./storage/address_book.json",
+        ));
+        let spending_ledger_path = expand_home(&pick(
+            "spending_ledger_path",
+            None,
+            "SPENDING_LEDGER_PATH",
+            None,
+            None,
+            "./storage/spending_ledger.json",
+        ));
+        let audit_log_path = expand_home(&pick(
+            "audit_log_path",
+            None,
+            "AUDIT_LOG_PATH",
+            None,
+            None,
+            "./storage/audit_log.jsonl",
+        ));
+        let shell_history_path = expand_home(&pick(
+            "shell_history_path",
+            None,
+            "SHELL_HISTORY_PATH",
+            None,
+            None,
+            "./storage/shell_history",
+        ));
+        let shell_idle_timeout = pick(
+            "shell_idle_timeout",
+            None,
+            "SHELL_IDLE_TIMEOUT",
+            None,
+            None,
+            "300",
+        )
+        .parse::<u64>()
+        .unwrap_or(300);
+        let rpc_url = pick(
+            "rpc_url",
+            Some(("--url", &overrides.rpc_url)),
+            "RPC_URL",
+            profile_values.rpc_url.clone().or_else(|| {
+                profile_values
+                    .rpc_urls
+                    .as_ref()
+                    .and_then(|urls| urls.first().cloned())
+            }),
+            solana_cli_config.json_rpc_url.clone(),
+            "https://api.devnet.solana.com", // URL par défaut pointant vers le Devnet de Solana.
+        );
+        let rpc_url = SolanaCliConfig::normalize_to_url_if_moniker(&rpc_url);
+        let fallback_rpc_urls = pick(
+            "rpc_urls",
+            None,
+            "RPC_URLS",
+            profile_values.rpc_urls.as_ref().map(|urls| urls.join(",")),
+            None,
+            "",
+        );
+        let mut rpc_urls = vec![rpc_url.clone()];
+        for url in fallback_rpc_urls.split(',').map(str::trim) {
+            let url = SolanaCliConfig::normalize_to_url_if_moniker(url);
+            if !url.is_empty() && !rpc_urls.contains(&url) {
+                rpc_urls.push(url);
+            }
+        }
+        let commitment = pick(
+            "commitment",
+            Some(("--commitment", &overrides.commitment)),
+            "COMMITMENT",
+            profile_values.commitment.clone(),
+            solana_cli_config.commitment.clone(),
+            "finalized",
+        );
+        let derivation_template = pick(
+            "derivation_template",
+            None,
+            "DERIVATION_TEMPLATE",
+            profile_values.derivation_template.clone(),
+            None,
+            DEFAULT_DERIVATION_TEMPLATE,
+        );
+        let explorer_template = pick(
+            "explorer_template",
+            None,
+            "EXPLORER_TEMPLATE",
+            profile_values.explorer_template.clone(),
+            None,
+            DEFAULT_EXPLORER_TEMPLATE,
+        );
+        let compute_unit_price = pick(
+            "compute_unit_price",
+            None,
+            "COMPUTE_UNIT_PRICE",
+            profile_values
+                .fee_policy
+                .as_ref()
+                .map(|_| fee_policy.compute_unit_price.to_string()),
+            None,
+            "0",
+        );
+        let compute_unit_limit = pick(
+            "compute_unit_limit",
+            None,
+            "COMPUTE_UNIT_LIMIT",
+            fee_policy.compute_unit_limit.map(|limit| limit.to_string()),
+            None,
+            "",
+        );
+        // Les frais de priorité invalides sont signalés par "validate" (jamais remplacés silencieusement).
+        let mut invalid_values = Vec::new();
+        let compute_unit_price = compute_unit_price.parse::<u64>().unwrap_or_else(|_| {
+            invalid_values.push(format!(
+                "Invalid compute_unit_price '{}' (expected a number of micro-lamports)",
+                compute_unit_price
+            ));
+            0
+        });
+        let compute_unit_limit = match compute_unit_limit.parse::<u32>() {
+            Ok(limit) if (1..=MAX_COMPUTE_UNIT_LIMIT).contains(&limit) => Some(limit),
+            _ if compute_unit_limit.is_empty() => None,
+            _ => {
+                invalid_values.push(format!(
+                    "Invalid compute_unit_limit '{}' (expected a number between 1 and {})",
+                    compute_unit_limit, MAX_COMPUTE_UNIT_LIMIT
+                ));
+                None
+            }
+        };
+        let max_resends = pick(
+            "max_resends",
+            None,
+            "MAX_RESENDS",
+            retry_policy
+                .as_ref()
+                .map(|policy| policy.max_resends.to_string()),
+            None,
+            &default_retry_policy.max_resends.to_string(),
+        );
+        let max_resends = parse_number(
+            "max_resends",
+            &max_resends,
+            default_retry_policy.max_resends,
+            &mut invalid_values,
+        );
+        let confirm_timeout = pick(
+            "confirm_timeout",
+            None,
+            "CONFIRM_TIMEOUT",
+            retry_policy
+                .as_ref()
+                .map(|policy| policy.confirm_timeout.to_string()),
+            None,
+            &default_retry_policy.confirm_timeout.to_string(),
+        );
+        let confirm_timeout = parse_number(
+            "confirm_timeout",
+            &confirm_timeout,
+            default_retry_policy.confirm_timeout,
+            &mut invalid_values,
+        );
+        let rpc_retries = pick(
+            "rpc_retries",
+            None,
+            "RPC_RETRIES",
+            retry_policy
+                .as_ref()
+                .map(|policy| policy.rpc_retries.to_string()),
+            None,
+            &default_retry_policy.rpc_retries.to_string(),
+        );
+        let rpc_retries = parse_number(
+            "rpc_retries",
+            &rpc_retries,
+            default_retry_policy.rpc_retries,
+            &mut invalid_values,
+        );
+        let rpc_backoff_ms = pick(
+            "rpc_backoff_ms",
+            None,
+            "RPC_BACKOFF_MS",
+            retry_policy
+                .as_ref()
+                .map(|policy| policy.rpc_backoff_ms.to_string()),
+            None,
+            &default_retry_policy.rpc_backoff_ms.to_string(),
+        );
+        let rpc_backoff_ms = parse_number(
+            "rpc_backoff_ms",
+            &rpc_backoff_ms,
+            default_retry_policy.rpc_backoff_ms,
+            &mut invalid_values,
+        );
+        let max_slot_lag = pick(
+            "max_slot_lag",
+            None,
+            "MAX_SLOT_LAG",
+            retry_policy
+                .as_ref()
+                .map(|policy| policy.max_slot_lag.to_string()),
+            None,
+            &default_retry_policy.max_slot_lag.to_string(),
+        );
+        let max_slot_lag = parse_number(
+            "max_slot_lag",
+            &max_slot_lag,
+            default_retry_policy.max_slot_lag,
+            &mut invalid_values,
+        );
+        let spending_policy = profile_values.spending_policy.clone().unwrap_or_default();
+        let output = pick(
+            "output",
+            Some(("--output", &overrides.output)),
+            "OUTPUT_FORMAT",
+            None,
+            None,
+            "text",
+        );
+        if let (Some(_), Some(name)) = (&profile_values.spending_policy, &profile_name) {
+            sources.insert("spending_policy", ConfigSource::Profile(name.clone()));
+        }
+
         Self {
-            nb_derivations: env::var("NB_DERIVATIONS")
-                .unwrap_or_else(|_| "0".to_string()) // Utilise "1" comme valeur par défaut si la variable n'est pas définie.
-                .parse::<usize>() // Tente de convertir la chaîne de caractères en un entier de type usize.
-                .unwrap_or(0), // Retourne 1 si la conversion échoue ou si la valeur convertie n'est pas un nombre.
-            keypair_path: env::var("KEYPAIR_PATH")
-                .unwrap_or_else(|_| "./storage/keypair/id.json".to_string()),
-            keypair_derivations_path: env::var("KEYPAIR_DERIVATIONS_PATH")
-                .unwrap_or_else(|_| "./storage/keypair/derived".to_string()),
-            rpc_url:
-                env::var("RPC_URL") // Lire l'URL RPC de l'environnement
-                    .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string()), // URL par défaut pointant vers le Testnet de Solana.
+            nb_derivations,
+            keypair_path,
+            keypair_derivations_path,
+            keystore_path,
+            address_book_path,
+            spending_ledger_path,
+            audit_log_path,
+            shell_history_path,
+            shell_idle_timeout,
+            rpc_url,
+            rpc_urls,
+            commitment,
+            derivation_template,
+            explorer_template,
+            fee_policy: FeePolicy {
+                compute_unit_price,
+                compute_unit_limit,
+            },
+            retry_policy: RetryPolicy {
+                max_resends,
+                confirm_timeout,
+                rpc_retries,
+                rpc_backoff_ms,
+                max_slot_lag,
+            },
+            spending_policy,
+            policy_confirmation: PolicyConfirmation::Prompt,
+            output,
+            profile: profile_name,
+            config_path: None,
+            sources,
+            invalid_values,
         }
     }
+
+    /// Vérifie les valeurs qui ne peuvent pas être corrigées silencieusement.
+    fn validate(&self) -> Result<(), String> {
+        if CommitmentConfig::from_str(&self.commitment).is_err() {
+            return Err(format!(
+                "Invalid commitment '{}' (expected processed, confirmed or finalized)",
+                self.commitment
+            ));
+        }
+        OutputFormat::from_str(&self.output)?;
+        if let Some(invalid_value) = self.invalid_values.first() {
+            return Err(invalid_value.clone());
+        }
+        if !self.derivation_template.contains("{index}") {
+            return Err(format!(
+                "Invalid derivation template '{}' (it must contain {{index}})",
+                self.derivation_template
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Convertit un réglage numérique. Une valeur invalide est signalée par "validate" (jamais remplacée silencieusement).
+fn parse_number<T: FromStr>(
+    key: &str,
+    value: &str,
+    default: T,
+    invalid_values: &mut Vec<String>,
+) -> T {
+    value.parse().unwrap_or_else(|_| {
+        invalid_values.push(format!("Invalid {} '{}' (expected a number)", key, value));
+        default
+    })
 }
 
 #[cfg(test)]
@@ -63,10 +641,36 @@ mod tests {
         env::remove_var("NB_DERIVATIONS");
         env::remove_var("KEYPAIR_PATH");
         env::remove_var("KEYPAIR_DERIVATIONS_PATH");
+        env::remove_var("KEYSTORE_PATH");
+        env::remove_var("ADDRESS_BOOK_PATH");
+        env::remove_var("SPENDING_LEDGER_PATH");
+        env::remove_var("AUDIT_LOG_PATH");
         env::remove_var("RPC_URL");
+        env::remove_var("COMMITMENT");
+        env::remove_var("DERIVATION_TEMPLATE");
+        env::remove_var("EXPLORER_TEMPLATE");
+        env::remove_var("COMPUTE_UNIT_PRICE");
+        env::remove_var("COMPUTE_UNIT_LIMIT");
+        env::remove_var("MAX_RESENDS");
+        env::remove_var("CONFIRM_TIMEOUT");
+        env::remove_var("RPC_URLS");
+        env::remove_var("SHELL_HISTORY_PATH");
+        env::remove_var("SHELL_IDLE_TIMEOUT");
+        env::remove_var("RPC_RETRIES");
+        env::remove_var("RPC_BACKOFF_MS");
+        env::remove_var("MAX_SLOT_LAG");
+        env::remove_var("WALLET_PROFILE");
+        env::remove_var("OUTPUT_FORMAT");
+        env::set_var("WALLET_CONFIG", "./storage/tests/missing_config.toml");
+        env::set_var(
+            "SOLANA_CLI_CONFIG",
+            "./storage/tests/missing_solana_cli_config.yml",
+        );
     }
 
     fn teardown() {
+        env::remove_var("WALLET_CONFIG");
+        env::remove_var("SOLANA_CLI_CONFIG");
         dotenv::from_filename(".env.testing").ok();
     }
 
@@ -79,6 +683,8 @@ mod tests {
         assert_eq!(config.nb_derivations, 0);
         assert_eq!(config.keypair_path, "./storage/keypair/id.json");
         assert_eq!(config.keypair_derivations_path, "./storage/keypair/derived");
+        assert_eq!(config.keystore_path, "./storage/keypair/keystore.json");
+        assert_eq!(config.address_book_path, "./storage/address_book.json");
         assert_eq!(config.rpc_url, "https://api.devnet.solana.com");
 
         teardown();
@@ -95,6 +701,8 @@ mod tests {
             "KEYPAIR_DERIVATIONS_PATH",
             "./storage/custom/keypair/derived",
         );
+        env::set_var("KEYSTORE_PATH", "./storage/custom/keypair/keystore.json");
+        env::set_var("ADDRESS_BOOK_PATH", "./storage/custom/address_book.json");
         env::set_var("RPC_URL", "https://custom.rpc.url");
 
         let config = WalletConfig::new();
@@ -104,6 +712,14 @@ mod tests {
             config.keypair_derivations_path,
             "./storage/custom/keypair/derived"
         );
+        assert_eq!(
+            config.keystore_path,
+            "./storage/custom/keypair/keystore.json"
+        );
+        assert_eq!(
+            config.address_book_path,
+            "./storage/custom/address_book.json"
+        );
         assert_eq!(config.rpc_url, "https://custom.rpc.url");
 
         teardown();
@@ -121,4 +737,241 @@ mod tests {
 
         teardown();
     }
+
+    fn write_config_file(name: &str) -> String {
+        let path = format!("./storage/tests/{}.toml", name);
+        std::fs::create_dir_all("./storage/tests").unwrap();
+        std::fs::write(
+            &path,
+            r#"
+            default_profile = "devnet"
+
+            [profiles.devnet]
+            rpc_url = "https://devnet.profile.url"
+
+            [profiles.failover]
+            rpc_urls = ["https://first.rpc.url", "https://second.rpc.url", "d"]
+
+            [profiles.mainnet]
+            rpc_url = "https://mainnet.profile.url"
+            commitment = "confirmed"
+            keystore_path = "./storage/mainnet/keystore.json"
+            derivation_template = "m/44'/501'/{index}'/0'"
+            fee_policy = { compute_unit_price = 5000, compute_unit_limit = 200000 }
+            "#,
+        )
+        .unwrap();
+        env::set_var("WALLET_CONFIG", &path);
+        path
+    }
+
+    #[test]
+    #[serial]
+    fn test_profile_values() {
+        setup();
+        let path = write_config_file("config_profile_test");
+
+        // Sans option, le profil par défaut du fichier est utilisé.
+        let config = WalletConfig::load(&ConfigOverrides::default()).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("devnet"));
+        assert_eq!(config.rpc_url, "https://devnet.profile.url");
+        assert_eq!(config.commitment, "finalized");
+        assert_eq!(config.sources["commitment"], ConfigSource::Default);
+
+        let overrides = ConfigOverrides {
+            profile: Some("mainnet".to_string()),
+            ..Default::default()
+        };
+        let config = WalletConfig::load(&overrides).unwrap();
+        assert_eq!(config.rpc_url, "https://mainnet.profile.url");
+        assert_eq!(config.commitment, "confirmed");
+        assert_eq!(config.keystore_path, "./storage/mainnet/keystore.json");
+        assert_eq!(config.derivation_template, "m/44'/501'/{index}'/0'");
+        assert_eq!(config.fee_policy.compute_unit_price, 5000);
+        assert_eq!(config.fee_policy.compute_unit_limit, Some(200000));
+        assert_eq!(
+            config.sources["rpc_url"],
+            ConfigSource::Profile("mainnet".to_string())
+        );
+        assert_eq!(config.sources["profile"], ConfigSource::Flag("--profile"));
+
+        let overrides = ConfigOverrides {
+            profile: Some("unknown".to_string()),
+            ..Default::default()
+        };
+        assert!(WalletConfig::load(&overrides).is_err());
+
+        // Une liste de points d'accès : le premier est l'URL RPC, les suivants sont les points d'accès de secours.
+        let overrides = ConfigOverrides {
+            profile: Some("failover".to_string()),
+            ..Default::default()
+        };
+        let config = WalletConfig::load(&overrides).unwrap();
+        assert_eq!(config.rpc_url, "https://first.rpc.url");
+        assert_eq!(
+            config.rpc_urls,
+            [
+                "https://first.rpc.url",
+                "https://second.rpc.url",
+                "https://api.devnet.solana.com"
+            ]
+        );
+
+        std::fs::remove_file(path).unwrap();
+        teardown();
+    }
+
+    #[test]
+    #[serial]
+    fn test_precedence() {
+        setup();
+        let path = write_config_file("config_precedence_test");
+
+        // La variable d'environnement est prioritaire sur le profil, l'option sur la variable d'environnement.
+        env::set_var("WALLET_PROFILE", "mainnet");
+        env::set_var("COMMITMENT", "processed");
+        env::set_var("RPC_URL", "https://env.rpc.url");
+        let overrides = ConfigOverrides {
+            rpc_url: Some("https://flag.rpc.url".to_string()),
+            ..Default::default()
+        };
+
+        let config = WalletConfig::load(&overrides).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("mainnet"));
+        assert_eq!(
+            config.sources["profile"],
+            ConfigSource::Env("WALLET_PROFILE")
+        );
+        assert_eq!(config.rpc_url, "https://flag.rpc.url");
+        assert_eq!(config.sources["rpc_url"], ConfigSource::Flag("--url"));
+        assert_eq!(config.commitment, "processed");
+        assert_eq!(
+            config.sources["commitment"],
+            ConfigSource::Env("COMMITMENT")
+        );
+
+        env::set_var("COMMITMENT", "nope");
+        assert!(WalletConfig::load(&overrides).is_err());
+
+        std::fs::remove_file(path).unwrap();
+        teardown();
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_fee_policy() {
+        setup();
+
+        env::set_var("COMPUTE_UNIT_PRICE", "5k");
+        let error = WalletConfig::load(&ConfigOverrides::default())
+            .err()
+            .unwrap();
+        assert!(error.contains("Invalid compute_unit_price '5k'"));
+        env::remove_var("COMPUTE_UNIT_PRICE");
+
+        env::set_var("COMPUTE_UNIT_LIMIT", "2000000");
+        let error = WalletConfig::load(&ConfigOverrides::default())
+            .err()
+            .unwrap();
+        assert!(error.contains("Invalid compute_unit_limit '2000000'"));
+        env::remove_var("COMPUTE_UNIT_LIMIT");
+
+        // Les valeurs du profil sont vérifiées de la même façon.
+        let path = "./storage/tests/config_fee_policy_test.toml";
+        std::fs::create_dir_all("./storage/tests").unwrap();
+        std::fs::write(
+            path,
+            r#"
+            [profiles.mainnet]
+            fee_policy = { compute_unit_price = 5000, compute_unit_limit = 0 }
+            "#,
+        )
+        .unwrap();
+        env::set_var("WALLET_CONFIG", path);
+        let overrides = ConfigOverrides {
+            profile: Some("mainnet".to_string()),
+            ..Default::default()
+        };
+        let error = WalletConfig::load(&overrides).err().unwrap();
+        assert!(error.contains("Invalid compute_unit_limit '0'"));
+
+        std::fs::remove_file(path).unwrap();
+        teardown();
+    }
+
+    #[test]
+    #[serial]
+    fn test_invalid_retry_policy() {
+        setup();
+
+        for (name, value) in [
+            ("MAX_RESENDS", "three"),
+            ("CONFIRM_TIMEOUT", "90s"),
+            ("RPC_RETRIES", "-1"),
+            ("RPC_BACKOFF_MS", "0.5"),
+            ("MAX_SLOT_LAG", ""),
+        ] {
+            env::set_var(name, value);
+            let error = WalletConfig::load(&ConfigOverrides::default())
+                .err()
+                .unwrap();
+            let expected = format!("Invalid {} '{}'", name.to_lowercase(), value);
+            assert!(error.contains(&expected), "{}", error);
+            env::remove_var(name);
+        }
+
+        teardown();
+    }
+
+    #[test]
+    #[serial]
+    fn test_expand_home_paths() {
+        setup();
+
+        let home = env::var("HOME").unwrap();
+        env::set_var("KEYPAIR_PATH", "~/.config/solana/id.json");
+        let config = WalletConfig::load(&ConfigOverrides::default()).unwrap();
+        assert_eq!(
+            config.keypair_path,
+            format!("{}/.config/solana/id.json", home)
+        );
+        env::remove_var("KEYPAIR_PATH");
+
+        teardown();
+    }
+
+    #[test]
+    #[serial]
+    fn test_solana_cli_config_fallback() {
+        setup();
+        let path = "./storage/tests/solana_cli_config_test.yml";
+        std::fs::create_dir_all("./storage/tests").unwrap();
+        std::fs::write(
+            path,
+            "---\njson_rpc_url: \"https://api.testnet.solana.com\"\nkeypair_path: /tmp/solana/id.json\ncommitment: confirmed\n",
+        )
+        .unwrap();
+        env::set_var("SOLANA_CLI_CONFIG", path);
+
+        // Sans autre réglage, les valeurs de la CLI Solana sont utilisées.
+        let config = WalletConfig::load(&ConfigOverrides::default()).unwrap();
+        assert_eq!(config.rpc_url, "https://api.testnet.solana.com");
+        assert_eq!(config.keypair_path, "/tmp/solana/id.json");
+        assert_eq!(config.commitment, "confirmed");
+        assert_eq!(config.sources["rpc_url"], ConfigSource::SolanaCli);
+
+        // Les réglages explicites restent prioritaires, et les noms de clusters sont convertis en URL.
+        env::set_var("COMMITMENT", "processed");
+        let overrides = ConfigOverrides {
+            rpc_url: Some("m".to_string()),
+            ..Default::default()
+        };
+        let config = WalletConfig::load(&overrides).unwrap();
+        assert_eq!(config.rpc_url, "https://api.mainnet-beta.solana.com");
+        assert_eq!(config.commitment, "processed");
+        assert_eq!(config.sources["rpc_url"], ConfigSource::Flag("--url"));
+
+        std::fs::remove_file(path).unwrap();
+        teardown();
+    }
 }
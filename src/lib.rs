@@ -2,3 +2,4 @@ pub mod app;
 pub mod bip;
 pub mod config;
 pub mod solana;
+pub mod store;
@@ -2,10 +2,13 @@ mod app;
 mod bip;
 mod config;
 mod solana;
+mod store;
 
 use app::cli::AppCli;
+use app::output::Output;
 use config::wallet_config::WalletConfig;
 use std::env;
+use std::process;
 
 use dotenv::dotenv;
 
@@ -15,12 +18,24 @@ fn main() {
         dotenv().ok();
     }
 
-    // Charger la configuration du wallet (selon les données du fichier .env).
-    let config = WalletConfig::new();
+    let matches = AppCli::new(WalletConfig::new()).setup_cli().get_matches();
 
-    let app_cli = AppCli::new(config.clone());
+    // Charger la configuration du wallet : options de la ligne de commande, puis variables d'environnement
+    // (fichier .env), puis profil du fichier de configuration, puis valeurs par défaut.
+    let config = match WalletConfig::load(&AppCli::config_overrides(&matches)) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {}", e);
+            process::exit(1);
+        }
+    };
 
-    let matches = app_cli.setup_cli().get_matches();
+    let app_cli = AppCli::new(config);
 
     app_cli.handle_matches(matches);
+
+    // Une commande en échec se termine avec un code de sortie non nul (pour les scripts).
+    if Output::take_error() {
+        process::exit(1);
+    }
 }
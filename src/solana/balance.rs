@@ -1,5 +1,5 @@
 use crate::config::wallet_config::WalletConfig;
-use solana_client::rpc_client::RpcClient;
+use crate::solana::rpc::SolanaRpc;
 use solana_sdk::pubkey::Pubkey;
 use std::str::FromStr;
 
@@ -23,7 +23,7 @@ impl SolanaBalance {
     /// - Err(e) - Si une erreur se produit lors de la récupération de la balance.
     pub fn get_balance_by_pubkey(&self, pubkey: &str) -> Result<u64, Box<dyn std::error::Error>> {
         let pubkey = Pubkey::from_str(pubkey)?;
-        let client = RpcClient::new(&self.config.rpc_url);
+        let client = SolanaRpc::client(&self.config);
         client.get_balance(&pubkey).map_err(Into::into)
     }
 }
@@ -0,0 +1,142 @@
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig, signature::Signature, transaction::TransactionError,
+};
+use solana_transaction_status::TransactionStatus;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Intervalle entre deux requêtes de statut.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Statut d'une transaction arrivée dans un bloc.
+#[derive(Debug)]
+pub struct LandedTransaction {
+    pub signature: Signature,
+    pub slot: u64,
+    /// Le niveau de confirmation atteint ("processed", "confirmed" ou "finalized").
+    pub confirmation_status: Option<String>,
+    /// L'erreur d'exécution de la transaction (la transaction est incluse dans un bloc, mais a échoué).
+    pub error: Option<TransactionError>,
+    /// Vrai si le niveau de confirmation demandé est atteint.
+    pub confirmed: bool,
+}
+
+/// Issue de l'attente d'une transaction envoyée.
+#[derive(Debug)]
+pub enum Landing {
+    /// L'une des signatures est arrivée dans un bloc et a atteint le niveau de confirmation demandé (ou a échoué).
+    Landed(LandedTransaction),
+    /// Le blockhash a expiré sans qu'aucune des signatures n'arrive dans un bloc : la transaction peut être signée à nouveau.
+    Expired,
+    /// Le délai d'attente est dépassé.
+    TimedOut(Option<LandedTransaction>),
+}
+
+pub struct SolanaConfirmation {}
+
+impl SolanaConfirmation {
+    /// Attend qu'une des signatures (les envois successifs d'une même transaction) arrive dans un bloc.
+    ///
+    /// Le blockhash n'est considéré comme expiré que lorsque la hauteur de bloc finalisée dépasse "last_valid_block_height" :
+    /// aucune des signatures ne peut alors plus arriver dans un bloc. Les statuts sont vérifiés une dernière fois
+    /// (historique compris) avant de retourner `Landing::Expired`, pour ne jamais renvoyer une transaction déjà traitée.
+    ///
+    /// # Arguments:
+    /// - client - Le client RPC.
+    /// - signatures - Les signatures de tous les envois de la transaction.
+    /// - commitment - Le niveau de confirmation attendu.
+    /// - last_valid_block_height - La dernière hauteur de bloc à laquelle le dernier envoi est valide.
+    /// - deadline - L'instant au-delà duquel l'attente est abandonnée.
+    pub fn wait_for_landing(
+        client: &RpcClient,
+        signatures: &[Signature],
+        commitment: CommitmentConfig,
+        last_valid_block_height: u64,
+        deadline: Instant,
+    ) -> Result<Landing, Box<dyn std::error::Error>> {
+        loop {
+            let mut landed = Self::find_landed(client, signatures, commitment, false)?;
+            if landed.is_none() {
+                let block_height =
+                    client.get_block_height_with_commitment(CommitmentConfig::finalized())?;
+                if block_height > last_valid_block_height {
+                    // Plus aucune signature ne peut arriver dans un bloc : dernière vérification, historique compris.
+                    landed = Self::find_landed(client, signatures, commitment, true)?;
+                    if landed.is_none() {
+                        return Ok(Landing::Expired);
+                    }
+                }
+            }
+
+            // Une transaction arrivée dans un bloc n'est jamais renvoyée : on attend qu'elle atteigne le niveau demandé.
+            match landed {
+                Some(landed) if landed.confirmed || landed.error.is_some() => {
+                    return Ok(Landing::Landed(landed))
+                }
+                landed if Instant::now() >= deadline => return Ok(Landing::TimedOut(landed)),
+                _ => thread::sleep(POLL_INTERVAL),
+            }
+        }
+    }
+
+    /// Attend que la transaction atteigne le niveau de confirmation demandé, échoue, ou que le délai soit dépassé.
+    /// Retourne le dernier statut connu (None si la transaction est introuvable).
+    pub fn wait_for_confirmation(
+        client: &RpcClient,
+        signature: &Signature,
+        commitment: CommitmentConfig,
+        timeout: Duration,
+    ) -> Result<Option<LandedTransaction>, Box<dyn std::error::Error>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let landed = Self::find_landed(client, &[*signature], commitment, true)?;
+            let done = landed
+                .as_ref()
+                .is_some_and(|landed| landed.confirmed || landed.error.is_some());
+            if done || Instant::now() >= deadline {
+                return Ok(landed);
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Retourne le statut de la première signature arrivée dans un bloc.
+    /// Avec "search_history", les transactions plus anciennes que le cache de statuts du nœud sont également recherchées.
+    fn find_landed(
+        client: &RpcClient,
+        signatures: &[Signature],
+        commitment: CommitmentConfig,
+        search_history: bool,
+    ) -> Result<Option<LandedTransaction>, Box<dyn std::error::Error>> {
+        let statuses = if search_history {
+            client.get_signature_statuses_with_history(signatures)?
+        } else {
+            client.get_signature_statuses(signatures)?
+        };
+
+        Ok(signatures
+            .iter()
+            .zip(statuses.value)
+            .find_map(|(signature, status)| {
+                status.map(|status| Self::landed(*signature, status, commitment))
+            }))
+    }
+
+    fn landed(
+        signature: Signature,
+        status: TransactionStatus,
+        commitment: CommitmentConfig,
+    ) -> LandedTransaction {
+        LandedTransaction {
+            signature,
+            slot: status.slot,
+            confirmed: status.satisfies_commitment(commitment),
+            confirmation_status: status
+                .confirmation_status
+                .as_ref()
+                .map(|level| format!("{:?}", level).to_lowercase()),
+            error: status.err,
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::lookup_table::SolanaLookupTable;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_sdk::compute_budget;
+use solana_sdk::instruction::{AccountMeta, CompiledInstruction, Instruction};
+use solana_sdk::message::v0::{LoadedAddresses, MessageAddressTableLookup};
+use solana_sdk::message::{AccountKeys, VersionedMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::parse_instruction::{self, ParseInstructionError};
+
+/// Une instruction décodée (programmes System, Token, Token-2022, ATA, Memo, ComputeBudget, Stake...).
+#[derive(Debug, Serialize)]
+pub struct DecodedInstruction {
+    /// Le nom du programme ("system", "spl-token", "compute-budget"...), ou "unknown".
+    pub program: String,
+    pub program_id: String,
+    /// Faux si le programme ou les données de l'instruction ne sont pas reconnus.
+    pub recognised: bool,
+    /// Le type de l'instruction ("transfer", "setComputeUnitPrice"...).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instruction_type: Option<String>,
+    /// Les paramètres et les comptes nommés de l'instruction.
+    #[serde(skip_serializing_if = "Value::is_null")]
+    pub info: Value,
+    /// Les comptes passés à l'instruction (ou "#<index>" pour un compte d'une table d'adresses non chargée).
+    pub accounts: Vec<String>,
+    /// Les données de l'instruction, en hexadécimal.
+    pub data: String,
+}
+
+pub struct SolanaDecoder {}
+
+impl SolanaDecoder {
+    /// Désérialise une transaction (legacy ou v0) encodée en base64 ou en base58.
+    /// Les espaces et retours à la ligne (transaction copiée sur plusieurs lignes) sont ignorés.
+    pub fn deserialize(encoded: &str) -> Result<VersionedTransaction, Box<dyn std::error::Error>> {
+        let encoded: String = encoded.split_whitespace().collect();
+        [
+            BASE64_STANDARD.decode(&encoded).ok(),
+            bs58::decode(&encoded).into_vec().ok(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|bytes| bincode::deserialize::<VersionedTransaction>(&bytes).ok())
+        .find(|transaction| transaction.sanitize().is_ok())
+        .ok_or_else(|| {
+            "Invalid transaction: expected a serialized legacy or v0 transaction, in base64 or base58"
+                .into()
+        })
+    }
+
+    /// Sérialise une transaction en base64.
+    pub fn serialize(
+        transaction: &VersionedTransaction,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(BASE64_STANDARD.encode(bincode::serialize(transaction)?))
+    }
+
+    /// Charge les adresses référencées par les tables d'adresses (v0) : celles en écriture de toutes les tables,
+    /// puis celles en lecture seule, dans l'ordre des comptes du message.
+    pub fn load_addresses(
+        config: &WalletConfig,
+        lookups: &[MessageAddressTableLookup],
+    ) -> Result<LoadedAddresses, Box<dyn std::error::Error>> {
+        let mut loaded = LoadedAddresses::default();
+        for lookup in lookups {
+            let table = SolanaLookupTable::fetch(config, &lookup.account_key)?;
+            let select = |indexes: &[u8]| {
+                indexes
+                    .iter()
+                    .map(|index| {
+                        table
+                            .addresses
+                            .get(*index as usize)
+                            .copied()
+                            .ok_or_else(|| {
+                                format!(
+                                    "Index {} out of range in address lookup table {}",
+                                    index, lookup.account_key
+                                )
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            loaded.writable.extend(select(&lookup.writable_indexes)?);
+            loaded.readonly.extend(select(&lookup.readonly_indexes)?);
+        }
+        Ok(loaded)
+    }
+
+    /// Reconstruit les instructions d'un message (les adresses des tables d'adresses sont chargées).
+    pub fn instructions(
+        config: &WalletConfig,
+        message: &VersionedMessage,
+    ) -> Result<Vec<Instruction>, Box<dyn std::error::Error>> {
+        let loaded = match message.address_table_lookups() {
+            Some(lookups) if !lookups.is_empty() => Some(Self::load_addresses(config, lookups)?),
+            _ => None,
+        };
+        let account_keys = AccountKeys::new(message.static_account_keys(), loaded.as_ref());
+        let key = |index: u8| {
+            account_keys
+                .get(index as usize)
+                .copied()
+                .ok_or_else(|| format!("Account index {} out of range", index))
+        };
+
+        message
+            .instructions()
+            .iter()
+            .map(|instruction| {
+                let accounts = instruction
+                    .accounts
+                    .iter()
+                    .map(|index| {
+                        let pubkey = key(*index)?;
+                        let index = *index as usize;
+                        Ok(if message.is_maybe_writable(index) {
+                            AccountMeta::new(pubkey, message.is_signer(index))
+                        } else {
+                            AccountMeta::new_readonly(pubkey, message.is_signer(index))
+                        })
+                    })
+                    .collect::<Result<Vec<_>, String>>()?;
+                Ok(Instruction {
+                    program_id: key(instruction.program_id_index)?,
+                    accounts,
+                    data: instruction.data.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Décode une instruction compilée.
+    ///
+    /// # Arguments:
+    /// - instruction - L'instruction, dont les comptes sont des indices dans "account_keys".
+    /// - account_keys - Les comptes du message (avec les adresses des tables d'adresses, si elles sont chargées).
+    pub fn decode_instruction(
+        instruction: &CompiledInstruction,
+        account_keys: &AccountKeys,
+    ) -> DecodedInstruction {
+        let key = |index: u8| {
+            account_keys
+                .get(index as usize)
+                .map_or_else(|| format!("#{}", index), Pubkey::to_string)
+        };
+        let mut decoded = DecodedInstruction {
+            program: "unknown".to_string(),
+            program_id: key(instruction.program_id_index),
+            recognised: false,
+            instruction_type: None,
+            info: Value::Null,
+            accounts: instruction
+                .accounts
+                .iter()
+                .map(|index| key(*index))
+                .collect(),
+            data: instruction
+                .data
+                .iter()
+                .map(|b| format!("{:02x}", b))
+                .collect(),
+        };
+
+        let Some(program_id) = account_keys.get(instruction.program_id_index as usize) else {
+            return decoded;
+        };
+
+        if *program_id == compute_budget::id() {
+            decoded.program = "compute-budget".to_string();
+            if let Some((instruction_type, info)) = Self::decode_compute_budget(&instruction.data) {
+                decoded.recognised = true;
+                decoded.instruction_type = Some(instruction_type.to_string());
+                decoded.info = info;
+            }
+            return decoded;
+        }
+
+        // Les décodeurs lisent les comptes par indice : ils ne sont appelés que si tous les comptes sont connus.
+        let resolved = instruction
+            .accounts
+            .iter()
+            .all(|index| account_keys.get(*index as usize).is_some());
+        if !resolved {
+            return decoded;
+        }
+
+        match parse_instruction::parse(program_id, instruction, account_keys, None) {
+            Ok(parsed) => {
+                decoded.program = parsed.program;
+                decoded.recognised = true;
+                match parsed.parsed {
+                    // Le programme Memo donne directement le texte du mémo.
+                    Value::String(memo) => {
+                        decoded.instruction_type = Some("memo".to_string());
+                        decoded.info = json!({ "memo": memo });
+                    }
+                    mut parsed => {
+                        decoded.instruction_type = parsed["type"].as_str().map(ToString::to_string);
+                        decoded.info = parsed["info"].take();
+                    }
+                }
+            }
+            Err(ParseInstructionError::InstructionNotParsable(program))
+            | Err(ParseInstructionError::InstructionKeyMismatch(program)) => {
+                decoded.program = kebab_case(&format!("{:?}", program));
+            }
+            Err(_) => {}
+        }
+        decoded
+    }
+
+    /// Décode une instruction du programme ComputeBudget (type et paramètres).
+    fn decode_compute_budget(data: &[u8]) -> Option<(&'static str, Value)> {
+        let u32_at = |offset: usize| {
+            data.get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+        };
+        match (data.first()?, data.len()) {
+            (0, 9) => Some((
+                "requestUnitsDeprecated",
+                json!({ "units": u32_at(1)?, "additionalFee": u32_at(5)? }),
+            )),
+            (1, 5) => Some(("requestHeapFrame", json!({ "bytes": u32_at(1)? }))),
+            (2, 5) => Some(("setComputeUnitLimit", json!({ "units": u32_at(1)? }))),
+            (3, 9) => {
+                let price = u64::from_le_bytes(data[1..9].try_into().ok()?);
+                Some(("setComputeUnitPrice", json!({ "microLamports": price })))
+            }
+            (4, 5) => Some((
+                "setLoadedAccountsDataSizeLimit",
+                json!({ "bytes": u32_at(1)? }),
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// "SplAssociatedTokenAccount" -> "spl-associated-token-account".
+fn kebab_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            result.push('-');
+        }
+        result.push(c.to_ascii_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::compute_budget::ComputeBudgetInstruction;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::message::{Message, VersionedMessage};
+    use solana_sdk::signature::{Keypair, Signer};
+    use solana_sdk::system_instruction;
+    use solana_sdk::transaction::Transaction;
+
+    #[test]
+    fn test_decode_legacy_transaction() {
+        let payer = Keypair::new();
+        let recipient = Pubkey::new_unique();
+        let unknown_program = Pubkey::new_unique();
+        let message = Message::new(
+            &[
+                ComputeBudgetInstruction::set_compute_unit_price(1_000),
+                system_instruction::transfer(&payer.pubkey(), &recipient, 5_000),
+                solana_sdk::instruction::Instruction::new_with_bytes(
+                    unknown_program,
+                    &[1, 2, 3],
+                    vec![],
+                ),
+            ],
+            Some(&payer.pubkey()),
+        );
+        let transaction =
+            VersionedTransaction::from(Transaction::new(&[&payer], message, Hash::default()));
+        let encoded = SolanaDecoder::serialize(&transaction).unwrap();
+        // Les transactions en base58 sont aussi acceptées.
+        let base58 = bs58::encode(bincode::serialize(&transaction).unwrap()).into_string();
+        assert_eq!(SolanaDecoder::deserialize(&base58).unwrap(), transaction);
+
+        let decoded = SolanaDecoder::deserialize(&encoded).unwrap();
+        assert!(matches!(decoded.message, VersionedMessage::Legacy(_)));
+        let keys = decoded.message.static_account_keys();
+        let account_keys = AccountKeys::new(keys, None);
+        let instructions: Vec<DecodedInstruction> = decoded
+            .message
+            .instructions()
+            .iter()
+            .map(|instruction| SolanaDecoder::decode_instruction(instruction, &account_keys))
+            .collect();
+
+        assert_eq!(instructions[0].program, "compute-budget");
+        assert_eq!(instructions[0].info["microLamports"], 1_000);
+        assert_eq!(instructions[1].program, "system");
+        assert_eq!(
+            instructions[1].instruction_type.as_deref(),
+            Some("transfer")
+        );
+        assert_eq!(instructions[1].info["lamports"], 5_000);
+        assert_eq!(instructions[1].info["destination"], recipient.to_string());
+        assert!(!instructions[2].recognised);
+        assert_eq!(instructions[2].data, "010203");
+
+        assert!(SolanaDecoder::deserialize("not a transaction").is_err());
+    }
+}
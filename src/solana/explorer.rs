@@ -0,0 +1,77 @@
+/// Modèle par défaut de l'URL d'une transaction dans l'explorateur.
+pub const DEFAULT_EXPLORER_TEMPLATE: &str =
+    "https://explorer.solana.com/tx/{signature}?cluster={cluster}";
+
+pub struct SolanaExplorer {}
+
+impl SolanaExplorer {
+    /// Retourne le cluster correspondant à l'URL RPC, tel qu'attendu par le paramètre "cluster" des explorateurs :
+    /// "mainnet-beta", "devnet", "testnet", ou "custom&customUrl=<URL RPC>" pour un autre réseau (validateur local par exemple).
+    /// Les URL des fournisseurs RPC contiennent généralement le nom du cluster ("https://devnet.helius-rpc.com"...).
+    pub fn cluster(rpc_url: &str) -> String {
+        let url = rpc_url.to_lowercase();
+        if url.contains("devnet") {
+            "devnet".to_string()
+        } else if url.contains("testnet") {
+            "testnet".to_string()
+        } else if url.contains("mainnet") {
+            "mainnet-beta".to_string()
+        } else {
+            format!("custom&customUrl={}", Self::encode(rpc_url))
+        }
+    }
+
+    /// Construit l'URL d'une transaction à partir du modèle, où "{signature}" est remplacé par la signature
+    /// et "{cluster}" par le cluster de l'URL RPC (voir `cluster`).
+    pub fn transaction_url(template: &str, rpc_url: &str, signature: &str) -> String {
+        template
+            .replace("{signature}", signature)
+            .replace("{cluster}", &Self::cluster(rpc_url))
+    }
+
+    /// Encode une valeur pour la placer dans une URL (les caractères non réservés sont conservés).
+    pub fn encode(value: &str) -> String {
+        value
+            .bytes()
+            .map(|byte| match byte {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                    (byte as char).to_string()
+                }
+                _ => format!("%{:02X}", byte),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_url() {
+        assert_eq!(
+            SolanaExplorer::transaction_url(
+                DEFAULT_EXPLORER_TEMPLATE,
+                "https://api.devnet.solana.com",
+                "abc"
+            ),
+            "https://explorer.solana.com/tx/abc?cluster=devnet"
+        );
+        assert_eq!(
+            SolanaExplorer::transaction_url(
+                "https://solscan.io/tx/{signature}?cluster={cluster}",
+                "https://api.mainnet-beta.solana.com",
+                "abc"
+            ),
+            "https://solscan.io/tx/abc?cluster=mainnet-beta"
+        );
+        assert_eq!(
+            SolanaExplorer::transaction_url(
+                DEFAULT_EXPLORER_TEMPLATE,
+                "http://localhost:8899",
+                "abc"
+            ),
+            "https://explorer.solana.com/tx/abc?cluster=custom&customUrl=http%3A%2F%2Flocalhost%3A8899"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::bip::mnemonic::BipMnemonic;
+use crate::bip::seed::BipSeed;
+use crate::solana::address::SolanaAddress;
+use solana_sdk::signature::{Keypair, Signer};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// L'alphabet base58 utilisé par Solana pour encoder les clés publiques.
+const BASE58_ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Le motif recherché dans la clé publique (préfixe et/ou suffixe).
+#[derive(Clone, Debug)]
+pub struct GrindPattern {
+    pub prefix: Option<String>,
+    pub suffix: Option<String>,
+    pub ignore_case: bool,
+}
+
+/// La façon de générer les paires de clés candidates.
+#[derive(Clone, Debug)]
+pub enum GrindMode {
+    /// Paires de clés aléatoires (non récupérables depuis une mnémonique).
+    Random,
+    /// Mnémoniques BIP39 aléatoires (sans passphrase), dérivées avec le chemin du wallet à l'index donné.
+    Mnemonic {
+        derivation_index: usize,
+        derivation_template: String,
+    },
+}
+
+/// Une paire de clés correspondant au motif recherché.
+pub struct GrindMatch {
+    pub keypair: Keypair,
+    /// La mnémonique dont est issue la paire de clés (mode `GrindMode::Mnemonic` uniquement).
+    pub phrase: Option<String>,
+}
+
+impl GrindPattern {
+    /// Vérifie que le motif n'est pas vide et ne contient que des caractères base58.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.prefix.is_none() && self.suffix.is_none() {
+            return Err("A prefix or a suffix is required".to_string());
+        }
+
+        for part in self.prefix.iter().chain(self.suffix.iter()) {
+            if part.is_empty() {
+                return Err("The prefix and the suffix cannot be empty".to_string());
+            }
+            if let Some(c) = part.chars().find(|c| self.alternatives(*c) == 0) {
+                return Err(format!("'{}' is not a base58 character", c));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Indique si la clé publique (encodée en base58) correspond au motif.
+    pub fn matches(&self, pubkey: &str) -> bool {
+        let matches_part = |part: &str, candidate: Option<&str>| match candidate {
+            Some(candidate) if self.ignore_case => candidate.eq_ignore_ascii_case(part),
+            Some(candidate) => candidate == part,
+            None => false,
+        };
+
+        let prefix_ok = match self.prefix.as_deref() {
+            Some(prefix) => matches_part(prefix, pubkey.get(..prefix.len())),
+            None => true,
+        };
+        let suffix_ok = match self.suffix.as_deref() {
+            Some(suffix) => {
+                let start = pubkey.len().checked_sub(suffix.len());
+                matches_part(suffix, start.and_then(|start| pubkey.get(start..)))
+            }
+            None => true,
+        };
+
+        prefix_ok && suffix_ok
+    }
+
+    /// Estime le nombre moyen de tentatives nécessaires pour trouver une correspondance.
+    /// (estimation : chaque caractère base58 est supposé équiprobable)
+    pub fn expected_attempts(&self) -> f64 {
+        self.prefix
+            .iter()
+            .chain(self.suffix.iter())
+            .flat_map(|part| part.chars())
+            .map(|c| 58.0 / self.alternatives(c) as f64)
+            .product()
+    }
+
+    /// Nombre de caractères base58 acceptés pour un caractère du motif (1 ou 2 si la casse est ignorée).
+    fn alternatives(&self, c: char) -> usize {
+        BASE58_ALPHABET
+            .chars()
+            .filter(|a| {
+                if self.ignore_case {
+                    a.eq_ignore_ascii_case(&c)
+                } else {
+                    *a == c
+                }
+            })
+            .count()
+    }
+}
+
+pub struct SolanaGrind {}
+
+impl SolanaGrind {
+    /// Recherche des paires de clés dont la clé publique correspond au motif, sur plusieurs threads.
+    ///
+    /// # Arguments:
+    /// - pattern - Le motif recherché (préalablement validé).
+    /// - mode - La façon de générer les paires de clés candidates.
+    /// - threads - Le nombre de threads de recherche.
+    /// - count - Le nombre de correspondances souhaitées.
+    /// - on_progress - Appelée environ chaque seconde avec le nombre total de tentatives.
+    ///
+    /// # Returns:
+    /// Retourne les correspondances trouvées (exactement "count").
+    pub fn grind<F>(
+        pattern: &GrindPattern,
+        mode: &GrindMode,
+        threads: usize,
+        count: usize,
+        mut on_progress: F,
+    ) -> Vec<GrindMatch>
+    where
+        F: FnMut(u64),
+    {
+        let attempts = AtomicU64::new(0);
+        let found = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+        let mut matches = Vec::with_capacity(count);
+
+        thread::scope(|scope| {
+            for _ in 0..threads.max(1) {
+                let sender = sender.clone();
+                let (attempts, found) = (&attempts, &found);
+
+                scope.spawn(move || {
+                    while found.load(Ordering::Relaxed) < count {
+                        let candidate = Self::generate_candidate(mode);
+                        attempts.fetch_add(1, Ordering::Relaxed);
+
+                        if pattern.matches(&candidate.keypair.pubkey().to_string())
+                            && found.fetch_add(1, Ordering::SeqCst) < count
+                        {
+                            let _ = sender.send(candidate);
+                        }
+                    }
+                });
+            }
+
+            // Seuls les threads de recherche détiennent désormais un "sender" : le canal se ferme quand ils se terminent.
+            drop(sender);
+
+            loop {
+                match receiver.recv_timeout(Duration::from_secs(1)) {
+                    Ok(candidate) => matches.push(candidate),
+                    Err(RecvTimeoutError::Timeout) => on_progress(attempts.load(Ordering::Relaxed)),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        on_progress(attempts.load(Ordering::Relaxed));
+        matches
+    }
+
+    /// Génère une paire de clés candidate selon le mode demandé.
+    fn generate_candidate(mode: &GrindMode) -> GrindMatch {
+        match mode {
+            GrindMode::Random => GrindMatch {
+                keypair: Keypair::new(),
+                phrase: None,
+            },
+            GrindMode::Mnemonic {
+                derivation_index,
+                derivation_template,
+            } => {
+                let mnemonic = BipMnemonic::generate_mnemonic();
+                let seed = BipSeed::generate_seed(&mnemonic, "");
+                let derived_seed_bytes = BipSeed::derive_seed_bytes(
+                    BipSeed::get_seed_bytes(&seed),
+                    *derivation_index,
+                    derivation_template,
+                )
+                .expect("Failed to derive seed bytes");
+
+                GrindMatch {
+                    keypair: SolanaAddress::generate_keypair(&derived_seed_bytes),
+                    phrase: Some(BipMnemonic::get_mnemonic_to_str(&mnemonic).to_string()),
+                }
+            }
+        }
+    }
+}
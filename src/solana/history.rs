@@ -0,0 +1,40 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use solana_client::rpc_client::GetConfirmedSignaturesForAddress2Config;
+use solana_client::rpc_response::RpcConfirmedTransactionStatusWithSignature;
+use solana_sdk::pubkey::Pubkey;
+
+pub struct SolanaHistory {
+    config: WalletConfig,
+}
+
+impl SolanaHistory {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Retourne les signatures des transactions les plus récentes impliquant une adresse (de la plus récente à la plus ancienne).
+    ///
+    /// # Arguments:
+    /// - pubkey - L'adresse dont on souhaite l'historique.
+    /// - limit - Le nombre maximum de transactions à retourner.
+    ///
+    /// # Returns:
+    /// - Ok(signatures) - Si la requête est réussie.
+    /// - Err(e) - Si une erreur se produit lors de la requête RPC.
+    pub fn get_history(
+        &self,
+        pubkey: &Pubkey,
+        limit: usize,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>, Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(&self.config);
+        let config = GetConfirmedSignaturesForAddress2Config {
+            limit: Some(limit),
+            ..GetConfirmedSignaturesForAddress2Config::default()
+        };
+
+        client
+            .get_signatures_for_address_with_config(pubkey, config)
+            .map_err(Into::into)
+    }
+}
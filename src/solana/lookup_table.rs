@@ -0,0 +1,70 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use solana_sdk::address_lookup_table::state::AddressLookupTable;
+use solana_sdk::address_lookup_table::AddressLookupTableAccount;
+use solana_sdk::pubkey::Pubkey;
+
+/// Le nombre maximal d'adresses ajoutées par transaction ("alt extend") : chaque adresse occupe 32 octets,
+/// et la transaction doit tenir dans un paquet avec ses signatures et ses autres comptes.
+pub const EXTEND_CHUNK_SIZE: usize = 20;
+
+/// L'état d'une table d'adresses (address lookup table).
+#[derive(Debug)]
+pub struct LookupTableState {
+    pub address: Pubkey,
+    /// La clé qui peut modifier la table (absente si la table est gelée).
+    pub authority: Option<Pubkey>,
+    /// Le slot de désactivation (absent si la table est active).
+    pub deactivation_slot: Option<u64>,
+    /// Le slot du dernier ajout d'adresses.
+    pub last_extended_slot: u64,
+    pub addresses: Vec<Pubkey>,
+}
+
+pub struct SolanaLookupTable {}
+
+impl SolanaLookupTable {
+    /// Lit une table d'adresses via RPC.
+    pub fn fetch(
+        config: &WalletConfig,
+        address: &Pubkey,
+    ) -> Result<LookupTableState, Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(config);
+        let account = client
+            .get_account(address)
+            .map_err(|e| format!("Cannot load address lookup table {}: {}", address, e))?;
+        if account.owner != solana_sdk::address_lookup_table::program::id() {
+            return Err(format!("{} is not an address lookup table", address).into());
+        }
+        let table = AddressLookupTable::deserialize(&account.data)
+            .map_err(|e| format!("{} is not an address lookup table: {}", address, e))?;
+
+        Ok(LookupTableState {
+            address: *address,
+            authority: table.meta.authority,
+            deactivation_slot: Some(table.meta.deactivation_slot).filter(|slot| *slot != u64::MAX),
+            last_extended_slot: table.meta.last_extended_slot,
+            addresses: table.addresses.to_vec(),
+        })
+    }
+
+    /// Lit des tables d'adresses, pour compiler un message v0 (voir `SolanaTransaction::send_versioned_instructions`).
+    pub fn fetch_accounts(
+        config: &WalletConfig,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, Box<dyn std::error::Error>> {
+        addresses
+            .iter()
+            .map(|address| {
+                let table = Self::fetch(config, address)?;
+                if table.deactivation_slot.is_some() {
+                    return Err(format!("Address lookup table {} is deactivated", address).into());
+                }
+                Ok(AddressLookupTableAccount {
+                    key: table.address,
+                    addresses: table.addresses,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,218 @@
+use serde::Serialize;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::offchain_message::{MessageFormat, OffchainMessage};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signature, Signer};
+use std::fmt;
+
+/// La version du format des messages hors chaîne ("off-chain messages") utilisée pour signer.
+const OFFCHAIN_MESSAGE_VERSION: u8 = 0;
+
+/// La façon dont un message a été signé.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageEncoding {
+    /// Signature ed25519 des octets du message, tels quels.
+    Raw,
+    /// Message hors chaîne Solana (version 0) : ASCII imprimable, au plus 1212 octets.
+    OffchainRestrictedAscii,
+    /// Message hors chaîne Solana (version 0) : UTF-8, au plus 1212 octets.
+    OffchainLimitedUtf8,
+    /// Message hors chaîne Solana (version 0) : UTF-8, au plus 65515 octets.
+    OffchainExtendedUtf8,
+}
+
+impl From<MessageFormat> for MessageEncoding {
+    fn from(format: MessageFormat) -> Self {
+        match format {
+            MessageFormat::RestrictedAscii => MessageEncoding::OffchainRestrictedAscii,
+            MessageFormat::LimitedUtf8 => MessageEncoding::OffchainLimitedUtf8,
+            MessageFormat::ExtendedUtf8 => MessageEncoding::OffchainExtendedUtf8,
+        }
+    }
+}
+
+impl fmt::Display for MessageEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MessageEncoding::Raw => write!(f, "raw ed25519"),
+            MessageEncoding::OffchainRestrictedAscii => {
+                write!(f, "off-chain message v0, restricted ASCII")
+            }
+            MessageEncoding::OffchainLimitedUtf8 => {
+                write!(f, "off-chain message v0, limited UTF-8")
+            }
+            MessageEncoding::OffchainExtendedUtf8 => {
+                write!(f, "off-chain message v0, extended UTF-8")
+            }
+        }
+    }
+}
+
+pub struct SolanaMessage {}
+
+impl SolanaMessage {
+    /// Signe un message.
+    ///
+    /// Par défaut, le message est d'abord encapsulé au format des messages hors chaîne Solana : domaine de signature
+    /// ("\xffsolana offchain"), version de l'en-tête, format (choisi selon le contenu et la taille) et longueur.
+    /// Une signature ainsi produite ne peut pas être confondue avec celle d'une transaction.
+    ///
+    /// # Arguments:
+    /// - keypair - La paire de clés qui signe.
+    /// - message - Les octets du message.
+    /// - raw - Signer les octets tels quels (signature ed25519 brute), sans le format hors chaîne.
+    pub fn sign(
+        keypair: &Keypair,
+        message: &[u8],
+        raw: bool,
+    ) -> Result<(Signature, MessageEncoding), Box<dyn std::error::Error>> {
+        if message.is_empty() {
+            return Err("The message cannot be empty".into());
+        }
+        if raw {
+            Self::check_raw(message)?;
+            return Ok((keypair.sign_message(message), MessageEncoding::Raw));
+        }
+
+        let offchain_message = Self::offchain_message(message)?;
+        let signature = offchain_message
+            .sign(keypair)
+            .map_err(|e| format!("Invalid off-chain message: {}", e))?;
+        Ok((signature, offchain_message.get_format().into()))
+    }
+
+    /// Vérifie la signature d'un message par une clé publique, au format hors chaîne puis brut.
+    /// Retourne la façon dont le message a été signé, ou None si la signature n'est pas celle de cette clé.
+    pub fn verify(
+        pubkey: &Pubkey,
+        message: &[u8],
+        signature: &Signature,
+    ) -> Option<MessageEncoding> {
+        if let Ok(offchain_message) = Self::offchain_message(message) {
+            if offchain_message.verify(pubkey, signature) == Ok(true) {
+                return Some(offchain_message.get_format().into());
+            }
+        }
+
+        signature
+            .verify(pubkey.as_ref(), message)
+            .then_some(MessageEncoding::Raw)
+    }
+
+    /// Refuse de signer en mode brut des octets qui ne sont pas un simple message : un message de transaction
+    /// (legacy ou v0), dont la signature serait celle d'une transaction valide (sans passer par "sign_tx" ni par
+    /// la politique de dépense), ou un message hors chaîne déjà encapsulé.
+    fn check_raw(message: &[u8]) -> Result<(), Box<dyn std::error::Error>> {
+        if message.starts_with(OffchainMessage::SIGNING_DOMAIN) {
+            return Err(
+                "The message starts with the off-chain message signing domain: sign its content without --raw"
+                    .into(),
+            );
+        }
+        if bincode::deserialize::<VersionedMessage>(message).is_ok() {
+            return Err(
+                "The message is a serialized Solana transaction message: use sign_tx to inspect and sign it"
+                    .into(),
+            );
+        }
+        Ok(())
+    }
+
+    fn offchain_message(message: &[u8]) -> Result<OffchainMessage, Box<dyn std::error::Error>> {
+        OffchainMessage::new(OFFCHAIN_MESSAGE_VERSION, message).map_err(|e| {
+            format!(
+                "Invalid off-chain message ({} bytes, at most 65515 bytes of UTF-8): {}",
+                message.len(),
+                e
+            )
+            .into()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_and_verify() {
+        let keypair = Keypair::new();
+        let other = Keypair::new();
+
+        let (signature, encoding) =
+            SolanaMessage::sign(&keypair, b"Hello, Solana!", false).unwrap();
+        assert_eq!(encoding, MessageEncoding::OffchainRestrictedAscii);
+        assert_eq!(
+            SolanaMessage::verify(&keypair.pubkey(), b"Hello, Solana!", &signature),
+            Some(MessageEncoding::OffchainRestrictedAscii)
+        );
+        assert_eq!(
+            SolanaMessage::verify(&other.pubkey(), b"Hello, Solana!", &signature),
+            None
+        );
+        assert_eq!(
+            SolanaMessage::verify(&keypair.pubkey(), b"Hello, Solana?", &signature),
+            None
+        );
+
+        // Le format hors chaîne ajoute un préfixe : la signature n'est pas celle des octets bruts.
+        assert!(!signature.verify(keypair.pubkey().as_ref(), b"Hello, Solana!"));
+
+        let (signature, encoding) =
+            SolanaMessage::sign(&keypair, "Ça va ?".as_bytes(), false).unwrap();
+        assert_eq!(encoding, MessageEncoding::OffchainLimitedUtf8);
+        assert!(
+            SolanaMessage::verify(&keypair.pubkey(), "Ça va ?".as_bytes(), &signature).is_some()
+        );
+    }
+
+    #[test]
+    fn test_raw_and_invalid_messages() {
+        let keypair = Keypair::new();
+
+        let (signature, encoding) = SolanaMessage::sign(&keypair, &[0xde, 0xad], true).unwrap();
+        assert_eq!(encoding, MessageEncoding::Raw);
+        assert_eq!(
+            SolanaMessage::verify(&keypair.pubkey(), &[0xde, 0xad], &signature),
+            Some(MessageEncoding::Raw)
+        );
+
+        // Les octets qui ne sont pas de l'UTF-8 ne peuvent être signés qu'en mode brut.
+        assert!(SolanaMessage::sign(&keypair, &[0xff, 0xfe], false).is_err());
+        assert!(SolanaMessage::sign(&keypair, b"", true).is_err());
+    }
+
+    #[test]
+    fn test_raw_transaction_messages_refused() {
+        let keypair = Keypair::new();
+        let instruction = solana_sdk::system_instruction::transfer(
+            &keypair.pubkey(),
+            &Pubkey::new_unique(),
+            1_000,
+        );
+
+        // Un message de transaction legacy, puis v0.
+        let legacy = solana_sdk::message::Message::new(
+            std::slice::from_ref(&instruction),
+            Some(&keypair.pubkey()),
+        );
+        let v0 = solana_sdk::message::v0::Message::try_compile(
+            &keypair.pubkey(),
+            &[instruction],
+            &[],
+            Default::default(),
+        )
+        .unwrap();
+        for message in [VersionedMessage::Legacy(legacy), VersionedMessage::V0(v0)] {
+            let error = SolanaMessage::sign(&keypair, &message.serialize(), true).unwrap_err();
+            assert!(error.to_string().contains("use sign_tx"));
+        }
+
+        // Un message hors chaîne déjà encapsulé.
+        let offchain_message = OffchainMessage::new(0, b"Hello").unwrap();
+        let error = SolanaMessage::sign(&keypair, &offchain_message.serialize().unwrap(), true)
+            .unwrap_err();
+        assert!(error.to_string().contains("signing domain"));
+    }
+}
@@ -1,3 +1,17 @@
 pub mod address;
 pub mod balance;
+pub mod confirmation;
+pub mod decoder;
+pub mod explorer;
+pub mod grind;
+pub mod history;
+pub mod lookup_table;
+pub mod message;
+pub mod multisig;
+pub mod pay;
+pub mod policy;
+pub mod recipient;
+pub mod rpc;
+pub mod stake;
+pub mod token;
 pub mod transaction;
@@ -0,0 +1,301 @@
+use crate::solana::decoder::SolanaDecoder;
+use crate::solana::pay::MEMO_PROGRAM_ID;
+use serde::{Deserialize, Serialize};
+use solana_sdk::compute_budget;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::VersionedMessage;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use solana_sdk::transaction::VersionedTransaction;
+use std::fs;
+
+/// La version du format des demandes de signature.
+const SIGNING_REQUEST_VERSION: u32 = 2;
+
+/// Le nombre maximal de variantes d'une demande de signature (une par groupe de co-signataires).
+pub const MAX_VARIANTS: usize = 32;
+
+/// Une demande de signature : un document JSON portable (envoyé par e-mail, copié sur une clé USB...)
+/// contenant une transaction à nonce durable, en plusieurs variantes. Chaque signataire y ajoute sa signature
+/// ("multisig approve"), puis une variante complète est envoyée ("multisig submit").
+///
+/// Le seuil "M sur N" des co-signataires est obtenu avec une variante par groupe de M co-signataires : chacune
+/// exige, en plus de la trésorerie, la signature de son groupe (comptes signataires de l'instruction Memo).
+/// Toutes les variantes avancent le même nonce durable : dès que l'une est exécutée, les autres sont invalides.
+///
+/// Le fichier ne contient que les transactions : le transfert, la description, les signataires et le seuil en
+/// sont déduits (voir `details`), un fichier modifié ne peut donc pas afficher un autre transfert que celui signé.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SigningRequest {
+    pub version: u32,
+    /// Les variantes de la transaction, en base64, avec les signatures déjà ajoutées.
+    pub transactions: Vec<String>,
+}
+
+/// Le contenu d'une demande de signature, déduit de ses transactions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SigningRequestDetails {
+    pub description: String,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub lamports: u64,
+    pub nonce_account: Pubkey,
+    pub nonce_authority: Pubkey,
+    /// La valeur du nonce utilisée comme blockhash.
+    pub nonce_blockhash: Hash,
+    pub fee_payer: Pubkey,
+    /// Les co-signataires, dans l'ordre de leur première apparition dans les variantes.
+    pub co_signers: Vec<Pubkey>,
+    /// Le nombre de co-signataires requis (0 sans co-signataire).
+    pub threshold: usize,
+}
+
+impl SigningRequest {
+    /// Crée une demande de signature à partir des variantes non signées d'une transaction.
+    pub fn new(transactions: &[VersionedTransaction]) -> Result<Self, Box<dyn std::error::Error>> {
+        let request = Self {
+            version: SIGNING_REQUEST_VERSION,
+            transactions: transactions
+                .iter()
+                .map(SolanaDecoder::serialize)
+                .collect::<Result<_, _>>()?,
+        };
+        request.details()?;
+        Ok(request)
+    }
+
+    /// Lit une demande de signature, et vérifie que ses variantes forment une demande cohérente.
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Cannot read '{}': {}", path, e))?;
+        let request: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("'{}' is not a signing request: {}", path, e))?;
+        if request.version != SIGNING_REQUEST_VERSION {
+            return Err(format!(
+                "Unsupported signing request version {} (expected {})",
+                request.version, SIGNING_REQUEST_VERSION
+            )
+            .into());
+        }
+        request
+            .details()
+            .map_err(|e| format!("'{}' is not a valid signing request: {}", path, e))?;
+        Ok(request)
+    }
+
+    /// Enregistre la demande de signature (le fichier est remplacé).
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .map_err(|e| format!("Cannot write '{}': {}", path, e).into())
+    }
+
+    pub fn transactions(&self) -> Result<Vec<VersionedTransaction>, Box<dyn std::error::Error>> {
+        self.transactions
+            .iter()
+            .map(|transaction| SolanaDecoder::deserialize(transaction))
+            .collect()
+    }
+
+    /// Décode les variantes : elles doivent avoir le même transfert, le même nonce et la même description, et
+    /// exiger chacune un groupe différent de co-signataires, de même taille (le seuil). Tous les groupes possibles
+    /// doivent être présents, pour que n'importe quels co-signataires en nombre suffisant puissent approuver.
+    pub fn details(&self) -> Result<SigningRequestDetails, Box<dyn std::error::Error>> {
+        let mut details: Option<SigningRequestDetails> = None;
+        let mut groups: Vec<Vec<Pubkey>> = Vec::new();
+        for transaction in self.transactions()? {
+            let (variant, group) = Self::parse_variant(&transaction)?;
+            match &mut details {
+                None => details = Some(variant),
+                Some(details) => {
+                    let expected = SigningRequestDetails {
+                        co_signers: details.co_signers.clone(),
+                        threshold: details.threshold,
+                        ..variant
+                    };
+                    if *details != expected {
+                        return Err("its transactions do not describe the same transfer".into());
+                    }
+                    if group.len() != details.threshold {
+                        return Err(
+                            "its transactions require different numbers of co-signers".into()
+                        );
+                    }
+                    for signer in &group {
+                        if !details.co_signers.contains(signer) {
+                            details.co_signers.push(*signer);
+                        }
+                    }
+                }
+            }
+            if groups.contains(&group) {
+                return Err("two of its transactions require the same co-signers".into());
+            }
+            groups.push(group);
+        }
+
+        let details = details.ok_or("it contains no transaction")?;
+        if groups.len() != combinations(details.co_signers.len(), details.threshold).len() {
+            return Err(format!(
+                "it does not contain a transaction for every group of {} of its {} co-signers",
+                details.threshold,
+                details.co_signers.len()
+            )
+            .into());
+        }
+        Ok(details)
+    }
+
+    /// Décode une variante : avance du nonce, instructions "Compute Budget" éventuelles, mémo signé par un groupe
+    /// de co-signataires, puis le transfert.
+    fn parse_variant(
+        transaction: &VersionedTransaction,
+    ) -> Result<(SigningRequestDetails, Vec<Pubkey>), Box<dyn std::error::Error>> {
+        let VersionedMessage::Legacy(message) = &transaction.message else {
+            return Err("its transactions must be legacy transactions".into());
+        };
+        let invalid = || "its transactions are not multisig transfers".to_string();
+        let keys = &message.account_keys;
+        let signers = keys
+            .get(..message.header.num_required_signatures as usize)
+            .filter(|signers| !signers.is_empty())
+            .ok_or_else(invalid)?;
+        let instructions = message
+            .instructions
+            .iter()
+            .map(|instruction| {
+                let accounts = instruction
+                    .accounts
+                    .iter()
+                    .map(|index| keys.get(*index as usize).copied())
+                    .collect::<Option<Vec<_>>>()?;
+                Some((
+                    *keys.get(instruction.program_id_index as usize)?,
+                    accounts,
+                    &instruction.data,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(invalid)?;
+
+        let [(nonce_program, nonce_accounts, nonce_data), budget @ .., (memo_program, memo_accounts, memo_data), (transfer_program, transfer_accounts, transfer_data)] =
+            instructions.as_slice()
+        else {
+            return Err(invalid().into());
+        };
+        if *nonce_program != system_program::id()
+            || *transfer_program != system_program::id()
+            || *memo_program != MEMO_PROGRAM_ID
+            || budget
+                .iter()
+                .any(|(program, _, _)| *program != compute_budget::id())
+        {
+            return Err(invalid().into());
+        }
+        let (
+            Ok(SystemInstruction::AdvanceNonceAccount),
+            Ok(SystemInstruction::Transfer { lamports }),
+            [nonce_account, _, nonce_authority],
+            [from, to],
+        ) = (
+            bincode::deserialize(nonce_data),
+            bincode::deserialize(transfer_data),
+            nonce_accounts.as_slice(),
+            transfer_accounts.as_slice(),
+        )
+        else {
+            return Err(invalid().into());
+        };
+        // Les co-signataires doivent être des signataires de la transaction, chacun une seule fois.
+        if memo_accounts.iter().enumerate().any(|(index, signer)| {
+            !signers.contains(signer) || memo_accounts[..index].contains(signer)
+        }) {
+            return Err(invalid().into());
+        }
+
+        Ok((
+            SigningRequestDetails {
+                description: String::from_utf8(memo_data.to_vec()).map_err(|_| invalid())?,
+                from: *from,
+                to: *to,
+                lamports,
+                nonce_account: *nonce_account,
+                nonce_authority: *nonce_authority,
+                nonce_blockhash: message.recent_blockhash,
+                fee_payer: signers[0],
+                co_signers: memo_accounts.clone(),
+                threshold: memo_accounts.len(),
+            },
+            memo_accounts.clone(),
+        ))
+    }
+
+    /// L'état des signatures : pour chaque signataire requis par au moins une variante, vrai si sa signature est
+    /// présente et valide dans toutes les variantes qui l'exigent.
+    pub fn signature_status(&self) -> Result<Vec<(Pubkey, bool)>, Box<dyn std::error::Error>> {
+        let mut status: Vec<(Pubkey, bool)> = Vec::new();
+        for transaction in self.transactions()? {
+            for ((signature, signer), valid) in transaction
+                .signatures
+                .iter()
+                .zip(transaction.message.static_account_keys())
+                .zip(transaction.verify_with_results())
+            {
+                let signed = valid && *signature != Signature::default();
+                match status.iter_mut().find(|(key, _)| key == signer) {
+                    Some((_, all_signed)) => *all_signed &= signed,
+                    None => status.push((*signer, signed)),
+                }
+            }
+        }
+        Ok(status)
+    }
+
+    /// La première variante dont toutes les signatures sont présentes et valides (elle peut être envoyée).
+    pub fn ready_transaction(
+        &self,
+    ) -> Result<Option<VersionedTransaction>, Box<dyn std::error::Error>> {
+        Ok(self.transactions()?.into_iter().find(|transaction| {
+            transaction
+                .signatures
+                .iter()
+                .zip(transaction.verify_with_results())
+                .all(|(signature, valid)| valid && *signature != Signature::default())
+        }))
+    }
+}
+
+/// Les groupes de "size" éléments parmi "count" (indices croissants, dans l'ordre lexicographique).
+pub fn combinations(count: usize, size: usize) -> Vec<Vec<usize>> {
+    if size > count {
+        return Vec::new();
+    }
+    let mut groups = Vec::new();
+    let mut group: Vec<usize> = (0..size).collect();
+    loop {
+        groups.push(group.clone());
+        // Avance le dernier indice qui peut l'être, puis replace les suivants juste après lui.
+        let Some(position) = (0..size).rev().find(|&i| group[i] < count - size + i) else {
+            return groups;
+        };
+        group[position] += 1;
+        for i in position + 1..size {
+            group[i] = group[i - 1] + 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combinations() {
+        assert_eq!(combinations(3, 2), vec![vec![0, 1], vec![0, 2], vec![1, 2]]);
+        assert_eq!(combinations(2, 0), vec![Vec::<usize>::new()]);
+        assert_eq!(combinations(4, 4).len(), 1);
+        assert_eq!(combinations(6, 3).len(), 20);
+        assert!(combinations(2, 3).is_empty());
+    }
+}
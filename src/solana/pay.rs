@@ -0,0 +1,381 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::token::{associated_token_address, TOKEN_PROGRAM_IDS};
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_instruction;
+use std::str::FromStr;
+
+/// Le schéma des URL Solana Pay.
+const SCHEME: &str = "solana:";
+
+/// Le programme "Memo" (v2).
+pub const MEMO_PROGRAM_ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// L'instruction "TransferChecked" des programmes Token et Token-2022.
+const TRANSFER_CHECKED_INSTRUCTION: u8 = 12;
+
+/// Les décimales du SOL (1 SOL = 1_000_000_000 lamports).
+pub const SOL_DECIMALS: u8 = 9;
+
+/// Une demande de transfert Solana Pay ("solana:<recipient>?amount=...&spl-token=...&reference=...").
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TransferRequest {
+    pub recipient: Pubkey,
+    /// Le montant, en unités de l'utilisateur (SOL ou unités du token, par exemple "1.5").
+    pub amount: Option<String>,
+    /// Le mint du token SPL à transférer (des SOL si absent).
+    pub spl_token: Option<Pubkey>,
+    /// Les clés de référence, ajoutées au transfert pour que le destinataire retrouve la transaction.
+    pub references: Vec<Pubkey>,
+    pub label: Option<String>,
+    pub message: Option<String>,
+    /// Le mémo à inclure dans la transaction.
+    pub memo: Option<String>,
+}
+
+impl TransferRequest {
+    /// Analyse une URL de demande de transfert Solana Pay.
+    pub fn parse(url: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let url = url.trim();
+        let rest = url
+            .get(..SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|_| &url[SCHEME.len()..])
+            .ok_or("A Solana Pay URL starts with \"solana:\"")?;
+        let (recipient, query) = rest.split_once('?').unwrap_or((rest, ""));
+
+        let recipient = percent_decode(recipient)?;
+        if recipient.starts_with("https:") {
+            return Err("Transaction requests (\"solana:https://...\") are not supported".into());
+        }
+        let mut request = Self {
+            recipient: Pubkey::from_str(&recipient)
+                .map_err(|_| format!("Invalid recipient '{}'", recipient))?,
+            ..Self::default()
+        };
+
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let key = percent_decode(key)?;
+            let value = percent_decode(value)?;
+            let field = match key.as_str() {
+                "reference" => {
+                    request.references.push(
+                        Pubkey::from_str(&value)
+                            .map_err(|_| format!("Invalid reference '{}'", value))?,
+                    );
+                    continue;
+                }
+                "amount" => {
+                    // Valide le format (nombre décimal positif) ; les décimales sont vérifiées lors du paiement.
+                    split_amount(&value)?;
+                    &mut request.amount
+                }
+                "spl-token" => {
+                    if request.spl_token.is_some() {
+                        return Err("Duplicate 'spl-token' parameter".into());
+                    }
+                    request.spl_token = Some(
+                        Pubkey::from_str(&value)
+                            .map_err(|_| format!("Invalid spl-token '{}'", value))?,
+                    );
+                    continue;
+                }
+                "label" => &mut request.label,
+                "message" => &mut request.message,
+                "memo" => &mut request.memo,
+                // Les paramètres inconnus sont ignorés.
+                _ => continue,
+            };
+            if field.is_some() {
+                return Err(format!("Duplicate '{}' parameter", key).into());
+            }
+            *field = Some(value);
+        }
+
+        Ok(request)
+    }
+
+    /// Génère l'URL de la demande de transfert.
+    pub fn to_url(&self) -> String {
+        let mut params: Vec<(&str, String)> = Vec::new();
+        if let Some(amount) = &self.amount {
+            params.push(("amount", amount.clone()));
+        }
+        if let Some(spl_token) = &self.spl_token {
+            params.push(("spl-token", spl_token.to_string()));
+        }
+        for reference in &self.references {
+            params.push(("reference", reference.to_string()));
+        }
+        for (key, value) in [
+            ("label", &self.label),
+            ("message", &self.message),
+            ("memo", &self.memo),
+        ] {
+            if let Some(value) = value {
+                params.push((key, value.clone()));
+            }
+        }
+
+        let query: Vec<String> = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, SolanaExplorer::encode(value)))
+            .collect();
+        if query.is_empty() {
+            format!("{}{}", SCHEME, self.recipient)
+        } else {
+            format!("{}{}?{}", SCHEME, self.recipient, query.join("&"))
+        }
+    }
+}
+
+pub struct SolanaPay {
+    config: WalletConfig,
+}
+
+impl SolanaPay {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Construit les instructions du transfert d'une demande Solana Pay : le mémo (s'il y en a un), puis le transfert
+    /// de SOL ou de tokens (vers le compte de token associé du destinataire), avec les clés de référence en lecture seule.
+    ///
+    /// # Arguments:
+    /// - payer - L'adresse qui paie.
+    /// - request - La demande de transfert.
+    /// - amount - Le montant, en unités de l'utilisateur (celui de la demande, ou saisi si la demande n'en a pas).
+    ///
+    /// # Returns:
+    /// Retourne les instructions et le montant en unités de base (lamports ou plus petites unités du token).
+    pub fn transfer_instructions(
+        &self,
+        payer: &Pubkey,
+        request: &TransferRequest,
+        amount: &str,
+    ) -> Result<(Vec<Instruction>, u64), Box<dyn std::error::Error>> {
+        let mut instructions = Vec::new();
+        if let Some(memo) = &request.memo {
+            instructions.push(Instruction::new_with_bytes(
+                MEMO_PROGRAM_ID,
+                memo.as_bytes(),
+                Vec::new(),
+            ));
+        }
+
+        let (mut transfer, base_units) = match &request.spl_token {
+            None => {
+                let lamports = parse_amount(amount, SOL_DECIMALS)?;
+                (
+                    system_instruction::transfer(payer, &request.recipient, lamports),
+                    lamports,
+                )
+            }
+            Some(mint) => self.token_transfer(payer, &request.recipient, mint, amount)?,
+        };
+        transfer.accounts.extend(
+            request
+                .references
+                .iter()
+                .map(|reference| AccountMeta::new_readonly(*reference, false)),
+        );
+        instructions.push(transfer);
+
+        Ok((instructions, base_units))
+    }
+
+    /// L'instruction "TransferChecked" d'un compte de token associé à un autre.
+    fn token_transfer(
+        &self,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        mint: &Pubkey,
+        amount: &str,
+    ) -> Result<(Instruction, u64), Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(&self.config);
+
+        let mint_account = client
+            .get_account(mint)
+            .map_err(|e| format!("Cannot read mint {}: {}", mint, e))?;
+        let token_program = mint_account.owner;
+        // Les décimales suivent l'autorité de mint (36 octets) et l'offre totale (8 octets) dans le compte du mint.
+        let decimals = match mint_account.data.get(44) {
+            Some(decimals) if TOKEN_PROGRAM_IDS.contains(&token_program) => *decimals,
+            _ => return Err(format!("{} is not a token mint", mint).into()),
+        };
+        let base_units = parse_amount(amount, decimals)?;
+
+        let source = associated_token_address(payer, mint, &token_program);
+        let destination = associated_token_address(recipient, mint, &token_program);
+        let destination_exists = client
+            .get_account_with_commitment(&destination, self.config.commitment_config())?
+            .value
+            .is_some();
+        if !destination_exists {
+            return Err(format!(
+                "The recipient {} has no token account for mint {}",
+                recipient, mint
+            )
+            .into());
+        }
+
+        let mut data = vec![TRANSFER_CHECKED_INSTRUCTION];
+        data.extend_from_slice(&base_units.to_le_bytes());
+        data.push(decimals);
+        let instruction = Instruction::new_with_bytes(
+            token_program,
+            &data,
+            vec![
+                AccountMeta::new(source, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(destination, false),
+                AccountMeta::new_readonly(*payer, true),
+            ],
+        );
+        Ok((instruction, base_units))
+    }
+}
+
+/// Convertit un montant décimal en unités de l'utilisateur ("1.5") en unités de base.
+///
+/// # Arguments:
+/// - amount - Le montant (nombre décimal positif, sans exposant).
+/// - decimals - Le nombre de décimales de l'unité (9 pour le SOL).
+pub fn parse_amount(amount: &str, decimals: u8) -> Result<u64, Box<dyn std::error::Error>> {
+    let (integer, fraction) = split_amount(amount)?;
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(format!("Amount '{}' has more than {} decimals", amount, decimals).into());
+    }
+
+    let too_large = || format!("Amount '{}' is too large", amount);
+    let scale = 10u64.checked_pow(decimals as u32).ok_or_else(too_large)?;
+    let fraction_units = match fraction {
+        "" => 0,
+        fraction => {
+            fraction.parse::<u64>()? * 10u64.pow((decimals as usize - fraction.len()) as u32)
+        }
+    };
+    integer
+        .parse::<u64>()
+        .ok()
+        .and_then(|integer| integer.checked_mul(scale))
+        .and_then(|units| units.checked_add(fraction_units))
+        .ok_or_else(|| too_large().into())
+}
+
+/// Sépare la partie entière et la partie décimale d'un montant, après avoir vérifié son format.
+pub fn split_amount(amount: &str) -> Result<(&str, &str), Box<dyn std::error::Error>> {
+    let (integer, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if integer.is_empty()
+        || !integer.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+        || (amount.contains('.') && fraction.is_empty())
+    {
+        return Err(format!("Invalid amount '{}'", amount).into());
+    }
+    Ok((integer, fraction))
+}
+
+/// Décode une valeur d'URL ("%XX", et "+" pour une espace).
+fn percent_decode(value: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let byte = value
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("Invalid percent-encoding in '{}'", value))?;
+                decoded.push(byte);
+                i += 3;
+            }
+            b'+' => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            byte => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded).map_err(|_| format!("Invalid UTF-8 in '{}'", value).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECIPIENT: &str = "mvines9iiHiQTysrwkJjGf2gb9Ex9jXJX8ns3qwf2kN";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+    const REFERENCE: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+
+    #[test]
+    fn test_parse_transfer_request() {
+        // Exemple de la spécification Solana Pay.
+        let url = format!(
+            "solana:{}?amount=0.01&spl-token={}&reference={}&label=Michael&message=Thanks%20for%20all%20the%20fish&memo=OrderId12345",
+            RECIPIENT, USDC, REFERENCE
+        );
+        let request = TransferRequest::parse(&url).unwrap();
+        assert_eq!(request.recipient.to_string(), RECIPIENT);
+        assert_eq!(request.amount.as_deref(), Some("0.01"));
+        assert_eq!(request.spl_token.unwrap().to_string(), USDC);
+        assert_eq!(request.references[0].to_string(), REFERENCE);
+        assert_eq!(request.label.as_deref(), Some("Michael"));
+        assert_eq!(request.message.as_deref(), Some("Thanks for all the fish"));
+        assert_eq!(request.memo.as_deref(), Some("OrderId12345"));
+        assert_eq!(request.to_url(), url);
+
+        let request = TransferRequest::parse(&format!("solana:{}", RECIPIENT)).unwrap();
+        assert_eq!(request.amount, None);
+        assert_eq!(request.to_url(), format!("solana:{}", RECIPIENT));
+
+        assert!(TransferRequest::parse(&format!("bitcoin:{}", RECIPIENT)).is_err());
+        assert!(TransferRequest::parse("solana:https%3A%2F%2Fexample.com%2Fpay").is_err());
+        assert!(TransferRequest::parse(&format!("solana:{}?amount=1e3", RECIPIENT)).is_err());
+        assert!(
+            TransferRequest::parse(&format!("solana:{}?amount=1&amount=2", RECIPIENT)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_parse_amount() {
+        assert_eq!(parse_amount("1", 9).unwrap(), 1_000_000_000);
+        assert_eq!(parse_amount("0.000000001", 9).unwrap(), 1);
+        assert_eq!(parse_amount("12.50", 6).unwrap(), 12_500_000);
+        assert!(parse_amount("0.0000001", 6).is_err());
+        assert!(parse_amount(".5", 9).is_err());
+        assert!(parse_amount("1.", 9).is_err());
+        assert!(parse_amount("-1", 9).is_err());
+        assert!(parse_amount("18446744073.709551616", 9).is_err());
+    }
+
+    #[test]
+    fn test_sol_transfer_instructions() {
+        let payer = Pubkey::new_unique();
+        let request = TransferRequest {
+            recipient: Pubkey::from_str(RECIPIENT).unwrap(),
+            references: vec![Pubkey::from_str(REFERENCE).unwrap()],
+            memo: Some("OrderId12345".to_string()),
+            ..TransferRequest::default()
+        };
+        let (instructions, lamports) = SolanaPay::new(WalletConfig::default())
+            .transfer_instructions(&payer, &request, "1.5")
+            .unwrap();
+        assert_eq!(lamports, 1_500_000_000);
+        assert_eq!(instructions[0].program_id, MEMO_PROGRAM_ID);
+        assert_eq!(instructions[0].data, b"OrderId12345");
+        let transfer = &instructions[1];
+        assert_eq!(transfer.accounts.len(), 3);
+        assert_eq!(transfer.accounts[2].pubkey.to_string(), REFERENCE);
+        assert!(!transfer.accounts[2].is_signer && !transfer.accounts[2].is_writable);
+    }
+}
@@ -0,0 +1,781 @@
+use crate::config::config_file::{SpendingLimits, SpendingPolicy};
+use crate::config::wallet_config::{PolicyConfirmation, WalletConfig};
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::token::{
+    associated_token_address, APPROVE_CHECKED_INSTRUCTION, APPROVE_INSTRUCTION,
+    CLOSE_ACCOUNT_INSTRUCTION, SET_AUTHORITY_INSTRUCTION, TOKEN_PROGRAM_IDS,
+    TRANSFER_CHECKED_INSTRUCTION, TRANSFER_INSTRUCTION,
+};
+use crate::store::address_book::AddressBook;
+use crate::store::keystore::KeyStore;
+use crate::store::spending_ledger::SpendingLedger;
+use solana_sdk::hash::Hash;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake;
+use solana_sdk::stake::instruction::StakeInstruction;
+use solana_sdk::stake::state::StakeAuthorize;
+use solana_sdk::system_instruction::SystemInstruction;
+use solana_sdk::system_program;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+/// Les types d'autorité "AccountOwner" et "CloseAccount" de l'instruction "SetAuthority" (compte de token).
+const ACCOUNT_OWNER_AUTHORITY: u8 = 2;
+const CLOSE_ACCOUNT_AUTHORITY: u8 = 3;
+
+/// Le hash du bloc de genèse du mainnet-beta.
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+
+/// Violations de la politique de dépense : la transaction n'est pas signée.
+#[derive(Debug, PartialEq)]
+pub enum PolicyViolation {
+    /// Le montant de la transaction dépasse le plafond par transaction : (clé, montant, plafond).
+    TransactionLimit(Pubkey, u64, u64),
+    /// Le montant ferait dépasser le plafond sur 24 heures glissantes : (clé, montant, déjà dépensé, plafond).
+    DailyLimit(Pubkey, u64, u64, u64),
+    /// Le destinataire est dans la liste interdite.
+    Denylisted(Pubkey),
+    /// Le destinataire n'est pas dans la liste autorisée.
+    NotAllowlisted(Pubkey),
+    /// Mode strict du mainnet : le destinataire n'est ni autorisé, ni un contact, ni une clé du key store.
+    UntrustedRecipient(Pubkey),
+    /// Une confirmation saisie est requise, sans saisie possible : (montant, clé, phrase à saisir).
+    ConfirmationRequired(u64, Pubkey, String),
+    /// La confirmation saisie ne correspond pas.
+    ConfirmationFailed,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyViolation::TransactionLimit(signer, amount, limit) => write!(
+                f,
+                "Spending policy: {} lamports exceed the per-transaction limit of {} lamports for {}",
+                amount, limit, signer
+            ),
+            PolicyViolation::DailyLimit(signer, amount, spent, limit) => write!(
+                f,
+                "Spending policy: {} lamports exceed the daily limit of {} lamports for {} ({} lamports spent in the last 24 hours)",
+                amount, limit, signer, spent
+            ),
+            PolicyViolation::Denylisted(recipient) => {
+                write!(f, "Spending policy: {} is on the denylist", recipient)
+            }
+            PolicyViolation::NotAllowlisted(recipient) => {
+                write!(f, "Spending policy: {} is not on the allowlist", recipient)
+            }
+            PolicyViolation::UntrustedRecipient(recipient) => write!(
+                f,
+                "Spending policy (mainnet strict mode): {} is not on the allowlist, a contact or a key store key",
+                recipient
+            ),
+            PolicyViolation::ConfirmationRequired(0, signer, _) => write!(
+                f,
+                "Spending policy: moving tokens or accounts from {} requires a confirmation.",
+                signer
+            ),
+            PolicyViolation::ConfirmationRequired(lamports, signer, _) => write!(
+                f,
+                "Spending policy: sending {} lamports ({} SOL) from {} requires a confirmation.",
+                lamports,
+                *lamports as f64 / 1_000_000_000_f64,
+                signer
+            ),
+            PolicyViolation::ConfirmationFailed => {
+                write!(f, "Spending policy: confirmation failed, nothing was signed")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+/// Un transfert sortant d'une transaction : des SOL, des tokens vers un compte de token, ou un compte
+/// cédé à un nouveau propriétaire (autorité d'un compte de stake ou de token, fermeture d'un compte de token).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Outflow {
+    pub recipient: Pubkey,
+    /// Les lamports envoyés (0 pour un transfert de tokens).
+    pub lamports: u64,
+    /// Le mint et le programme du token ("TransferChecked"), pour reconnaître le compte associé d'un destinataire.
+    pub token: Option<(Pubkey, Pubkey)>,
+    /// Le compte dont les lamports passent au destinataire (compte de stake ou de token) : ils ne figurent pas
+    /// dans l'instruction et sont lus via RPC.
+    pub account: Option<Pubkey>,
+    /// Vrai si des tokens sortent (transfert, délégation ou compte de token cédé) : les plafonds en lamports
+    /// ne peuvent pas les évaluer.
+    pub tokens: bool,
+}
+
+impl Outflow {
+    /// Un transfert de lamports vers "recipient".
+    pub fn new(recipient: Pubkey, lamports: u64) -> Self {
+        Outflow {
+            recipient,
+            lamports,
+            token: None,
+            account: None,
+            tokens: false,
+        }
+    }
+}
+
+/// Les listes de destinataires de la politique, résolues en clés publiques.
+#[derive(Debug, Default)]
+pub struct RecipientLists {
+    pub allow: Vec<Pubkey>,
+    pub deny: Vec<Pubkey>,
+    /// Les contacts et les clés du key store (mode strict du mainnet).
+    pub trusted: Vec<Pubkey>,
+}
+
+pub struct SolanaPolicy {}
+
+impl SolanaPolicy {
+    /// Évalue la politique de dépense de la configuration pour des instructions que "signer" va signer,
+    /// et demande la confirmation saisie si elle est requise (voir `PolicyConfirmation`).
+    ///
+    /// # Returns:
+    /// Les lamports envoyés par la transaction (à enregistrer avec `record` après la signature),
+    /// ou une `PolicyViolation` si la transaction ne doit pas être signée.
+    pub fn check(
+        config: &WalletConfig,
+        signer: &Pubkey,
+        instructions: &[Instruction],
+    ) -> Result<u64, Box<dyn std::error::Error>> {
+        let policy = &config.spending_policy;
+        let mut outflows = Self::outflows(signer, instructions);
+        if policy.is_empty() || outflows.is_empty() {
+            return Ok(0);
+        }
+        Self::read_account_lamports(config, &mut outflows)?;
+
+        let address_book = AddressBook::new(config.clone());
+        let resolve = |list: &[String]| {
+            list.iter()
+                .map(|entry| address_book.resolve(entry))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let manifest = KeyStore::new(config.clone()).load()?;
+        let mainnet = policy.mainnet_strict && Self::is_mainnet(config);
+        let mut lists = RecipientLists {
+            allow: resolve(&policy.allowlist)?,
+            deny: resolve(&policy.denylist)?,
+            trusted: Vec::new(),
+        };
+        if mainnet {
+            let contacts = address_book.load()?.contacts;
+            lists.trusted = contacts
+                .iter()
+                .map(|contact| contact.pubkey.as_str())
+                .chain(manifest.keys.iter().map(|entry| entry.pubkey.as_str()))
+                .filter_map(|pubkey| pubkey.parse().ok())
+                .collect();
+        }
+
+        let label =
+            KeyStore::find(&manifest, &signer.to_string()).map(|entry| entry.label.as_str());
+        let limits = Self::limits(policy, signer, label);
+        let spent_today = match limits.max_daily {
+            Some(_) => SpendingLedger::new(config.clone()).spent_today(signer)?,
+            None => 0,
+        };
+
+        let lamports = outflows.iter().map(|outflow| outflow.lamports).sum();
+        if let Some(phrase) = Self::evaluate(
+            policy,
+            &limits,
+            &lists,
+            signer,
+            &outflows,
+            spent_today,
+            mainnet,
+        )? {
+            let answer = match &config.policy_confirmation {
+                PolicyConfirmation::Prompt => {
+                    let output = config.output();
+                    output.message(
+                        PolicyViolation::ConfirmationRequired(lamports, *signer, phrase.clone())
+                            .to_string(),
+                    );
+                    output.message(format!("Type \"{}\" to sign:", phrase));
+                    let mut answer = String::new();
+                    io::stdin().read_line(&mut answer)?;
+                    answer
+                }
+                PolicyConfirmation::Deferred => {
+                    return Err(
+                        PolicyViolation::ConfirmationRequired(lamports, *signer, phrase).into(),
+                    );
+                }
+                PolicyConfirmation::Typed(answer) => answer.clone(),
+            };
+            if answer.trim() != phrase {
+                return Err(PolicyViolation::ConfirmationFailed.into());
+            }
+        }
+        Ok(lamports)
+    }
+
+    /// Vrai si le nœud RPC est sur le mainnet, d'après le hash de son bloc de genèse (l'URL peut être celle
+    /// d'un fournisseur quelconque). Un cluster inconnu (nœud injoignable) est traité comme le mainnet.
+    fn is_mainnet(config: &WalletConfig) -> bool {
+        let mainnet = Hash::from_str(MAINNET_GENESIS_HASH).expect("valid hash");
+        SolanaRpc::client(config)
+            .get_genesis_hash()
+            .map_or(true, |hash| hash == mainnet)
+    }
+
+    /// Lit les lamports des comptes cédés par les transferts sortants (chaque compte n'est compté qu'une fois).
+    fn read_account_lamports(
+        config: &WalletConfig,
+        outflows: &mut [Outflow],
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut accounts: Vec<Pubkey> = Vec::new();
+        for account in outflows.iter().filter_map(|outflow| outflow.account) {
+            if !accounts.contains(&account) {
+                accounts.push(account);
+            }
+        }
+        if accounts.is_empty() {
+            return Ok(());
+        }
+        let balances = SolanaRpc::client(config)
+            .get_multiple_accounts(&accounts)
+            .map_err(|e| {
+                format!(
+                    "Spending policy: cannot read the transferred accounts: {}",
+                    e
+                )
+            })?;
+        for (address, balance) in accounts.iter().zip(balances) {
+            let lamports = balance.map_or(0, |account| account.lamports);
+            if let Some(outflow) = outflows
+                .iter_mut()
+                .find(|outflow| outflow.account == Some(*address))
+            {
+                outflow.lamports = outflow.lamports.saturating_add(lamports);
+            }
+        }
+        Ok(())
+    }
+
+    /// Enregistre les lamports envoyés par une transaction signée (plafond sur 24 heures glissantes).
+    pub fn record(
+        config: &WalletConfig,
+        signer: &Pubkey,
+        lamports: u64,
+        signature: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        if lamports == 0 {
+            return Ok(());
+        }
+        SpendingLedger::new(config.clone()).record(signer, lamports, signature)
+    }
+
+    /// Les transferts sortants de "signer" : transferts de SOL (System), créations de comptes financés par
+    /// "signer" et retraits de comptes de nonce ou de stake dont il est l'autorité, transferts et délégations
+    /// de tokens (Token et Token-2022), transferts d'autorité (Stake "Authorize", Token "SetAuthority") et
+    /// fermetures de comptes de tokens ("CloseAccount", vers le destinataire du loyer).
+    /// Les transferts vers "signer" lui-même sont ignorés.
+    pub fn outflows(signer: &Pubkey, instructions: &[Instruction]) -> Vec<Outflow> {
+        let account = |instruction: &Instruction, index: usize| {
+            instruction
+                .accounts
+                .get(index)
+                .map(|account| account.pubkey)
+        };
+
+        instructions
+            .iter()
+            .filter_map(|instruction| {
+                if instruction.program_id == system_program::id() {
+                    match bincode::deserialize(&instruction.data).ok()? {
+                        SystemInstruction::Transfer { lamports }
+                        | SystemInstruction::CreateAccount { lamports, .. }
+                        | SystemInstruction::CreateAccountWithSeed { lamports, .. }
+                            if account(instruction, 0)? == *signer =>
+                        {
+                            Some(Outflow::new(account(instruction, 1)?, lamports))
+                        }
+                        SystemInstruction::TransferWithSeed { lamports, .. }
+                            if account(instruction, 1)? == *signer =>
+                        {
+                            Some(Outflow::new(account(instruction, 2)?, lamports))
+                        }
+                        SystemInstruction::WithdrawNonceAccount(lamports)
+                            if account(instruction, 4)? == *signer =>
+                        {
+                            Some(Outflow::new(account(instruction, 1)?, lamports))
+                        }
+                        _ => None,
+                    }
+                } else if instruction.program_id == stake::program::id() {
+                    // L'autorité de retrait cède les lamports du compte de stake ; celle de délégation, son contrôle.
+                    let (recipient, authorize) =
+                        match bincode::deserialize(&instruction.data).ok()? {
+                            StakeInstruction::Authorize(new_authority, authorize)
+                                if account(instruction, 2)? == *signer =>
+                            {
+                                (new_authority, authorize)
+                            }
+                            StakeInstruction::AuthorizeChecked(authorize)
+                                if account(instruction, 2)? == *signer =>
+                            {
+                                (account(instruction, 3)?, authorize)
+                            }
+                            StakeInstruction::Withdraw(lamports)
+                                if account(instruction, 4)? == *signer =>
+                            {
+                                return Some(Outflow::new(account(instruction, 1)?, lamports));
+                            }
+                            _ => return None,
+                        };
+                    Some(Outflow {
+                        account: (authorize == StakeAuthorize::Withdrawer)
+                            .then_some(account(instruction, 0)?),
+                        ..Outflow::new(recipient, 0)
+                    })
+                } else if TOKEN_PROGRAM_IDS.contains(&instruction.program_id) {
+                    match *instruction.data.first()? {
+                        TRANSFER_INSTRUCTION if account(instruction, 2)? == *signer => {
+                            Some(Outflow {
+                                tokens: true,
+                                ..Outflow::new(account(instruction, 1)?, 0)
+                            })
+                        }
+                        TRANSFER_CHECKED_INSTRUCTION if account(instruction, 3)? == *signer => {
+                            Some(Outflow {
+                                token: Some((account(instruction, 1)?, instruction.program_id)),
+                                tokens: true,
+                                ..Outflow::new(account(instruction, 2)?, 0)
+                            })
+                        }
+                        // Le délégué peut transférer les tokens du compte.
+                        APPROVE_INSTRUCTION if account(instruction, 2)? == *signer => {
+                            Some(Outflow {
+                                tokens: true,
+                                ..Outflow::new(account(instruction, 1)?, 0)
+                            })
+                        }
+                        APPROVE_CHECKED_INSTRUCTION if account(instruction, 3)? == *signer => {
+                            Some(Outflow {
+                                tokens: true,
+                                ..Outflow::new(account(instruction, 2)?, 0)
+                            })
+                        }
+                        CLOSE_ACCOUNT_INSTRUCTION if account(instruction, 2)? == *signer => {
+                            Some(Outflow {
+                                account: Some(account(instruction, 0)?),
+                                ..Outflow::new(account(instruction, 1)?, 0)
+                            })
+                        }
+                        // Le nouveau propriétaire (ou la nouvelle autorité de fermeture) d'un compte de token.
+                        SET_AUTHORITY_INSTRUCTION if account(instruction, 1)? == *signer => {
+                            let authority_type = *instruction.data.get(1)?;
+                            if (authority_type != ACCOUNT_OWNER_AUTHORITY
+                                && authority_type != CLOSE_ACCOUNT_AUTHORITY)
+                                || *instruction.data.get(2)? != 1
+                            {
+                                return None;
+                            }
+                            Some(Outflow {
+                                account: Some(account(instruction, 0)?),
+                                tokens: authority_type == ACCOUNT_OWNER_AUTHORITY,
+                                ..Outflow::new(
+                                    Pubkey::try_from(instruction.data.get(3..35)?).ok()?,
+                                    0,
+                                )
+                            })
+                        }
+                        _ => None,
+                    }
+                } else {
+                    None
+                }
+            })
+            .filter(|outflow| outflow.recipient != *signer)
+            .collect()
+    }
+
+    /// Les plafonds d'une clé : ceux de la clé (par label ou clé publique), à défaut ceux de la politique.
+    pub fn limits(policy: &SpendingPolicy, signer: &Pubkey, label: Option<&str>) -> SpendingLimits {
+        let key_limits = label
+            .and_then(|label| policy.keys.get(label))
+            .or_else(|| policy.keys.get(&signer.to_string()));
+        SpendingLimits {
+            max_per_transaction: key_limits
+                .and_then(|limits| limits.max_per_transaction)
+                .or(policy.max_per_transaction),
+            max_daily: key_limits
+                .and_then(|limits| limits.max_daily)
+                .or(policy.max_daily),
+        }
+    }
+
+    /// Évalue la politique pour les transferts sortants d'une transaction.
+    ///
+    /// # Returns:
+    /// La phrase à saisir si une confirmation est requise (le montant en lamports, ou "confirm" pour un
+    /// transfert de tokens seul), ou la première règle violée. Avec un plafond, toute sortie de tokens
+    /// requiert une confirmation.
+    pub fn evaluate(
+        policy: &SpendingPolicy,
+        limits: &SpendingLimits,
+        lists: &RecipientLists,
+        signer: &Pubkey,
+        outflows: &[Outflow],
+        spent_today: u64,
+        mainnet: bool,
+    ) -> Result<Option<String>, PolicyViolation> {
+        let strict = mainnet && policy.mainnet_strict;
+        for outflow in outflows {
+            if Self::contains(&lists.deny, outflow) {
+                return Err(PolicyViolation::Denylisted(outflow.recipient));
+            }
+            if !lists.allow.is_empty() && !Self::contains(&lists.allow, outflow) {
+                return Err(PolicyViolation::NotAllowlisted(outflow.recipient));
+            }
+            if strict
+                && !Self::contains(&lists.allow, outflow)
+                && !Self::contains(&lists.trusted, outflow)
+            {
+                return Err(PolicyViolation::UntrustedRecipient(outflow.recipient));
+            }
+        }
+
+        let lamports: u64 = outflows.iter().map(|outflow| outflow.lamports).sum();
+        if let Some(limit) = limits.max_per_transaction {
+            if lamports > limit {
+                return Err(PolicyViolation::TransactionLimit(*signer, lamports, limit));
+            }
+        }
+        if let Some(limit) = limits.max_daily {
+            if spent_today.saturating_add(lamports) > limit {
+                return Err(PolicyViolation::DailyLimit(
+                    *signer,
+                    lamports,
+                    spent_today,
+                    limit,
+                ));
+            }
+        }
+
+        // Les plafonds sont en lamports : une sortie de tokens sous plafond demande une confirmation saisie.
+        let limited = limits.max_per_transaction.is_some() || limits.max_daily.is_some();
+        let confirm = strict
+            || (limited && outflows.iter().any(|outflow| outflow.tokens))
+            || policy
+                .confirm_above
+                .is_some_and(|threshold| lamports > threshold);
+        Ok(confirm.then(|| {
+            if lamports > 0 {
+                lamports.to_string()
+            } else {
+                "confirm".to_string()
+            }
+        }))
+    }
+
+    /// Vrai si le destinataire est dans la liste : l'adresse elle-même, ou son compte de token associé.
+    fn contains(list: &[Pubkey], outflow: &Outflow) -> bool {
+        list.iter().any(|address| {
+            *address == outflow.recipient
+                || outflow.token.is_some_and(|(mint, program)| {
+                    associated_token_address(address, &mint, &program) == outflow.recipient
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solana::token::{close_account, TokenAccount};
+    use solana_sdk::instruction::AccountMeta;
+    use solana_sdk::stake::instruction::{authorize, withdraw};
+    use solana_sdk::system_instruction;
+
+    fn transfer(signer: &Pubkey, recipient: &Pubkey, lamports: u64) -> Vec<Outflow> {
+        SolanaPolicy::outflows(
+            signer,
+            &[system_instruction::transfer(signer, recipient, lamports)],
+        )
+    }
+
+    #[test]
+    fn test_outflows() {
+        let signer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let instructions = [
+            system_instruction::transfer(&signer, &recipient, 10),
+            // Les transferts d'une autre clé, ou vers la clé elle-même, ne sont pas des dépenses.
+            system_instruction::transfer(&recipient, &signer, 20),
+            system_instruction::transfer(&signer, &signer, 30),
+            // Les lamports d'un compte créé, ou retirés d'un compte de nonce ou de stake, quittent la clé.
+            system_instruction::create_account(&signer, &recipient, 40, 0, &system_program::id()),
+            system_instruction::create_account_with_seed(
+                &signer,
+                &recipient,
+                &signer,
+                "seed",
+                50,
+                0,
+                &system_program::id(),
+            ),
+            system_instruction::withdraw_nonce_account(&account, &signer, &recipient, 60),
+            withdraw(&account, &signer, &recipient, 70, None),
+            withdraw(&account, &recipient, &recipient, 80, None),
+        ];
+        assert_eq!(
+            SolanaPolicy::outflows(&signer, &instructions),
+            [10, 40, 50, 60, 70]
+                .into_iter()
+                .map(|lamports| Outflow::new(recipient, lamports))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_token_outflows() {
+        let signer = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let source = Pubkey::new_unique();
+        let mut approve = vec![APPROVE_INSTRUCTION];
+        approve.extend_from_slice(&1_000_000u64.to_le_bytes());
+        let instructions = [Instruction::new_with_bytes(
+            TOKEN_PROGRAM_IDS[0],
+            &approve,
+            vec![
+                AccountMeta::new(source, false),
+                AccountMeta::new_readonly(delegate, false),
+                AccountMeta::new_readonly(signer, true),
+            ],
+        )];
+        let outflows = SolanaPolicy::outflows(&signer, &instructions);
+        assert_eq!(
+            outflows,
+            vec![Outflow {
+                tokens: true,
+                ..Outflow::new(delegate, 0)
+            }]
+        );
+
+        // Les plafonds sont en lamports : une délégation de tokens sous plafond requiert une confirmation.
+        let policy = SpendingPolicy {
+            max_daily: Some(100),
+            ..SpendingPolicy::default()
+        };
+        let evaluate = |limits: &SpendingLimits| {
+            SolanaPolicy::evaluate(
+                &policy,
+                limits,
+                &RecipientLists::default(),
+                &signer,
+                &outflows,
+                0,
+                false,
+            )
+        };
+        assert_eq!(
+            evaluate(&SolanaPolicy::limits(&policy, &signer, None)),
+            Ok(Some("confirm".to_string()))
+        );
+        assert_eq!(evaluate(&SpendingLimits::default()), Ok(None));
+    }
+
+    #[test]
+    fn test_ownership_outflows() {
+        let signer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let stake_account = Pubkey::new_unique();
+        let token_account = TokenAccount {
+            address: Pubkey::new_unique(),
+            mint: Pubkey::new_unique(),
+            program_id: TOKEN_PROGRAM_IDS[0],
+            amount: 0,
+            ui_amount: "0".to_string(),
+            decimals: 6,
+            lamports: 2_039_280,
+            frozen: false,
+            close_authority: None,
+            withheld_fees: 0,
+        };
+        let mut set_owner = vec![SET_AUTHORITY_INSTRUCTION, ACCOUNT_OWNER_AUTHORITY, 1];
+        set_owner.extend_from_slice(recipient.as_ref());
+        let instructions = [
+            authorize(
+                &stake_account,
+                &signer,
+                &recipient,
+                StakeAuthorize::Staker,
+                None,
+            ),
+            authorize(
+                &stake_account,
+                &signer,
+                &recipient,
+                StakeAuthorize::Withdrawer,
+                None,
+            ),
+            close_account(&token_account, &recipient, &signer),
+            Instruction::new_with_bytes(
+                TOKEN_PROGRAM_IDS[0],
+                &set_owner,
+                vec![
+                    AccountMeta::new(token_account.address, false),
+                    AccountMeta::new_readonly(signer, true),
+                ],
+            ),
+            // La fermeture d'un compte vers la clé elle-même n'est pas une dépense.
+            close_account(&token_account, &signer, &signer),
+        ];
+        let outflow = |account: Option<Pubkey>, tokens: bool| Outflow {
+            account,
+            tokens,
+            ..Outflow::new(recipient, 0)
+        };
+
+        // Seule l'autorité de retrait cède les lamports du compte de stake (lus via RPC) ; le nouveau
+        // propriétaire d'un compte de token en reçoit aussi les tokens.
+        assert_eq!(
+            SolanaPolicy::outflows(&signer, &instructions),
+            vec![
+                outflow(None, false),
+                outflow(Some(stake_account), false),
+                outflow(Some(token_account.address), false),
+                outflow(Some(token_account.address), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_limits() {
+        let signer = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let mut policy = SpendingPolicy {
+            max_per_transaction: Some(100),
+            max_daily: Some(150),
+            ..SpendingPolicy::default()
+        };
+        policy.keys.insert(
+            "treasury".to_string(),
+            SpendingLimits {
+                max_per_transaction: Some(1000),
+                max_daily: None,
+            },
+        );
+        let evaluate = |label: Option<&str>, lamports: u64, spent: u64| {
+            SolanaPolicy::evaluate(
+                &policy,
+                &SolanaPolicy::limits(&policy, &signer, label),
+                &RecipientLists::default(),
+                &signer,
+                &transfer(&signer, &recipient, lamports),
+                spent,
+                false,
+            )
+        };
+
+        assert_eq!(evaluate(None, 100, 0), Ok(None));
+        assert_eq!(
+            evaluate(None, 101, 0),
+            Err(PolicyViolation::TransactionLimit(signer, 101, 100))
+        );
+        assert_eq!(
+            evaluate(None, 100, 60),
+            Err(PolicyViolation::DailyLimit(signer, 100, 60, 150))
+        );
+        // Les plafonds de la clé remplacent ceux de la politique (le plafond journalier reste celui de la politique).
+        assert_eq!(evaluate(Some("treasury"), 120, 0), Ok(None));
+        assert_eq!(
+            evaluate(Some("treasury"), 120, 60),
+            Err(PolicyViolation::DailyLimit(signer, 120, 60, 150))
+        );
+    }
+
+    #[test]
+    fn test_recipient_lists() {
+        let signer = Pubkey::new_unique();
+        let allowed = Pubkey::new_unique();
+        let denied = Pubkey::new_unique();
+        let contact = Pubkey::new_unique();
+        let policy = SpendingPolicy {
+            mainnet_strict: true,
+            ..SpendingPolicy::default()
+        };
+        let mut lists = RecipientLists {
+            allow: Vec::new(),
+            deny: vec![denied],
+            trusted: vec![contact],
+        };
+        let evaluate = |lists: &RecipientLists, recipient: &Pubkey, mainnet: bool| {
+            SolanaPolicy::evaluate(
+                &policy,
+                &SpendingLimits::default(),
+                lists,
+                &signer,
+                &transfer(&signer, recipient, 5),
+                0,
+                mainnet,
+            )
+        };
+
+        assert_eq!(
+            evaluate(&lists, &denied, false),
+            Err(PolicyViolation::Denylisted(denied))
+        );
+        assert_eq!(evaluate(&lists, &allowed, false), Ok(None));
+        // Mode strict du mainnet : destinataires de confiance uniquement, et confirmation saisie.
+        assert_eq!(
+            evaluate(&lists, &allowed, true),
+            Err(PolicyViolation::UntrustedRecipient(allowed))
+        );
+        assert_eq!(evaluate(&lists, &contact, true), Ok(Some("5".to_string())));
+
+        lists.allow = vec![allowed];
+        assert_eq!(
+            evaluate(&lists, &contact, false),
+            Err(PolicyViolation::NotAllowlisted(contact))
+        );
+        assert_eq!(evaluate(&lists, &allowed, true), Ok(Some("5".to_string())));
+    }
+
+    #[test]
+    fn test_token_recipient() {
+        let signer = Pubkey::new_unique();
+        let allowed = Pubkey::new_unique();
+        let mint = Pubkey::new_unique();
+        let program = TOKEN_PROGRAM_IDS[0];
+        let outflow = Outflow {
+            token: Some((mint, program)),
+            tokens: true,
+            ..Outflow::new(associated_token_address(&allowed, &mint, &program), 0)
+        };
+        let lists = RecipientLists {
+            allow: vec![allowed],
+            ..RecipientLists::default()
+        };
+        let policy = SpendingPolicy {
+            confirm_above: Some(0),
+            ..SpendingPolicy::default()
+        };
+
+        // Le compte de token associé d'un destinataire autorisé est autorisé.
+        assert_eq!(
+            SolanaPolicy::evaluate(
+                &policy,
+                &SpendingLimits::default(),
+                &lists,
+                &signer,
+                &[outflow],
+                0,
+                false
+            ),
+            Ok(None)
+        );
+    }
+}
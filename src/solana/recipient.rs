@@ -0,0 +1,324 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use crate::solana::token::TOKEN_PROGRAM_IDS;
+use crate::solana::transaction::SolanaTransaction;
+use solana_sdk::account::Account;
+use solana_sdk::pubkey::Pubkey;
+use std::fmt;
+
+/// La taille d'un mint (sans extensions Token-2022).
+const MINT_SIZE: usize = 82;
+/// La position du type de compte ("AccountType") d'un compte Token-2022 avec extensions
+/// (après les données d'un compte de token, les mints avec extensions étant complétés jusqu'à cette taille).
+const ACCOUNT_TYPE_OFFSET: usize = 165;
+/// Le type de compte d'un mint Token-2022 avec extensions.
+const MINT_ACCOUNT_TYPE: u8 = 1;
+
+/// Les contrôles du destinataire d'un envoi de SOL ignorés à la demande de l'utilisateur (options "--allow-...").
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RecipientOverrides {
+    pub allow_self: bool,
+    pub allow_off_curve: bool,
+    pub allow_token_account: bool,
+    pub allow_program: bool,
+    pub allow_below_rent: bool,
+}
+
+/// Destinataires d'un envoi de SOL refusés : la transaction n'est pas signée.
+#[derive(Debug, PartialEq)]
+pub enum UnsafeRecipient {
+    /// Le destinataire est l'expéditeur lui-même.
+    Sender(Pubkey),
+    /// Le destinataire n'est pas sur la courbe ed25519 (adresse dérivée d'un programme) : aucune clé privée ne peut
+    /// signer pour lui.
+    OffCurve(Pubkey),
+    /// Le destinataire est un compte de token, pas un wallet.
+    TokenAccount(Pubkey),
+    /// Le destinataire est un mint, pas un wallet.
+    Mint(Pubkey),
+    /// Le destinataire est un programme exécutable.
+    Program(Pubkey),
+    /// Le solde restant de l'expéditeur serait inférieur au minimum de l'exemption de loyer : (restant, minimum).
+    BelowRent(u64, u64),
+}
+
+impl fmt::Display for UnsafeRecipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsafeRecipient::Sender(recipient) => write!(
+                f,
+                "{} is the sender itself (use --allow-self to send anyway)",
+                recipient
+            ),
+            UnsafeRecipient::OffCurve(recipient) => write!(
+                f,
+                "{} is off-curve (a program derived address): no private key can sign for it (use --allow-off-curve to send anyway)",
+                recipient
+            ),
+            UnsafeRecipient::TokenAccount(recipient) => write!(
+                f,
+                "{} is a token account, not a wallet: send the SOL to its owner (use --allow-token-account to send anyway)",
+                recipient
+            ),
+            UnsafeRecipient::Mint(recipient) => write!(
+                f,
+                "{} is a token mint, not a wallet (use --allow-token-account to send anyway)",
+                recipient
+            ),
+            UnsafeRecipient::Program(recipient) => write!(
+                f,
+                "{} is an executable program, not a wallet (use --allow-program to send anyway)",
+                recipient
+            ),
+            UnsafeRecipient::BelowRent(remaining, minimum) => write!(
+                f,
+                "The sender would keep {} lamports, below the rent-exempt minimum of {} lamports: the network would reject the transaction (send the whole balance, or use --allow-below-rent to send anyway)",
+                remaining, minimum
+            ),
+        }
+    }
+}
+
+impl std::error::Error for UnsafeRecipient {}
+
+pub struct SolanaRecipient {}
+
+impl SolanaRecipient {
+    /// Contrôle le destinataire d'un envoi de SOL avant la signature : l'expéditeur lui-même, une adresse hors courbe,
+    /// un compte de token ou un mint, un programme, ou un solde restant inférieur à l'exemption de loyer.
+    ///
+    /// # Returns:
+    /// Des avertissements (les contrôles qui demandent le compte du destinataire sont ignorés si le nœud RPC
+    /// ne répond pas), ou un `UnsafeRecipient` si l'envoi ne doit pas être signé.
+    pub fn check(
+        config: &WalletConfig,
+        sender: &Pubkey,
+        recipient: &Pubkey,
+        lamports: u64,
+        overrides: &RecipientOverrides,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        Self::check_all(config, sender, &[*recipient], lamports, overrides)
+    }
+
+    /// Contrôle les destinataires des envois de SOL d'une même transaction (voir `check`), puis le solde restant
+    /// de l'expéditeur après le total des envois ("lamports").
+    pub fn check_all(
+        config: &WalletConfig,
+        sender: &Pubkey,
+        recipients: &[Pubkey],
+        lamports: u64,
+        overrides: &RecipientOverrides,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        // Les contrôles sans le nœud RPC d'abord : une erreur ne dépend pas de sa disponibilité.
+        for recipient in recipients {
+            Self::evaluate(sender, recipient, None, None, overrides)?;
+        }
+
+        let mut warnings = Vec::new();
+        if overrides.allow_token_account && overrides.allow_program && overrides.allow_below_rent {
+            return Ok(warnings);
+        }
+        let fetched = Self::fetch_accounts(config, recipients)
+            .and_then(|accounts| Ok((accounts, Self::fetch_rent(config, sender)?)));
+        match fetched {
+            Ok((accounts, (balance, minimum))) => {
+                let fee = SolanaTransaction::estimate_transfer_fee(config);
+                let remaining = balance.saturating_sub(lamports.saturating_add(fee));
+                for (recipient, account) in recipients.iter().zip(&accounts) {
+                    Self::evaluate(
+                        sender,
+                        recipient,
+                        account.as_ref(),
+                        Some((remaining, minimum)),
+                        overrides,
+                    )?;
+                }
+            }
+            Err(e) => warnings.push(format!("Recipient account not checked: {}", e)),
+        }
+        Ok(warnings)
+    }
+
+    /// Lit les comptes des destinataires (absents s'ils n'existent pas).
+    fn fetch_accounts(
+        config: &WalletConfig,
+        recipients: &[Pubkey],
+    ) -> Result<Vec<Option<Account>>, Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(config);
+        let mut accounts = Vec::with_capacity(recipients.len());
+        // Le nœud accepte au plus 100 comptes par requête.
+        for chunk in recipients.chunks(100) {
+            accounts.extend(
+                client
+                    .get_multiple_accounts_with_commitment(chunk, config.commitment_config())?
+                    .value,
+            );
+        }
+        Ok(accounts)
+    }
+
+    /// Lit le solde de l'expéditeur et le minimum de l'exemption de loyer d'un compte système.
+    fn fetch_rent(
+        config: &WalletConfig,
+        sender: &Pubkey,
+    ) -> Result<(u64, u64), Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(config);
+        Ok((
+            client.get_balance(sender)?,
+            client.get_minimum_balance_for_rent_exemption(0)?,
+        ))
+    }
+
+    /// Évalue les contrôles du destinataire.
+    ///
+    /// # Arguments:
+    /// - account - Le compte du destinataire (absent s'il n'existe pas ou n'a pas été lu).
+    /// - rent - Le solde restant de l'expéditeur après l'envoi et les frais, et le minimum de l'exemption de loyer
+    ///   (absents s'ils n'ont pas été lus).
+    pub fn evaluate(
+        sender: &Pubkey,
+        recipient: &Pubkey,
+        account: Option<&Account>,
+        rent: Option<(u64, u64)>,
+        overrides: &RecipientOverrides,
+    ) -> Result<(), UnsafeRecipient> {
+        if recipient == sender && !overrides.allow_self {
+            return Err(UnsafeRecipient::Sender(*recipient));
+        }
+        if !recipient.is_on_curve() && !overrides.allow_off_curve {
+            return Err(UnsafeRecipient::OffCurve(*recipient));
+        }
+
+        if let Some(account) = account {
+            if account.executable && !overrides.allow_program {
+                return Err(UnsafeRecipient::Program(*recipient));
+            }
+            if TOKEN_PROGRAM_IDS.contains(&account.owner) && !overrides.allow_token_account {
+                let is_mint = account.data.len() == MINT_SIZE
+                    || account.data.get(ACCOUNT_TYPE_OFFSET) == Some(&MINT_ACCOUNT_TYPE);
+                return Err(if is_mint {
+                    UnsafeRecipient::Mint(*recipient)
+                } else {
+                    UnsafeRecipient::TokenAccount(*recipient)
+                });
+            }
+        }
+
+        // Un compte système vidé est fermé ; un solde restant non nul doit couvrir l'exemption de loyer.
+        if let Some((remaining, minimum)) = rent {
+            if remaining > 0 && remaining < minimum && !overrides.allow_below_rent {
+                return Err(UnsafeRecipient::BelowRent(remaining, minimum));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::bpf_loader_upgradeable;
+    use solana_sdk::signature::{Keypair, Signer};
+
+    fn evaluate(
+        recipient: &Pubkey,
+        account: Option<&Account>,
+        rent: Option<(u64, u64)>,
+        overrides: &RecipientOverrides,
+    ) -> Result<(), UnsafeRecipient> {
+        let sender = Keypair::new().pubkey();
+        SolanaRecipient::evaluate(&sender, recipient, account, rent, overrides)
+    }
+
+    #[test]
+    fn test_sender_and_off_curve() {
+        let sender = Keypair::new().pubkey();
+        let pda = Pubkey::find_program_address(&[b"vault"], &Pubkey::new_unique()).0;
+        let none = RecipientOverrides::default();
+
+        assert_eq!(
+            SolanaRecipient::evaluate(&sender, &sender, None, None, &none),
+            Err(UnsafeRecipient::Sender(sender))
+        );
+        assert_eq!(
+            evaluate(&pda, None, None, &none),
+            Err(UnsafeRecipient::OffCurve(pda))
+        );
+        let overrides = RecipientOverrides {
+            allow_off_curve: true,
+            ..RecipientOverrides::default()
+        };
+        assert_eq!(evaluate(&pda, None, None, &overrides), Ok(()));
+    }
+
+    #[test]
+    fn test_recipient_account() {
+        let recipient = Keypair::new().pubkey();
+        let none = RecipientOverrides::default();
+        let account = |owner: Pubkey, size: usize, executable: bool| Account {
+            lamports: 1_000_000,
+            data: vec![0; size],
+            owner,
+            executable,
+            rent_epoch: 0,
+        };
+
+        assert_eq!(
+            evaluate(
+                &recipient,
+                Some(&account(TOKEN_PROGRAM_IDS[0], 165, false)),
+                None,
+                &none
+            ),
+            Err(UnsafeRecipient::TokenAccount(recipient))
+        );
+        assert_eq!(
+            evaluate(
+                &recipient,
+                Some(&account(TOKEN_PROGRAM_IDS[0], MINT_SIZE, false)),
+                None,
+                &none
+            ),
+            Err(UnsafeRecipient::Mint(recipient))
+        );
+        assert_eq!(
+            evaluate(
+                &recipient,
+                Some(&account(bpf_loader_upgradeable::id(), 36, true)),
+                None,
+                &none
+            ),
+            Err(UnsafeRecipient::Program(recipient))
+        );
+        // Un wallet existant (compte système) est un destinataire normal.
+        assert_eq!(
+            evaluate(
+                &recipient,
+                Some(&account(Pubkey::default(), 0, false)),
+                None,
+                &none
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_rent() {
+        let recipient = Keypair::new().pubkey();
+        let none = RecipientOverrides::default();
+
+        assert_eq!(
+            evaluate(&recipient, None, Some((1_000, 890_880)), &none),
+            Err(UnsafeRecipient::BelowRent(1_000, 890_880))
+        );
+        // Envoyer tout le solde ferme le compte de l'expéditeur.
+        assert_eq!(
+            evaluate(&recipient, None, Some((0, 890_880)), &none),
+            Ok(())
+        );
+        assert_eq!(
+            evaluate(&recipient, None, Some((890_880, 890_880)), &none),
+            Ok(())
+        );
+    }
+}
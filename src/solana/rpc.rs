@@ -0,0 +1,234 @@
+use crate::config::config_file::RetryPolicy;
+use crate::config::wallet_config::WalletConfig;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{json, Value};
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_custom_error::JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY;
+use solana_client::rpc_request::{RpcError, RpcRequest};
+use solana_client::rpc_sender::{RpcSender, RpcTransportStats};
+use solana_rpc_client::http_sender::HttpSender;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Nature de l'échec d'une requête RPC.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Failure {
+    /// La requête n'a pas été traitée par le nœud (connexion refusée, 429, 502, 503, 504, nœud en retard) :
+    /// elle peut être envoyée à nouveau, y compris une transaction.
+    NotProcessed,
+    /// La requête a pu être traitée (délai dépassé, connexion interrompue, autre erreur 5xx) :
+    /// une lecture peut être envoyée à nouveau, pas une transaction.
+    Ambiguous,
+    /// Réponse du nœud (erreur RPC, échec de la simulation de la transaction...) : le résultat serait le même ailleurs.
+    Final,
+}
+
+/// État d'un point d'accès RPC ("getHealth" et slot courant).
+#[derive(Clone, Debug, Serialize)]
+pub struct EndpointHealth {
+    pub url: String,
+    /// Vrai si le nœud répond "ok" à "getHealth".
+    pub healthy: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot: Option<u64>,
+    /// Retard (en slots) sur le point d'accès le plus avancé.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slot_lag: Option<u64>,
+    /// Vrai si le point d'accès est utilisé en priorité (en bonne santé et retard inférieur à "max_slot_lag").
+    pub preferred: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Transport RPC à plusieurs points d'accès.
+///
+/// - Lors de la première requête (s'il y a plusieurs points d'accès), leur santé est vérifiée ("getHealth" et retard
+///   du slot sur le plus avancé) : les points d'accès en bonne santé sont essayés en premier, dans l'ordre de la configuration.
+/// - Une requête qui échoue avec une erreur 429, 5xx ou de connexion est envoyée à nouveau sur le même point d'accès,
+///   après un délai doublé à chaque tentative ("rpc_retries" et "rpc_backoff_ms"), puis au point d'accès suivant.
+/// - Une transaction ("sendTransaction") n'est envoyée à nouveau que si le nœud ne l'a pas traitée (`Failure::NotProcessed`) :
+///   ce sont alors exactement les mêmes octets signés (même signature), qui ne peuvent être exécutés qu'une seule fois.
+///   Si le nœud a pu la traiter, l'erreur est retournée : le statut de sa signature doit être vérifié avant tout nouvel envoi.
+pub struct FailoverSender {
+    endpoints: Vec<HttpSender>,
+    policy: RetryPolicy,
+    /// L'ordre d'essai des points d'accès (établi lors de la première requête).
+    order: Mutex<Option<Vec<usize>>>,
+}
+
+impl FailoverSender {
+    pub fn new(urls: &[String], policy: RetryPolicy) -> Self {
+        Self {
+            endpoints: urls.iter().map(HttpSender::new).collect(),
+            policy,
+            order: Mutex::new(None),
+        }
+    }
+
+    /// Vérifie la santé de chaque point d'accès ("getHealth" et slot courant).
+    pub async fn check_health(&self) -> Vec<EndpointHealth> {
+        let mut health = Vec::new();
+        for endpoint in &self.endpoints {
+            let status = endpoint.send(RpcRequest::GetHealth, Value::Null).await;
+            let slot = endpoint
+                .send(RpcRequest::GetSlot, json!([{ "commitment": "processed" }]))
+                .await;
+            let error = match (&status, &slot) {
+                (Err(e), _) | (Ok(_), Err(e)) => Some(e.to_string()),
+                _ => None,
+            };
+            health.push(EndpointHealth {
+                url: endpoint.url(),
+                healthy: status.is_ok_and(|status| status == "ok"),
+                slot: slot.ok().and_then(|slot| slot.as_u64()),
+                slot_lag: None,
+                preferred: false,
+                error,
+            });
+        }
+
+        let max_slot = health.iter().filter_map(|endpoint| endpoint.slot).max();
+        for endpoint in &mut health {
+            endpoint.slot_lag = endpoint
+                .slot
+                .zip(max_slot)
+                .map(|(slot, max_slot)| max_slot - slot);
+            endpoint.preferred = endpoint.healthy
+                && endpoint
+                    .slot_lag
+                    .is_some_and(|lag| lag <= self.policy.max_slot_lag);
+        }
+        health
+    }
+
+    /// Retourne l'ordre d'essai des points d'accès : ceux utilisés en priorité, puis les autres (en dernier recours).
+    async fn order(&self) -> Vec<usize> {
+        if self.endpoints.len() == 1 {
+            return vec![0];
+        }
+        if let Some(order) = self.order.lock().unwrap().clone() {
+            return order;
+        }
+
+        let health = self.check_health().await;
+        let (mut order, others): (Vec<usize>, Vec<usize>) =
+            (0..health.len()).partition(|&index| health[index].preferred);
+        order.extend(others);
+
+        *self.order.lock().unwrap() = Some(order.clone());
+        order
+    }
+
+    /// Envoie la requête à un point d'accès, avec de nouvelles tentatives espacées d'un délai croissant.
+    async fn send_with_retries(
+        &self,
+        endpoint: &HttpSender,
+        request: RpcRequest,
+        params: &Value,
+    ) -> ClientResult<Value> {
+        let mut delay = Duration::from_millis(self.policy.rpc_backoff_ms);
+        let mut retries = 0;
+        loop {
+            match endpoint.send(request, params.clone()).await {
+                Err(e) if retries < self.policy.rpc_retries && Self::can_resend(request, &e) => {
+                    tokio::time::sleep(delay).await;
+                    delay *= 2;
+                    retries += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Vrai si la requête peut être envoyée à nouveau (au même point d'accès ou au suivant) après cette erreur.
+    fn can_resend(request: RpcRequest, error: &ClientError) -> bool {
+        match Self::failure(error) {
+            Failure::NotProcessed => true,
+            Failure::Ambiguous => request != RpcRequest::SendTransaction,
+            Failure::Final => false,
+        }
+    }
+
+    fn failure(error: &ClientError) -> Failure {
+        match error.kind() {
+            ClientErrorKind::Reqwest(e) if e.is_connect() => Failure::NotProcessed,
+            ClientErrorKind::Reqwest(e) => match e.status().map(|status| status.as_u16()) {
+                Some(429 | 502 | 503 | 504) => Failure::NotProcessed,
+                Some(status) if status >= 500 => Failure::Ambiguous,
+                Some(_) => Failure::Final,
+                None => Failure::Ambiguous,
+            },
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code, .. })
+                if *code == JSON_RPC_SERVER_ERROR_NODE_UNHEALTHY =>
+            {
+                Failure::NotProcessed
+            }
+            ClientErrorKind::Io(_) => Failure::Ambiguous,
+            _ => Failure::Final,
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for FailoverSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        let mut last_error = None;
+        for index in self.order().await {
+            match self
+                .send_with_retries(&self.endpoints[index], request, &params)
+                .await
+            {
+                Err(e) if Self::can_resend(request, &e) => last_error = Some(e),
+                result => return result,
+            }
+        }
+        Err(last_error.expect("at least one RPC endpoint"))
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.endpoints
+            .iter()
+            .map(RpcSender::get_transport_stats)
+            .fold(RpcTransportStats::default(), |mut total, stats| {
+                total.request_count += stats.request_count;
+                total.elapsed_time += stats.elapsed_time;
+                total.rate_limited_time += stats.rate_limited_time;
+                total
+            })
+    }
+
+    fn url(&self) -> String {
+        self.endpoints[0].url()
+    }
+}
+
+pub struct SolanaRpc {}
+
+impl SolanaRpc {
+    /// Crée le client RPC de la configuration (points d'accès "rpc_urls", niveau de confirmation et politique de renvoi).
+    pub fn client(config: &WalletConfig) -> RpcClient {
+        RpcClient::new_sender(
+            FailoverSender::new(&config.rpc_urls, config.retry_policy.clone()),
+            RpcClientConfig::with_commitment(config.commitment_config()),
+        )
+    }
+
+    /// Vérifie la santé des points d'accès de la configuration.
+    pub fn check_health(
+        config: &WalletConfig,
+    ) -> Result<Vec<EndpointHealth>, Box<dyn std::error::Error>> {
+        let sender = FailoverSender::new(&config.rpc_urls, config.retry_policy.clone());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(runtime.block_on(sender.check_health()))
+    }
+
+    /// Vrai si la transaction a pu être reçue par le nœud malgré l'erreur de "sendTransaction" :
+    /// le statut de sa signature doit alors être vérifié avant de la signer à nouveau.
+    pub fn may_have_been_sent(error: &ClientError) -> bool {
+        FailoverSender::failure(error) == Failure::Ambiguous
+    }
+}
@@ -0,0 +1,98 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use serde_json::json;
+use solana_client::rpc_request::RpcRequest;
+use solana_client::rpc_response::RpcKeyedAccount;
+use solana_sdk::account::Account;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake;
+use solana_sdk::stake::instruction::authorize;
+use solana_sdk::stake::state::{StakeAuthorize, StakeStateV2};
+
+/// La position de l'autorité de retrait dans les données d'un compte de stake
+/// (après le type d'état, la réserve de loyer et l'autorité de délégation).
+const WITHDRAWER_OFFSET: usize = 44;
+
+/// Un compte de stake dont une clé est l'autorité de retrait.
+#[derive(Debug)]
+pub struct StakeAccount {
+    pub address: Pubkey,
+    pub lamports: u64,
+    pub staker: Pubkey,
+    pub withdrawer: Pubkey,
+    /// Le compte de vote auquel le stake est délégué (absent s'il n'est pas délégué).
+    pub voter: Option<Pubkey>,
+}
+
+pub struct SolanaStake {}
+
+impl SolanaStake {
+    /// Retourne les comptes de stake dont "withdrawer" est l'autorité de retrait.
+    pub fn get_stake_accounts(
+        config: &WalletConfig,
+        withdrawer: &Pubkey,
+    ) -> Result<Vec<StakeAccount>, Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(config);
+        // Les données sont demandées en base64 (un compte de stake dépasse la limite de 128 octets du base58).
+        let accounts = client.send::<Vec<RpcKeyedAccount>>(
+            RpcRequest::GetProgramAccounts,
+            json!([
+                stake::program::id().to_string(),
+                {
+                    "encoding": "base64",
+                    "commitment": config.commitment_config().commitment,
+                    "filters": [
+                        { "dataSize": StakeStateV2::size_of() },
+                        { "memcmp": { "offset": WITHDRAWER_OFFSET, "bytes": withdrawer.to_string() } }
+                    ]
+                }
+            ]),
+        )?;
+
+        Ok(accounts
+            .into_iter()
+            .filter_map(|keyed| {
+                let account: Account = keyed.account.decode()?;
+                let state: StakeStateV2 = bincode::deserialize(&account.data).ok()?;
+                let meta = state.meta()?;
+                Some(StakeAccount {
+                    address: keyed.pubkey.parse().ok()?,
+                    lamports: account.lamports,
+                    staker: meta.authorized.staker,
+                    withdrawer: meta.authorized.withdrawer,
+                    voter: state.delegation().map(|delegation| delegation.voter_pubkey),
+                })
+            })
+            .collect())
+    }
+
+    /// Les instructions qui transfèrent les autorités d'un compte de stake (celles que détient "authority")
+    /// à une nouvelle clé.
+    pub fn transfer_authorities(
+        account: &StakeAccount,
+        authority: &Pubkey,
+        new_authority: &Pubkey,
+    ) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if account.staker == *authority {
+            instructions.push(authorize(
+                &account.address,
+                authority,
+                new_authority,
+                StakeAuthorize::Staker,
+                None,
+            ));
+        }
+        if account.withdrawer == *authority {
+            instructions.push(authorize(
+                &account.address,
+                authority,
+                new_authority,
+                StakeAuthorize::Withdrawer,
+                None,
+            ));
+        }
+        instructions
+    }
+}
@@ -0,0 +1,220 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::rpc::SolanaRpc;
+use serde::Serialize;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_sdk::instruction::{AccountMeta, Instruction};
+use solana_sdk::pubkey;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::system_program;
+
+/// Les programmes des tokens SPL : "Token" et "Token-2022".
+pub const TOKEN_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV"),
+    pubkey!("TokenzQdBNbLqP5VEhdkAS6EH5r2n1eT4hMHo7X9Nf8"),
+];
+
+/// Le programme "Associated Token Account" (adresse du compte de token d'un propriétaire pour un mint).
+const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// L'instruction "Transfer" des programmes Token et Token-2022.
+pub const TRANSFER_INSTRUCTION: u8 = 3;
+/// L'instruction "TransferChecked" des programmes Token et Token-2022.
+pub const TRANSFER_CHECKED_INSTRUCTION: u8 = 12;
+/// L'instruction "Approve" des programmes Token et Token-2022.
+pub const APPROVE_INSTRUCTION: u8 = 4;
+/// L'instruction "ApproveChecked" des programmes Token et Token-2022.
+pub const APPROVE_CHECKED_INSTRUCTION: u8 = 13;
+/// L'instruction "SetAuthority" des programmes Token et Token-2022.
+pub const SET_AUTHORITY_INSTRUCTION: u8 = 6;
+/// L'instruction "CloseAccount" des programmes Token et Token-2022.
+pub const CLOSE_ACCOUNT_INSTRUCTION: u8 = 9;
+/// L'instruction "BurnChecked" des programmes Token et Token-2022.
+const BURN_CHECKED_INSTRUCTION: u8 = 15;
+/// L'instruction "CreateIdempotent" du programme Associated Token Account.
+const CREATE_IDEMPOTENT_INSTRUCTION: u8 = 1;
+
+/// Un compte de token SPL, tel que décodé par le nœud.
+#[derive(Clone, Debug)]
+pub struct TokenAccount {
+    pub address: Pubkey,
+    pub mint: Pubkey,
+    /// Le programme du compte (Token ou Token-2022).
+    pub program_id: Pubkey,
+    /// Le montant, en unités de base.
+    pub amount: u64,
+    /// Le montant, en unités du token (par exemple "12.5").
+    pub ui_amount: String,
+    pub decimals: u8,
+    /// Les lamports du compte (son loyer, récupéré à sa fermeture).
+    pub lamports: u64,
+    /// Vrai si le compte est gelé par l'autorité de gel du mint (il ne peut alors pas être fermé).
+    pub frozen: bool,
+    /// La clé autorisée à fermer le compte, si elle est définie (sinon, son propriétaire).
+    pub close_authority: Option<Pubkey>,
+    /// Les frais de transfert retenus (Token-2022) : le compte ne peut pas être fermé tant qu'il y en a.
+    pub withheld_fees: u64,
+}
+
+/// Le solde d'un compte de token SPL.
+#[derive(Clone, Debug, Serialize)]
+pub struct TokenBalance {
+    /// L'adresse du compte de token.
+    pub account: String,
+    pub mint: String,
+    /// Le montant, en unités du token (par exemple "12.5").
+    pub amount: String,
+    pub decimals: u8,
+}
+
+pub struct SolanaToken {
+    config: WalletConfig,
+}
+
+impl SolanaToken {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Retourne les soldes des comptes de tokens SPL (Token et Token-2022) détenus par une adresse.
+    ///
+    /// # Arguments:
+    /// - owner - Le propriétaire des comptes de tokens.
+    ///
+    /// # Returns:
+    /// - Ok(balances) - Si la requête est réussie (les comptes dont les données ne sont pas décodées sont ignorés).
+    /// - Err(e) - Si une erreur se produit lors de la requête RPC.
+    pub fn get_token_balances(
+        &self,
+        owner: &Pubkey,
+    ) -> Result<Vec<TokenBalance>, Box<dyn std::error::Error>> {
+        Ok(self
+            .get_token_accounts(owner)?
+            .into_iter()
+            .map(|account| TokenBalance {
+                account: account.address.to_string(),
+                mint: account.mint.to_string(),
+                amount: account.ui_amount,
+                decimals: account.decimals,
+            })
+            .collect())
+    }
+
+    /// Retourne les comptes de tokens SPL (Token et Token-2022) détenus par une adresse.
+    /// Les comptes dont les données ne sont pas décodées sont ignorés.
+    pub fn get_token_accounts(
+        &self,
+        owner: &Pubkey,
+    ) -> Result<Vec<TokenAccount>, Box<dyn std::error::Error>> {
+        let client = SolanaRpc::client(&self.config);
+
+        let mut token_accounts = Vec::new();
+        for program_id in TOKEN_PROGRAM_IDS {
+            let accounts = client
+                .get_token_accounts_by_owner(owner, TokenAccountsFilter::ProgramId(program_id))?;
+
+            // Les données sont décodées par le nœud ("jsonParsed") : { "parsed": { "info": { "mint", "tokenAmount" } } }.
+            token_accounts.extend(accounts.into_iter().filter_map(|keyed| {
+                let data = serde_json::to_value(&keyed.account.data).ok()?;
+                let info = &data["parsed"]["info"];
+                let withheld_fees = info["extensions"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|extension| extension["extension"] == "transferFeeAmount")
+                    .filter_map(|extension| extension["state"]["withheldAmount"].as_u64())
+                    .sum();
+                Some(TokenAccount {
+                    address: keyed.pubkey.parse().ok()?,
+                    mint: info["mint"].as_str()?.parse().ok()?,
+                    program_id,
+                    amount: info["tokenAmount"]["amount"].as_str()?.parse().ok()?,
+                    ui_amount: info["tokenAmount"]["uiAmountString"].as_str()?.to_string(),
+                    decimals: info["tokenAmount"]["decimals"].as_u64()? as u8,
+                    lamports: keyed.account.lamports,
+                    frozen: info["state"] == "frozen",
+                    close_authority: info["closeAuthority"]
+                        .as_str()
+                        .and_then(|authority| authority.parse().ok()),
+                    withheld_fees,
+                })
+            }));
+        }
+        Ok(token_accounts)
+    }
+}
+
+/// L'adresse du compte de token associé d'un propriétaire pour un mint.
+pub fn associated_token_address(owner: &Pubkey, mint: &Pubkey, token_program: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// L'instruction qui crée le compte de token associé d'un propriétaire pour un mint (sans erreur s'il existe déjà).
+pub fn create_associated_token_account(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    Instruction::new_with_bytes(
+        ASSOCIATED_TOKEN_PROGRAM_ID,
+        &[CREATE_IDEMPOTENT_INSTRUCTION],
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(owner, mint, token_program), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// L'instruction qui ferme un compte de token vide, et verse son loyer à "destination".
+pub fn close_account(account: &TokenAccount, destination: &Pubkey, owner: &Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        account.program_id,
+        &[CLOSE_ACCOUNT_INSTRUCTION],
+        vec![
+            AccountMeta::new(account.address, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+/// L'instruction qui transfère tout le solde d'un compte de token vers un autre compte de token du même mint.
+pub fn transfer_all(account: &TokenAccount, destination: &Pubkey, owner: &Pubkey) -> Instruction {
+    let mut data = vec![TRANSFER_CHECKED_INSTRUCTION];
+    data.extend_from_slice(&account.amount.to_le_bytes());
+    data.push(account.decimals);
+    Instruction::new_with_bytes(
+        account.program_id,
+        &data,
+        vec![
+            AccountMeta::new(account.address, false),
+            AccountMeta::new_readonly(account.mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
+
+/// L'instruction qui brûle tout le solde d'un compte de token (avant sa fermeture).
+pub fn burn_all(account: &TokenAccount, owner: &Pubkey) -> Instruction {
+    let mut data = vec![BURN_CHECKED_INSTRUCTION];
+    data.extend_from_slice(&account.amount.to_le_bytes());
+    data.push(account.decimals);
+    Instruction::new_with_bytes(
+        account.program_id,
+        &data,
+        vec![
+            AccountMeta::new(account.address, false),
+            AccountMeta::new(account.mint, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+    )
+}
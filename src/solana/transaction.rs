@@ -1,12 +1,76 @@
-use solana_client::rpc_client::RpcClient;
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::confirmation::{Landing, SolanaConfirmation};
+use crate::solana::policy::SolanaPolicy;
+use crate::solana::rpc::SolanaRpc;
+use serde::Serialize;
 use solana_sdk::{
-    message::Message,
+    address_lookup_table::AddressLookupTableAccount,
+    compute_budget::ComputeBudgetInstruction,
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, Message, VersionedMessage},
+    nonce::state::{State as NonceState, Versions as NonceVersions},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
-    signature::{Keypair, Signer},
+    signature::{Keypair, Signature, Signer},
     system_instruction,
-    transaction::Transaction,
+    transaction::VersionedTransaction,
 };
 use std::env;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Les frais de base, par signature.
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+/// La limite d'unités de calcul par défaut d'une instruction (sans instruction "SetComputeUnitLimit").
+const DEFAULT_COMPUTE_UNIT_LIMIT: u64 = 200_000;
+
+/// Une transaction envoyée (ou simulée en mode test).
+#[derive(Debug)]
+pub struct SentTransaction {
+    pub signature: Signature,
+    /// Vrai en mode test : la transaction est signée mais pas envoyée.
+    pub simulated: bool,
+    /// Vrai si la transaction a atteint le niveau de confirmation configuré (faux avec "--no-wait").
+    pub confirmed: bool,
+    /// Le nombre d'envois (plus d'un si le blockhash a expiré avant que la transaction n'arrive dans un bloc).
+    pub attempts: u32,
+    /// Le slot dans lequel la transaction a été traitée.
+    pub slot: Option<u64>,
+    /// Les frais payés, en lamports.
+    pub fee: Option<u64>,
+    /// Le niveau de confirmation atteint ("processed", "confirmed" ou "finalized").
+    pub confirmation_status: Option<String>,
+}
+
+/// Une transaction signée qui a quitté le wallet sans que sa confirmation soit obtenue (délai dépassé, réponse
+/// ambiguë du nœud) : elle peut encore arriver dans un bloc, elle est donc enregistrée dans le journal d'audit.
+#[derive(Debug, Serialize)]
+pub struct UnconfirmedTransaction {
+    /// La clé qui a signé la transaction (et payé ses frais).
+    pub from: String,
+    pub signature: String,
+    #[serde(skip)]
+    pub message: String,
+}
+
+impl UnconfirmedTransaction {
+    fn new(from: &Pubkey, signature: &Signature, message: String) -> Box<Self> {
+        Box::new(Self {
+            from: from.to_string(),
+            signature: signature.to_string(),
+            message,
+        })
+    }
+}
+
+impl fmt::Display for UnconfirmedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for UnconfirmedTransaction {}
 
 pub struct SolanaTransaction {}
 
@@ -14,55 +78,421 @@ impl SolanaTransaction {
     /// Envoyer des SOL à une adresse spécifique.
     ///
     /// # Arguments:
-    /// - rpc_url - L'URL du point de terminaison RPC pour se connecter au réseau Solana.
+    /// - config - La configuration du wallet (URL RPC, niveau de confirmation, politiques de frais et de renvoi).
     /// - sender_keypair - La paire de clés du compte expéditeur utilisée pour signer la transaction.
     /// - recipient_pubkey - La clé publique du destinataire qui recevra les SOL.
     /// - lamports - Le montant en lamports à envoyer (1 SOL = 1_000_000_000 lamports).
+    /// - wait - Attendre la confirmation de la transaction (sinon, la signature est retournée dès l'envoi).
     ///
     /// # Returns:
-    /// Retourne un "Result" qui est Ok si la transaction est réussie, ou une erreur en cas d'échec.
+    /// Retourne la transaction envoyée (et sa signature), ou une erreur en cas d'échec.
     pub fn send_lamports(
-        rpc_url: &str,
+        config: &WalletConfig,
         sender_keypair: &Keypair,
         recipient_pubkey: &Pubkey,
         lamports: u64,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+        wait: bool,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        // Crée une instruction pour transférer des lamports du compte expéditeur au destinataire.
+        let instruction =
+            system_instruction::transfer(&sender_keypair.pubkey(), recipient_pubkey, lamports);
+        Self::send_instructions(config, sender_keypair, vec![instruction], wait)
+    }
+
+    /// Signe et envoie des instructions (par exemple un transfert Solana Pay avec son mémo et ses clés de référence).
+    ///
+    /// # Arguments:
+    /// - config - La configuration du wallet.
+    /// - sender_keypair - La paire de clés qui signe la transaction et paie les frais.
+    /// - instructions - Les instructions de la transaction.
+    /// - wait - Attendre la confirmation de la transaction.
+    pub fn send_instructions(
+        config: &WalletConfig,
+        sender_keypair: &Keypair,
+        instructions: Vec<Instruction>,
+        wait: bool,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        Self::send_versioned_instructions(config, sender_keypair, instructions, &[], wait)
+    }
+
+    /// Signe et envoie des instructions dans une transaction legacy, ou dans une transaction v0 si des tables
+    /// d'adresses sont données : chaque compte présent dans une table n'occupe alors qu'un octet
+    /// (au lieu de 32), ce qui permet par exemple de payer bien plus de destinataires dans une même transaction.
+    ///
+    /// # Arguments:
+    /// - config - La configuration du wallet.
+    /// - sender_keypair - La paire de clés qui signe la transaction et paie les frais.
+    /// - instructions - Les instructions de la transaction.
+    /// - lookup_tables - Les tables d'adresses (voir `SolanaLookupTable::fetch_accounts`), vide pour une transaction legacy.
+    /// - wait - Attendre la confirmation de la transaction.
+    pub fn send_versioned_instructions(
+        config: &WalletConfig,
+        sender_keypair: &Keypair,
+        instructions: Vec<Instruction>,
+        lookup_tables: &[AddressLookupTableAccount],
+        wait: bool,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        // Les instructions "Compute Budget" (frais de priorité) précèdent les autres si la politique de frais en définit.
+        let mut all_instructions = Self::fee_policy_instructions(config);
+        all_instructions.extend(instructions);
+
+        // Emballe les instructions dans un message, en spécifiant le compte expéditeur comme compte de frais.
+        let message =
+            Self::compile_message(&sender_keypair.pubkey(), &all_instructions, lookup_tables)?;
+
+        // La politique de dépense du profil est évaluée avant toute signature.
+        let spent = SolanaPolicy::check(config, &sender_keypair.pubkey(), &all_instructions)?;
+
         // Vérifier si nous sommes en mode test.
         if env::var("TEST_MODE").unwrap_or_default() == "true" {
-            // Simulation de la transaction.
-            println!(
-                "Simulating transaction: {} lamports from {} to {}",
-                lamports,
-                sender_keypair.pubkey(),
-                recipient_pubkey
-            );
-            println!("Transaction sent successfully!");
-            Ok(())
+            // Simulation de la transaction : elle est signée (avec un blockhash nul) mais pas envoyée.
+            let transaction = VersionedTransaction::try_new(message, &[sender_keypair])?;
+            SolanaPolicy::record(
+                config,
+                &sender_keypair.pubkey(),
+                spent,
+                &transaction.signatures[0].to_string(),
+            )?;
+            return Ok(SentTransaction {
+                signature: transaction.signatures[0],
+                simulated: true,
+                confirmed: false,
+                attempts: 1,
+                slot: None,
+                fee: None,
+                confirmation_status: None,
+            });
+        }
+
+        // Envoi réel de la transaction via le réseau Solana.
+        Self::send_message(config, sender_keypair, message, spent, wait)
+    }
+
+    /// Compile les instructions en un message legacy (sans table d'adresses) ou v0 (avec un blockhash nul,
+    /// remplacé avant la signature), et vérifie que la transaction signée tiendra dans un paquet.
+    fn compile_message(
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedMessage, Box<dyn std::error::Error>> {
+        let message = if lookup_tables.is_empty() {
+            VersionedMessage::Legacy(Message::new(instructions, Some(payer)))
         } else {
-            // Envoi réel de la transaction via le réseau Solana :
+            VersionedMessage::V0(v0::Message::try_compile(
+                payer,
+                instructions,
+                lookup_tables,
+                Hash::default(),
+            )?)
+        };
 
-            // Crée un client pour interagir avec le réseau Solana via RPC.
-            let client = RpcClient::new(String::from(rpc_url));
+        // Une signature (64 octets) par signataire, précédée de leur nombre (un octet tant qu'il est inférieur à 128).
+        let size =
+            1 + 64 * message.header().num_required_signatures as usize + message.serialize().len();
+        if size > PACKET_DATA_SIZE {
+            return Err(format!(
+                "Transaction too large ({} bytes, max {}){}",
+                size,
+                PACKET_DATA_SIZE,
+                if lookup_tables.is_empty() {
+                    ": use an address lookup table (\"alt\" commands) to reference more accounts"
+                } else {
+                    ""
+                }
+            )
+            .into());
+        }
+        Ok(message)
+    }
 
-            // Récupère le dernier blockhash utilisé comme référence de frais pour la transaction.
-            let recent_blockhash = client.get_latest_blockhash()?;
+    /// Signe et envoie un message, puis attend sa confirmation (sauf si "wait" est faux).
+    ///
+    /// Si le blockhash expire avant que la transaction n'arrive dans un bloc, le message est signé à nouveau avec
+    /// un nouveau blockhash et renvoyé, au plus "max_resends" fois (politique de renvoi de la configuration).
+    /// Les signatures de tous les envois précédents sont surveillées : dès que l'une d'elles arrive dans un bloc,
+    /// plus aucun envoi n'a lieu.
+    ///
+    /// La dépense ("spent", en lamports) est enregistrée dans le journal des dépenses dès le premier envoi,
+    /// avant la confirmation : une transaction qui n'est pas confirmée à temps peut tout de même arriver dans un bloc.
+    fn send_message(
+        config: &WalletConfig,
+        sender_keypair: &Keypair,
+        mut message: VersionedMessage,
+        spent: u64,
+        wait: bool,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        // Crée un client pour interagir avec le réseau Solana via RPC (avec bascule entre les points d'accès).
+        let commitment = config.commitment_config();
+        let client = SolanaRpc::client(config);
+
+        let policy = &config.retry_policy;
+        let deadline = Instant::now() + Duration::from_secs(policy.confirm_timeout);
+        let mut signatures = Vec::new();
+        let mut fee = None;
+
+        loop {
+            // Récupère le dernier blockhash (et la dernière hauteur de bloc à laquelle il est valide).
+            let (recent_blockhash, last_valid_block_height) =
+                client.get_latest_blockhash_with_commitment(commitment)?;
+            message.set_recent_blockhash(recent_blockhash);
+
+            // Les frais du message (frais de base et frais de priorité).
+            if fee.is_none() {
+                fee = match &message {
+                    VersionedMessage::Legacy(message) => client.get_fee_for_message(message),
+                    VersionedMessage::V0(message) => client.get_fee_for_message(message),
+                }
+                .ok();
+            }
+
+            // Crée la transaction en utilisant la paire de clés de l'expéditeur et le message (avec le blockhash récent).
+            // La transaction est signée par la paire de clés de l'expéditeur lors de la création.
+            let transaction = VersionedTransaction::try_new(message.clone(), &[sender_keypair])?;
+
+            // Envoie la transaction signée au réseau Solana (sans attendre sa confirmation).
+            // Si le nœud a pu la recevoir malgré l'erreur, sa signature est surveillée comme celle d'un envoi réussi :
+            // elle ne sera signée à nouveau qu'après l'expiration de son blockhash.
+            let signature = match client.send_transaction(&transaction) {
+                Ok(signature) => signature,
+                Err(e) if wait && SolanaRpc::may_have_been_sent(&e) => transaction.signatures[0],
+                Err(e) if SolanaRpc::may_have_been_sent(&e) => {
+                    if signatures.is_empty() {
+                        SolanaPolicy::record(
+                            config,
+                            &sender_keypair.pubkey(),
+                            spent,
+                            &transaction.signatures[0].to_string(),
+                        )?;
+                    }
+                    return Err(UnconfirmedTransaction::new(
+                        &sender_keypair.pubkey(),
+                        &transaction.signatures[0],
+                        format!(
+                            "{} (the transaction may have been sent, check it with \"confirm {}\")",
+                            e, transaction.signatures[0]
+                        ),
+                    ));
+                }
+                Err(e) => return Err(e.into()),
+            };
+            // Les renvois (nouveau blockhash) portent sur la même dépense : elle n'est enregistrée qu'une fois.
+            if signatures.is_empty() {
+                SolanaPolicy::record(
+                    config,
+                    &sender_keypair.pubkey(),
+                    spent,
+                    &signature.to_string(),
+                )?;
+            }
+            signatures.push(signature);
+            let attempts = signatures.len() as u32;
+
+            if !wait {
+                return Ok(SentTransaction {
+                    signature,
+                    simulated: false,
+                    confirmed: false,
+                    attempts,
+                    slot: None,
+                    fee,
+                    confirmation_status: None,
+                });
+            }
+
+            match SolanaConfirmation::wait_for_landing(
+                &client,
+                &signatures,
+                commitment,
+                last_valid_block_height,
+                deadline,
+            )? {
+                Landing::Landed(landed) => {
+                    if let Some(error) = landed.error {
+                        return Err(
+                            format!("Transaction {} failed: {}", landed.signature, error).into(),
+                        );
+                    }
+                    return Ok(SentTransaction {
+                        signature: landed.signature,
+                        simulated: false,
+                        confirmed: landed.confirmed,
+                        attempts,
+                        slot: Some(landed.slot),
+                        fee,
+                        confirmation_status: landed.confirmation_status,
+                    });
+                }
+                Landing::Expired if attempts <= policy.max_resends => {}
+                Landing::Expired => {
+                    return Err(format!(
+                        "Blockhash expired before the transaction landed ({} attempts)",
+                        attempts
+                    )
+                    .into())
+                }
+                Landing::TimedOut(landed) => {
+                    let signature = landed.map_or(signature, |landed| landed.signature);
+                    return Err(UnconfirmedTransaction::new(
+                        &sender_keypair.pubkey(),
+                        &signature,
+                        format!(
+                            "Transaction {} not confirmed after {} seconds (check it with \"confirm {}\")",
+                            signature, policy.confirm_timeout, signature
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Construit un message non signé utilisant un nonce durable : sa première instruction fait avancer le nonce,
+    /// et la valeur du nonce remplace le blockhash récent. La transaction n'expire donc pas, ce qui laisse le temps
+    /// aux signataires de la signer chacun de leur côté.
+    ///
+    /// # Arguments:
+    /// - config - La configuration du wallet (politique de frais).
+    /// - payer - Le compte qui paie les frais.
+    /// - instructions - Les instructions de la transaction.
+    /// - nonce_account - Le compte de nonce.
+    /// - nonce_authority - L'autorité du compte de nonce (signataire de la transaction).
+    /// - nonce_blockhash - La valeur actuelle du nonce (voir `fetch_nonce`).
+    pub fn nonce_message(
+        config: &WalletConfig,
+        payer: &Pubkey,
+        instructions: Vec<Instruction>,
+        nonce_account: &Pubkey,
+        nonce_authority: &Pubkey,
+        nonce_blockhash: Hash,
+    ) -> Message {
+        let mut all_instructions = Self::fee_policy_instructions(config);
+        all_instructions.extend(instructions);
+        let mut message = Message::new_with_nonce(
+            all_instructions,
+            Some(payer),
+            nonce_account,
+            nonce_authority,
+        );
+        message.recent_blockhash = nonce_blockhash;
+        message
+    }
 
-            // Crée une instruction pour transférer des lamports du compte expéditeur au destinataire.
-            let instruction =
-                system_instruction::transfer(&sender_keypair.pubkey(), recipient_pubkey, lamports);
+    /// Lit un compte de nonce durable via RPC.
+    ///
+    /// # Returns:
+    /// La valeur actuelle du nonce et l'autorité du compte.
+    pub fn fetch_nonce(
+        config: &WalletConfig,
+        nonce_account: &Pubkey,
+    ) -> Result<(Hash, Pubkey), Box<dyn std::error::Error>> {
+        let account = SolanaRpc::client(config)
+            .get_account(nonce_account)
+            .map_err(|e| format!("Cannot load nonce account {}: {}", nonce_account, e))?;
+        let versions: NonceVersions = bincode::deserialize(&account.data)
+            .map_err(|_| format!("{} is not a nonce account", nonce_account))?;
+        match versions.state() {
+            NonceState::Initialized(data) => Ok((data.blockhash(), data.authority)),
+            NonceState::Uninitialized => {
+                Err(format!("Nonce account {} is not initialized", nonce_account).into())
+            }
+        }
+    }
 
-            // Emballe l'instruction dans un message, en spécifiant le compte expéditeur comme compte de frais.
-            let message = Message::new(&[instruction], Some(&sender_keypair.pubkey()));
+    /// Envoie une transaction déjà signée par tous ses signataires (par exemple une transaction à nonce durable),
+    /// puis attend sa confirmation (sauf si "wait" est faux). La transaction n'est jamais signée à nouveau.
+    pub fn send_signed(
+        config: &WalletConfig,
+        transaction: &VersionedTransaction,
+        wait: bool,
+    ) -> Result<SentTransaction, Box<dyn std::error::Error>> {
+        let signature = transaction.signatures[0];
+        let mut sent = SentTransaction {
+            signature,
+            simulated: false,
+            confirmed: false,
+            attempts: 1,
+            slot: None,
+            fee: None,
+            confirmation_status: None,
+        };
 
-            // Crée la transaction en utilisant la paire de clés de l'expéditeur, le message et le blockhash récent.
-            // La transaction est automatiquement signée par la paire de clés de l'expéditeur lors de la création.
-            let transaction = Transaction::new(&[sender_keypair], message, recent_blockhash);
+        // En mode test, la transaction n'est pas envoyée.
+        if env::var("TEST_MODE").unwrap_or_default() == "true" {
+            sent.simulated = true;
+            return Ok(sent);
+        }
 
-            // Envoie la transaction signée au réseau Solana et attend la confirmation.
-            client.send_and_confirm_transaction(&transaction)?;
+        let fee_payer = transaction.message.static_account_keys()[0];
+        let client = SolanaRpc::client(config);
+        match client.send_transaction(transaction) {
+            Ok(_) => {}
+            Err(e) if SolanaRpc::may_have_been_sent(&e) => {
+                return Err(UnconfirmedTransaction::new(
+                    &fee_payer,
+                    &signature,
+                    format!(
+                        "{} (the transaction may have been sent, check it with \"confirm {}\")",
+                        e, signature
+                    ),
+                ))
+            }
+            Err(e) => return Err(e.into()),
+        }
+        if !wait {
+            return Ok(sent);
+        }
+
+        let timeout = Duration::from_secs(config.retry_policy.confirm_timeout);
+        match SolanaConfirmation::wait_for_confirmation(
+            &client,
+            &signature,
+            config.commitment_config(),
+            timeout,
+        )? {
+            Some(landed) => {
+                if let Some(error) = landed.error {
+                    return Err(format!("Transaction {} failed: {}", signature, error).into());
+                }
+                sent.confirmed = landed.confirmed;
+                sent.slot = Some(landed.slot);
+                sent.confirmation_status = landed.confirmation_status;
+                Ok(sent)
+            }
+            None => Err(UnconfirmedTransaction::new(
+                &fee_payer,
+                &signature,
+                format!(
+                    "Transaction {} not found after {} seconds (check it with \"confirm {}\")",
+                    signature, config.retry_policy.confirm_timeout, signature
+                ),
+            )),
+        }
+    }
 
-            // Retourne Ok si tout s'est bien passé.
-            Ok(())
+    /// Estime les frais (en lamports) d'un transfert signé par une seule clé, sans requête RPC :
+    /// frais de base et frais de priorité de la politique de frais (prix de l'unité de calcul x limite d'unités).
+    pub fn estimate_transfer_fee(config: &WalletConfig) -> u64 {
+        let compute_unit_limit = config
+            .fee_policy
+            .compute_unit_limit
+            .map_or(DEFAULT_COMPUTE_UNIT_LIMIT, u64::from);
+        // Le prix de l'unité de calcul est en micro-lamports.
+        let priority_fee = (config.fee_policy.compute_unit_price as u128
+            * compute_unit_limit as u128)
+            .div_ceil(1_000_000) as u64;
+        LAMPORTS_PER_SIGNATURE + priority_fee
+    }
+
+    /// Retourne les instructions "Compute Budget" correspondant à la politique de frais de la configuration.
+    pub fn fee_policy_instructions(config: &WalletConfig) -> Vec<Instruction> {
+        let mut instructions = Vec::new();
+        if let Some(limit) = config.fee_policy.compute_unit_limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(limit));
+        }
+        if config.fee_policy.compute_unit_price > 0 {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                config.fee_policy.compute_unit_price,
+            ));
         }
+        instructions
     }
 }
@@ -0,0 +1,151 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::store::keystore::KeyStore;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Un contact du carnet d'adresses.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Contact {
+    pub name: String,
+    pub pubkey: String,
+    pub created_at: String,
+}
+
+/// Le carnet d'adresses, sérialisé en JSON dans le fichier `ADDRESS_BOOK_PATH`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct AddressBookData {
+    pub contacts: Vec<Contact>,
+    /// Adresses auxquelles des fonds ont déjà été envoyés (pour avertir lors d'un premier envoi).
+    #[serde(default)]
+    pub known_recipients: Vec<String>,
+}
+
+pub struct AddressBook {
+    config: WalletConfig,
+}
+
+impl AddressBook {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Charge le carnet d'adresses depuis le disque.
+    /// Retourne un carnet vide si le fichier n'existe pas encore.
+    pub fn load(&self) -> Result<AddressBookData, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.address_book_path);
+        if !path.exists() {
+            return Ok(AddressBookData::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid address book: {}", e).into())
+    }
+
+    /// Enregistre le carnet d'adresses sur le disque (en créant le dossier parent si nécessaire).
+    pub fn save(&self, data: &AddressBookData) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.address_book_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(data)?)?;
+        Ok(())
+    }
+
+    /// Ajoute un contact.
+    ///
+    /// # Arguments:
+    /// - name - Le nom du contact (ne doit pas être une clé publique).
+    /// - pubkey - La clé publique du contact.
+    pub fn add(&self, name: &str, pubkey: &Pubkey) -> Result<Contact, Box<dyn std::error::Error>> {
+        let mut data = self.load()?;
+
+        if name.trim().is_empty() || Pubkey::from_str(name).is_ok() {
+            return Err("Contact name cannot be empty or a public key".into());
+        }
+        if data.contacts.iter().any(|c| c.name == name) {
+            return Err(format!("Contact '{}' already exists", name).into());
+        }
+
+        let contact = Contact {
+            name: name.to_string(),
+            pubkey: pubkey.to_string(),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        data.contacts.push(contact.clone());
+
+        self.save(&data)?;
+        Ok(contact)
+    }
+
+    /// Retire un contact (par nom).
+    pub fn remove(&self, name: &str) -> Result<Contact, Box<dyn std::error::Error>> {
+        let mut data = self.load()?;
+
+        let position = data
+            .contacts
+            .iter()
+            .position(|c| c.name == name)
+            .ok_or_else(|| format!("No contact named '{}'", name))?;
+        let removed = data.contacts.remove(position);
+
+        self.save(&data)?;
+        Ok(removed)
+    }
+
+    /// Convertit un nom (contact ou label du key store, y compris "watch-only") ou une clé publique en `Pubkey`.
+    ///
+    /// # Arguments:
+    /// - name_or_pubkey - Une clé publique en base58, un nom de contact ou un label du key store.
+    ///
+    /// # Returns:
+    /// Retourne la clé publique correspondante, ou une erreur si rien ne correspond.
+    pub fn resolve(&self, name_or_pubkey: &str) -> Result<Pubkey, Box<dyn std::error::Error>> {
+        if let Ok(pubkey) = Pubkey::from_str(name_or_pubkey) {
+            return Ok(pubkey);
+        }
+
+        let data = self.load()?;
+        if let Some(contact) = data.contacts.iter().find(|c| c.name == name_or_pubkey) {
+            return Ok(Pubkey::from_str(&contact.pubkey)?);
+        }
+
+        let manifest = KeyStore::new(self.config.clone()).load()?;
+        if let Some(entry) = KeyStore::find(&manifest, name_or_pubkey) {
+            return Ok(Pubkey::from_str(&entry.pubkey)?);
+        }
+
+        Err(format!(
+            "'{}' is neither a public key, a contact nor a key store label",
+            name_or_pubkey
+        )
+        .into())
+    }
+
+    /// Indique si l'adresse est connue : contact, clé du key store, ou destinataire d'un envoi précédent.
+    pub fn is_known(&self, pubkey: &Pubkey) -> Result<bool, Box<dyn std::error::Error>> {
+        let pubkey = pubkey.to_string();
+        let data = self.load()?;
+        let manifest = KeyStore::new(self.config.clone()).load()?;
+
+        Ok(data.contacts.iter().any(|c| c.pubkey == pubkey)
+            || data.known_recipients.contains(&pubkey)
+            || manifest.keys.iter().any(|e| e.pubkey == pubkey))
+    }
+
+    /// Mémorise un destinataire après un envoi réussi.
+    pub fn remember_recipient(&self, pubkey: &Pubkey) -> Result<(), Box<dyn std::error::Error>> {
+        let mut data = self.load()?;
+        let pubkey = pubkey.to_string();
+
+        if !data.known_recipients.contains(&pubkey) {
+            data.known_recipients.push(pubkey);
+            self.save(&data)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,401 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::explorer::SolanaExplorer;
+use crate::solana::transaction::UnconfirmedTransaction;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::Signature;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Le hachage qui précède la première entrée du journal.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Les champs d'un résultat de commande qui contiennent des clés publiques.
+const PUBKEY_FIELDS: [&str; 15] = [
+    "from",
+    "to",
+    "pubkey",
+    "signer",
+    "owner",
+    "mint",
+    "account",
+    "destination",
+    "recipient",
+    "authority",
+    "table",
+    "added",
+    "nonce_account",
+    "approved_by",
+    "missing_signers",
+];
+/// Les champs d'un résultat de commande qui contiennent des signatures de transaction.
+const SIGNATURE_FIELDS: [&str; 2] = ["signature", "signatures"];
+/// Les champs d'un résultat de commande qui contiennent des montants en lamports.
+const LAMPORTS_FIELDS: [&str; 3] = ["lamports", "total_lamports", "reclaimed_lamports"];
+/// Les commandes qui envoient des transactions : elles ne sont enregistrées que si une transaction a été signée
+/// (pas les aperçus, "--dry-run" ou balayages annulés).
+const SEND_COMMANDS: [&str; 8] = [
+    "send",
+    "batch_send",
+    "sweep",
+    "pay",
+    "token_accounts cleanup",
+    "create_ata",
+    "alt",
+    "multisig submit",
+];
+
+/// Une entrée du journal d'audit. Seuls des clés publiques, des montants et des signatures y figurent,
+/// jamais de secrets (mnémoniques, shares, clés privées).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// La date de l'opération (RFC 3339).
+    pub timestamp: String,
+    /// La commande, avec sa sous-commande (par exemple "multisig approve").
+    pub command: String,
+    pub profile: Option<String>,
+    /// Le cluster de l'URL RPC ("custom" pour un nœud privé : son URL peut contenir une clé d'API).
+    pub cluster: String,
+    pub pubkeys: Vec<String>,
+    /// Les montants, avec leur unité (par exemple "1000 lamports" ou "12.5 <mint>").
+    pub amounts: Vec<String>,
+    pub signatures: Vec<String>,
+    /// "unconfirmed" pour une transaction envoyée dont la confirmation n'a pas été obtenue (absent sinon).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// Le hachage de l'entrée précédente ("GENESIS_HASH" pour la première).
+    pub prev_hash: String,
+}
+
+/// Une entrée lue depuis le journal, avec son hachage.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct AuditLine {
+    /// Le numéro de la ligne dans le fichier (à partir de 1).
+    pub line: usize,
+    #[serde(flatten)]
+    pub entry: AuditEntry,
+    pub hash: String,
+}
+
+/// Le journal d'audit des opérations du wallet (générations de clés, exports, signatures et envois),
+/// au format JSON Lines dans le fichier `AUDIT_LOG_PATH`.
+///
+/// Chaque entrée contient le hachage SHA-256 de la précédente ("prev_hash") et son propre hachage ("hash",
+/// calculé sur l'entrée sans ce champ) : une entrée modifiée, supprimée ou insérée rompt la chaîne.
+/// La suppression des dernières entrées ne rompt pas la chaîne : elle n'est détectée qu'avec une ancre, le hachage
+/// d'une entrée conservé ailleurs (voir `verify`).
+pub struct AuditLog {
+    config: WalletConfig,
+}
+
+impl AuditLog {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Ajoute le résultat d'une commande au journal (en créant le dossier parent si nécessaire).
+    /// Les clés publiques, montants et signatures sont extraits des champs connus du résultat.
+    ///
+    /// # Returns:
+    /// Faux si l'entrée n'est pas enregistrée (commande d'envoi sans transaction signée).
+    pub fn record<T: Serialize>(
+        &self,
+        command: &str,
+        result: &T,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        self.append(command, result, None)
+    }
+
+    /// Ajoute au journal une transaction envoyée dont la confirmation n'a pas été obtenue (la commande a échoué,
+    /// mais la transaction peut encore arriver dans un bloc), avec le statut "unconfirmed".
+    pub fn record_unconfirmed(
+        &self,
+        command: &str,
+        transaction: &UnconfirmedTransaction,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        self.append(command, transaction, Some("unconfirmed"))
+    }
+
+    /// Enregistre l'issue d'une commande : son résultat si elle a réussi, ou la transaction envoyée mais non
+    /// confirmée qui l'a fait échouer (les autres échecs ne sont pas enregistrés).
+    pub fn record_outcome<T: Serialize, E: AsRef<dyn std::error::Error>>(
+        &self,
+        command: &str,
+        result: &Result<T, E>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        match result {
+            Ok(value) => self.record(command, value),
+            Err(e) => match e.as_ref().downcast_ref::<UnconfirmedTransaction>() {
+                Some(transaction) => self.record_unconfirmed(command, transaction),
+                None => Ok(false),
+            },
+        }
+    }
+
+    fn append<T: Serialize>(
+        &self,
+        command: &str,
+        result: &T,
+        status: Option<&str>,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let result = serde_json::to_value(result)?;
+        let mut entry = AuditEntry {
+            timestamp: Utc::now().to_rfc3339(),
+            command: command.to_string(),
+            profile: self.config.profile.clone(),
+            cluster: Self::cluster(&self.config.rpc_url),
+            pubkeys: Vec::new(),
+            amounts: Vec::new(),
+            signatures: Vec::new(),
+            status: status.map(ToString::to_string),
+            prev_hash: String::new(),
+        };
+        Self::extract(&result, &mut entry);
+
+        let is_send = SEND_COMMANDS
+            .iter()
+            .any(|name| command == *name || command.starts_with(&format!("{} ", name)));
+        if is_send && entry.signatures.is_empty() {
+            return Ok(false);
+        }
+
+        entry.prev_hash = match self.load()?.last() {
+            Some(line) => line.hash.clone(),
+            None => GENESIS_HASH.to_string(),
+        };
+        let Value::Object(mut object) = serde_json::to_value(&entry)? else {
+            unreachable!("an audit entry is serialized as an object");
+        };
+        let hash = Self::hash(&object)?;
+        // Le hachage est le dernier champ de la ligne.
+        object.insert("hash".to_string(), Value::String(hash));
+
+        let path = Path::new(&self.config.audit_log_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(&object)?)?;
+        Ok(true)
+    }
+
+    /// Charge les entrées du journal (vide si le fichier n'existe pas encore), sans vérifier la chaîne.
+    pub fn load(&self) -> Result<Vec<AuditLine>, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.audit_log_path);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        fs::read_to_string(path)?
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                let (entry, hash) = Self::parse(line)
+                    .map_err(|e| format!("Invalid audit log at line {}: {}", index + 1, e))?;
+                Ok(AuditLine {
+                    line: index + 1,
+                    entry: serde_json::from_value(entry)?,
+                    hash,
+                })
+            })
+            .collect()
+    }
+
+    /// Vérifie la chaîne des hachages du journal.
+    ///
+    /// # Arguments:
+    /// - anchor - Le hachage d'une entrée relevé lors d'une vérification précédente : le journal doit toujours la
+    ///   contenir. Sans ancre, un journal tronqué (dernières entrées supprimées) reste une chaîne valide.
+    ///
+    /// # Returns:
+    /// Le nombre d'entrées et le hachage de la dernière ("GENESIS_HASH" si le journal est vide),
+    /// ou la première ligne dont le contenu ou le chaînage est invalide.
+    pub fn verify(
+        &self,
+        anchor: Option<&str>,
+    ) -> Result<(usize, String), Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.audit_log_path);
+        let content = if path.exists() {
+            fs::read_to_string(path)?
+        } else {
+            String::new()
+        };
+
+        let mut previous = GENESIS_HASH.to_string();
+        let mut count = 0;
+        let mut anchored = anchor.is_none() || anchor == Some(GENESIS_HASH);
+        for (index, line) in content.lines().enumerate() {
+            let number = index + 1;
+            let (entry, hash) = Self::parse(line)
+                .map_err(|e| format!("Audit log broken at line {}: {}", number, e))?;
+            let Value::Object(object) = &entry else {
+                return Err(format!("Audit log broken at line {}: not an object", number).into());
+            };
+            if Self::hash(object)? != hash {
+                return Err(format!(
+                    "Audit log broken at line {}: the entry was modified",
+                    number
+                )
+                .into());
+            }
+            if entry["prev_hash"] != previous.as_str() {
+                return Err(format!(
+                    "Audit log broken at line {}: the previous entry is missing or was modified",
+                    number
+                )
+                .into());
+            }
+            anchored |= anchor == Some(hash.as_str());
+            previous = hash;
+            count += 1;
+        }
+        if !anchored {
+            return Err(format!(
+                "Audit log truncated: the anchored entry {} is missing",
+                anchor.unwrap_or_default()
+            )
+            .into());
+        }
+        Ok((count, previous))
+    }
+
+    /// Sépare une ligne du journal en son entrée et son hachage.
+    fn parse(line: &str) -> Result<(Value, String), Box<dyn std::error::Error>> {
+        let mut entry: Value = serde_json::from_str(line)?;
+        let hash = entry
+            .as_object_mut()
+            .and_then(|object| object.remove("hash"))
+            .and_then(|hash| hash.as_str().map(ToString::to_string))
+            .ok_or("missing hash")?;
+        Ok((entry, hash))
+    }
+
+    /// Le hachage SHA-256 (hexadécimal) d'une entrée, calculé sur sa sérialisation JSON.
+    fn hash(object: &Map<String, Value>) -> Result<String, Box<dyn std::error::Error>> {
+        let digest = Sha256::digest(serde_json::to_string(object)?.as_bytes());
+        Ok(digest.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Le cluster d'une URL RPC, sans l'URL d'un nœud privé.
+    fn cluster(rpc_url: &str) -> String {
+        let cluster = SolanaExplorer::cluster(rpc_url);
+        if cluster.starts_with("custom") {
+            "custom".to_string()
+        } else {
+            cluster
+        }
+    }
+
+    /// Extrait les clés publiques, montants et signatures des champs connus d'un résultat (récursivement).
+    /// Les autres champs, dont ceux qui contiennent des secrets, sont ignorés.
+    fn extract(value: &Value, entry: &mut AuditEntry) {
+        match value {
+            Value::Object(object) => {
+                for (key, field) in object {
+                    let strings: Vec<&str> = match field {
+                        Value::String(s) => vec![s.as_str()],
+                        Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+                        _ => Vec::new(),
+                    };
+                    let key = key.as_str();
+                    if PUBKEY_FIELDS.contains(&key) {
+                        for s in strings {
+                            if Pubkey::from_str(s).is_ok() && !entry.pubkeys.iter().any(|p| p == s)
+                            {
+                                entry.pubkeys.push(s.to_string());
+                            }
+                        }
+                    } else if SIGNATURE_FIELDS.contains(&key) {
+                        for s in strings {
+                            if Signature::from_str(s).is_ok() {
+                                entry.signatures.push(s.to_string());
+                            }
+                        }
+                    } else if LAMPORTS_FIELDS.contains(&key) {
+                        if let Some(lamports) = field.as_u64() {
+                            entry.amounts.push(format!("{} lamports", lamports));
+                        }
+                    } else if key == "amount" {
+                        // Un montant de tokens (avec son mint), ou de SOL ("pay" sans "spl_token").
+                        if let Some(amount) = field.as_str() {
+                            let unit = object
+                                .get("mint")
+                                .or_else(|| object.get("spl_token"))
+                                .and_then(Value::as_str)
+                                .unwrap_or("SOL");
+                            entry.amounts.push(format!("{} {}", amount, unit));
+                        }
+                    }
+                    Self::extract(field, entry);
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    Self::extract(item, entry);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extract(result: Value) -> AuditEntry {
+        let mut entry = AuditEntry {
+            timestamp: String::new(),
+            command: String::new(),
+            profile: None,
+            cluster: String::new(),
+            pubkeys: Vec::new(),
+            amounts: Vec::new(),
+            signatures: Vec::new(),
+            status: None,
+            prev_hash: String::new(),
+        };
+        AuditLog::extract(&result, &mut entry);
+        entry
+    }
+
+    #[test]
+    fn test_extract() {
+        let from = Pubkey::new_unique().to_string();
+        let to = Pubkey::new_unique().to_string();
+        let signature = Signature::new_unique().to_string();
+        let entry = extract(json!({
+            "from": from,
+            "transfers": [{ "to": to, "lamports": 1000 }],
+            "signature": signature,
+            "explorer_url": "https://explorer.solana.com/tx/x",
+        }));
+        assert_eq!(entry.pubkeys, vec![from, to]);
+        assert_eq!(entry.amounts, vec!["1000 lamports"]);
+        assert_eq!(entry.signatures, vec![signature]);
+    }
+
+    #[test]
+    fn test_extract_ignores_secrets() {
+        let pubkey = Pubkey::new_unique().to_string();
+        let entry = extract(json!({
+            "matches": [{
+                "pubkey": pubkey,
+                "mnemonic": "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "keypair_path": "./storage/keypair/grind.json",
+            }],
+            "hex": "f".repeat(64),
+            "shares": ["academic acid acrobat"],
+        }));
+        assert_eq!(entry.pubkeys, vec![pubkey]);
+        assert!(entry.amounts.is_empty() && entry.signatures.is_empty());
+        assert!(!serde_json::to_string(&entry).unwrap().contains("abandon"));
+    }
+}
@@ -0,0 +1,336 @@
+use crate::config::wallet_config::WalletConfig;
+use crate::solana::address::SolanaAddress;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signer::keypair::Keypair;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Origine d'une paire de clés gérée par le key store.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum KeySource {
+    /// Clé dérivée d'une mnémonique BIP39 (identifiée par l'empreinte de sa clé maîtresse et le chemin de dérivation).
+    Mnemonic { fingerprint: String, path: String },
+    /// Clé ed25519 dérivée selon BIP85 (empreinte de la clé maîtresse et chemin BIP85).
+    Bip85 { fingerprint: String, path: String },
+    /// Clé importée depuis un fichier existant.
+    Imported,
+    /// Clé générée aléatoirement (sans mnémonique).
+    Generated,
+    /// Adresse surveillée dont nous ne détenons pas la clé privée (aucun fichier associé).
+    WatchOnly,
+}
+
+impl std::fmt::Display for KeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeySource::Mnemonic { fingerprint, path } => {
+                write!(f, "mnemonic ({}, {})", fingerprint, path)
+            }
+            KeySource::Bip85 { fingerprint, path } => {
+                write!(f, "bip85 ({}, {})", fingerprint, path)
+            }
+            KeySource::Imported => write!(f, "imported"),
+            KeySource::Generated => write!(f, "generated"),
+            KeySource::WatchOnly => write!(f, "watch-only"),
+        }
+    }
+}
+
+/// Une entrée du manifeste : décrit une paire de clés stockée localement.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyEntry {
+    pub label: String,
+    pub source: KeySource,
+    pub pubkey: String,
+    /// Chemin du fichier de la paire de clés (absent pour une entrée "watch-only").
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub created_at: String,
+}
+
+/// Le manifeste du key store, sérialisé en JSON dans le fichier `KEYSTORE_PATH`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct KeyStoreManifest {
+    /// Label de la paire de clés utilisée par défaut (voir la commande "wallet use").
+    pub active: Option<String>,
+    pub keys: Vec<KeyEntry>,
+}
+
+pub struct KeyStore {
+    config: WalletConfig,
+}
+
+impl KeyStore {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Charge le manifeste depuis le disque.
+    /// Retourne un manifeste vide si le fichier n'existe pas encore.
+    pub fn load(&self) -> Result<KeyStoreManifest, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.keystore_path);
+        if !path.exists() {
+            return Ok(KeyStoreManifest::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid key store manifest: {}", e).into())
+    }
+
+    /// Enregistre le manifeste sur le disque (en créant le dossier parent si nécessaire).
+    pub fn save(&self, manifest: &KeyStoreManifest) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.keystore_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(manifest)?)?;
+        Ok(())
+    }
+
+    /// Enregistre une paire de clés dans le manifeste.
+    /// Une entrée pointant déjà vers le même fichier est remplacée (le fichier ayant été réécrit).
+    /// Si le label est déjà utilisé par une autre clé, un suffixe numérique est ajouté.
+    ///
+    /// # Arguments:
+    /// - label - Le label souhaité pour la paire de clés.
+    /// - source - L'origine de la paire de clés.
+    /// - keypair_path - Le chemin du fichier contenant la paire de clés.
+    /// - pubkey - La clé publique de la paire de clés.
+    ///
+    /// # Returns:
+    /// Retourne l'entrée enregistrée, ou une erreur si le manifeste ne peut être lu ou écrit.
+    pub fn register(
+        &self,
+        label: &str,
+        source: KeySource,
+        keypair_path: &str,
+        pubkey: &Pubkey,
+    ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut manifest = self.load()?;
+
+        // Retire l'ancienne entrée associée à ce fichier (son contenu vient d'être remplacé).
+        if let Some(position) = manifest
+            .keys
+            .iter()
+            .position(|e| e.path.as_deref() == Some(keypair_path))
+        {
+            let removed = manifest.keys.remove(position);
+            if manifest.active.as_deref() == Some(removed.label.as_str()) {
+                manifest.active = None;
+            }
+        }
+
+        let entry = KeyEntry {
+            label: Self::unique_label(&manifest, label),
+            source,
+            pubkey: pubkey.to_string(),
+            path: Some(keypair_path.to_string()),
+            created_at: Utc::now().to_rfc3339(),
+        };
+        manifest.keys.push(entry.clone());
+
+        self.save(&manifest)?;
+        Ok(entry)
+    }
+
+    /// Retourne le chemin du fichier à utiliser pour une nouvelle paire de clés gérée par le key store
+    /// (dans le dossier du manifeste, nommé d'après la clé publique).
+    pub fn new_keypair_path(&self, pubkey: &Pubkey) -> String {
+        let directory = Path::new(&self.config.keystore_path)
+            .parent()
+            .unwrap_or_else(|| Path::new("."));
+        directory
+            .join(format!("{}.json", pubkey))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Ajoute une entrée "watch-only" : une adresse suivie (solde, historique) sans clé privée.
+    pub fn watch(
+        &self,
+        label: &str,
+        pubkey: &Pubkey,
+    ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut manifest = self.load()?;
+        Self::validate_label(label)?;
+
+        if manifest.keys.iter().any(|e| e.label == label) {
+            return Err(format!("Label '{}' is already used", label).into());
+        }
+
+        let entry = KeyEntry {
+            label: label.to_string(),
+            source: KeySource::WatchOnly,
+            pubkey: pubkey.to_string(),
+            path: None,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        manifest.keys.push(entry.clone());
+
+        self.save(&manifest)?;
+        Ok(entry)
+    }
+
+    /// Renomme une paire de clés.
+    pub fn rename(
+        &self,
+        label: &str,
+        new_label: &str,
+    ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut manifest = self.load()?;
+        Self::validate_label(new_label)?;
+
+        if manifest.keys.iter().any(|e| e.label == new_label) {
+            return Err(format!("Label '{}' is already used", new_label).into());
+        }
+
+        let entry = manifest
+            .keys
+            .iter_mut()
+            .find(|e| e.label == label)
+            .ok_or_else(|| format!("No key labelled '{}'", label))?;
+        entry.label = new_label.to_string();
+        let entry = entry.clone();
+
+        if manifest.active.as_deref() == Some(label) {
+            manifest.active = Some(new_label.to_string());
+        }
+
+        self.save(&manifest)?;
+        Ok(entry)
+    }
+
+    /// Définit la paire de clés utilisée par défaut par les commandes qui signent ou lisent une clé.
+    pub fn set_active(&self, selector: &str) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut manifest = self.load()?;
+        let entry = Self::find(&manifest, selector)
+            .ok_or_else(|| format!("No key matching '{}'", selector))?
+            .clone();
+
+        if entry.source == KeySource::WatchOnly {
+            return Err(format!("Key '{}' is watch-only and cannot sign", entry.label).into());
+        }
+
+        manifest.active = Some(entry.label.clone());
+        self.save(&manifest)?;
+        Ok(entry)
+    }
+
+    /// Retire une paire de clés du manifeste.
+    /// Le fichier de la paire de clés n'est supprimé que si `delete_file` est vrai.
+    pub fn remove(
+        &self,
+        selector: &str,
+        delete_file: bool,
+    ) -> Result<KeyEntry, Box<dyn std::error::Error>> {
+        let mut manifest = self.load()?;
+        let label = Self::find(&manifest, selector)
+            .ok_or_else(|| format!("No key matching '{}'", selector))?
+            .label
+            .clone();
+
+        let position = manifest
+            .keys
+            .iter()
+            .position(|e| e.label == label)
+            .expect("Entry found above");
+        let removed = manifest.keys.remove(position);
+
+        if manifest.active.as_deref() == Some(label.as_str()) {
+            manifest.active = None;
+        }
+
+        if let Some(path) = removed.path.as_deref().filter(|_| delete_file) {
+            if Path::new(path).exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        self.save(&manifest)?;
+        Ok(removed)
+    }
+
+    /// Détermine le chemin du fichier de la paire de clés à utiliser.
+    ///
+    /// # Arguments:
+    /// - from - Un label ou une clé publique (option "--from"). Si absent, la clé active est utilisée,
+    ///   puis à défaut `KEYPAIR_PATH`.
+    ///
+    /// # Returns:
+    /// Retourne le chemin du fichier, ou une erreur si aucune clé ne correspond.
+    pub fn resolve_keypair_path(
+        &self,
+        from: Option<&str>,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let manifest = self.load()?;
+
+        let selector = match from {
+            Some(selector) => selector,
+            None => match manifest.active.as_deref() {
+                Some(active) => active,
+                None => return Ok(self.config.keypair_path.clone()),
+            },
+        };
+
+        let entry = Self::find(&manifest, selector)
+            .ok_or_else(|| format!("No key matching '{}'", selector))?;
+        entry
+            .path
+            .clone()
+            .ok_or_else(|| format!("Key '{}' is watch-only (no keypair file)", entry.label).into())
+    }
+
+    /// Lit la paire de clés sélectionnée (voir `resolve_keypair_path`).
+    pub fn resolve_keypair(
+        &self,
+        from: Option<&str>,
+    ) -> Result<Keypair, Box<dyn std::error::Error>> {
+        let keypair_path = self.resolve_keypair_path(from)?;
+        SolanaAddress::read_keypair_from_file(&keypair_path)
+            .map_err(|e| format!("Failed to read keypair from '{}': {}", keypair_path, e).into())
+    }
+
+    /// Recherche une entrée par label, ou à défaut par clé publique.
+    pub fn find<'a>(manifest: &'a KeyStoreManifest, selector: &str) -> Option<&'a KeyEntry> {
+        manifest
+            .keys
+            .iter()
+            .find(|e| e.label == selector)
+            .or_else(|| {
+                Pubkey::from_str(selector)
+                    .ok()
+                    .and_then(|_| manifest.keys.iter().find(|e| e.pubkey == selector))
+            })
+    }
+
+    /// Retourne un label libre, en ajoutant si besoin un suffixe numérique ("main-2", "main-3", etc.).
+    fn unique_label(manifest: &KeyStoreManifest, label: &str) -> String {
+        let is_free = |candidate: &str| !manifest.keys.iter().any(|e| e.label == candidate);
+
+        if is_free(label) {
+            return label.to_string();
+        }
+
+        (2..)
+            .map(|n| format!("{}-{}", label, n))
+            .find(|candidate| is_free(candidate))
+            .expect("An unused label always exists")
+    }
+
+    /// Un label ne doit pas être vide ni pouvoir être confondu avec une clé publique.
+    fn validate_label(label: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if label.trim().is_empty() {
+            return Err("Label cannot be empty".into());
+        }
+        if Pubkey::from_str(label).is_ok() {
+            return Err("Label cannot be a public key".into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod address_book;
+pub mod audit_log;
+pub mod keystore;
+pub mod shell_history;
+pub mod spending_ledger;
@@ -0,0 +1,97 @@
+use crate::config::wallet_config::WalletConfig;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Commandes dont les arguments contiennent des secrets (mnémonique, shares SLIP-39).
+const SECRET_COMMANDS: [&str; 3] = ["recover_seed", "bip85", "backup"];
+/// Options dont la valeur est un secret (entropie de la mnémonique).
+const SECRET_OPTIONS: [&str; 2] = ["--entropy-hex", "--entropy-dice"];
+/// Nombre minimal de mots d'une mnémonique BIP39.
+const MNEMONIC_MIN_WORDS: usize = 12;
+
+/// L'historique du mode interactif ("shell"), une commande par ligne dans le fichier `SHELL_HISTORY_PATH`.
+/// Les commandes contenant des secrets n'y sont jamais enregistrées.
+pub struct ShellHistory {
+    config: WalletConfig,
+}
+
+impl ShellHistory {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Charge l'historique depuis le disque (vide si le fichier n'existe pas encore).
+    pub fn load(&self) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.shell_history_path);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .map(ToString::to_string)
+            .collect())
+    }
+
+    /// Ajoute une commande à la fin de l'historique (en créant le dossier parent si nécessaire).
+    /// Retourne faux si la commande contient un secret (elle n'est alors pas enregistrée).
+    ///
+    /// # Arguments:
+    /// - line - La ligne saisie.
+    /// - args - Les arguments de la ligne (après le traitement des guillemets).
+    pub fn append(&self, line: &str, args: &[String]) -> Result<bool, Box<dyn std::error::Error>> {
+        if Self::is_secret(args) {
+            return Ok(false);
+        }
+
+        let path = Path::new(&self.config.shell_history_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", line)?;
+        Ok(true)
+    }
+
+    /// Vrai si les arguments contiennent un secret : commande à mnémonique ou à shares, option d'entropie,
+    /// ou argument ressemblant à une mnémonique (au moins 12 mots).
+    pub fn is_secret(args: &[String]) -> bool {
+        args.iter().any(|arg| {
+            SECRET_COMMANDS.contains(&arg.as_str())
+                || SECRET_OPTIONS
+                    .iter()
+                    .any(|option| arg == option || arg.starts_with(&format!("{}=", option)))
+                || arg.split_whitespace().count() >= MNEMONIC_MIN_WORDS
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_is_secret() {
+        assert!(ShellHistory::is_secret(&args(&[
+            "recover_seed",
+            "mechanic spread manual soul flash above wrist weasel creek ill lazy tourist"
+        ])));
+        assert!(ShellHistory::is_secret(&args(&["bip85", "hex", "phrase"])));
+        assert!(ShellHistory::is_secret(&args(&[
+            "generate_seed",
+            "--entropy-hex=00ff"
+        ])));
+        assert!(!ShellHistory::is_secret(&args(&["send", "alice", "1000"])));
+        assert!(!ShellHistory::is_secret(&args(&[
+            "generate_seed",
+            "--words",
+            "24"
+        ])));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::config::wallet_config::WalletConfig;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::fs;
+use std::path::Path;
+
+/// La durée de la fenêtre glissante du plafond journalier, en secondes.
+pub const DAY_SECONDS: i64 = 24 * 60 * 60;
+
+/// Une dépense : les SOL envoyés par une transaction signée par le wallet.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpendingEntry {
+    pub signer: String,
+    pub lamports: u64,
+    pub signature: String,
+    /// La date de la signature (timestamp Unix, en secondes).
+    pub timestamp: i64,
+}
+
+/// Le journal des dépenses des dernières 24 heures, sérialisé en JSON dans le fichier `SPENDING_LEDGER_PATH`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpendingLedgerData {
+    pub entries: Vec<SpendingEntry>,
+}
+
+pub struct SpendingLedger {
+    config: WalletConfig,
+}
+
+impl SpendingLedger {
+    pub fn new(config: WalletConfig) -> Self {
+        Self { config }
+    }
+
+    /// Charge le journal depuis le disque.
+    /// Retourne un journal vide si le fichier n'existe pas encore.
+    pub fn load(&self) -> Result<SpendingLedgerData, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.spending_ledger_path);
+        if !path.exists() {
+            return Ok(SpendingLedgerData::default());
+        }
+
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| format!("Invalid spending ledger: {}", e).into())
+    }
+
+    /// Enregistre le journal sur le disque (en créant le dossier parent si nécessaire).
+    pub fn save(&self, data: &SpendingLedgerData) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config.spending_ledger_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(data)?)?;
+        Ok(())
+    }
+
+    /// Le montant envoyé par une clé au cours des dernières 24 heures, en lamports.
+    pub fn spent_today(&self, signer: &Pubkey) -> Result<u64, Box<dyn std::error::Error>> {
+        let since = Utc::now().timestamp() - DAY_SECONDS;
+        let signer = signer.to_string();
+        Ok(self
+            .load()?
+            .entries
+            .iter()
+            .filter(|entry| entry.signer == signer && entry.timestamp > since)
+            .map(|entry| entry.lamports)
+            .sum())
+    }
+
+    /// Ajoute une dépense au journal (les dépenses de plus de 24 heures en sont retirées).
+    pub fn record(
+        &self,
+        signer: &Pubkey,
+        lamports: u64,
+        signature: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let now = Utc::now().timestamp();
+        let mut data = self.load()?;
+        data.entries
+            .retain(|entry| entry.timestamp > now - DAY_SECONDS);
+        data.entries.push(SpendingEntry {
+            signer: signer.to_string(),
+            lamports,
+            signature: signature.to_string(),
+            timestamp: now,
+        });
+        self.save(&data)
+    }
+}
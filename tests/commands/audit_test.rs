@@ -0,0 +1,159 @@
+use crate::common;
+use serde_json::Value;
+use serial_test::serial;
+use std::fs;
+
+// cargo test --test mod -- commands::audit_test --nocapture
+
+#[test]
+#[serial]
+fn test_audit_log() {
+    common::setup();
+
+    let storage = common::TempStorage::new("audit_test");
+    let audit_log = format!("{}/audit_log.jsonl", storage.dir);
+
+    let recipient = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/5 : Génération de clé et envoi enregistrés, sans secret
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["generate_seed"]);
+    let mnemonic = common::find_value(&output, "BIP39 Mnemonic").to_string();
+    let seed = common::find_value(&output, "Seed:").to_string();
+    let pubkey = common::find_value(&output, "Solana Public Key:").to_string();
+
+    let output = common::run(&["--output", "json", "send", recipient, "1000"]);
+    let sent: Value = serde_json::from_str(&output).unwrap();
+    let signature = sent["signature"].as_str().unwrap();
+
+    let content = fs::read_to_string(&audit_log).unwrap();
+    assert_eq!(content.lines().count(), 2, "Error: entries not recorded");
+    assert!(
+        !content.contains(&mnemonic) && !content.contains(&seed),
+        "Error: a secret is in the audit log"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/5 : "audit show" et ses filtres
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["audit", "show", "--command", "send"]);
+    assert!(
+        output.contains(&format!("  Keys: {}, {}", pubkey, recipient))
+            && output.contains("  Amounts: 1000 lamports")
+            && output.contains(&format!("  Signature: {}", signature)),
+        "Error: send entry not displayed"
+    );
+    assert!(
+        !output.contains("generate_seed"),
+        "Error: command filter not applied"
+    );
+
+    let output = common::run(&["--output", "json", "audit", "show", "--pubkey", &pubkey]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["entries"].as_array().unwrap().len(), 2);
+    assert_eq!(result["entries"][0]["command"], "generate_seed");
+
+    let output = common::run(&["audit", "show", "--since", "2999-01-01"]);
+    assert!(
+        output.contains("No audit entries."),
+        "Error: date filter not applied"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/5 : Un envoi non confirmé est enregistré
+    |--------------------------------------------------------------------------
+    */
+
+    let url = common::stub_unconfirmed_rpc_server();
+    {
+        let _env = common::EnvVars::set(&[("TEST_MODE", "false"), ("CONFIRM_TIMEOUT", "1")]);
+        let output = common::run_failure(&["--url", &url, "send", recipient, "2000"]);
+        assert!(
+            output.contains("not confirmed after 1 seconds"),
+            "Error: unconfirmed transaction not reported"
+        );
+    }
+
+    let output = common::run(&["--output", "json", "audit", "show", "--command", "send"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    let entry = &result["entries"][1];
+    assert_eq!(entry["status"], "unconfirmed");
+    assert_eq!(entry["signatures"].as_array().unwrap().len(), 1);
+    let output = common::run(&["audit", "show", "--command", "send"]);
+    assert!(
+        output.contains(&format!(
+            "  Signature: {} (unconfirmed)",
+            entry["signatures"][0].as_str().unwrap()
+        )),
+        "Error: unconfirmed send not displayed"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/5 : "audit verify" d'un journal intact
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["audit", "verify"]);
+    assert!(
+        output.contains("Entries: 3") && output.contains("The hash chain is intact."),
+        "Error: intact audit log not verified"
+    );
+    let last_hash = common::find_value(&output, "Last hash").to_string();
+    let output = common::run(&["audit", "verify", "--anchor", &last_hash]);
+    assert!(
+        output.contains(&format!("Anchored entry {} found.", last_hash)),
+        "Error: anchored entry not found"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 5/5 : Une entrée modifiée, supprimée ou tronquée (avec une ancre) est détectée
+    |--------------------------------------------------------------------------
+    */
+
+    fs::write(&audit_log, content.replace("1000 lamports", "100 lamports")).unwrap();
+    let output = common::run_failure(&["audit", "verify"]);
+    assert!(
+        output.contains("Audit log broken at line 2: the entry was modified"),
+        "Error: modified entry not detected"
+    );
+
+    // Sans ancre, un journal tronqué reste une chaîne valide.
+    let first_lines: String = content
+        .lines()
+        .take(2)
+        .map(|line| format!("{}\n", line))
+        .collect();
+    fs::write(&audit_log, first_lines).unwrap();
+    let output = common::run(&["audit", "verify"]);
+    assert!(
+        output.contains("Entries: 2"),
+        "Error: truncated audit log not verified"
+    );
+    let output = common::run_failure(&["audit", "verify", "--anchor", &last_hash]);
+    assert!(
+        output.contains(&format!(
+            "Audit log truncated: the anchored entry {} is missing",
+            last_hash
+        )),
+        "Error: truncated audit log not detected"
+    );
+
+    let second_line = content.lines().nth(1).unwrap();
+    fs::write(&audit_log, format!("{}\n", second_line)).unwrap();
+    let output = common::run_failure(&["audit", "verify"]);
+    assert!(
+        output
+            .contains("Audit log broken at line 1: the previous entry is missing or was modified"),
+        "Error: removed entry not detected"
+    );
+}
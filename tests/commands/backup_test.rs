@@ -0,0 +1,98 @@
+use crate::common;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::backup_test --nocapture
+
+#[test]
+#[serial]
+fn test_backup_commands() {
+    common::setup();
+
+    let phrase = "shed scorpion manual wheat monster phone winter toe dream kitchen salad column";
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : Partage simple (3 shares sur 5)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "backup",
+        "split",
+        phrase,
+        "--threshold",
+        "3",
+        "--shares",
+        "5",
+    ]);
+    let shares: Vec<String> = (1..=5)
+        .map(|i| common::find_value(&output, &format!("Share 1-{}", i)).to_string())
+        .collect();
+    assert!(
+        shares
+            .iter()
+            .all(|share| share.split_whitespace().count() == 20),
+        "Error: a 128-bit share should contain 20 words"
+    );
+
+    let output = common::run(&[
+        "backup", "combine", "--share", &shares[4], "--share", &shares[0], "--share", &shares[2],
+    ]);
+    assert_eq!(
+        common::find_value(&output, "BIP39 Mnemonic"),
+        phrase,
+        "The mnemonic should be recovered from 3 shares"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : Shares insuffisantes
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&[
+        "backup", "combine", "--share", &shares[0], "--share", &shares[1],
+    ]);
+    assert!(
+        output.contains("Failed to combine shares"),
+        "Error: 2 shares out of 3 should not be enough"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Groupes (2 groupes sur 3 requis)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "backup",
+        "split",
+        phrase,
+        "--group-threshold",
+        "2",
+        "--group",
+        "1/1",
+        "--group",
+        "2/3",
+        "--group",
+        "3/5",
+        "--iteration-exponent",
+        "0",
+    ]);
+
+    let output = common::run(&[
+        "backup",
+        "combine",
+        "--share",
+        common::find_value(&output, "Share 1-1"),
+        "--share",
+        common::find_value(&output, "Share 2-3"),
+        "--share",
+        common::find_value(&output, "Share 2-1"),
+    ]);
+    assert_eq!(
+        common::find_value(&output, "BIP39 Mnemonic"),
+        phrase,
+        "The mnemonic should be recovered from 2 complete groups"
+    );
+}
@@ -0,0 +1,74 @@
+use crate::common;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::bip85_test --nocapture
+
+#[test]
+#[serial]
+fn test_bip85_commands() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("bip85_test");
+
+    let master = "shed scorpion manual wheat monster phone winter toe dream kitchen salad column";
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : Mnémonique enfant importée dans le key store
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "bip85", "mnemonic", master, "--words", "24", "--index", "3", "--import", "alice",
+    ]);
+    assert_eq!(
+        common::find_value(&output, "BIP85 Path"),
+        "m/83696968'/39'/0'/24'/3'"
+    );
+    let child = common::find_value(&output, "BIP39 Mnemonic (child 3)").to_string();
+    assert_eq!(child.split_whitespace().count(), 24);
+    let pubkey = common::find_value(&output, "Solana Public Key (alice)").to_string();
+
+    // La dérivation est déterministe.
+    let output = common::run(&["bip85", "mnemonic", master, "--words", "24", "--index", "3"]);
+    assert_eq!(
+        common::find_value(&output, "BIP39 Mnemonic (child 3)"),
+        child
+    );
+
+    // La clé importée est celle que "recover_seed" produit à partir de la mnémonique enfant.
+    let recover_output = common::run(&["recover_seed", &child]);
+    assert_eq!(
+        common::find_value(&recover_output, "Solana Public Key:"),
+        pubkey
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : Clé ed25519 importée dans le key store
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "bip85", "ed25519", master, "--index", "1", "--import", "bob",
+    ]);
+    let pubkey = common::find_value(&output, "Solana Public Key (bob)").to_string();
+    common::verify_pubkey(&pubkey);
+
+    let list_output = common::run(&["wallet", "list"]);
+    assert!(
+        list_output.contains(&format!("bob {} bip85", pubkey)),
+        "Error: BIP85 key not registered in key store"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Entropie brute
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["bip85", "hex", master, "--bytes", "64"]);
+    let hex = common::find_value(&output, "Hex (child 0)");
+    assert_eq!(hex.len(), 128);
+    assert!(hex.chars().all(|c| c.is_ascii_hexdigit()));
+}
@@ -0,0 +1,112 @@
+use crate::common;
+use serial_test::serial;
+use std::fs;
+use std::process::Command;
+
+// cargo test --test mod -- commands::config_test --nocapture
+
+#[test]
+#[serial]
+fn test_config_show_command() {
+    common::setup();
+
+    let storage = common::TempStorage::new("config_test");
+    fs::create_dir_all(&storage.dir).unwrap();
+    fs::write(
+        format!("{}/config.toml", storage.dir),
+        r#"
+default_profile = "devnet"
+
+[profiles.devnet]
+commitment = "confirmed"
+
+[profiles.mainnet]
+rpc_url = "https://api.mainnet-beta.solana.com"
+commitment = "finalized"
+derivation_template = "m/44'/501'/{index}'/0'"
+fee_policy = { compute_unit_price = 5000 }
+"#,
+    )
+    .unwrap();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : Profil par défaut du fichier de configuration
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["config", "show"]);
+    assert_eq!(
+        common::find_value(&output, "profile"),
+        "devnet (config file)"
+    );
+    assert_eq!(
+        common::find_value(&output, "commitment"),
+        "confirmed (profile 'devnet')"
+    );
+    // RPC_URL est défini dans ".env.testing" : la variable d'environnement est prioritaire sur le profil.
+    assert_eq!(
+        common::find_value(&output, "rpc_url"),
+        "https://api.devnet.solana.com (env RPC_URL)"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : Profil et options de la ligne de commande
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "config",
+        "show",
+        "--profile",
+        "mainnet",
+        "--url",
+        "http://localhost:8899",
+    ]);
+    assert_eq!(
+        common::find_value(&output, "profile"),
+        "mainnet (flag --profile)"
+    );
+    assert_eq!(
+        common::find_value(&output, "rpc_url"),
+        "http://localhost:8899 (flag --url)"
+    );
+    assert_eq!(
+        common::find_value(&output, "derivation_template"),
+        "m/44'/501'/{index}'/0' (profile 'mainnet')"
+    );
+    assert_eq!(
+        common::find_value(&output, "compute_unit_price"),
+        "5000 (profile 'mainnet')"
+    );
+    assert_eq!(
+        common::find_value(&output, "address_book_path"),
+        format!("{}/address_book.json (env ADDRESS_BOOK_PATH)", storage.dir)
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : Nom de cluster (comme avec "solana -u")
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["config", "show", "-u", "t"]);
+    assert_eq!(
+        common::find_value(&output, "rpc_url"),
+        "https://api.testnet.solana.com (flag --url)"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : Profil inconnu
+    |--------------------------------------------------------------------------
+    */
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "config", "show", "--profile", "unknown"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success(), "An unknown profile should fail");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown profile 'unknown'"));
+}
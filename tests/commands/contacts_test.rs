@@ -0,0 +1,97 @@
+use crate::common;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::contacts_test --nocapture
+
+#[test]
+#[serial]
+fn test_contacts_commands() {
+    common::setup();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Préparation d'un stockage temporaire (supprimé à la fin du test).
+    |--------------------------------------------------------------------------
+    */
+
+    let _storage = common::TempStorage::new("contacts_test");
+
+    // Génère la paire de clés de l'expéditeur.
+    common::run(&["generate_seed"]);
+
+    let alice = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+    let unknown = "FTGJPL5hia749v3jhNWJA7uE2VoVGyofB7BBL2cLwoPc";
+    let treasury = "7zjLEhEEazFnjoq13cQ3LABLhR69tXbJQqTBunBazEUP";
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : Ajout de contacts et d'une adresse "watch-only"
+    |--------------------------------------------------------------------------
+    */
+
+    common::run(&["contacts", "add", "alice", alice]);
+    common::run(&["wallet", "watch", "treasury", treasury]);
+
+    let list_output = common::run(&["contacts", "list"]);
+    assert!(
+        list_output.contains(&format!("alice {}", alice)),
+        "Error: contact not found in contacts list"
+    );
+
+    let wallet_output = common::run(&["wallet", "list"]);
+    assert!(
+        wallet_output.contains(&format!("treasury {} watch-only", treasury)),
+        "Error: watch-only entry not found in wallet list"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : Envoi vers des noms (aucun avertissement)
+    |--------------------------------------------------------------------------
+    */
+
+    let send_output = common::run(&["send", "alice", "1000"]);
+    assert!(
+        send_output.contains("1000 lamports from") && send_output.contains(alice),
+        "Error: contact name not resolved"
+    );
+    assert!(
+        !send_output.contains("Warning"),
+        "Error: no warning expected for a contact"
+    );
+
+    let send_output = common::run(&["send", "treasury", "1000"]);
+    assert!(
+        send_output.contains(treasury),
+        "Error: watch-only label not resolved"
+    );
+    assert!(
+        !send_output.contains("Warning"),
+        "Error: no warning expected for a watch-only entry"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Premier envoi vers une adresse inconnue
+    |--------------------------------------------------------------------------
+    */
+
+    let send_output = common::run(&["send", unknown, "1000"]);
+    assert!(
+        send_output.contains(&format!("Warning: {}", unknown)),
+        "Error: warning expected for an unknown recipient"
+    );
+
+    let send_output = common::run(&["send", unknown, "1000"]);
+    assert!(
+        !send_output.contains("Warning"),
+        "Error: no warning expected for a known recipient"
+    );
+
+    common::run(&["contacts", "remove", "alice"]);
+    let list_output = common::run(&["contacts", "list"]);
+    assert!(
+        !list_output.contains("alice"),
+        "Error: removed contact still listed"
+    );
+}
@@ -0,0 +1,91 @@
+use crate::common;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::Value;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::decode_test --nocapture
+
+const PAYER: &str = "EvvyH83F635WYPrKreLzYL1ADfij716a5ssqhXhTsib8";
+const RECIPIENT: &str = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+const UNKNOWN_PROGRAM: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+
+/// Transaction legacy signée par PAYER : limite et prix des unités de calcul, mémo "Order #42",
+/// transfert de 0.25 SOL à RECIPIENT, puis une instruction d'un programme inconnu (données 010203).
+const TRANSACTION: &str = "AeIEK0sC2mIwajVGkfe4OSDHZXeWNyfMwsNdapznz06YqhptMYjnUKBYe8MgmVUs9iTGfy6c+hESizJ4+uG5pgYBAAQGzvi8wsf9ivDX5JqgG9Ryu9r5SrpdWoyE4d8tJX3Y7m+6DUca7M9qlDHgL6KvzHJ2IAJ79ZjE6kSO/1PjRx7vAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAwZGb+UhFzL/7K26csOb57yM5bvF9xJrLEObOkAAAAAFSlNamSkhBk0k6HFg2jh8fDW13bySu4HkH6hAQQVEjWhn588KYMSbwLzREtwP7uAKb5H3qh+0JMvbsS5455QyzEkOkozS44c7s0P8ldozF5ymD02/RsLDbpEpnVXU5rkFAwAFAkANAwADAAkD6AMAAAAAAAAEAQAJT3JkZXIgIzQyAgIAAQwCAAAAgLLmDgAAAAAFAQEDAQID";
+
+#[test]
+#[serial]
+fn test_decode() {
+    common::setup();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : Décodage d'une transaction signée
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["decode", TRANSACTION]);
+    assert_eq!(common::find_value(&output, "Version"), "legacy");
+    assert_eq!(common::find_value(&output, "Fee payer"), PAYER);
+    assert!(
+        output.contains(&format!("{}: valid", PAYER)),
+        "Error: signature not verified"
+    );
+    assert!(
+        output.contains(&format!("#0 {} (signer, writable)", PAYER))
+            && output.contains(&format!("#1 {} (writable)", RECIPIENT)),
+        "Error: account flags not displayed"
+    );
+    assert!(
+        output.contains("compute-budget: setComputeUnitPrice")
+            && output.contains("spl-memo: memo")
+            && output.contains("Order #42")
+            && output.contains("system: transfer")
+            && output.contains("250000000 (0.25 SOL)"),
+        "Error: instructions not decoded"
+    );
+    assert!(
+        output.contains(&format!(
+            "Warning: Instruction 5 is not recognised (program {})",
+            UNKNOWN_PROGRAM
+        )),
+        "Error: unknown instruction not flagged"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : Sortie JSON, transaction lue sur l'entrée standard
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_with_input(&["--output", "json", "decode", "-"], TRANSACTION);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["instructions"][3]["program"], "system");
+    assert_eq!(
+        result["instructions"][3]["info"]["lamports"],
+        250_000_000u64
+    );
+    assert_eq!(result["instructions"][4]["recognised"], false);
+    assert_eq!(result["instructions"][4]["data"], "010203");
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Transaction modifiée après la signature, transaction invalide
+    |--------------------------------------------------------------------------
+    */
+
+    let mut bytes = BASE64_STANDARD.decode(TRANSACTION).unwrap();
+    *bytes.last_mut().unwrap() = 0x04;
+    let output = common::run(&["decode", &BASE64_STANDARD.encode(bytes)]);
+    assert!(
+        output.contains(&format!("{}: invalid", PAYER)) && output.contains("data: 010204"),
+        "Error: invalid signature not reported"
+    );
+
+    let output = common::run_failure(&["decode", "not-a-transaction"]);
+    assert!(
+        output.contains("Failed to decode transaction"),
+        "Error: invalid transaction accepted"
+    );
+}
@@ -1,5 +1,7 @@
 use crate::common;
 use serial_test::serial;
+use solana_sdk::signature::{write_keypair_file, Keypair};
+use std::fs;
 use std::process::Command;
 use std::str;
 
@@ -92,3 +94,111 @@ fn test_generate_seed_command() {
     let pubkey = pubkey_line.split(':').nth(1).unwrap().trim();
     common::verify_pubkey(pubkey);
 }
+
+#[test]
+#[serial]
+fn test_generate_seed_with_user_entropy() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("generate_seed_entropy_test");
+
+    /*
+    |--------------------------------------------------------------------------
+    | Lancers de dé : conversion déterministe (SHA-256 des lancers)
+    |--------------------------------------------------------------------------
+    */
+
+    let rolls = "123456".repeat(17);
+    let output = common::run(&["generate_seed", "--entropy-dice", &rolls]);
+    assert!(
+        output.contains("Entropy: 102 rolls, 263.7 bits collected (128 bits required)"),
+        "Error: collected entropy not displayed"
+    );
+    let phrase = common::find_value(&output, "BIP39 Mnemonic (user entropy)").to_string();
+
+    let output = common::run(&["generate_seed", "--entropy-dice", &rolls]);
+    assert_eq!(
+        common::find_value(&output, "BIP39 Mnemonic (user entropy)"),
+        phrase,
+        "The same rolls should give the same mnemonic"
+    );
+
+    let output = common::run(&[
+        "generate_seed",
+        "--entropy-dice",
+        &rolls,
+        "--mix-os-entropy",
+    ]);
+    assert_ne!(
+        common::find_value(&output, "BIP39 Mnemonic (user entropy)"),
+        phrase,
+        "The OS entropy should change the mnemonic"
+    );
+
+    let output = common::run_failure(&[
+        "generate_seed",
+        "--entropy-dice",
+        &rolls[..60],
+        "--words",
+        "24",
+    ]);
+    assert!(
+        output.contains("Failed to collect entropy: Insufficient entropy"),
+        "Error: 60 rolls should not be enough for 24 words"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Entropie hexadécimale (vecteur BIP39 "00000000...")
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["generate_seed", "--entropy-hex", &"00".repeat(16)]);
+    assert_eq!(
+        common::find_value(&output, "BIP39 Mnemonic (user entropy)"),
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+    );
+}
+
+#[test]
+#[serial]
+fn test_generate_seed_keeps_solana_cli_keypair() {
+    common::setup();
+
+    let storage = common::TempStorage::new("generate_seed_cli_keypair_test");
+    let cli_keypair = format!("{}/solana/id.json", storage.dir);
+    write_keypair_file(&Keypair::new(), &cli_keypair).unwrap();
+    let content = fs::read(&cli_keypair).unwrap();
+
+    // Sans KEYPAIR_PATH, la paire de clés de la CLI Solana est la clé de signature par défaut.
+    std::env::remove_var("KEYPAIR_PATH");
+    fs::write(
+        format!("{}/solana_cli_config.yml", storage.dir),
+        format!("---\nkeypair_path: {}\n", cli_keypair),
+    )
+    .unwrap();
+
+    let output = common::run(&["generate_seed"]);
+    assert!(
+        output.contains(&format!(
+            "The Solana CLI keypair {} is only used for signing",
+            cli_keypair
+        )),
+        "Error: Solana CLI keypair used as the write target"
+    );
+    assert_eq!(
+        fs::read(&cli_keypair).unwrap(),
+        content,
+        "Error: Solana CLI keypair overwritten"
+    );
+
+    common::run(&[
+        "recover_seed",
+        "mechanic spread manual soul flash above wrist weasel creek ill lazy tourist",
+    ]);
+    assert_eq!(
+        fs::read(&cli_keypair).unwrap(),
+        content,
+        "Error: Solana CLI keypair overwritten"
+    );
+}
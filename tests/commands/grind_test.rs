@@ -0,0 +1,58 @@
+use crate::common;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::grind_test --nocapture
+
+#[test]
+#[serial]
+fn test_grind_command() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("grind_test");
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/2 : Paire de clés aléatoire (préfixe, sensible à la casse)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["grind", "--starts-with", "A", "--threads", "2"]);
+    let pubkey = common::find_value(&output, "Solana Public Key (vanity)");
+    common::verify_pubkey(pubkey);
+    assert!(pubkey.starts_with('A'), "Error: prefix not matched");
+
+    let list_output = common::run(&["wallet", "list"]);
+    assert!(
+        list_output.contains(&format!("vanity {} generated", pubkey)),
+        "Error: vanity key not registered in key store"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/2 : Mnémonique (suffixe, insensible à la casse), récupérable avec "recover_seed"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "grind",
+        "--ends-with",
+        "z",
+        "--ignore-case",
+        "--use-mnemonic",
+        "--label",
+        "recoverable",
+    ]);
+    let phrase = common::find_value(&output, "BIP39 Mnemonic (vanity phrase)").to_string();
+    let pubkey = common::find_value(&output, "Solana Public Key (recoverable)").to_string();
+    assert!(
+        pubkey.to_lowercase().ends_with('z'),
+        "Error: suffix not matched"
+    );
+
+    let recover_output = common::run(&["recover_seed", &phrase]);
+    assert_eq!(
+        common::find_value(&recover_output, "Solana Public Key:"),
+        pubkey,
+        "The vanity key should be recoverable from its mnemonic"
+    );
+}
@@ -0,0 +1,168 @@
+use crate::common;
+use rust_solana_wallet::config::config_file::RetryPolicy;
+use rust_solana_wallet::solana::rpc::{FailoverSender, SolanaRpc};
+use serde_json::{json, Value};
+use serial_test::serial;
+use solana_client::rpc_client::{RpcClient, RpcClientConfig};
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::pubkey::Pubkey;
+use std::sync::Mutex;
+
+// cargo test --test mod -- commands::health_test --nocapture
+
+#[test]
+#[serial]
+fn test_rpc_failover() {
+    common::setup();
+
+    // Le premier point d'accès est en bonne santé mais limite le débit de "getBalance" (429),
+    // le deuxième est en retard, le troisième est à jour.
+    let limited = common::stub_rpc_server(|method| match method {
+        "getBalance" => (429, Value::Null),
+        _ => common::healthy(method, 1000),
+    });
+    let late = common::stub_rpc_server(|method| common::healthy(method, 10));
+    let up_to_date = common::stub_rpc_server(|method| common::healthy(method, 1000));
+
+    let _env = common::EnvVars::set(&[
+        ("RPC_URL", &limited),
+        ("RPC_URLS", &format!("{},{}", late, up_to_date)),
+        ("RPC_BACKOFF_MS", "1"),
+    ]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/2 : Santé des points d'accès
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["health"]);
+    assert!(
+        output.contains(&format!("{}: ok, slot 1000 (0 behind)", limited)),
+        "Error: healthy endpoint not reported"
+    );
+    assert!(
+        output.contains(&format!(
+            "{}: ok, slot 10 (990 behind), used as last resort",
+            late
+        )),
+        "Error: lagging endpoint not reported"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/2 : Bascule vers le point d'accès suivant
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "balance_by_pubkey",
+        "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2",
+    ]);
+    assert!(
+        output.contains("Balance: 2.000000000 SOL (2000000000 lamports)"),
+        "Error: balance not retrieved from the fallback endpoint"
+    );
+}
+
+fn failover_client(urls: &[&String]) -> RpcClient {
+    let policy = RetryPolicy {
+        rpc_backoff_ms: 1,
+        ..RetryPolicy::default()
+    };
+    let urls: Vec<String> = urls.iter().map(|url| url.to_string()).collect();
+    RpcClient::new_sender(
+        FailoverSender::new(&urls, policy),
+        RpcClientConfig::default(),
+    )
+}
+
+fn count(requests: &Mutex<Vec<Value>>, method: &str) -> usize {
+    requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|request| request["method"] == method)
+        .count()
+}
+
+#[test]
+fn test_retry_then_failover() {
+    // Le premier point d'accès est en bonne santé, mais répond 503 à "getBalance".
+    let (first_url, first) = common::recording_stub_rpc_server(|method, _| match method {
+        "getBalance" => (503, Value::Null),
+        _ => common::healthy(method, 100),
+    });
+    let (second_url, second) =
+        common::recording_stub_rpc_server(|method, _| common::healthy(method, 100));
+
+    let client = failover_client(&[&first_url, &second_url]);
+    assert_eq!(
+        client.get_balance(&Pubkey::new_unique()).unwrap(),
+        2_000_000_000
+    );
+
+    // 1 envoi + 2 nouvelles tentatives, puis le point d'accès suivant.
+    assert_eq!(count(&first, "getBalance"), 3);
+    assert_eq!(count(&second, "getBalance"), 1);
+}
+
+#[test]
+fn test_health_ordering() {
+    // Le premier point d'accès est en retard de 900 slots, le deuxième ne répond pas "ok" à "getHealth".
+    let (late_url, late) =
+        common::recording_stub_rpc_server(|method, _| common::healthy(method, 100));
+    let (unhealthy_url, unhealthy) = common::recording_stub_rpc_server(|method, _| match method {
+        "getHealth" => (503, Value::Null),
+        _ => common::healthy(method, 1000),
+    });
+    let (ok_url, ok) = common::recording_stub_rpc_server(|method, _| common::healthy(method, 1000));
+
+    let client = failover_client(&[&late_url, &unhealthy_url, &ok_url]);
+    assert_eq!(
+        client.get_balance(&Pubkey::new_unique()).unwrap(),
+        2_000_000_000
+    );
+    assert_eq!(count(&late, "getBalance"), 0);
+    assert_eq!(count(&unhealthy, "getBalance"), 0);
+    assert_eq!(count(&ok, "getBalance"), 1);
+
+    // L'ordre est établi une seule fois.
+    client.get_balance(&Pubkey::new_unique()).unwrap();
+    assert_eq!(count(&ok, "getHealth"), 1);
+}
+
+#[test]
+fn test_send_transaction_failover() {
+    // 429 : la transaction n'a pas été traitée, les mêmes octets sont envoyés au point d'accès suivant.
+    let (limited_url, limited) = common::recording_stub_rpc_server(|method, _| match method {
+        "sendTransaction" => (429, Value::Null),
+        _ => common::healthy(method, 100),
+    });
+    let (ok_url, ok) = common::recording_stub_rpc_server(|method, _| common::healthy(method, 100));
+
+    let client = failover_client(&[&limited_url, &ok_url]);
+    let params = json!(["AQID", { "encoding": "base64" }]);
+    let result = client
+        .send::<String>(RpcRequest::SendTransaction, params.clone())
+        .unwrap();
+    assert_eq!(result, "signature");
+    let sent = ok.lock().unwrap().last().unwrap()["params"].clone();
+    assert_eq!(sent, params);
+    assert!(count(&limited, "sendTransaction") > 0);
+
+    // 500 : la transaction a pu être traitée, elle n'est envoyée nulle part ailleurs.
+    let (failing_url, failing) = common::recording_stub_rpc_server(|method, _| match method {
+        "sendTransaction" => (500, Value::Null),
+        _ => common::healthy(method, 100),
+    });
+    let (ok_url, ok) = common::recording_stub_rpc_server(|method, _| common::healthy(method, 100));
+
+    let client = failover_client(&[&failing_url, &ok_url]);
+    let error = client
+        .send::<String>(RpcRequest::SendTransaction, params)
+        .unwrap_err();
+    assert!(SolanaRpc::may_have_been_sent(&error));
+    assert_eq!(count(&failing, "sendTransaction"), 1);
+    assert_eq!(count(&ok, "sendTransaction"), 0);
+}
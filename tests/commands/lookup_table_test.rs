@@ -0,0 +1,147 @@
+use crate::common;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use serial_test::serial;
+use solana_sdk::address_lookup_table::state::{AddressLookupTable, LookupTableMeta};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::borrow::Cow;
+use std::fs;
+use std::str::FromStr;
+
+// cargo test --test mod -- commands::lookup_table_test --nocapture
+
+const TABLE: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+const RECIPIENTS: usize = 30;
+
+/// Réponses du nœud de test : tout compte lu est une table d'adresses active de "authority",
+/// contenant "addresses".
+fn stub_lookup_table(authority: Pubkey, addresses: Vec<Pubkey>) -> String {
+    let data = AddressLookupTable {
+        meta: LookupTableMeta::new(authority),
+        addresses: Cow::Owned(addresses),
+    }
+    .serialize_for_tests()
+    .unwrap();
+    let data = BASE64_STANDARD.encode(&data);
+
+    common::stub_rpc_server_with_params(move |method, params| match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        // Le slot de création de la table doit être finalisé (présent dans "SlotHashes").
+        "getSlot" if params[0]["commitment"] == "finalized" => (200, json!(301_457_218)),
+        "getAccountInfo" => (
+            200,
+            json!({
+                "context": { "slot": 1000 },
+                "value": {
+                    "data": [data, "base64"],
+                    "executable": false,
+                    "lamports": 10_000_000,
+                    "owner": "AddressLookupTab1e1111111111111111111111111",
+                    "rentEpoch": 0,
+                    "space": 56 + 32 * RECIPIENTS
+                }
+            }),
+        ),
+        _ => (404, Value::Null),
+    })
+}
+
+#[test]
+#[serial]
+fn test_lookup_tables() {
+    common::setup();
+
+    let storage = common::TempStorage::new("lookup_table_test");
+
+    let output = common::run(&["generate_seed"]);
+    let pubkey = Pubkey::from_str(common::find_value(&output, "Solana Public Key:")).unwrap();
+
+    // Des adresses de wallets (sur la courbe), pour passer les vérifications des destinataires.
+    let recipients: Vec<Pubkey> = (0..RECIPIENTS).map(|_| Keypair::new().pubkey()).collect();
+    let url = stub_lookup_table(pubkey, recipients.clone());
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : "alt show" et "alt create"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["alt", "show", TABLE]);
+    assert_eq!(common::find_value(&output, "Authority"), pubkey.to_string());
+    assert_eq!(common::find_value(&output, "Status"), "active");
+    assert_eq!(
+        common::find_value(&output, "Addresses"),
+        RECIPIENTS.to_string()
+    );
+    assert!(
+        output.contains(&format!("  0 {}", recipients[0])),
+        "Error: addresses not displayed"
+    );
+
+    let output = common::run(&["alt", "create"]);
+    assert!(
+        output.contains(&format!("Table created (authority {})", pubkey)),
+        "Error: table not created"
+    );
+    common::verify_pubkey(common::find_value(&output, "Address lookup table"));
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : "alt extend", "alt close"
+    |--------------------------------------------------------------------------
+    */
+
+    let new_address = Pubkey::new_unique().to_string();
+    let output = common::run(&[
+        "--output",
+        "json",
+        "alt",
+        "extend",
+        TABLE,
+        &recipients[0].to_string(),
+        &new_address,
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["added"], json!([new_address]));
+    assert_eq!(result["simulated"], true);
+
+    let output = common::run_failure(&["alt", "close", TABLE]);
+    assert!(
+        output.contains("must be deactivated first"),
+        "Error: active table closed"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Paiement groupé, legacy puis v0 avec la table d'adresses
+    |--------------------------------------------------------------------------
+    */
+
+    let payments = format!("{}/payments.csv", storage.dir);
+    let lines: Vec<String> = recipients
+        .iter()
+        .map(|recipient| format!("{},1000", recipient))
+        .collect();
+    fs::write(&payments, format!("# Payouts\n{}\n", lines.join("\n"))).unwrap();
+
+    let output = common::run_failure(&["batch_send", &payments]);
+    assert!(
+        output.contains("Transaction too large") && output.contains("address lookup table"),
+        "Error: oversized legacy transaction accepted"
+    );
+
+    let output = common::run(&["batch_send", &payments, "--lookup-table", TABLE]);
+    assert!(
+        output.contains(&format!(
+            "Simulating batch payout: {} transfers ({} lamports) from {} in a v0 transaction",
+            RECIPIENTS,
+            RECIPIENTS * 1000,
+            pubkey
+        )),
+        "Error: v0 batch payout not simulated"
+    );
+    common::find_value(&output, "Signature");
+}
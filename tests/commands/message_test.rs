@@ -0,0 +1,107 @@
+use crate::common;
+use serde_json::Value;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::message_test --nocapture
+
+#[test]
+#[serial]
+fn test_sign_and_verify_message() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("message_test");
+
+    let output = common::run(&["generate_seed"]);
+    let pubkey = common::find_value(&output, "Solana Public Key:").to_string();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : Message hors chaîne signé puis vérifié avec les clés du key store
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["sign_message", "I own this address"]);
+    assert_eq!(common::find_value(&output, "Signer"), pubkey);
+    assert_eq!(
+        common::find_value(&output, "Format"),
+        "off-chain message v0, restricted ASCII"
+    );
+    let signature = common::find_value(&output, "Signature").to_string();
+
+    let output = common::run(&["verify_message", "I own this address", &signature]);
+    assert!(
+        output.contains(&format!("Valid signature by {} (main)", pubkey)),
+        "Error: signer not found"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : Message modifié et signataire attendu différent
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["verify_message", "I own this address!", &signature]);
+    assert!(
+        output.contains("Invalid signature: not signed by any of the"),
+        "Error: tampered message accepted"
+    );
+
+    let output = common::run(&[
+        "verify_message",
+        "I own this address",
+        &signature,
+        "--signer",
+        "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2",
+    ]);
+    assert!(
+        output.contains("Invalid signature: not signed by any of the 1 key(s) checked."),
+        "Error: wrong signer accepted"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : Signature ed25519 brute d'un message hexadécimal
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["sign_message", "--hex", "--raw", "0xdeadbeef"]);
+    assert_eq!(common::find_value(&output, "Format"), "raw ed25519");
+    let signature = common::find_value(&output, "Signature").to_string();
+
+    let output = common::run(&[
+        "verify_message",
+        "--hex",
+        "deadbeef",
+        &signature,
+        "--signer",
+        &pubkey,
+    ]);
+    assert!(
+        output.contains(&format!("Valid signature by {}", pubkey))
+            && output.contains("Format: raw ed25519"),
+        "Error: raw signature not verified"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : Sortie JSON
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json", "sign_message", "Ça va ?"]);
+    let signed: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(signed["signer"], pubkey.as_str());
+    assert_eq!(signed["encoding"], "offchain_limited_utf8");
+
+    let output = common::run(&[
+        "--output",
+        "json",
+        "verify_message",
+        "Ça va ?",
+        signed["signature"].as_str().unwrap(),
+    ]);
+    let verified: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(verified["valid"], true);
+    assert_eq!(verified["signer"], pubkey.as_str());
+    assert_eq!(verified["signer_name"], "main");
+}
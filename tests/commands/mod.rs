@@ -1,5 +1,27 @@
+pub mod audit_test;
+pub mod backup_test;
 pub mod balance_by_pubkey_test;
+pub mod bip85_test;
+pub mod config_test;
+pub mod contacts_test;
+pub mod decode_test;
 pub mod generate_seed_test;
+pub mod grind_test;
+pub mod health_test;
+pub mod lookup_table_test;
+pub mod message_test;
+pub mod multisig_test;
+pub mod output_test;
+pub mod pay_test;
 
 pub mod pubkey_test;
+pub mod recipient_checks_test;
 pub mod recover_seed_test;
+pub mod send_test;
+pub mod shell_test;
+pub mod sign_tx_test;
+pub mod spending_policy_test;
+pub mod sweep_test;
+pub mod token_accounts_test;
+pub mod tui_test;
+pub mod wallet_test;
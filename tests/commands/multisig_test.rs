@@ -0,0 +1,225 @@
+use crate::common;
+use serde_json::Value;
+use serial_test::serial;
+use std::fs;
+
+// cargo test --test mod -- commands::multisig_test --nocapture
+
+const RECIPIENT: &str = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+const NONCE_ACCOUNT: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+const NONCE_BLOCKHASH: &str = "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N";
+
+#[test]
+#[serial]
+fn test_multisig_workflow() {
+    common::setup();
+
+    let storage = common::TempStorage::new("multisig_test");
+    let _env = common::EnvVars::set(&[("NB_DERIVATIONS", "3")]);
+
+    // La trésorerie ("main"), deux co-signataires dont un seul doit approuver ("derivation-1" et
+    // "derivation-2") et une clé non requise.
+    let mnemonic = "shed scorpion manual wheat monster phone winter toe dream kitchen salad column";
+    let output = common::run(&["recover_seed", mnemonic]);
+    let treasury = common::find_value(&output, "Solana Public Key:").to_string();
+    let first = common::find_value(&output, "Solana Public Key (derivation 1):").to_string();
+    let second = common::find_value(&output, "Solana Public Key (derivation 2):").to_string();
+    let request = format!("{}/request.json", storage.dir);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : "multisig propose" (hors ligne, avec la valeur du nonce)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "multisig",
+        "propose",
+        &request,
+        "--from",
+        "main",
+        "--to",
+        RECIPIENT,
+        "--amount",
+        "1000000",
+        "--signer",
+        "derivation-1",
+        "--signer",
+        "derivation-2",
+        "--threshold",
+        "1",
+        "--nonce",
+        NONCE_ACCOUNT,
+        "--nonce-blockhash",
+        NONCE_BLOCKHASH,
+        "--description",
+        "Q3 audit invoice",
+    ]);
+    assert_eq!(
+        common::find_value(&output, "Threshold"),
+        "1 of 2 co-signers"
+    );
+    assert_eq!(common::find_value(&output, "Approvals"), "0/1");
+    assert!(
+        [&treasury, &first, &second]
+            .iter()
+            .all(|signer| output.contains(&format!("  {} pending", signer))),
+        "Error: required signers not listed"
+    );
+
+    // Une variante par co-signataire, avec le même nonce.
+    let document: Value = serde_json::from_str(&fs::read_to_string(&request).unwrap()).unwrap();
+    let transactions = document["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+
+    // Le transfert, le mémo et l'avance du nonce sont visibles dans la transaction.
+    let output = common::run(&["decode", transactions[0].as_str().unwrap()]);
+    assert_eq!(
+        common::find_value(&output, "Recent blockhash"),
+        NONCE_BLOCKHASH
+    );
+    assert!(
+        output.contains("system: advanceNonce")
+            && output.contains("memo: Q3 audit invoice")
+            && output.contains("system: transfer"),
+        "Error: invalid signing request transaction"
+    );
+
+    let output = common::run_failure(&[
+        "multisig",
+        "propose",
+        &request,
+        "--from",
+        "main",
+        "--to",
+        RECIPIENT,
+        "--amount",
+        "1",
+        "--nonce",
+        NONCE_ACCOUNT,
+        "--nonce-blockhash",
+        NONCE_BLOCKHASH,
+    ]);
+    assert!(
+        output.contains("already exists"),
+        "Error: signing request overwritten"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : une demande modifiée est refusée
+    |--------------------------------------------------------------------------
+    */
+
+    // Deux fois la variante du premier co-signataire : le second ne pourrait plus approuver.
+    let tampered = format!("{}/tampered.json", storage.dir);
+    let mut copy = document.clone();
+    copy["transactions"][1] = copy["transactions"][0].clone();
+    fs::write(&tampered, copy.to_string()).unwrap();
+    let output = common::run_failure(&["multisig", "status", &tampered]);
+    assert!(
+        output.contains(
+            "is not a valid signing request: two of its transactions require the same co-signers"
+        ),
+        "Error: incomplete signing request accepted"
+    );
+
+    // Une variante d'un autre transfert.
+    let other = format!("{}/other.json", storage.dir);
+    common::run(&[
+        "multisig",
+        "propose",
+        &other,
+        "--from",
+        "main",
+        "--to",
+        RECIPIENT,
+        "--amount",
+        "999999999",
+        "--signer",
+        "derivation-2",
+        "--nonce",
+        NONCE_ACCOUNT,
+        "--nonce-blockhash",
+        NONCE_BLOCKHASH,
+        "--description",
+        "Q3 audit invoice",
+    ]);
+    let other: Value = serde_json::from_str(&fs::read_to_string(&other).unwrap()).unwrap();
+    let mut copy = document.clone();
+    copy["transactions"][1] = other["transactions"][0].clone();
+    fs::write(&tampered, copy.to_string()).unwrap();
+    let output = common::run_failure(&["multisig", "status", &tampered]);
+    assert!(
+        output.contains("do not describe the same transfer"),
+        "Error: signing request with another transfer accepted"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : "multisig approve" par la trésorerie et un co-signataire
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_with_input(&["multisig", "approve", &request], "y\n");
+    assert!(
+        output.contains("Sign this transaction with"),
+        "Error: no confirmation asked"
+    );
+    assert_eq!(common::find_value(&output, "Approved by"), treasury);
+    assert_eq!(common::find_value(&output, "Approvals"), "0/1");
+
+    let output = common::run_failure(&["multisig", "submit", &request]);
+    assert!(
+        output.contains(&format!("Missing signatures: 1 of {}, {}", first, second)),
+        "Error: incomplete transaction submitted"
+    );
+
+    let output = common::run_failure(&[
+        "multisig",
+        "approve",
+        &request,
+        "--from",
+        "derivation-3",
+        "--yes",
+    ]);
+    assert!(
+        output.contains("is not a required signer of this transaction"),
+        "Error: signature of a non-required key accepted"
+    );
+
+    let output = common::run(&[
+        "multisig",
+        "approve",
+        &request,
+        "--from",
+        "derivation-2",
+        "--yes",
+    ]);
+    assert_eq!(common::find_value(&output, "Approvals"), "1/1");
+    assert!(
+        output.contains(&format!("  {} signed", second))
+            && output.contains(&format!("  {} pending", first))
+            && output.contains("All required signatures are present"),
+        "Error: complete request not reported"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : "multisig status" et "multisig submit"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json", "multisig", "status", &request]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["ready"], true);
+    assert_eq!(result["description"], "Q3 audit invoice");
+
+    let output = common::run(&["multisig", "submit", &request]);
+    assert!(
+        output.contains("Simulating submission")
+            && output.contains("Transaction sent successfully!"),
+        "Error: signing request not submitted"
+    );
+    common::find_value(&output, "Signature");
+}
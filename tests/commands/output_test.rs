@@ -0,0 +1,83 @@
+use crate::common;
+use serde_json::Value;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::output_test --nocapture
+
+#[test]
+#[serial]
+fn test_json_output() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("output_test");
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : "--output json" (la sortie standard ne contient que le résultat)
+    |--------------------------------------------------------------------------
+    */
+
+    let phrase = "mechanic spread manual soul flash above wrist weasel creek ill lazy tourist";
+    let output = common::run(&["recover_seed", phrase, "--output", "json"]);
+    let result: Value = serde_json::from_str(&output).expect("Output should be valid JSON");
+    assert_eq!(result["origin"], "given");
+    assert_eq!(result["mnemonic"], phrase);
+    assert_eq!(
+        result["keys"][0]["pubkey"],
+        "7zjLEhEEazFnjoq13cQ3LABLhR69tXbJQqTBunBazEUP"
+    );
+    // NB_DERIVATIONS=2 dans ".env.testing" : la clé principale et 2 dérivations.
+    assert_eq!(result["keys"].as_array().unwrap().len(), 3);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : "--output json-compact" (une seule ligne)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json-compact", "pubkey"]);
+    assert_eq!(output.lines().count(), 1, "Compact JSON should be one line");
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(
+        result["pubkey"],
+        "7zjLEhEEazFnjoq13cQ3LABLhR69tXbJQqTBunBazEUP"
+    );
+
+    let output = common::run(&[
+        "--output",
+        "json-compact",
+        "send",
+        "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2",
+        "1000",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["lamports"], 1000);
+    assert_eq!(result["simulated"], true);
+    assert!(
+        result["signature"].as_str().unwrap().len() > 80,
+        "Error: signature not found"
+    );
+    assert_eq!(
+        result["explorer_url"],
+        format!(
+            "https://explorer.solana.com/tx/{}?cluster=devnet",
+            result["signature"].as_str().unwrap()
+        )
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Erreurs
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["--output", "json", "balance_by_pubkey", "unknown-name"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert!(
+        result["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Failed to retrieve balance"),
+        "Error: error message not found"
+    );
+}
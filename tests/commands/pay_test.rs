@@ -0,0 +1,161 @@
+use crate::common;
+use serde_json::{json, Value};
+use serial_test::serial;
+
+// cargo test --test mod -- commands::pay_test --nocapture
+
+const MERCHANT: &str = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const REFERENCE: &str = "82ZJ7nbGpixjeDCmEhUcmwXYfvurzAgGdtSMuHnUgyny";
+
+/// Réponses du nœud de test : tout compte lu est un mint du programme Token, avec 6 décimales.
+fn respond(method: &str) -> (u16, Value) {
+    match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getAccountInfo" => (
+            200,
+            json!({
+                "context": { "slot": 1000 },
+                "value": {
+                    "data": [
+                        "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAGAQAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA==",
+                        "base64"
+                    ],
+                    "executable": false,
+                    "lamports": 1_461_600,
+                    "owner": "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV",
+                    "rentEpoch": 0,
+                    "space": 82
+                }
+            }),
+        ),
+        _ => (404, Value::Null),
+    }
+}
+
+#[test]
+#[serial]
+fn test_pay_commands() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("pay_test");
+    let url = common::stub_rpc_server(respond);
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    let output = common::run(&["generate_seed"]);
+    let pubkey = common::find_value(&output, "Solana Public Key:").to_string();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : "request_payment" (URL et QR code)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "request_payment",
+        "--amount",
+        "1.5",
+        "--label",
+        "Coffee shop",
+        "--memo",
+        "Order #42",
+        "--new-reference",
+        "--qr",
+    ]);
+    let request_url = common::find_value(&output, "Payment request").to_string();
+    assert!(
+        request_url.starts_with(&format!("solana:{}?amount=1.5&reference=", pubkey)),
+        "Error: invalid payment request URL"
+    );
+    assert!(
+        request_url.ends_with("&label=Coffee%20shop&memo=Order%20%2342"),
+        "Error: label and memo not encoded"
+    );
+    common::verify_pubkey(common::find_value(&output, "Reference"));
+    assert!(output.contains('█'), "Error: QR code not displayed");
+
+    let output = common::run_failure(&["request_payment", "--amount", "0.0000000001"]);
+    assert!(
+        output.contains("has more than 9 decimals"),
+        "Error: invalid amount accepted"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : "pay" d'une demande de SOL (simulée en mode test)
+    |--------------------------------------------------------------------------
+    */
+
+    let pay_url = format!(
+        "solana:{}?amount=0.25&reference={}&label=Michael&message=Thanks%20for%20all%20the%20fish&memo=OrderId12345",
+        MERCHANT, REFERENCE
+    );
+    let output = common::run(&["pay", &pay_url]);
+    assert!(
+        output.contains(&format!(
+            "Simulating payment: 0.25 SOL from {} to {}",
+            pubkey, MERCHANT
+        )),
+        "Error: payment not simulated"
+    );
+    assert!(
+        output.contains("Label: Michael")
+            && output.contains("Message: Thanks for all the fish")
+            && output.contains("Memo: OrderId12345"),
+        "Error: request details not displayed"
+    );
+    common::find_value(&output, "Signature");
+
+    let output = common::run(&["--output", "json", "pay", &pay_url]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["base_units"], 250_000_000u64);
+    assert_eq!(result["references"][0], REFERENCE);
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : Demande sans montant
+    |--------------------------------------------------------------------------
+    */
+
+    let open_url = format!("solana:{}", MERCHANT);
+    let output = common::run_failure(&["pay", &open_url]);
+    assert!(
+        output.contains("The payment request has no amount"),
+        "Error: missing amount not reported"
+    );
+
+    let output = common::run(&["pay", &open_url, "--amount", "2"]);
+    assert!(
+        output.contains("Simulating payment: 2 SOL"),
+        "Error: --amount not used"
+    );
+
+    let output = common::run_failure(&["pay", &pay_url, "--amount", "2"]);
+    assert!(
+        output.contains("does not match"),
+        "Error: conflicting amount accepted"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : "pay" d'une demande de token SPL
+    |--------------------------------------------------------------------------
+    */
+
+    let token_url = format!("solana:{}?amount=12.5&spl-token={}", MERCHANT, MINT);
+    let output = common::run(&["--output", "json", "pay", &token_url]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["spl_token"], MINT);
+    assert_eq!(result["amount"], "12.5");
+    assert_eq!(result["base_units"], 12_500_000u64);
+
+    let output = common::run_failure(&[
+        "pay",
+        &format!("solana:{}?amount=12.1234567&spl-token={}", MERCHANT, MINT),
+    ]);
+    assert!(
+        output.contains("has more than 6 decimals"),
+        "Error: token decimals not checked"
+    );
+}
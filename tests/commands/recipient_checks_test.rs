@@ -0,0 +1,236 @@
+use crate::common;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use std::str::FromStr;
+
+// cargo test --test mod -- commands::recipient_checks_test --nocapture
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV";
+const UPGRADEABLE_LOADER: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+const BALANCE: u64 = 1_000_000;
+const RENT: u64 = 890_880;
+
+/// Un compte lu par "getMultipleAccounts".
+fn account(owner: &str, size: usize, executable: bool) -> Value {
+    json!({
+        "data": [BASE64_STANDARD.encode(vec![0; size]), "base64"],
+        "executable": executable,
+        "lamports": RENT,
+        "owner": owner,
+        "rentEpoch": 0,
+        "space": size
+    })
+}
+
+#[test]
+#[serial]
+fn test_recipient_checks() {
+    common::setup();
+
+    let storage = common::TempStorage::new("recipient_checks_test");
+
+    let output = common::run(&["generate_seed"]);
+    let sender = common::find_value(&output, "Solana Public Key:").to_string();
+
+    let wallet = Keypair::new().pubkey().to_string();
+    let token_account = Keypair::new().pubkey().to_string();
+    let mint = Keypair::new().pubkey().to_string();
+    let program = Keypair::new().pubkey().to_string();
+    let pda = Pubkey::find_program_address(&[b"vault"], &Pubkey::from_str(TOKEN_PROGRAM).unwrap())
+        .0
+        .to_string();
+
+    // Le nœud de test : le solde de l'expéditeur, un compte de token, un mint et un programme.
+    let (accounts, mint_address, program_address) =
+        (token_account.clone(), mint.clone(), program.clone());
+    let url = common::stub_rpc_server_with_params(move |method, params| match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getBalance" => (
+            200,
+            json!({ "context": { "slot": 1000 }, "value": BALANCE }),
+        ),
+        "getMinimumBalanceForRentExemption" => (200, json!(RENT)),
+        "getMultipleAccounts" => {
+            let value: Vec<Value> = params[0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|address| {
+                    let address = address.as_str().unwrap_or_default();
+                    if address == accounts {
+                        account(TOKEN_PROGRAM, 165, false)
+                    } else if address == mint_address {
+                        account(TOKEN_PROGRAM, 82, false)
+                    } else if address == program_address {
+                        account(UPGRADEABLE_LOADER, 36, true)
+                    } else {
+                        Value::Null
+                    }
+                })
+                .collect();
+            (200, json!({ "context": { "slot": 1000 }, "value": value }))
+        }
+        _ => (404, Value::Null),
+    });
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/5 : L'expéditeur lui-même et une adresse hors courbe
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["send", &sender, "1000"]);
+    assert!(
+        output.contains(&format!(
+            "{} is the sender itself (use --allow-self to send anyway)",
+            sender
+        )),
+        "Error: send to the sender not rejected"
+    );
+    let output = common::run(&["--output", "json", "send", &sender, "1000", "--allow-self"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    let output = common::run_failure(&["send", &pda, "1000"]);
+    assert!(
+        output.contains(&format!("{} is off-curve (a program derived address)", pda)),
+        "Error: off-curve recipient not rejected"
+    );
+    let output = common::run(&[
+        "--output",
+        "json",
+        "send",
+        &pda,
+        "1000",
+        "--allow-off-curve",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/5 : Un compte de token, un mint et un programme
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["send", &token_account, "1000"]);
+    assert!(
+        output.contains(&format!(
+            "{} is a token account, not a wallet: send the SOL to its owner",
+            token_account
+        )),
+        "Error: token account not rejected"
+    );
+    let output = common::run_failure(&["send", &mint, "1000"]);
+    assert!(
+        output.contains(&format!("{} is a token mint, not a wallet", mint)),
+        "Error: mint not rejected"
+    );
+    let output = common::run(&[
+        "--output",
+        "json",
+        "send",
+        &token_account,
+        "1000",
+        "--allow-token-account",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    let output = common::run_failure(&["send", &program, "1000"]);
+    assert!(
+        output.contains(&format!(
+            "{} is an executable program, not a wallet (use --allow-program to send anyway)",
+            program
+        )),
+        "Error: program not rejected"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/5 : Un solde restant inférieur à l'exemption de loyer
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["send", &wallet, "500000"]);
+    assert!(
+        output.contains(&format!(
+            "The sender would keep 495000 lamports, below the rent-exempt minimum of {} lamports",
+            RENT
+        )),
+        "Error: remaining balance below rent not rejected"
+    );
+    let output = common::run(&[
+        "--output",
+        "json",
+        "send",
+        &wallet,
+        "500000",
+        "--allow-below-rent",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    // Envoyer tout le solde (moins les frais) ferme le compte de l'expéditeur : il n'est pas refusé.
+    let output = common::run(&["--output", "json", "send", &wallet, "995000"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/5 : Les destinataires de "batch_send" et de "pay"
+    |--------------------------------------------------------------------------
+    */
+
+    let payments = format!("{}/payments.csv", storage.dir);
+    std::fs::write(
+        &payments,
+        format!("{},1000\n{},2000\n", wallet, token_account),
+    )
+    .unwrap();
+    let output = common::run_failure(&["batch_send", &payments]);
+    assert!(
+        output.contains(&format!(
+            "{} is a token account, not a wallet",
+            token_account
+        )),
+        "Error: token account in a batch payout not rejected"
+    );
+    let output = common::run(&[
+        "--output",
+        "json",
+        "batch_send",
+        &payments,
+        "--allow-token-account",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    let request = format!("solana:{}?amount=0.000001", pda);
+    let output = common::run_failure(&["pay", &request]);
+    assert!(
+        output.contains(&format!("{} is off-curve (a program derived address)", pda)),
+        "Error: off-curve Solana Pay recipient not rejected"
+    );
+    let output = common::run(&["--output", "json", "pay", &request, "--allow-off-curve"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 5/5 : Sans nœud RPC, seul un avertissement est affiché
+    |--------------------------------------------------------------------------
+    */
+
+    let _env = common::EnvVars::set(&[("RPC_URL", "http://127.0.0.1:1")]);
+    let output = common::run(&["send", &wallet, "1000"]);
+    assert!(
+        output.contains("Recipient account not checked:") && output.contains("Signature:"),
+        "Error: unavailable RPC node not reported as a warning"
+    );
+}
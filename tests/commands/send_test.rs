@@ -0,0 +1,62 @@
+use crate::common;
+use serde_json::Value;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::send_test --nocapture
+
+#[test]
+#[serial]
+fn test_send_and_confirm() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("send_test");
+
+    // Génère la paire de clés de l'expéditeur.
+    common::run(&["generate_seed"]);
+
+    let recipient = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/2 : "send --no-wait" (la signature est retournée sans attendre la confirmation)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&[
+        "--output",
+        "json",
+        "--commitment",
+        "confirmed",
+        "send",
+        recipient,
+        "1000",
+        "--no-wait",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+    assert_eq!(result["confirmed"], false);
+    assert_eq!(result["attempts"], 1);
+    assert!(
+        result["signature"].as_str().unwrap().len() > 80,
+        "Error: signature not found"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/2 : "confirm" avec une signature invalide
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["confirm", "not-a-signature", "--timeout", "1"]);
+    assert!(
+        output.contains("Failed to confirm transaction: Invalid signature 'not-a-signature'"),
+        "Error: invalid signature not rejected"
+    );
+
+    let output = common::run(&["config", "show"]);
+    assert!(
+        output.contains("max_resends: 3 (default)")
+            && output.contains("confirm_timeout: 90 (default)"),
+        "Error: retry policy not displayed"
+    );
+}
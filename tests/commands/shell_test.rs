@@ -0,0 +1,76 @@
+use crate::common;
+use serial_test::serial;
+use std::fs;
+
+// cargo test --test mod -- commands::shell_test --nocapture
+
+#[test]
+#[serial]
+fn test_shell_session_and_history() {
+    common::setup();
+
+    let storage = common::TempStorage::new("shell_test");
+
+    let mnemonic = "shed scorpion manual wheat monster phone winter toe dream kitchen salad column";
+    let alice = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+
+    // La passphrase (vide) est saisie à la première commande, réutilisée par la deuxième, puis redemandée après "lock".
+    let input = [
+        format!("recover_seed \"{}\"", mnemonic),
+        String::new(),
+        format!("recover_seed \"{}\"", mnemonic),
+        "lock".to_string(),
+        format!("recover_seed \"{}\"", mnemonic),
+        String::new(),
+        format!("contacts add alice {}", alice),
+        "pubkey".to_string(),
+        "shell".to_string(),
+        "exit".to_string(),
+    ]
+    .join("\n");
+
+    let output = common::run_with_input(&["shell"], &input);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/2 : Session (passphrase conservée jusqu'au verrouillage)
+    |--------------------------------------------------------------------------
+    */
+
+    assert_eq!(
+        output
+            .matches(&format!("BIP39 Mnemonic (given phrase): {}", mnemonic))
+            .count(),
+        3,
+        "Error: recover_seed not executed three times"
+    );
+    assert_eq!(
+        output.matches("Enter passphrase (optional)").count(),
+        2,
+        "Error: passphrase not kept by the session"
+    );
+    assert!(
+        output.contains("Session locked."),
+        "Error: session not locked"
+    );
+    assert!(
+        output.contains(alice),
+        "Error: contacts add / pubkey not executed"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/2 : Historique (sans les commandes contenant des secrets)
+    |--------------------------------------------------------------------------
+    */
+
+    let history = fs::read_to_string(format!("{}/shell_history", storage.dir)).unwrap();
+    assert!(
+        history.contains(&format!("contacts add alice {}", alice)) && history.contains("pubkey"),
+        "Error: commands not recorded in the history"
+    );
+    assert!(
+        !history.contains("recover_seed") && !history.contains("scorpion"),
+        "Error: secret recorded in the history"
+    );
+}
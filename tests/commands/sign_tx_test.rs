@@ -0,0 +1,104 @@
+use crate::common;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::Value;
+use serial_test::serial;
+use solana_sdk::hash::Hash;
+use solana_sdk::message::Message;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::Transaction;
+use std::str::FromStr;
+
+// cargo test --test mod -- commands::sign_tx_test --nocapture
+
+/// Transaction non signée (base64) : transfert de 1000 lamports de "from" vers une adresse aléatoire,
+/// frais payés par "fee_payer".
+fn unsigned_transfer(fee_payer: &Pubkey, from: &Pubkey) -> String {
+    let instruction = system_instruction::transfer(from, &Pubkey::new_unique(), 1_000);
+    let message = Message::new_with_blockhash(&[instruction], Some(fee_payer), &Hash::default());
+    let transaction = Transaction::new_unsigned(message);
+    BASE64_STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+#[test]
+#[serial]
+fn test_sign_tx() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("sign_tx_test");
+
+    let output = common::run(&["generate_seed"]);
+    let pubkey = Pubkey::from_str(common::find_value(&output, "Solana Public Key:")).unwrap();
+    let dapp = Keypair::new();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : Co-signature d'une transaction dont les frais sont payés par un tiers
+    |--------------------------------------------------------------------------
+    */
+
+    let transaction = unsigned_transfer(&dapp.pubkey(), &pubkey);
+    let output = common::run_with_input(&["sign_tx", &transaction], "y\n");
+    assert!(
+        output.contains("system: transfer") && output.contains("Sign this transaction with"),
+        "Error: transaction not displayed before signing"
+    );
+    assert_eq!(common::find_value(&output, "Signed by"), pubkey.to_string());
+    assert!(
+        output.contains(&format!("Missing signatures: {}", dapp.pubkey())),
+        "Error: missing fee payer signature not reported"
+    );
+    assert!(
+        !output.contains("You pay the fees"),
+        "Error: fee payer warning for a third-party fee payer"
+    );
+
+    // Le tiers ajoute sa signature : la transaction est complète et valide.
+    let partially_signed = common::find_value(&output, "Transaction").to_string();
+    let output = common::run(&["decode", &partially_signed]);
+    assert!(
+        output.contains(&format!("{}: valid", pubkey))
+            && output.contains(&format!("{}: missing", dapp.pubkey())),
+        "Error: invalid partial signature"
+    );
+
+    let output = common::run_with_input_failure(&["sign_tx", &transaction], "n\n");
+    assert!(
+        output.contains("Failed to sign transaction: Signature cancelled"),
+        "Error: transaction signed without confirmation"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : Transaction dont nous payons les frais (--yes)
+    |--------------------------------------------------------------------------
+    */
+
+    let transaction = unsigned_transfer(&pubkey, &pubkey);
+    let output = common::run(&["--output", "json", "sign_tx", &transaction, "--yes"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["fee_payer"], true);
+    assert_eq!(result["complete"], true);
+    assert_eq!(
+        result["warnings"][0],
+        "You pay the fees of this transaction"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : Transaction dont nous ne sommes pas signataire
+    |--------------------------------------------------------------------------
+    */
+
+    let transaction = unsigned_transfer(&dapp.pubkey(), &dapp.pubkey());
+    let output = common::run_failure(&["sign_tx", &transaction, "--yes"]);
+    assert!(
+        output.contains(&format!(
+            "{} is not a required signer of this transaction",
+            pubkey
+        )),
+        "Error: transaction signed by a non-required signer"
+    );
+}
@@ -0,0 +1,203 @@
+use crate::common;
+use serde_json::{json, Value};
+use serial_test::serial;
+use std::fs;
+
+// cargo test --test mod -- commands::spending_policy_test --nocapture
+
+const RECIPIENT: &str = "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2";
+const DENIED: &str = "FTGJPL5hia749v3jhNWJA7uE2VoVGyofB7BBL2cLwoPc";
+const MAINNET_GENESIS_HASH: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d";
+const DEVNET_GENESIS_HASH: &str = "EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG";
+
+/// Un nœud de test qui ne répond qu'à "getGenesisHash".
+fn stub_cluster(genesis_hash: &'static str) -> String {
+    common::stub_rpc_server(move |method| match method {
+        "getGenesisHash" => (200, json!(genesis_hash)),
+        _ => (404, Value::Null),
+    })
+}
+
+#[test]
+#[serial]
+fn test_spending_policy() {
+    common::setup();
+
+    let storage = common::TempStorage::new("spending_policy_test");
+    fs::create_dir_all(&storage.dir).unwrap();
+    fs::write(
+        format!("{}/config.toml", storage.dir),
+        format!(
+            r#"
+default_profile = "limited"
+
+[profiles.limited.spending_policy]
+max_per_transaction = 5000
+max_daily = 8000
+confirm_above = 3000
+denylist = ["{}"]
+
+[profiles.mainnet.spending_policy]
+mainnet_strict = true
+"#,
+            DENIED
+        ),
+    )
+    .unwrap();
+
+    common::run(&["generate_seed"]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/6 : Plafond par transaction et liste interdite
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["send", RECIPIENT, "6000"]);
+    assert!(
+        output.contains(
+            "Spending policy: 6000 lamports exceed the per-transaction limit of 5000 lamports"
+        ),
+        "Error: per-transaction limit not enforced"
+    );
+
+    let output = common::run_failure(&["send", DENIED, "1000"]);
+    assert!(
+        output.contains(&format!("Spending policy: {} is on the denylist", DENIED)),
+        "Error: denylist not enforced"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/6 : Confirmation saisie au-delà du seuil
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_with_input_failure(&["send", RECIPIENT, "4000"], "yes\n");
+    assert!(
+        output.contains("Type \"4000\" to sign:"),
+        "Error: typed confirmation not asked"
+    );
+    assert!(
+        output.contains("Spending policy: confirmation failed, nothing was signed"),
+        "Error: wrong confirmation accepted"
+    );
+
+    let output = common::run_with_input(&["--output", "json", "send", RECIPIENT, "4000"], "4000\n");
+    let json_start = output.find('{').unwrap();
+    let result: Value = serde_json::from_str(&output[json_start..]).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/6 : Plafond sur 24 heures glissantes
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json", "send", RECIPIENT, "2000"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["simulated"], true);
+
+    let output = common::run_failure(&["send", RECIPIENT, "2500"]);
+    assert!(
+        output.contains("2500 lamports exceed the daily limit of 8000 lamports")
+            && output.contains("(6000 lamports spent in the last 24 hours)"),
+        "Error: daily limit not enforced"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/6 : Dépense enregistrée dès l'envoi, même non confirmée
+    |--------------------------------------------------------------------------
+    */
+
+    // Le nœud accepte la transaction mais ne la confirme jamais.
+    let url = common::stub_unconfirmed_rpc_server();
+    {
+        let _env = common::EnvVars::set(&[("TEST_MODE", "false"), ("CONFIRM_TIMEOUT", "1")]);
+        let output = common::run_failure(&["--url", &url, "send", RECIPIENT, "1500"]);
+        assert!(
+            output.contains("not confirmed after 1 seconds"),
+            "Error: unconfirmed transaction not reported"
+        );
+    }
+
+    let output = common::run_failure(&["send", RECIPIENT, "1000"]);
+    assert!(
+        output.contains("(7500 lamports spent in the last 24 hours)"),
+        "Error: unconfirmed transaction not recorded in the spending ledger"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 5/6 : Mode strict du mainnet
+    |--------------------------------------------------------------------------
+    */
+
+    let mainnet = [
+        "--profile",
+        "mainnet",
+        "--url",
+        "https://api.mainnet-beta.solana.com",
+    ];
+    let output = common::run_failure(&[&mainnet[..], &["send", RECIPIENT, "1000"]].concat());
+    assert!(
+        output.contains(&format!(
+            "Spending policy (mainnet strict mode): {} is not on the allowlist",
+            RECIPIENT
+        )),
+        "Error: unknown recipient accepted in mainnet strict mode"
+    );
+
+    // Le cluster est reconnu au hash du bloc de genèse, pas à l'URL du nœud.
+    let mainnet_node = stub_cluster(MAINNET_GENESIS_HASH);
+    let output = common::run_failure(&[
+        "--profile",
+        "mainnet",
+        "--url",
+        &mainnet_node,
+        "send",
+        RECIPIENT,
+        "1000",
+    ]);
+    assert!(
+        output.contains("Spending policy (mainnet strict mode)"),
+        "Error: mainnet node behind a custom URL not detected"
+    );
+    let devnet_node = stub_cluster(DEVNET_GENESIS_HASH);
+    let output = common::run(&[
+        "--profile",
+        "mainnet",
+        "--url",
+        &devnet_node,
+        "send",
+        RECIPIENT,
+        "1000",
+    ]);
+    assert!(
+        !output.contains("Spending policy") && output.contains("Signature:"),
+        "Error: mainnet strict mode applied to devnet"
+    );
+
+    common::run(&["contacts", "add", "alice", RECIPIENT]);
+    let output = common::run_with_input(
+        &[&mainnet[..], &["send", "alice", "1000"]].concat(),
+        "1000\n",
+    );
+    assert!(
+        output.contains("Type \"1000\" to sign:") && output.contains("Signature:"),
+        "Error: contact not accepted in mainnet strict mode"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 6/6 : La politique est affichée par "config show"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["config", "show"]);
+    assert_eq!(
+        common::find_value(&output, "spending_policy"),
+        "max_per_transaction=5000, max_daily=8000, confirm_above=3000, denylist=1 (profile 'limited')"
+    );
+}
@@ -0,0 +1,218 @@
+use crate::common;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::stake::state::{Authorized, Lockup, Meta, StakeStateV2};
+use std::fs;
+use std::str::FromStr;
+
+// cargo test --test mod -- commands::sweep_test --nocapture
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV";
+const MINT: &str = "EPjFWdd5AufqSSqeM2qFLxzdmkaQeaHG6Xg1uUq3pJjQ";
+const BALANCE: u64 = 1_000_000_000;
+const RENT: u64 = 2_039_280;
+const FEE: u64 = 5_000;
+
+const FUNDED_ACCOUNT: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+const EMPTY_ACCOUNT: &str = "3yBzsWhyBMkPLZzFqy4swTbSoF7LC9ZhhHK1uMkdCHHb";
+const STAKE_ACCOUNT: &str = "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T";
+
+/// Réponses du nœud de test : le solde de "owner", deux comptes de tokens (l'un vide, dont "owner" est
+/// explicitement l'autorité de fermeture) et un compte de stake dont il détient les autorités.
+/// Les comptes de tokens de la destination n'existent pas.
+fn stub_assets(owner: Pubkey) -> String {
+    let mut stake = bincode::serialize(&StakeStateV2::Initialized(Meta {
+        rent_exempt_reserve: RENT,
+        authorized: Authorized {
+            staker: owner,
+            withdrawer: owner,
+        },
+        lockup: Lockup::default(),
+    }))
+    .unwrap();
+    stake.resize(StakeStateV2::size_of(), 0);
+    let stake = BASE64_STANDARD.encode(&stake);
+
+    common::stub_rpc_server_with_params(move |method, params| match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getBalance" => (
+            200,
+            json!({ "context": { "slot": 1000 }, "value": BALANCE }),
+        ),
+        "getTokenAccountsByOwner" => {
+            let accounts = if params[1]["programId"] == TOKEN_PROGRAM {
+                let mut empty =
+                    common::token_account(EMPTY_ACCOUNT, &owner, MINT, 0, "initialized", RENT);
+                empty["account"]["data"]["parsed"]["info"]["closeAuthority"] =
+                    json!(owner.to_string());
+                json!([
+                    common::token_account(
+                        FUNDED_ACCOUNT,
+                        &owner,
+                        MINT,
+                        12_500_000,
+                        "initialized",
+                        RENT
+                    ),
+                    empty,
+                ])
+            } else {
+                json!([])
+            };
+            (
+                200,
+                json!({ "context": { "slot": 1000 }, "value": accounts }),
+            )
+        }
+        "getMultipleAccounts" => {
+            let accounts: Vec<Value> = params[0]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|_| Value::Null)
+                .collect();
+            (
+                200,
+                json!({ "context": { "slot": 1000 }, "value": accounts }),
+            )
+        }
+        "getProgramAccounts" => (
+            200,
+            json!([{
+                "pubkey": STAKE_ACCOUNT,
+                "account": {
+                    "data": [stake, "base64"],
+                    "executable": false,
+                    "lamports": 5 * BALANCE,
+                    "owner": "Stake11111111111111111111111111111111111111",
+                    "rentEpoch": 0,
+                    "space": 200
+                }
+            }]),
+        ),
+        _ => (404, Value::Null),
+    })
+}
+
+#[test]
+#[serial]
+fn test_sweep() {
+    common::setup();
+
+    let storage = common::TempStorage::new("sweep_test");
+
+    let output = common::run(&["generate_seed"]);
+    let owner = Pubkey::from_str(common::find_value(&output, "Solana Public Key:")).unwrap();
+    let destination = Pubkey::new_unique().to_string();
+
+    let url = stub_assets(owner);
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : Aperçu, puis annulation
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_with_input_failure(&["sweep", "--to", &destination], "n\n");
+    assert!(
+        output.contains(&format!("Sweep plan to {}", destination)),
+        "Error: plan not displayed"
+    );
+    assert!(
+        output.contains(&format!(
+            "  Token {}: 12.5 (creates the destination account), account closed",
+            MINT
+        )),
+        "Error: token transfer not planned"
+    );
+    assert!(
+        output.contains(&format!(
+            "  Stake account {}: 5000000000 lamports, authorities transferred",
+            STAKE_ACCOUNT
+        )),
+        "Error: stake account not planned"
+    );
+    assert!(
+        output.contains("Sweep everything to"),
+        "Error: no confirmation asked"
+    );
+    assert!(
+        output.contains("Sweep cancelled"),
+        "Error: sweep not cancelled"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : Balayage, et rapport final
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json", "sweep", "--to", &destination, "--yes"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["executed"], true);
+    assert_eq!(result["simulated"], true);
+    let key = &result["keys"][0];
+    assert_eq!(key["from"], owner.to_string());
+    assert_eq!(key["tokens"].as_array().unwrap().len(), 2);
+    assert_eq!(key["tokens"][0]["create_destination"], true);
+    assert_eq!(key["tokens"][1]["create_destination"], false);
+    assert_eq!(key["tokens"][1]["close"], true);
+    assert!(key["skipped"].as_array().unwrap().is_empty());
+    assert_eq!(key["stake_accounts"][0]["account"], STAKE_ACCOUNT);
+    // Le loyer du compte vide fermé est récupéré ; celui du compte vidé paie le compte de destination.
+    // Trois transactions : les tokens, le stake, puis les SOL.
+    assert_eq!(key["lamports"], BALANCE + RENT - 3 * FEE);
+    assert_eq!(key["signatures"].as_array().unwrap().len(), 3);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : La destination ne peut pas être la clé balayée
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["sweep", "--to", &owner.to_string(), "--yes"]);
+    assert!(
+        output.contains("is the swept key"),
+        "Error: key swept to itself"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : Un échec est rapporté avec les transactions déjà envoyées
+    |--------------------------------------------------------------------------
+    */
+
+    // Les transferts vers la destination sont refusés par la politique de dépense.
+    fs::write(
+        format!("{}/config.toml", storage.dir),
+        format!(
+            "default_profile = \"strict\"\n[profiles.strict.spending_policy]\ndenylist = [\"{}\"]\n",
+            destination
+        ),
+    )
+    .unwrap();
+    let output = common::run(&["--output", "json", "sweep", "--to", &destination, "--yes"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["executed"], true);
+    assert_eq!(result["failed"], 1);
+    let key = &result["keys"][0];
+    assert!(key["signatures"].as_array().unwrap().is_empty());
+    assert!(
+        key["error"]
+            .as_str()
+            .unwrap()
+            .contains("is on the denylist"),
+        "Error: failure not reported"
+    );
+
+    let output = common::run(&["sweep", "--to", &destination, "--yes"]);
+    assert!(
+        output.contains("SOL), not sent")
+            && output.contains("Sweep incomplete: 1 of 1 key(s) failed"),
+        "Error: failure not displayed"
+    );
+}
@@ -0,0 +1,214 @@
+use crate::common;
+use serde_json::{json, Value};
+use serial_test::serial;
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+// cargo test --test mod -- commands::token_accounts_test --nocapture
+
+const TOKEN_PROGRAM: &str = "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV";
+const ASSOCIATED_TOKEN_PROGRAM: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+const MINT: &str = "EPjFWdd5AufqSSqeM2qFLxzdmkaQeaHG6Xg1uUq3pJjQ";
+const RENT: u64 = 2_039_280;
+
+const EMPTY_ACCOUNT: &str = "3yBzsWhyBMkPLZzFqy4swTbSoF7LC9ZhhHK1uMkdCHHb";
+const DUST_ACCOUNT: &str = "6Jr3oxMSN5XJj8Eaeb3Vt4UVMUwNcNyY8V2QCWnDkyBx";
+const FUNDED_ACCOUNT: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+const FROZEN_ACCOUNT: &str = "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T";
+
+/// Réponses du nœud de test : les comptes de tokens de "owner" (programme Token), et le mint.
+/// Les autres comptes n'existent pas, sauf "existing".
+fn stub_token_accounts(owner: Pubkey, existing: Pubkey) -> String {
+    common::stub_rpc_server_with_params(move |method, params| match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getTokenAccountsByOwner" => {
+            let accounts = if params[1]["programId"] == TOKEN_PROGRAM {
+                json!([
+                    common::token_account(EMPTY_ACCOUNT, &owner, MINT, 0, "initialized", RENT),
+                    common::token_account(DUST_ACCOUNT, &owner, MINT, 5, "initialized", RENT),
+                    common::token_account(
+                        FUNDED_ACCOUNT,
+                        &owner,
+                        MINT,
+                        12_500_000,
+                        "initialized",
+                        RENT
+                    ),
+                    common::token_account(FROZEN_ACCOUNT, &owner, MINT, 0, "frozen", RENT),
+                ])
+            } else {
+                json!([])
+            };
+            (
+                200,
+                json!({ "context": { "slot": 1000 }, "value": accounts }),
+            )
+        }
+        "getAccountInfo" => {
+            let value = if params[0] == MINT || params[0] == existing.to_string() {
+                json!({
+                    "data": ["", "base64"],
+                    "executable": false,
+                    "lamports": RENT,
+                    "owner": TOKEN_PROGRAM,
+                    "rentEpoch": 0,
+                    "space": 0
+                })
+            } else {
+                Value::Null
+            };
+            (200, json!({ "context": { "slot": 1000 }, "value": value }))
+        }
+        _ => (404, Value::Null),
+    })
+}
+
+#[test]
+#[serial]
+fn test_token_accounts() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("token_accounts_test");
+
+    let output = common::run(&["generate_seed"]);
+    let owner = Pubkey::from_str(common::find_value(&output, "Solana Public Key:")).unwrap();
+
+    let mint = Pubkey::from_str(MINT).unwrap();
+    let token_program = Pubkey::from_str(TOKEN_PROGRAM).unwrap();
+    let ata = |owner: &Pubkey| {
+        Pubkey::find_program_address(
+            &[owner.as_ref(), token_program.as_ref(), mint.as_ref()],
+            &Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM).unwrap(),
+        )
+        .0
+    };
+    let other = Pubkey::new_unique();
+    let url = stub_token_accounts(owner, ata(&other));
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : "token_accounts cleanup --dry-run"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["token_accounts", "cleanup", "--dry-run"]);
+    assert_eq!(common::find_value(&output, "Accounts to close"), "1");
+    assert!(
+        output.contains(&format!("  {} (mint {})", EMPTY_ACCOUNT, MINT)),
+        "Error: empty account not listed"
+    );
+    assert_eq!(common::find_value(&output, "Accounts kept"), "3");
+    assert!(
+        output.contains(&format!("  {}: frozen by the mint", FROZEN_ACCOUNT)),
+        "Error: frozen account not kept"
+    );
+    assert!(
+        output.contains(&format!("  {}: balance 0.000005", DUST_ACCOUNT)),
+        "Error: dust account not kept"
+    );
+    assert_eq!(
+        common::find_value(&output, "Rent to reclaim"),
+        "2039280 lamports (0.00203928 SOL)"
+    );
+    assert!(
+        !output.contains("Signature"),
+        "Error: transaction sent in dry run"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : "token_accounts cleanup --burn-dust"
+    |--------------------------------------------------------------------------
+    */
+
+    // Le seuil est comparé en unités de base : 0.0000049 est inférieur au solde de 5 unités (6 décimales).
+    let output = common::run(&[
+        "token_accounts",
+        "cleanup",
+        "--burn-dust",
+        "0.0000049",
+        "--dry-run",
+    ]);
+    assert!(
+        output.contains(&format!("  {}: balance 0.000005", DUST_ACCOUNT)),
+        "Error: balance above the dust amount burned"
+    );
+    let output = common::run(&[
+        "token_accounts",
+        "cleanup",
+        "--burn-dust",
+        "0",
+        "--burn-dust",
+        &format!("{}=0.000005", MINT),
+        "--dry-run",
+    ]);
+    assert!(
+        output.contains(&format!(
+            "  {} (mint {}, burned 0.000005)",
+            DUST_ACCOUNT, MINT
+        )),
+        "Error: dust amount of the mint not applied"
+    );
+
+    // Le burn est confirmé avant l'envoi.
+    let output = common::run_with_input_failure(
+        &["token_accounts", "cleanup", "--burn-dust", "0.001"],
+        "n\n",
+    );
+    assert!(
+        output.contains("Burn 1 token balances and close these accounts? [y/N]"),
+        "Error: burn not confirmed"
+    );
+    assert!(
+        output.contains("Failed to clean up token accounts: Cleanup cancelled"),
+        "Error: cancelled cleanup sent"
+    );
+
+    let output = common::run(&[
+        "--output",
+        "json",
+        "token_accounts",
+        "cleanup",
+        "--burn-dust",
+        "0.001",
+        "--yes",
+    ]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(result["closed"].as_array().unwrap().len(), 2);
+    assert_eq!(result["closed"][1]["account"], DUST_ACCOUNT);
+    assert_eq!(result["closed"][1]["burned"], "0.000005");
+    assert_eq!(result["skipped"][0]["account"], FUNDED_ACCOUNT);
+    assert_eq!(result["reclaimed_lamports"], 2 * RENT);
+    assert_eq!(result["signatures"].as_array().unwrap().len(), 1);
+    assert_eq!(result["simulated"], true);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : "create_ata"
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["create_ata", MINT]);
+    assert_eq!(
+        common::find_value(&output, "Token account"),
+        ata(&owner).to_string()
+    );
+    assert_eq!(common::find_value(&output, "Owner"), owner.to_string());
+    assert!(
+        output.contains("(simulated: transaction signed but not sent)"),
+        "Error: token account not created"
+    );
+
+    let output = common::run_failure(&["create_ata", MINT, "--owner", &other.to_string()]);
+    assert!(
+        output.contains("already exists"),
+        "Error: existing token account created again"
+    );
+
+    let output = common::run_failure(&["create_ata", &Pubkey::new_unique().to_string()]);
+    assert!(
+        output.contains("Cannot read mint"),
+        "Error: unknown mint accepted"
+    );
+}
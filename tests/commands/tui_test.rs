@@ -0,0 +1,146 @@
+use crate::common;
+use serde_json::{json, Value};
+use serial_test::serial;
+
+// cargo test --test mod -- commands::tui_test --nocapture
+
+const MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const SIGNATURE: &str =
+    "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+
+/// Réponses du nœud de test : 2 SOL, un compte de token (12.5 unités) et une transaction par adresse.
+fn respond(method: &str) -> (u16, Value) {
+    match method {
+        "getHealth" => (200, json!("ok")),
+        "getSlot" => (200, json!(1000)),
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getBalance" => (
+            200,
+            json!({ "context": { "slot": 1000 }, "value": 2_000_000_000u64 }),
+        ),
+        "getTokenAccountsByOwner" => (
+            200,
+            json!({
+                "context": { "slot": 1000 },
+                "value": [{
+                    "pubkey": "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin",
+                    "account": {
+                        "lamports": 2_039_280,
+                        "owner": "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV",
+                        "executable": false,
+                        "rentEpoch": 0,
+                        "space": 165,
+                        "data": {
+                            "program": "spl-token",
+                            "space": 165,
+                            "parsed": {
+                                "type": "account",
+                                "info": {
+                                    "mint": MINT,
+                                    "tokenAmount": {
+                                        "amount": "12500000",
+                                        "decimals": 6,
+                                        "uiAmount": 12.5,
+                                        "uiAmountString": "12.5"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }]
+            }),
+        ),
+        "getSignaturesForAddress" => (
+            200,
+            json!([{
+                "signature": SIGNATURE,
+                "slot": 990,
+                "err": null,
+                "memo": null,
+                "blockTime": 1_700_000_000,
+                "confirmationStatus": "finalized"
+            }]),
+        ),
+        _ => (404, Value::Null),
+    }
+}
+
+#[test]
+#[serial]
+fn test_tui_snapshot() {
+    common::setup();
+
+    let _storage = common::TempStorage::new("tui_test");
+    let url = common::stub_rpc_server(respond);
+    let _env = common::EnvVars::set(&[("RPC_URL", &url)]);
+
+    // Une paire de clés ("main", active) et une adresse surveillée.
+    common::run(&["generate_seed"]);
+    common::run(&[
+        "wallet",
+        "watch",
+        "savings",
+        "DXGaLHJ2w4Q4Jer5gH6qcscKdjNpP8gPadjdRY7Tm3D2",
+    ]);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/3 : "tui --snapshot" (texte)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["tui", "--snapshot"]);
+    assert!(
+        output.contains("Keypairs") && output.contains("2.000000000 SOL"),
+        "Error: keypairs and balances not displayed"
+    );
+    assert!(
+        output.contains("savings") && output.contains("(watch-only)"),
+        "Error: watch-only address not displayed"
+    );
+    assert!(
+        output.contains("Tokens - main") && output.contains(&format!("{} 12.5", MINT)),
+        "Error: token balances not displayed"
+    );
+    assert!(
+        output.contains("Recent transactions - main") && output.contains(SIGNATURE),
+        "Error: recent transactions not displayed"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/3 : "tui --snapshot" (JSON)
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run(&["--output", "json", "tui", "--snapshot"]);
+    let result: Value = serde_json::from_str(&output).unwrap();
+    let account = |label: &str| {
+        result["accounts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|account| account["label"] == label)
+            .unwrap_or_else(|| panic!("Account '{}' not found", label))
+            .clone()
+    };
+    assert_eq!(result["selected"], "main");
+    assert_eq!(account("main")["active"], true);
+    assert_eq!(account("main")["lamports"], 2_000_000_000u64);
+    assert_eq!(account("main")["tokens"][0]["mint"], MINT);
+    assert_eq!(account("main")["tokens"][0]["amount"], "12.5");
+    assert_eq!(account("savings")["watch_only"], true);
+    assert_eq!(result["history"]["transactions"][0]["signature"], SIGNATURE);
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/3 : "tui" sans terminal
+    |--------------------------------------------------------------------------
+    */
+
+    let output = common::run_failure(&["tui"]);
+    assert!(
+        output.contains("The dashboard needs a terminal"),
+        "Error: missing terminal not reported"
+    );
+}
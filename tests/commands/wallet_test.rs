@@ -0,0 +1,102 @@
+use crate::common;
+use serial_test::serial;
+
+// cargo test --test mod -- commands::wallet_test --nocapture
+
+#[test]
+#[serial]
+fn test_wallet_commands() {
+    common::setup();
+
+    /*
+    |--------------------------------------------------------------------------
+    | Préparation d'un key store temporaire (supprimé à la fin du test).
+    |--------------------------------------------------------------------------
+    */
+
+    let _storage = common::TempStorage::new("wallet_test");
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 1/4 : Génération des clés (enregistrées dans le key store)
+    |--------------------------------------------------------------------------
+    */
+
+    let mnemonic = "shed scorpion manual wheat monster phone winter toe dream kitchen salad column";
+    let recover_output = common::run(&["recover_seed", mnemonic]);
+    let main_pubkey = common::find_value(&recover_output, "Solana Public Key:").to_string();
+    let derived_pubkey =
+        common::find_value(&recover_output, "Solana Public Key (derivation 1):").to_string();
+
+    let list_output = common::run(&["wallet", "list"]);
+    assert!(
+        list_output.contains(&format!("main {}", main_pubkey)),
+        "Error: main key not found in wallet list"
+    );
+    assert!(
+        list_output.contains(&format!("derivation-1 {}", derived_pubkey)),
+        "Error: derivation 1 not found in wallet list"
+    );
+    assert!(
+        list_output.contains("m/44'/501'/0'/0/1"),
+        "Error: derivation path not found in wallet list"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 2/4 : Renommage et sélection avec "--from"
+    |--------------------------------------------------------------------------
+    */
+
+    common::run(&["wallet", "rename", "derivation-1", "savings"]);
+
+    let from_output = common::run(&["pubkey", "--from", "savings"]);
+    assert_eq!(
+        common::find_value(&from_output, "Solana Public Key"),
+        derived_pubkey,
+        "The public key selected with --from doesn't match derivation 1"
+    );
+
+    let from_pubkey_output = common::run(&["pubkey", "--from", &derived_pubkey]);
+    assert_eq!(
+        common::find_value(&from_pubkey_output, "Solana Public Key"),
+        derived_pubkey,
+        "The public key selected by pubkey doesn't match derivation 1"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 3/4 : Clé active avec "wallet use"
+    |--------------------------------------------------------------------------
+    */
+
+    let default_output = common::run(&["pubkey"]);
+    assert_eq!(
+        common::find_value(&default_output, "Solana Public Key"),
+        main_pubkey,
+        "Without an active key, KEYPAIR_PATH should be used"
+    );
+
+    common::run(&["wallet", "use", "savings"]);
+
+    let active_output = common::run(&["pubkey"]);
+    assert_eq!(
+        common::find_value(&active_output, "Solana Public Key"),
+        derived_pubkey,
+        "The active key should be used by default"
+    );
+
+    /*
+    |--------------------------------------------------------------------------
+    | Étape 4/4 : Suppression
+    |--------------------------------------------------------------------------
+    */
+
+    common::run(&["wallet", "remove", "savings"]);
+
+    let list_output = common::run(&["wallet", "list"]);
+    assert!(
+        !list_output.contains("savings"),
+        "Error: removed key still listed"
+    );
+}
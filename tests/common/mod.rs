@@ -1,4 +1,14 @@
-use std::sync::Once;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
+use serde_json::{json, Value};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output, Stdio};
+use std::str;
+use std::sync::{Arc, Mutex, Once};
+use std::thread;
 
 static INIT: Once = Once::new();
 
@@ -26,3 +36,308 @@ pub fn verify_pubkey(pubkey: &str) {
         "Public key contains non-alphanumeric characters"
     );
 }
+
+/// Exécute la commande donnée (via "cargo run"), avec "input" sur l'entrée standard.
+fn execute(args: &[&str], input: &str) -> Output {
+    // "--quiet" : la sortie d'erreur ne contient que celle de la commande (pas la compilation).
+    let mut child = Command::new("cargo")
+        .args(["run", "--quiet", "--"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to execute command");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .expect("Failed to write input");
+    child.wait_with_output().expect("Failed to execute command")
+}
+
+/// Exécute la commande donnée (via "cargo run") et retourne sa sortie (stdout).
+pub fn run(args: &[&str]) -> String {
+    run_with_input(args, "")
+}
+
+/// Exécute la commande donnée avec "input" sur l'entrée standard, et retourne sa sortie standard.
+pub fn run_with_input(args: &[&str], input: &str) -> String {
+    let output = execute(args, input);
+
+    assert!(
+        output.status.success(),
+        "Error: '{}' command failed to execute: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    str::from_utf8(&output.stdout)
+        .expect("Invalid UTF-8 output")
+        .to_string()
+}
+
+/// Exécute une commande qui doit échouer, et retourne sa sortie standard suivie de sa sortie d'erreur.
+pub fn run_failure(args: &[&str]) -> String {
+    run_with_input_failure(args, "")
+}
+
+/// Exécute une commande qui doit échouer avec "input" sur l'entrée standard, et retourne sa sortie standard
+/// suivie de sa sortie d'erreur.
+pub fn run_with_input_failure(args: &[&str], input: &str) -> String {
+    let output = execute(args, input);
+
+    assert!(
+        !output.status.success(),
+        "Error: '{}' command did not fail",
+        args.join(" ")
+    );
+
+    let mut text = str::from_utf8(&output.stdout)
+        .expect("Invalid UTF-8 output")
+        .to_string();
+    text.push_str(str::from_utf8(&output.stderr).expect("Invalid UTF-8 output"));
+    text
+}
+
+/// Extrait la valeur (après ":") de la première ligne commençant par le préfixe donné.
+pub fn find_value<'a>(output: &'a str, prefix: &str) -> &'a str {
+    let line = output
+        .lines()
+        .find(|line| line.starts_with(prefix))
+        .unwrap_or_else(|| panic!("Line starting with '{}' not found", prefix));
+    line.split_once(':').unwrap().1.trim()
+}
+
+/// Dossier de stockage temporaire propre à un test.
+/// Redirige les variables d'environnement de stockage (paires de clés, key store, carnet d'adresses, fichier de configuration)
+/// vers ce dossier, puis les restaure et supprime le dossier à la fin du test.
+pub struct TempStorage {
+    pub dir: String,
+    old_values: Vec<(&'static str, Option<String>)>,
+}
+
+impl TempStorage {
+    pub fn new(name: &str) -> Self {
+        let dir = format!("./storage/tests/{}", name);
+        let values = [
+            ("KEYPAIR_PATH", format!("{}/keypair/id.json", dir)),
+            (
+                "KEYPAIR_DERIVATIONS_PATH",
+                format!("{}/keypair/derived", dir),
+            ),
+            ("KEYSTORE_PATH", format!("{}/keypair/keystore.json", dir)),
+            ("ADDRESS_BOOK_PATH", format!("{}/address_book.json", dir)),
+            ("SHELL_HISTORY_PATH", format!("{}/shell_history", dir)),
+            (
+                "SPENDING_LEDGER_PATH",
+                format!("{}/spending_ledger.json", dir),
+            ),
+            ("AUDIT_LOG_PATH", format!("{}/audit_log.jsonl", dir)),
+            ("WALLET_CONFIG", format!("{}/config.toml", dir)),
+            (
+                "SOLANA_CLI_CONFIG",
+                format!("{}/solana_cli_config.yml", dir),
+            ),
+        ];
+
+        let old_values = values
+            .iter()
+            .map(|(key, _)| (*key, std::env::var(key).ok()))
+            .collect();
+        for (key, value) in values {
+            std::env::set_var(key, value);
+        }
+
+        Self { dir, old_values }
+    }
+}
+
+impl Drop for TempStorage {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+        for (key, value) in &self.old_values {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
+
+/// Variables d'environnement modifiées pendant un test (restaurées à la fin du test).
+pub struct EnvVars {
+    old_values: Vec<(String, Option<String>)>,
+}
+
+impl EnvVars {
+    pub fn set(values: &[(&str, &str)]) -> Self {
+        let old_values = values
+            .iter()
+            .map(|(key, _)| (key.to_string(), std::env::var(key).ok()))
+            .collect();
+        for (key, value) in values {
+            std::env::set_var(key, value);
+        }
+        Self { old_values }
+    }
+}
+
+impl Drop for EnvVars {
+    fn drop(&mut self) {
+        for (key, value) in &self.old_values {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
+
+/// Un compte de token SPL (programme Token, 6 décimales), tel que décodé par le nœud ("jsonParsed").
+pub fn token_account(
+    pubkey: &str,
+    owner: &Pubkey,
+    mint: &str,
+    amount: u64,
+    state: &str,
+    lamports: u64,
+) -> Value {
+    json!({
+        "pubkey": pubkey,
+        "account": {
+            "data": {
+                "program": "spl-token",
+                "parsed": {
+                    "type": "account",
+                    "info": {
+                        "isNative": false,
+                        "mint": mint,
+                        "owner": owner.to_string(),
+                        "state": state,
+                        "tokenAmount": {
+                            "amount": amount.to_string(),
+                            "decimals": 6,
+                            "uiAmount": amount as f64 / 1_000_000_f64,
+                            "uiAmountString": (amount as f64 / 1_000_000_f64).to_string()
+                        }
+                    }
+                },
+                "space": 165
+            },
+            "executable": false,
+            "lamports": lamports,
+            "owner": "TokenkegQfeZyiNwAJbNbGqPXBBQEyT5fdT2Sc5sPTV",
+            "rentEpoch": 0,
+            "space": 165
+        }
+    })
+}
+
+/// Serveur RPC de test : répond à chaque requête JSON-RPC selon sa méthode (statut HTTP et "result").
+/// Le serveur s'arrête avec le processus de test.
+pub fn stub_rpc_server(respond: impl Fn(&str) -> (u16, Value) + Send + 'static) -> String {
+    stub_rpc_server_with_params(move |method, _| respond(method))
+}
+
+/// Serveur RPC de test : répond à chaque requête JSON-RPC selon sa méthode et ses paramètres.
+pub fn stub_rpc_server_with_params(
+    respond: impl Fn(&str, &Value) -> (u16, Value) + Send + 'static,
+) -> String {
+    recording_stub_rpc_server(respond).0
+}
+
+/// Serveur RPC de test : répond à chaque requête JSON-RPC selon sa méthode et ses paramètres, et mémorise les
+/// requêtes reçues.
+pub fn recording_stub_rpc_server(
+    respond: impl Fn(&str, &Value) -> (u16, Value) + Send + 'static,
+) -> (String, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let received = requests.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" || line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request: Value = serde_json::from_slice(&body).unwrap();
+
+            let (status, result) = respond(
+                request["method"].as_str().unwrap_or_default(),
+                &request["params"],
+            );
+            let body =
+                json!({ "jsonrpc": "2.0", "result": result, "id": request["id"] }).to_string();
+            received.lock().unwrap().push(request);
+            let response = format!(
+                "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nRetry-After: 0\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        }
+    });
+
+    (url, requests)
+}
+
+/// Réponse d'un nœud RPC en bonne santé, au slot donné.
+pub fn healthy(method: &str, slot: u64) -> (u16, Value) {
+    match method {
+        "getHealth" => (200, json!("ok")),
+        "getSlot" => (200, json!(slot)),
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getBalance" => (
+            200,
+            json!({ "context": { "slot": slot }, "value": 2_000_000_000u64 }),
+        ),
+        "sendTransaction" => (200, json!("signature")),
+        _ => (404, Value::Null),
+    }
+}
+
+/// Serveur RPC de test qui accepte les transactions envoyées mais ne les confirme jamais.
+pub fn stub_unconfirmed_rpc_server() -> String {
+    stub_rpc_server_with_params(|method, params| match method {
+        "getVersion" => (200, json!({ "solana-core": "1.18.26" })),
+        "getLatestBlockhash" => (
+            200,
+            json!({
+                "context": { "slot": 1000 },
+                "value": {
+                    "blockhash": "EkSnNWid2cvwEVnVx9aBqawnmiCNiDgp3gUdkDPTKN1N",
+                    "lastValidBlockHeight": 1150
+                }
+            }),
+        ),
+        "getFeeForMessage" => (200, json!({ "context": { "slot": 1000 }, "value": 5000 })),
+        "sendTransaction" => {
+            let bytes = BASE64_STANDARD
+                .decode(params[0].as_str().unwrap_or_default())
+                .unwrap_or_default();
+            let transaction: VersionedTransaction = bincode::deserialize(&bytes).unwrap();
+            (200, json!(transaction.signatures[0].to_string()))
+        }
+        "getSignatureStatuses" => (200, json!({ "context": { "slot": 1000 }, "value": [null] })),
+        "getBlockHeight" => (200, json!(1000)),
+        _ => (404, Value::Null),
+    })
+}